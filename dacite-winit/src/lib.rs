@@ -33,6 +33,9 @@
 //! let events_loop = winit::EventsLoop::new();
 //! let window = winit::Window::new(&events_loop).unwrap();
 //!
+//! // Load the Vulkan library:
+//! let loader = dacite::core::Loader::new().unwrap();
+//!
 //! // Determine required extensions before a Vulkan instance is created:
 //! let required_extensions = match window.get_required_extensions() {
 //!     Ok(required_extensions) => required_extensions,
@@ -52,7 +55,7 @@
 //!     // ...
 //! };
 //!
-//! let instance = dacite::core::Instance::create(&create_info, None).unwrap();
+//! let instance = dacite::core::Instance::create(&loader, &create_info, None).unwrap();
 //!
 //! // While searching for a suitable physical device, use
 //! // WindowExt::is_presentation_supported() to determine if the physical device has a queue
@@ -242,7 +242,7 @@ macro_rules! gen_chain_struct {
                         let mut $field_name: Box<_> = Box::new(<$field_wrapper_ty>::new($field_name, false));
                         unsafe {
                             *pnext = &$field_name.vks_struct as *const _ as *mut ::libc::c_void;
-                            pnext = &mut ($field_name.vks_struct.pNext as *mut _);
+                            pnext = &mut $field_name.vks_struct.pNext as *mut _ as *mut *mut ::libc::c_void;
                         }
 
                         $field_name
@@ -342,7 +342,7 @@ macro_rules! gen_chain_struct {
             #[allow(unused_variables)]
             pub fn new(query: &$query_name) -> Self {
                 let mut vks_struct: $vks_ty = Default::default();
-                let mut pnext: *mut *mut ::libc::c_void = &mut (vks_struct.pNext as *mut _);
+                let mut pnext: *mut *mut ::libc::c_void = &mut vks_struct.pNext as *mut _ as *mut *mut ::libc::c_void;
 
                 $(
                     let $field_name = if query.$field_name {
@@ -351,7 +351,7 @@ macro_rules! gen_chain_struct {
                             let $field_name: &mut $field_vks_ty = &mut $field_name;
                             *pnext = $field_name as *mut _ as *mut ::libc::c_void;
                         }
-                        pnext = &mut ($field_name.pNext as *mut _);
+                        pnext = &mut $field_name.pNext as *mut _ as *mut *mut ::libc::c_void;
                         Some($field_name)
                     }
                     else {
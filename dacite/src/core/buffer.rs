@@ -92,9 +92,17 @@ impl Buffer {
             owned: owned,
             device: device,
             allocator: allocator,
+            memory: None,
         }))
     }
 
+    /// Makes this buffer keep `memory` alive until it is destroyed.
+    ///
+    /// Must only be called on newly created objects, which are not shared yet.
+    pub(crate) fn set_memory(&mut self, memory: DeviceMemory) {
+        Arc::get_mut(&mut self.0).unwrap().memory = Some(memory);
+    }
+
     #[inline]
     pub(crate) fn handle(&self) -> vks::vk::VkBuffer {
         self.0.handle
@@ -110,6 +118,14 @@ impl Buffer {
         self.0.device.handle()
     }
 
+    /// Returns the `DeviceMemory` owned by this buffer.
+    ///
+    /// This is only the case for buffers created with
+    /// [`Device::create_buffer_with_memory`](struct.Device.html#method.create_buffer_with_memory).
+    pub fn memory(&self) -> Option<&DeviceMemory> {
+        self.0.memory.as_ref()
+    }
+
     /// See [`vkBindBufferMemory`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkBindBufferMemory)
     pub fn bind_memory(&self, memory: DeviceMemory, offset: u64) -> Result<(), core::Error> {
         let res = unsafe {
@@ -140,6 +156,7 @@ struct Inner {
     owned: bool,
     device: Device,
     allocator: Option<AllocatorHelper>,
+    memory: Option<DeviceMemory>,
 }
 
 impl Drop for Inner {
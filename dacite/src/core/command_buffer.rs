@@ -19,23 +19,76 @@ use VulkanObject;
 use core::{
     self,
     Buffer,
+    CommandBufferRecorder,
     CommandPool,
     DescriptorSet,
     Event,
+    Framebuffer,
     Image,
     Pipeline,
     PipelineLayout,
     QueryPool,
+    RenderPass,
 };
 use ext_debug_marker;
 use libc::c_void;
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
+use std::error;
+use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::mem;
 use std::ptr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, Weak};
+use sync;
 use vks;
 
+/// Error type for [`CommandBuffer::generate_mipmaps`](struct.CommandBuffer.html#method.generate_mipmaps).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MipmapError {
+    /// The format does not support `FormatFeatureFlags::BLIT_SRC` and
+    /// `FormatFeatureFlags::BLIT_DST` with the tiling of the image.
+    BlitNotSupported(core::Format),
+
+    /// The format does not support `FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR` with the
+    /// tiling of the image.
+    LinearFilterNotSupported(core::Format),
+
+    /// The format has a depth or stencil aspect. Blits of such formats must use
+    /// `Filter::Nearest`, which is not suitable for generating mip levels.
+    DepthStencilFormat(core::Format),
+
+    /// The extent, mip levels and tiling of the image are unknown, because it was not created
+    /// with [`Device::create_image`](struct.Device.html#method.create_image).
+    UnknownImageProperties,
+}
+
+impl fmt::Display for MipmapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MipmapError::BlitNotSupported(format) => write!(f, "Format {:?} does not support blits", format),
+            MipmapError::LinearFilterNotSupported(format) => write!(f, "Format {:?} does not support linear filtering", format),
+            MipmapError::DepthStencilFormat(format) => write!(f, "Format {:?} has a depth or stencil aspect", format),
+            MipmapError::UnknownImageProperties => write!(f, "The properties of the image are unknown"),
+        }
+    }
+}
+
+impl error::Error for MipmapError {
+    fn description(&self) -> &str {
+        match *self {
+            MipmapError::BlitNotSupported(_) => "BlitNotSupported",
+            MipmapError::LinearFilterNotSupported(_) => "LinearFilterNotSupported",
+            MipmapError::DepthStencilFormat(_) => "DepthStencilFormat",
+            MipmapError::UnknownImageProperties => "UnknownImageProperties",
+        }
+    }
+}
+
 /// See [`VkCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkCommandBuffer)
+///
+/// Objects referenced by recorded commands are kept alive, until the command buffer is begun again
+/// or reset. Command buffers recorded with `ONE_TIME_SUBMIT` also release them, once the `Fence`
+/// of their submission is observed to be signaled.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CommandBuffer(Arc<Inner>);
 
@@ -91,10 +144,14 @@ impl FromNativeObject for CommandBuffer {
 
 impl CommandBuffer {
     pub(crate) fn new(handle: vks::vk::VkCommandBuffer, owned: bool, command_pool: CommandPool) -> Self {
+        let resources = Arc::new(Mutex::new(Resources::default()));
+        command_pool.add_command_buffer_resources(Arc::downgrade(&resources));
+
         CommandBuffer(Arc::new(Inner {
             handle: handle,
             owned: owned,
             command_pool: command_pool,
+            resources: resources,
         }))
     }
 
@@ -108,6 +165,75 @@ impl CommandBuffer {
         self.0.command_pool.loader()
     }
 
+    /// Returns a handle to the current recording, if it was recorded with `ONE_TIME_SUBMIT`.
+    ///
+    /// Referenced objects are only released after a submission for such command buffers, because
+    /// all others may still be submitted again.
+    pub(crate) fn submission(&self) -> Option<Submission> {
+        let resources = self.0.resources.lock().unwrap();
+        if resources.one_time_submit {
+            Some(Submission {
+                resources: Arc::downgrade(&self.0.resources),
+                generation: resources.generation,
+            })
+        }
+        else {
+            None
+        }
+    }
+
+    fn keep_alive<I>(&self, objects: I)
+        where I: IntoIterator<Item = Resource>
+    {
+        self.0.resources.lock().unwrap().objects.extend(objects);
+    }
+
+    fn keep_alive_barriers(&self, buffer_memory_barriers: Option<&[core::BufferMemoryBarrier]>, image_memory_barriers: Option<&[core::ImageMemoryBarrier]>) {
+        if let Some(buffer_memory_barriers) = buffer_memory_barriers {
+            self.keep_alive(buffer_memory_barriers.iter().map(|b| Resource::Buffer(b.buffer.clone())));
+        }
+
+        if let Some(image_memory_barriers) = image_memory_barriers {
+            self.keep_alive(image_memory_barriers.iter().map(|i| Resource::Image(i.image.clone())));
+        }
+    }
+
+    /// Records the new layouts of tracked images.
+    fn track_image_layouts(&self, dst_stage_mask: core::PipelineStageFlags, image_memory_barriers: Option<&[core::ImageMemoryBarrier]>) {
+        if let Some(image_memory_barriers) = image_memory_barriers {
+            for barrier in image_memory_barriers {
+                if let Some(ref mut tracker) = *barrier.image.layout_tracker() {
+                    tracker.set(&barrier.subresource_range, barrier.new_layout, dst_stage_mask, barrier.dst_access_mask);
+                }
+            }
+        }
+    }
+
+    /// Records the final layouts of the attachments of a render pass.
+    fn track_render_pass_layouts(&self, render_pass: &RenderPass, framebuffer: &Framebuffer) {
+        for (attachment, &final_layout) in framebuffer.attachments().iter().zip(render_pass.final_layouts()) {
+            if let Some((image, subresource_range)) = attachment.image() {
+                if let Some(ref mut tracker) = *image.layout_tracker() {
+                    let access_mask = if subresource_range.aspect_mask.contains(core::ImageAspectFlags::COLOR) {
+                        core::AccessFlags::COLOR_ATTACHMENT_WRITE
+                    }
+                    else {
+                        core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE
+                    };
+
+                    tracker.set(subresource_range, final_layout, core::PipelineStageFlags::ALL_GRAPHICS, access_mask);
+                }
+            }
+        }
+    }
+
+    fn release_resources(&self, one_time_submit: bool) {
+        let objects = self.0.resources.lock().unwrap().release(one_time_submit);
+        drop(objects);
+    }
+
+    /// Objects referenced by previously recorded commands are released.
+    ///
     /// See [`vkBeginCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkBeginCommandBuffer)
     pub fn begin(&self, begin_info: &core::CommandBufferBeginInfo) -> Result<(), core::Error> {
         let begin_info_wrapper = core::VkCommandBufferBeginInfoWrapper::new(begin_info, true);
@@ -117,6 +243,7 @@ impl CommandBuffer {
         };
 
         if res == vks::vk::VK_SUCCESS {
+            self.release_resources(begin_info.flags.contains(core::CommandBufferUsageFlags::ONE_TIME_SUBMIT));
             Ok(())
         }
         else {
@@ -124,6 +251,19 @@ impl CommandBuffer {
         }
     }
 
+    /// Begins recording and returns a `CommandBufferRecorder`.
+    ///
+    /// Unlike `begin`, the returned recorder ensures at compile time, that commands are only
+    /// recorded inside or outside of a render pass, as required. Recording is ended, when the
+    /// recorder is dropped.
+    ///
+    /// See [`vkBeginCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkBeginCommandBuffer)
+    pub fn record<'a>(&'a self, begin_info: &core::CommandBufferBeginInfo) -> Result<CommandBufferRecorder<'a>, core::Error> {
+        self.begin(begin_info)?;
+        let render_pass_continue = begin_info.flags.contains(core::CommandBufferUsageFlags::RENDER_PASS_CONTINUE);
+        Ok(CommandBufferRecorder::new(self, render_pass_continue))
+    }
+
     /// See [`vkEndCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkEndCommandBuffer)
     pub fn end(&self) -> Result<(), core::Error> {
         let res = unsafe {
@@ -138,6 +278,8 @@ impl CommandBuffer {
         }
     }
 
+    /// Objects referenced by previously recorded commands are released.
+    ///
     /// See [`vkResetCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkResetCommandBuffer)
     pub fn reset(&self, flags: core::CommandBufferResetFlags) -> Result<(), core::Error> {
         let res = unsafe {
@@ -145,6 +287,7 @@ impl CommandBuffer {
         };
 
         if res == vks::vk::VK_SUCCESS {
+            self.release_resources(false);
             Ok(())
         }
         else {
@@ -154,6 +297,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdBindPipeline`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBindPipeline)
     pub fn bind_pipeline(&self, pipeline_bind_point: core::PipelineBindPoint, pipeline: &Pipeline) {
+        self.keep_alive(Some(Resource::Pipeline(pipeline.clone())));
+
         unsafe {
             self.loader().vk.vkCmdBindPipeline(self.handle(), pipeline_bind_point.into(), pipeline.handle());
         }
@@ -226,6 +371,9 @@ impl CommandBuffer {
 
     /// See [`vkCmdBindDescriptorSets`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBindDescriptorSets)
     pub fn bind_descriptor_sets(&self, pipeline_bind_point: core::PipelineBindPoint, layout: &PipelineLayout, first_set: u32, descriptor_sets: &[DescriptorSet], dynamic_offsets: Option<&[u32]>) {
+        self.keep_alive(Some(Resource::PipelineLayout(layout.clone())));
+        self.keep_alive(descriptor_sets.iter().cloned().map(Resource::DescriptorSet));
+
         let descriptor_sets: Vec<_> = descriptor_sets.iter().map(DescriptorSet::handle).collect();
 
         let (dynamic_offsets_count, dynamic_offsets_ptr) = match dynamic_offsets {
@@ -240,6 +388,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdBindIndexBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBindIndexBuffer)
     pub fn bind_index_buffer(&self, buffer: &Buffer, offset: u64, index_type: core::IndexType) {
+        self.keep_alive(Some(Resource::Buffer(buffer.clone())));
+
         unsafe {
             self.loader().vk.vkCmdBindIndexBuffer(self.handle(), buffer.handle(), offset, index_type.into());
         }
@@ -247,6 +397,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdBindVertexBuffers`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBindVertexBuffers)
     pub fn bind_vertex_buffers(&self, first_binding: u32, buffers: &[Buffer], offsets: &[u64]) {
+        self.keep_alive(buffers.iter().cloned().map(Resource::Buffer));
+
         let buffers: Vec<_> = buffers.iter().map(Buffer::handle).collect();
         unsafe {
             self.loader().vk.vkCmdBindVertexBuffers(self.handle(), first_binding, buffers.len() as u32, buffers.as_ptr(), offsets.as_ptr());
@@ -269,6 +421,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdDrawIndirect`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndirect)
     pub fn draw_indirect(&self, buffer: &Buffer, offset: u64, draw_count: u32, stride: u32) {
+        self.keep_alive(Some(Resource::Buffer(buffer.clone())));
+
         unsafe {
             self.loader().vk.vkCmdDrawIndirect(self.handle(), buffer.handle(), offset, draw_count, stride);
         }
@@ -276,6 +430,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdDrawIndexedIndirect`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndexedIndirect)
     pub fn draw_indexed_indirect(&self, buffer: &Buffer, offset: u64, draw_count: u32, stride: u32) {
+        self.keep_alive(Some(Resource::Buffer(buffer.clone())));
+
         unsafe {
             self.loader().vk.vkCmdDrawIndexedIndirect(self.handle(), buffer.handle(), offset, draw_count, stride);
         }
@@ -290,6 +446,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdDispatchIndirect`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDispatchIndirect)
     pub fn dispatch_indirect(&self, buffer: &Buffer, offset: u64) {
+        self.keep_alive(Some(Resource::Buffer(buffer.clone())));
+
         unsafe {
             self.loader().vk.vkCmdDispatchIndirect(self.handle(), buffer.handle(), offset);
         }
@@ -297,6 +455,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdCopyBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyBuffer)
     pub fn copy_buffer(&self, src_buffer: &Buffer, dst_buffer: &Buffer, regions: &[core::BufferCopy]) {
+        self.keep_alive(vec![Resource::Buffer(src_buffer.clone()), Resource::Buffer(dst_buffer.clone())]);
+
         let regions: Vec<_> = regions.iter().map(From::from).collect();
         unsafe {
             self.loader().vk.vkCmdCopyBuffer(self.handle(), src_buffer.handle(), dst_buffer.handle(), regions.len() as u32, regions.as_ptr());
@@ -305,6 +465,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdCopyImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyImage)
     pub fn copy_image(&self, src_image: &Image, src_image_layout: core::ImageLayout, dst_image: &Image, dst_image_layout: core::ImageLayout, regions: &[core::ImageCopy]) {
+        self.keep_alive(vec![Resource::Image(src_image.clone()), Resource::Image(dst_image.clone())]);
+
         let regions: Vec<_> = regions.iter().map(From::from).collect();
         unsafe {
             self.loader().vk.vkCmdCopyImage(self.handle(), src_image.handle(), src_image_layout.into(), dst_image.handle(), dst_image_layout.into(), regions.len() as u32, regions.as_ptr());
@@ -313,6 +475,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdBlitImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBlitImage)
     pub fn blit_image(&self, src_image: &Image, src_image_layout: core::ImageLayout, dst_image: &Image, dst_image_layout: core::ImageLayout, regions: &[core::ImageBlit], filter: core::Filter) {
+        self.keep_alive(vec![Resource::Image(src_image.clone()), Resource::Image(dst_image.clone())]);
+
         let regions: Vec<_> = regions.iter().map(From::from).collect();
         unsafe {
             self.loader().vk.vkCmdBlitImage(self.handle(), src_image.handle(), src_image_layout.into(), dst_image.handle(), dst_image_layout.into(), regions.len() as u32, regions.as_ptr(), filter.into());
@@ -321,6 +485,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdCopyBufferToImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyBufferToImage)
     pub fn copy_buffer_to_image(&self, src_buffer: &Buffer, dst_image: &Image, dst_image_layout: core::ImageLayout, regions: &[core::BufferImageCopy]) {
+        self.keep_alive(vec![Resource::Buffer(src_buffer.clone()), Resource::Image(dst_image.clone())]);
+
         let regions: Vec<_> = regions.iter().map(From::from).collect();
         unsafe {
             self.loader().vk.vkCmdCopyBufferToImage(self.handle(), src_buffer.handle(), dst_image.handle(), dst_image_layout.into(), regions.len() as u32, regions.as_ptr());
@@ -329,6 +495,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdCopyImageToBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyImageToBuffer)
     pub fn copy_image_to_buffer(&self, src_image: &Image, src_image_layout: core::ImageLayout, dst_buffer: &Buffer, regions: &[core::BufferImageCopy]) {
+        self.keep_alive(vec![Resource::Image(src_image.clone()), Resource::Buffer(dst_buffer.clone())]);
+
         let regions: Vec<_> = regions.iter().map(From::from).collect();
         unsafe {
             self.loader().vk.vkCmdCopyImageToBuffer(self.handle(), src_image.handle(), src_image_layout.into(), dst_buffer.handle(), regions.len() as u32, regions.as_ptr());
@@ -337,6 +505,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdUpdateBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdUpdateBuffer)
     pub fn update_buffer(&self, dst_buffer: &Buffer, dst_offset: u64, data: &[u8]) {
+        self.keep_alive(Some(Resource::Buffer(dst_buffer.clone())));
+
         unsafe {
             self.loader().vk.vkCmdUpdateBuffer(self.handle(), dst_buffer.handle(), dst_offset, data.len() as u64, data.as_ptr() as *const _);
         }
@@ -344,6 +514,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdFillBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdFillBuffer)
     pub fn fill_buffer(&self, dst_buffer: &Buffer, dst_offset: u64, size: core::OptionalDeviceSize, data: u32) {
+        self.keep_alive(Some(Resource::Buffer(dst_buffer.clone())));
+
         unsafe {
             self.loader().vk.vkCmdFillBuffer(self.handle(), dst_buffer.handle(), dst_offset, size.into(), data);
         }
@@ -351,6 +523,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdClearColorImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdClearColorImage)
     pub fn clear_color_image(&self, image: &Image, image_layout: core::ImageLayout, color: &core::ClearColorValue, ranges: &[core::ImageSubresourceRange]) {
+        self.keep_alive(Some(Resource::Image(image.clone())));
+
         let color = color.into();
         let ranges: Vec<_> = ranges.iter().map(From::from).collect();
         unsafe {
@@ -360,6 +534,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdClearDepthStencilImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdClearDepthStencilImage)
     pub fn clear_depth_stencil_image(&self, image: &Image, image_layout: core::ImageLayout, depth_stencil: &core::ClearDepthStencilValue, ranges: &[core::ImageSubresourceRange]) {
+        self.keep_alive(Some(Resource::Image(image.clone())));
+
         let depth_stencil = depth_stencil.into();
         let ranges: Vec<_> = ranges.iter().map(From::from).collect();
         unsafe {
@@ -378,6 +554,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdResolveImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdResolveImage)
     pub fn resolve_image(&self, src_image: &Image, src_image_layout: core::ImageLayout, dst_image: &Image, dst_image_layout: core::ImageLayout, regions: &[core::ImageResolve]) {
+        self.keep_alive(vec![Resource::Image(src_image.clone()), Resource::Image(dst_image.clone())]);
+
         let regions: Vec<_> = regions.iter().map(From::from).collect();
         unsafe {
             self.loader().vk.vkCmdResolveImage(self.handle(), src_image.handle(), src_image_layout.into(), dst_image.handle(), dst_image_layout.into(), regions.len() as u32, regions.as_ptr());
@@ -386,6 +564,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdSetEvent`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetEvent)
     pub fn set_event(&self, event: &Event, stage_mask: core::PipelineStageFlags) {
+        self.keep_alive(Some(Resource::Event(event.clone())));
+
         unsafe {
             self.loader().vk.vkCmdSetEvent(self.handle(), event.handle(), stage_mask.bits());
         }
@@ -393,6 +573,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdResetEvent`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdResetEvent)
     pub fn reset_event(&self, event: &Event, stage_mask: core::PipelineStageFlags) {
+        self.keep_alive(Some(Resource::Event(event.clone())));
+
         unsafe {
             self.loader().vk.vkCmdResetEvent(self.handle(), event.handle(), stage_mask.bits());
         }
@@ -400,6 +582,10 @@ impl CommandBuffer {
 
     /// See [`vkCmdWaitEvents`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdWaitEvents)
     pub fn wait_events(&self, events: &[Event], src_stage_mask: core::PipelineStageFlags, dst_stage_mask: core::PipelineStageFlags, memory_barriers: Option<&[core::MemoryBarrier]>, buffer_memory_barriers: Option<&[core::BufferMemoryBarrier]>, image_memory_barriers: Option<&[core::ImageMemoryBarrier]>) {
+        self.keep_alive(events.iter().cloned().map(Resource::Event));
+        self.keep_alive_barriers(buffer_memory_barriers, image_memory_barriers);
+        self.track_image_layouts(dst_stage_mask, image_memory_barriers);
+
         let events: Vec<_> = events.iter().map(Event::handle).collect();
 
         #[allow(unused_variables)]
@@ -442,6 +628,9 @@ impl CommandBuffer {
 
     /// See [`vkCmdPipelineBarrier`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdPipelineBarrier)
     pub fn pipeline_barrier(&self, src_stage_mask: core::PipelineStageFlags, dst_stage_mask: core::PipelineStageFlags, dependency_flags: core::DependencyFlags, memory_barriers: Option<&[core::MemoryBarrier]>, buffer_memory_barriers: Option<&[core::BufferMemoryBarrier]>, image_memory_barriers: Option<&[core::ImageMemoryBarrier]>) {
+        self.keep_alive_barriers(buffer_memory_barriers, image_memory_barriers);
+        self.track_image_layouts(dst_stage_mask, image_memory_barriers);
+
         #[allow(unused_variables)]
         let (memory_barriers_count, memory_barriers_ptr, vk_memory_barriers, memory_barriers_wrappers) = match memory_barriers {
             Some(memory_barriers) => {
@@ -480,8 +669,154 @@ impl CommandBuffer {
         }
     }
 
+    /// Records a pipeline barrier described by access types.
+    ///
+    /// Pipeline stages, access masks and image layouts are derived with
+    /// [`sync::PipelineBarrier::new`](../sync/struct.PipelineBarrier.html#method.new).
+    pub fn barrier(&self, global_barrier: Option<&sync::GlobalBarrier>, buffer_barriers: &[sync::BufferBarrier], image_barriers: &[sync::ImageBarrier]) {
+        let barrier = sync::PipelineBarrier::new(global_barrier, buffer_barriers, image_barriers);
+        let memory_barriers: Vec<_> = barrier.memory_barrier.into_iter().collect();
+
+        self.pipeline_barrier(barrier.src_stage_mask, barrier.dst_stage_mask, core::DependencyFlags::empty(), non_empty(&memory_barriers), non_empty(&barrier.buffer_memory_barriers), non_empty(&barrier.image_memory_barriers));
+    }
+
+    /// Transitions all mip levels and array layers of `image` to `layout`.
+    ///
+    /// See [`transition_range_to`](#method.transition_range_to).
+    pub fn transition_to(&self, image: &Image, layout: core::ImageLayout, dst_stage_mask: core::PipelineStageFlags) -> Result<(), core::LayoutTrackingError> {
+        let subresource_range = image.layout_tracker().as_ref().ok_or(core::LayoutTrackingError::NotEnabled)?.full_range();
+        self.transition_range_to(image, &subresource_range, layout, dst_stage_mask)
+    }
+
+    /// Transitions `subresource_range` of `image` to `layout`.
+    ///
+    /// The old layouts and the source stage and access masks are taken from the layouts tracked by
+    /// `image` (see [`Image::enable_layout_tracking`](struct.Image.html#method.enable_layout_tracking)).
+    /// The destination access mask is derived from `layout`, so `dst_stage_mask` must include a
+    /// stage, which can perform those accesses. Subresources already in `layout` are skipped.
+    ///
+    /// All transitions are recorded with a single pipeline barrier. Nothing is recorded, if the
+    /// whole range is already in `layout`.
+    ///
+    /// Returns `LayoutTrackingError::NotEnabled`, if layout tracking is not enabled for `image`.
+    pub fn transition_range_to(&self, image: &Image, subresource_range: &core::ImageSubresourceRange, layout: core::ImageLayout, dst_stage_mask: core::PipelineStageFlags) -> Result<(), core::LayoutTrackingError> {
+        let (src_stage_mask, transitions) = image.layout_tracker().as_ref().ok_or(core::LayoutTrackingError::NotEnabled)?.transition(subresource_range, layout);
+        if transitions.is_empty() {
+            return Ok(());
+        }
+
+        let dst_access_mask = core::image_layout_tracker::layout_access_mask(layout);
+        let image_memory_barriers: Vec<_> = transitions.into_iter().map(|(subresource_range, old_layout, src_access_mask)| {
+            core::ImageMemoryBarrier {
+                src_access_mask: src_access_mask,
+                dst_access_mask: dst_access_mask,
+                old_layout: old_layout,
+                new_layout: layout,
+                src_queue_family_index: core::QueueFamilyIndex::Ignored,
+                dst_queue_family_index: core::QueueFamilyIndex::Ignored,
+                image: image.clone(),
+                subresource_range: subresource_range,
+                chain: None,
+            }
+        }).collect();
+
+        self.pipeline_barrier(src_stage_mask, dst_stage_mask, core::DependencyFlags::empty(), None, None, Some(&image_memory_barriers));
+        Ok(())
+    }
+
+    /// Generates all mip levels of `image` from its base level with a chain of linear blits.
+    ///
+    /// The format, extent, mip levels, array layers and tiling are taken from the
+    /// `ImageCreateInfo`, with which `image` was created. The base level must be in
+    /// `base_level_layout` and the contents of all other levels are discarded. Afterwards, all mip
+    /// levels are in `final_layout` and visible to `dst_stage_mask`.
+    ///
+    /// Nothing is recorded and an error is returned, if the format of `image` is a depth/stencil
+    /// format, does not support blits or linear filtering with the tiling of `image`, or if
+    /// `image` was not created with [`Device::create_image`](struct.Device.html#method.create_image).
+    pub fn generate_mipmaps(&self, image: &Image, base_level_layout: core::ImageLayout, final_layout: core::ImageLayout, dst_stage_mask: core::PipelineStageFlags) -> Result<(), MipmapError> {
+        let properties = image.properties().ok_or(MipmapError::UnknownImageProperties)?;
+        let format = properties.format;
+        if format.aspect_mask() != core::ImageAspectFlags::COLOR {
+            return Err(MipmapError::DepthStencilFormat(format));
+        }
+
+        let format_properties = self.0.command_pool.device().physical_device().get_format_properties(format);
+        let features = match properties.tiling {
+            core::ImageTiling::Linear => format_properties.linear_tiling_features,
+            _ => format_properties.optimal_tiling_features,
+        };
+
+        if !features.contains(core::FormatFeatureFlags::BLIT_SRC | core::FormatFeatureFlags::BLIT_DST) {
+            return Err(MipmapError::BlitNotSupported(format));
+        }
+
+        if !features.contains(core::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR) {
+            return Err(MipmapError::LinearFilterNotSupported(format));
+        }
+
+        let mip_levels = properties.mip_levels;
+        let layer_count = properties.array_layers;
+        let barrier = |base_mip_level, level_count, src_access_mask, dst_access_mask, old_layout, new_layout| {
+            core::ImageMemoryBarrier {
+                src_access_mask: src_access_mask,
+                dst_access_mask: dst_access_mask,
+                old_layout: old_layout,
+                new_layout: new_layout,
+                src_queue_family_index: core::QueueFamilyIndex::Ignored,
+                dst_queue_family_index: core::QueueFamilyIndex::Ignored,
+                image: image.clone(),
+                subresource_range: core::ImageSubresourceRange::new(format, base_mip_level, level_count, 0, layer_count),
+                chain: None,
+            }
+        };
+
+        let final_access_mask = core::image_layout_tracker::layout_access_mask(final_layout);
+        if mip_levels <= 1 {
+            let image_memory_barriers = [barrier(0, 1, core::AccessFlags::MEMORY_WRITE, final_access_mask, base_level_layout, final_layout)];
+            self.pipeline_barrier(core::PipelineStageFlags::ALL_COMMANDS, dst_stage_mask, core::DependencyFlags::empty(), None, None, Some(&image_memory_barriers));
+            return Ok(());
+        }
+
+        let image_memory_barriers = [
+            barrier(0, 1, core::AccessFlags::MEMORY_WRITE, core::AccessFlags::TRANSFER_READ, base_level_layout, core::ImageLayout::TransferSrcOptimal),
+            barrier(1, mip_levels - 1, core::AccessFlags::empty(), core::AccessFlags::TRANSFER_WRITE, core::ImageLayout::Undefined, core::ImageLayout::TransferDstOptimal),
+        ];
+        self.pipeline_barrier(core::PipelineStageFlags::ALL_COMMANDS, core::PipelineStageFlags::TRANSFER, core::DependencyFlags::empty(), None, None, Some(&image_memory_barriers));
+
+        let mip_offset = |mip_level: u32| {
+            let extent = properties.extent;
+            core::Offset3D::new(cmp::max(extent.width >> mip_level, 1) as i32, cmp::max(extent.height >> mip_level, 1) as i32, cmp::max(extent.depth >> mip_level, 1) as i32)
+        };
+
+        for mip_level in 1..mip_levels {
+            let region = core::ImageBlit {
+                src_subresource: core::ImageSubresourceLayers::new(format, mip_level - 1, 0, layer_count),
+                src_offsets: [core::Offset3D::zero(), mip_offset(mip_level - 1)],
+                dst_subresource: core::ImageSubresourceLayers::new(format, mip_level, 0, layer_count),
+                dst_offsets: [core::Offset3D::zero(), mip_offset(mip_level)],
+            };
+            self.blit_image(image, core::ImageLayout::TransferSrcOptimal, image, core::ImageLayout::TransferDstOptimal, &[region], core::Filter::Linear);
+
+            if mip_level + 1 < mip_levels {
+                let image_memory_barriers = [barrier(mip_level, 1, core::AccessFlags::TRANSFER_WRITE, core::AccessFlags::TRANSFER_READ, core::ImageLayout::TransferDstOptimal, core::ImageLayout::TransferSrcOptimal)];
+                self.pipeline_barrier(core::PipelineStageFlags::TRANSFER, core::PipelineStageFlags::TRANSFER, core::DependencyFlags::empty(), None, None, Some(&image_memory_barriers));
+            }
+        }
+
+        let image_memory_barriers = [
+            barrier(0, mip_levels - 1, core::AccessFlags::empty(), final_access_mask, core::ImageLayout::TransferSrcOptimal, final_layout),
+            barrier(mip_levels - 1, 1, core::AccessFlags::TRANSFER_WRITE, final_access_mask, core::ImageLayout::TransferDstOptimal, final_layout),
+        ];
+        self.pipeline_barrier(core::PipelineStageFlags::TRANSFER, dst_stage_mask, core::DependencyFlags::empty(), None, None, Some(&image_memory_barriers));
+
+        Ok(())
+    }
+
     /// See [`vkCmdBeginQuery`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBeginQuery)
     pub fn begin_query(&self, query_pool: &QueryPool, query: u32, flags: core::QueryControlFlags) {
+        self.keep_alive(Some(Resource::QueryPool(query_pool.clone())));
+
         unsafe {
             self.loader().vk.vkCmdBeginQuery(self.handle(), query_pool.handle(), query, flags.bits());
         }
@@ -489,6 +824,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdEndQuery`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdEndQuery)
     pub fn end_query(&self, query_pool: &QueryPool, query: u32) {
+        self.keep_alive(Some(Resource::QueryPool(query_pool.clone())));
+
         unsafe {
             self.loader().vk.vkCmdEndQuery(self.handle(), query_pool.handle(), query);
         }
@@ -496,6 +833,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdResetQueryPool`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdResetQueryPool)
     pub fn reset_query_pool(&self, query_pool: &QueryPool, first_query: u32, query_count: u32) {
+        self.keep_alive(Some(Resource::QueryPool(query_pool.clone())));
+
         unsafe {
             self.loader().vk.vkCmdResetQueryPool(self.handle(), query_pool.handle(), first_query, query_count);
         }
@@ -503,6 +842,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdWriteTimestamp`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdWriteTimestamp)
     pub fn write_timestamp(&self, pipeline_stage: core::PipelineStageFlagBits, query_pool: &QueryPool, query: u32) {
+        self.keep_alive(Some(Resource::QueryPool(query_pool.clone())));
+
         unsafe {
             self.loader().vk.vkCmdWriteTimestamp(self.handle(), pipeline_stage.bit(), query_pool.handle(), query);
         }
@@ -511,6 +852,8 @@ impl CommandBuffer {
     /// See [`vkCmdCopyQueryPoolResults`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyQueryPoolResults)
     #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
     pub fn copy_query_pool_results(&self, query_pool: &QueryPool, first_query: u32, query_count: u32, dst_buffer: &Buffer, dst_offset: u64, stride: u64, flags: core::QueryResultFlags) {
+        self.keep_alive(vec![Resource::QueryPool(query_pool.clone()), Resource::Buffer(dst_buffer.clone())]);
+
         unsafe {
             self.loader().vk.vkCmdCopyQueryPoolResults(self.handle(), query_pool.handle(), first_query, query_count, dst_buffer.handle(), dst_offset, stride, flags.bits());
         }
@@ -518,6 +861,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdPushConstants`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdPushConstants)
     pub fn push_constants(&self, layout: &PipelineLayout, stage_flags: core::ShaderStageFlags, offset: u32, values: &[u8]) {
+        self.keep_alive(Some(Resource::PipelineLayout(layout.clone())));
+
         unsafe {
             self.loader().vk.vkCmdPushConstants(self.handle(), layout.handle(), stage_flags.bits(), offset, values.len() as u32, values.as_ptr() as *const c_void);
         }
@@ -525,6 +870,9 @@ impl CommandBuffer {
 
     /// See [`vkCmdBeginRenderPass`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBeginRenderPass)
     pub fn begin_render_pass(&self, render_pass_begin: &core::RenderPassBeginInfo, contents: core::SubpassContents) {
+        self.keep_alive(vec![Resource::RenderPass(render_pass_begin.render_pass.clone()), Resource::Framebuffer(render_pass_begin.framebuffer.clone())]);
+        self.track_render_pass_layouts(&render_pass_begin.render_pass, &render_pass_begin.framebuffer);
+
         let render_pass_begin_wrapper = core::VkRenderPassBeginInfoWrapper::new(render_pass_begin, true);
         unsafe {
             self.loader().vk.vkCmdBeginRenderPass(self.handle(), &render_pass_begin_wrapper.vks_struct, contents.into());
@@ -547,6 +895,8 @@ impl CommandBuffer {
 
     /// See [`vkCmdExecuteCommands`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdExecuteCommands)
     pub fn execute_commands(&self, command_buffers: &[CommandBuffer]) {
+        self.keep_alive(command_buffers.iter().cloned().map(Resource::CommandBuffer));
+
         let command_buffers: Vec<_> = command_buffers.iter().map(CommandBuffer::handle).collect();
         unsafe {
             self.loader().vk.vkCmdExecuteCommands(self.handle(), command_buffers.len() as u32, command_buffers.as_ptr());
@@ -556,6 +906,8 @@ impl CommandBuffer {
     /// See [`vkCmdDrawIndirectCountAMD`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndirectCountAMD)
     /// and extension [`VK_AMD_draw_indirect_count`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_AMD_draw_indirect_count)
     pub fn draw_indirect_count_amd(&self, buffer: &Buffer, offset: u64, count_buffer: &Buffer, count_buffer_offset: u64, max_draw_count: u32, stride: u32) {
+        self.keep_alive(vec![Resource::Buffer(buffer.clone()), Resource::Buffer(count_buffer.clone())]);
+
         unsafe {
             self.loader().amd_draw_indirect_count.vkCmdDrawIndirectCountAMD(self.handle(), buffer.handle(), offset, count_buffer.handle(), count_buffer_offset, max_draw_count, stride);
         }
@@ -564,6 +916,8 @@ impl CommandBuffer {
     /// See [`vkCmdDrawIndexedIndirectCountAMD`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndexedIndirectCountAMD)
     /// and extension [`VK_AMD_draw_indirect_count`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_AMD_draw_indirect_count)
     pub fn draw_indexed_indirect_count_amd(&self, buffer: &Buffer, offset: u64, count_buffer: &Buffer, count_buffer_offset: u64, max_draw_count: u32, stride: u32) {
+        self.keep_alive(vec![Resource::Buffer(buffer.clone()), Resource::Buffer(count_buffer.clone())]);
+
         unsafe {
             self.loader().amd_draw_indirect_count.vkCmdDrawIndexedIndirectCountAMD(self.handle(), buffer.handle(), offset, count_buffer.handle(), count_buffer_offset, max_draw_count, stride);
         }
@@ -595,6 +949,83 @@ struct Inner {
     handle: vks::vk::VkCommandBuffer,
     owned: bool,
     command_pool: CommandPool,
+    resources: Arc<Mutex<Resources>>,
+}
+
+/// Objects referenced by the recorded commands of a `CommandBuffer`.
+#[derive(Debug, Default)]
+pub(crate) struct Resources {
+    generation: u64,
+    one_time_submit: bool,
+    objects: Vec<Resource>,
+}
+
+impl Resources {
+    /// Starts a new recording generation and returns the objects referenced by the previous one.
+    ///
+    /// The returned objects should be dropped after the lock on `Resources` has been released.
+    pub(crate) fn release(&mut self, one_time_submit: bool) -> Vec<Resource> {
+        self.generation += 1;
+        self.one_time_submit = one_time_submit;
+        mem::replace(&mut self.objects, Vec::new())
+    }
+}
+
+/// A submitted recording of a `CommandBuffer`, which does not keep the command buffer alive.
+#[derive(Debug, Clone)]
+pub(crate) struct Submission {
+    resources: Weak<Mutex<Resources>>,
+    generation: u64,
+}
+
+impl Submission {
+    /// Releases the objects referenced by the submitted recording, unless the command buffer has
+    /// been begun or reset since.
+    pub fn completed(&self) {
+        let resources = match self.resources.upgrade() {
+            Some(resources) => resources,
+            None => return,
+        };
+
+        let objects = {
+            let mut resources = resources.lock().unwrap();
+            if resources.generation != self.generation {
+                return;
+            }
+
+            mem::replace(&mut resources.objects, Vec::new())
+        };
+
+        drop(objects);
+    }
+
+    /// Returns `true`, if there is nothing left to release.
+    pub fn is_released(&self) -> bool {
+        match self.resources.upgrade() {
+            Some(resources) => {
+                let resources = resources.lock().unwrap();
+                (resources.generation != self.generation) || resources.objects.is_empty()
+            }
+
+            None => true,
+        }
+    }
+}
+
+/// An object, which is kept alive by a `CommandBuffer`.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub(crate) enum Resource {
+    Buffer(Buffer),
+    CommandBuffer(CommandBuffer),
+    DescriptorSet(DescriptorSet),
+    Event(Event),
+    Framebuffer(Framebuffer),
+    Image(Image),
+    Pipeline(Pipeline),
+    PipelineLayout(PipelineLayout),
+    QueryPool(QueryPool),
+    RenderPass(RenderPass),
 }
 
 impl Drop for Inner {
@@ -640,3 +1071,12 @@ impl Hash for Inner {
         self.handle.hash(state);
     }
 }
+
+fn non_empty<T>(slice: &[T]) -> Option<&[T]> {
+    if !slice.is_empty() {
+        Some(slice)
+    }
+    else {
+        None
+    }
+}
@@ -0,0 +1,342 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core::{
+    self,
+    Buffer,
+    CommandBuffer,
+    DescriptorSet,
+    Event,
+    Image,
+    Pipeline,
+    PipelineLayout,
+    QueryPool,
+};
+use ext_debug_marker;
+use sync;
+
+/// Generates methods, which forward to the `CommandBuffer` method of the same name.
+macro_rules! forward_commands {
+    ($( $(#[$attr:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*); )*) => {
+        $(
+            $(#[$attr])*
+            #[inline]
+            pub fn $name(&self $(, $arg: $ty)*) {
+                self.command_buffer().$name($($arg),*)
+            }
+        )*
+    }
+}
+
+/// Generates methods for commands, which are valid both inside and outside of a render pass.
+macro_rules! common_commands {
+    () => {
+        forward_commands! {
+            /// See [`vkCmdBindPipeline`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBindPipeline)
+            fn bind_pipeline(&self, pipeline_bind_point: core::PipelineBindPoint, pipeline: &Pipeline);
+
+            /// See [`vkCmdSetViewport`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetViewport)
+            fn set_viewport(&self, first_viewport: u32, viewports: &[core::Viewport]);
+
+            /// See [`vkCmdSetScissor`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetScissor)
+            fn set_scissor(&self, first_scissor: u32, scissors: &[core::Rect2D]);
+
+            /// See [`vkCmdSetLineWidth`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetLineWidth)
+            fn set_line_width(&self, line_width: f32);
+
+            /// See [`vkCmdSetDepthBias`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetDepthBias)
+            fn set_depth_bias(&self, depth_bias_constant_factor: f32, depth_bias_clamp: f32, depth_bias_slope_factor: f32);
+
+            /// See [`vkCmdSetBlendConstants`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetBlendConstants)
+            fn set_blend_constants(&self, blend_constants: &[f32]);
+
+            /// See [`vkCmdSetDepthBounds`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetDepthBounds)
+            fn set_depth_bounds(&self, min_depth_bounds: f32, max_depth_bounds: f32);
+
+            /// See [`vkCmdSetStencilCompareMask`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetStencilCompareMask)
+            fn set_stencil_compare_mask(&self, face_mask: core::StencilFaceFlags, compare_mask: u32);
+
+            /// See [`vkCmdSetStencilWriteMask`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetStencilWriteMask)
+            fn set_stencil_write_mask(&self, face_mask: core::StencilFaceFlags, write_mask: u32);
+
+            /// See [`vkCmdSetStencilReference`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetStencilReference)
+            fn set_stencil_reference(&self, face_mask: core::StencilFaceFlags, reference: u32);
+
+            /// See [`vkCmdBindDescriptorSets`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBindDescriptorSets)
+            fn bind_descriptor_sets(&self, pipeline_bind_point: core::PipelineBindPoint, layout: &PipelineLayout, first_set: u32, descriptor_sets: &[DescriptorSet], dynamic_offsets: Option<&[u32]>);
+
+            /// See [`vkCmdBindIndexBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBindIndexBuffer)
+            fn bind_index_buffer(&self, buffer: &Buffer, offset: u64, index_type: core::IndexType);
+
+            /// See [`vkCmdBindVertexBuffers`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBindVertexBuffers)
+            fn bind_vertex_buffers(&self, first_binding: u32, buffers: &[Buffer], offsets: &[u64]);
+
+            /// See [`vkCmdBeginQuery`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBeginQuery)
+            fn begin_query(&self, query_pool: &QueryPool, query: u32, flags: core::QueryControlFlags);
+
+            /// See [`vkCmdEndQuery`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdEndQuery)
+            fn end_query(&self, query_pool: &QueryPool, query: u32);
+
+            /// See [`vkCmdWriteTimestamp`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdWriteTimestamp)
+            fn write_timestamp(&self, pipeline_stage: core::PipelineStageFlagBits, query_pool: &QueryPool, query: u32);
+
+            /// See [`vkCmdPushConstants`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdPushConstants)
+            fn push_constants(&self, layout: &PipelineLayout, stage_flags: core::ShaderStageFlags, offset: u32, values: &[u8]);
+
+            /// See [`vkCmdExecuteCommands`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdExecuteCommands)
+            fn execute_commands(&self, command_buffers: &[CommandBuffer]);
+
+            /// See [`vkCmdDebugMarkerBeginEXT`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDebugMarkerBeginEXT)
+            /// and extension [`VK_EXT_debug_marker`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_EXT_debug_marker)
+            fn debug_marker_begin_ext(&self, marker_info: &ext_debug_marker::DebugMarkerMarkerInfoExt);
+
+            /// See [`vkCmdDebugMarkerEndEXT`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDebugMarkerEndEXT)
+            /// and extension [`VK_EXT_debug_marker`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_EXT_debug_marker)
+            fn debug_marker_end_ext(&self);
+
+            /// See [`vkCmdDebugMarkerInsertEXT`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDebugMarkerInsertEXT)
+            /// and extension [`VK_EXT_debug_marker`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_EXT_debug_marker)
+            fn debug_marker_insert_ext(&self, marker_info: &ext_debug_marker::DebugMarkerMarkerInfoExt);
+        }
+    }
+}
+
+/// A `CommandBuffer` in the recording state.
+///
+/// Returned by [`CommandBuffer::record`](struct.CommandBuffer.html#method.record). Only commands,
+/// which are valid outside of a render pass, can be recorded. Commands for the inside of a render
+/// pass are recorded with the `RenderPassRecorder` returned by `begin_render_pass`.
+///
+/// Recording is ended with `end`, or implicitly when the `CommandBufferRecorder` is dropped. In the
+/// latter case, errors are ignored.
+#[derive(Debug)]
+pub struct CommandBufferRecorder<'a> {
+    command_buffer: &'a CommandBuffer,
+    render_pass_continue: bool,
+    ended: bool,
+}
+
+impl<'a> Drop for CommandBufferRecorder<'a> {
+    fn drop(&mut self) {
+        if !self.ended {
+            let _ = self.command_buffer.end();
+        }
+    }
+}
+
+impl<'a> CommandBufferRecorder<'a> {
+    pub(crate) fn new(command_buffer: &'a CommandBuffer, render_pass_continue: bool) -> Self {
+        CommandBufferRecorder {
+            command_buffer: command_buffer,
+            render_pass_continue: render_pass_continue,
+            ended: false,
+        }
+    }
+
+    /// Returns the `CommandBuffer`, which is being recorded.
+    ///
+    /// Commands recorded directly on the `CommandBuffer` are not checked. In particular, nothing
+    /// prevents recording commands, which are only valid inside of a render pass.
+    #[inline]
+    pub fn command_buffer(&self) -> &CommandBuffer {
+        self.command_buffer
+    }
+
+    /// Ends recording.
+    ///
+    /// See [`vkEndCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkEndCommandBuffer)
+    pub fn end(mut self) -> Result<(), core::Error> {
+        self.ended = true;
+        self.command_buffer.end()
+    }
+
+    /// Begins a render pass.
+    ///
+    /// The render pass is ended, when the returned `RenderPassRecorder` is dropped.
+    ///
+    /// See [`vkCmdBeginRenderPass`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBeginRenderPass)
+    pub fn begin_render_pass<'b>(&'b mut self, render_pass_begin: &core::RenderPassBeginInfo, contents: core::SubpassContents) -> RenderPassRecorder<'b, 'a> {
+        self.command_buffer.begin_render_pass(render_pass_begin, contents);
+        RenderPassRecorder::new(self, true)
+    }
+
+    /// Records commands into a secondary command buffer, which continues a render pass.
+    ///
+    /// The render pass is not ended, when the returned `RenderPassRecorder` is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if recording was not begun with `CommandBufferUsageFlags::RENDER_PASS_CONTINUE`.
+    pub fn continue_render_pass<'b>(&'b mut self) -> RenderPassRecorder<'b, 'a> {
+        assert!(self.render_pass_continue, "command buffer was not begun with RENDER_PASS_CONTINUE");
+        RenderPassRecorder::new(self, false)
+    }
+
+    common_commands!();
+
+    forward_commands! {
+        /// See [`vkCmdDispatch`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDispatch)
+        fn dispatch(&self, group_count_x: u32, group_count_y: u32, group_count_z: u32);
+
+        /// See [`vkCmdDispatchIndirect`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDispatchIndirect)
+        fn dispatch_indirect(&self, buffer: &Buffer, offset: u64);
+
+        /// See [`vkCmdCopyBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyBuffer)
+        fn copy_buffer(&self, src_buffer: &Buffer, dst_buffer: &Buffer, regions: &[core::BufferCopy]);
+
+        /// See [`vkCmdCopyImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyImage)
+        fn copy_image(&self, src_image: &Image, src_image_layout: core::ImageLayout, dst_image: &Image, dst_image_layout: core::ImageLayout, regions: &[core::ImageCopy]);
+
+        /// See [`vkCmdBlitImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdBlitImage)
+        fn blit_image(&self, src_image: &Image, src_image_layout: core::ImageLayout, dst_image: &Image, dst_image_layout: core::ImageLayout, regions: &[core::ImageBlit], filter: core::Filter);
+
+        /// See [`vkCmdCopyBufferToImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyBufferToImage)
+        fn copy_buffer_to_image(&self, src_buffer: &Buffer, dst_image: &Image, dst_image_layout: core::ImageLayout, regions: &[core::BufferImageCopy]);
+
+        /// See [`vkCmdCopyImageToBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyImageToBuffer)
+        fn copy_image_to_buffer(&self, src_image: &Image, src_image_layout: core::ImageLayout, dst_buffer: &Buffer, regions: &[core::BufferImageCopy]);
+
+        /// See [`vkCmdUpdateBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdUpdateBuffer)
+        fn update_buffer(&self, dst_buffer: &Buffer, dst_offset: u64, data: &[u8]);
+
+        /// See [`vkCmdFillBuffer`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdFillBuffer)
+        fn fill_buffer(&self, dst_buffer: &Buffer, dst_offset: u64, size: core::OptionalDeviceSize, data: u32);
+
+        /// See [`vkCmdClearColorImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdClearColorImage)
+        fn clear_color_image(&self, image: &Image, image_layout: core::ImageLayout, color: &core::ClearColorValue, ranges: &[core::ImageSubresourceRange]);
+
+        /// See [`vkCmdClearDepthStencilImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdClearDepthStencilImage)
+        fn clear_depth_stencil_image(&self, image: &Image, image_layout: core::ImageLayout, depth_stencil: &core::ClearDepthStencilValue, ranges: &[core::ImageSubresourceRange]);
+
+        /// See [`vkCmdResolveImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdResolveImage)
+        fn resolve_image(&self, src_image: &Image, src_image_layout: core::ImageLayout, dst_image: &Image, dst_image_layout: core::ImageLayout, regions: &[core::ImageResolve]);
+
+        /// See [`vkCmdSetEvent`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdSetEvent)
+        fn set_event(&self, event: &Event, stage_mask: core::PipelineStageFlags);
+
+        /// See [`vkCmdResetEvent`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdResetEvent)
+        fn reset_event(&self, event: &Event, stage_mask: core::PipelineStageFlags);
+
+        /// See [`vkCmdWaitEvents`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdWaitEvents)
+        fn wait_events(&self, events: &[Event], src_stage_mask: core::PipelineStageFlags, dst_stage_mask: core::PipelineStageFlags, memory_barriers: Option<&[core::MemoryBarrier]>, buffer_memory_barriers: Option<&[core::BufferMemoryBarrier]>, image_memory_barriers: Option<&[core::ImageMemoryBarrier]>);
+
+        /// See [`vkCmdPipelineBarrier`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdPipelineBarrier)
+        fn pipeline_barrier(&self, src_stage_mask: core::PipelineStageFlags, dst_stage_mask: core::PipelineStageFlags, dependency_flags: core::DependencyFlags, memory_barriers: Option<&[core::MemoryBarrier]>, buffer_memory_barriers: Option<&[core::BufferMemoryBarrier]>, image_memory_barriers: Option<&[core::ImageMemoryBarrier]>);
+
+        /// See [`CommandBuffer::barrier`](struct.CommandBuffer.html#method.barrier)
+        fn barrier(&self, global_barrier: Option<&sync::GlobalBarrier>, buffer_barriers: &[sync::BufferBarrier], image_barriers: &[sync::ImageBarrier]);
+
+        /// See [`vkCmdResetQueryPool`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdResetQueryPool)
+        fn reset_query_pool(&self, query_pool: &QueryPool, first_query: u32, query_count: u32);
+
+        /// See [`vkCmdCopyQueryPoolResults`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdCopyQueryPoolResults)
+        fn copy_query_pool_results(&self, query_pool: &QueryPool, first_query: u32, query_count: u32, dst_buffer: &Buffer, dst_offset: u64, stride: u64, flags: core::QueryResultFlags);
+    }
+
+    /// See [`CommandBuffer::generate_mipmaps`](struct.CommandBuffer.html#method.generate_mipmaps)
+    #[inline]
+    pub fn generate_mipmaps(&self, image: &Image, base_level_layout: core::ImageLayout, final_layout: core::ImageLayout, dst_stage_mask: core::PipelineStageFlags) -> Result<(), core::MipmapError> {
+        self.command_buffer().generate_mipmaps(image, base_level_layout, final_layout, dst_stage_mask)
+    }
+
+    /// See [`CommandBuffer::transition_to`](struct.CommandBuffer.html#method.transition_to)
+    #[inline]
+    pub fn transition_to(&self, image: &Image, layout: core::ImageLayout, dst_stage_mask: core::PipelineStageFlags) -> Result<(), core::LayoutTrackingError> {
+        self.command_buffer().transition_to(image, layout, dst_stage_mask)
+    }
+
+    /// See [`CommandBuffer::transition_range_to`](struct.CommandBuffer.html#method.transition_range_to)
+    #[inline]
+    pub fn transition_range_to(&self, image: &Image, subresource_range: &core::ImageSubresourceRange, layout: core::ImageLayout, dst_stage_mask: core::PipelineStageFlags) -> Result<(), core::LayoutTrackingError> {
+        self.command_buffer().transition_range_to(image, subresource_range, layout, dst_stage_mask)
+    }
+}
+
+/// A `CommandBuffer` in the recording state inside of a render pass.
+///
+/// Returned by [`CommandBufferRecorder::begin_render_pass`](struct.CommandBufferRecorder.html#method.begin_render_pass).
+/// Only commands, which are valid inside of a render pass, can be recorded. Pipeline barriers and
+/// `wait_events` are not available, because inside of a render pass they require a subpass
+/// self-dependency. The render pass is ended with `end`, or implicitly when the
+/// `RenderPassRecorder` is dropped.
+#[derive(Debug)]
+pub struct RenderPassRecorder<'b, 'a: 'b> {
+    recorder: &'b mut CommandBufferRecorder<'a>,
+    end_render_pass: bool,
+}
+
+impl<'b, 'a> Drop for RenderPassRecorder<'b, 'a> {
+    fn drop(&mut self) {
+        if self.end_render_pass {
+            self.recorder.command_buffer.end_render_pass();
+        }
+    }
+}
+
+impl<'b, 'a> RenderPassRecorder<'b, 'a> {
+    fn new(recorder: &'b mut CommandBufferRecorder<'a>, end_render_pass: bool) -> Self {
+        RenderPassRecorder {
+            recorder: recorder,
+            end_render_pass: end_render_pass,
+        }
+    }
+
+    #[inline]
+    fn command_buffer(&self) -> &CommandBuffer {
+        self.recorder.command_buffer
+    }
+
+    /// Ends the render pass.
+    ///
+    /// Nothing is recorded for a `RenderPassRecorder` returned by
+    /// `CommandBufferRecorder::continue_render_pass`.
+    ///
+    /// See [`vkCmdEndRenderPass`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdEndRenderPass)
+    pub fn end(mut self) {
+        if self.end_render_pass {
+            self.end_render_pass = false;
+            self.recorder.command_buffer.end_render_pass();
+        }
+    }
+
+    common_commands!();
+
+    forward_commands! {
+        /// See [`vkCmdNextSubpass`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdNextSubpass)
+        fn next_subpass(&self, contents: core::SubpassContents);
+
+        /// See [`vkCmdDraw`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDraw)
+        fn draw(&self, vertex_count: u32, instance_count: u32, first_vertex: u32, first_instance: u32);
+
+        /// See [`vkCmdDrawIndexed`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndexed)
+        fn draw_indexed(&self, index_count: u32, instance_count: u32, first_index: u32, vertex_offset: i32, first_instance: u32);
+
+        /// See [`vkCmdDrawIndirect`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndirect)
+        fn draw_indirect(&self, buffer: &Buffer, offset: u64, draw_count: u32, stride: u32);
+
+        /// See [`vkCmdDrawIndexedIndirect`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndexedIndirect)
+        fn draw_indexed_indirect(&self, buffer: &Buffer, offset: u64, draw_count: u32, stride: u32);
+
+        /// See [`vkCmdClearAttachments`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdClearAttachments)
+        fn clear_attachments(&self, attachments: &[core::ClearAttachment], rects: &[core::ClearRect]);
+
+        /// See [`vkCmdDrawIndirectCountAMD`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndirectCountAMD)
+        /// and extension [`VK_AMD_draw_indirect_count`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_AMD_draw_indirect_count)
+        fn draw_indirect_count_amd(&self, buffer: &Buffer, offset: u64, count_buffer: &Buffer, count_buffer_offset: u64, max_draw_count: u32, stride: u32);
+
+        /// See [`vkCmdDrawIndexedIndirectCountAMD`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCmdDrawIndexedIndirectCountAMD)
+        /// and extension [`VK_AMD_draw_indirect_count`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_AMD_draw_indirect_count)
+        fn draw_indexed_indirect_count_amd(&self, buffer: &Buffer, offset: u64, count_buffer: &Buffer, count_buffer_offset: u64, max_draw_count: u32, stride: u32);
+    }
+}
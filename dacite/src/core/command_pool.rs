@@ -17,11 +17,12 @@ use TryDestroyError;
 use TryDestroyErrorKind;
 use VulkanObject;
 use core::allocator_helper::AllocatorHelper;
+use core::command_buffer::Resources;
 use core::{self, CommandBuffer, Device};
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 use std::ptr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, Weak};
 use vks;
 
 /// See [`VkCommandPool`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkCommandPool)
@@ -91,6 +92,7 @@ impl CommandPool {
             owned: owned,
             device: device,
             allocator: allocator,
+            command_buffers: Mutex::new(Vec::new()),
         }))
     }
 
@@ -104,11 +106,26 @@ impl CommandPool {
         self.0.device.loader()
     }
 
+    #[inline]
+    pub(crate) fn device(&self) -> &Device {
+        &self.0.device
+    }
+
     #[inline]
     pub(crate) fn device_handle(&self) -> vks::vk::VkDevice {
         self.0.device.handle()
     }
 
+    /// Registers the resources of a `CommandBuffer` allocated from this pool, so that they can be
+    /// released when the pool is reset.
+    pub(crate) fn add_command_buffer_resources(&self, resources: Weak<Mutex<Resources>>) {
+        let mut command_buffers = self.0.command_buffers.lock().unwrap();
+        command_buffers.retain(|resources| resources.upgrade().is_some());
+        command_buffers.push(resources);
+    }
+
+    /// Objects referenced by commands recorded to any `CommandBuffer` of this pool are released.
+    ///
     /// See [`vkResetCommandPool`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkResetCommandPool)
     pub fn reset(&self, flags: core::CommandPoolResetFlags) -> Result<(), core::Error> {
         let res = unsafe {
@@ -116,6 +133,12 @@ impl CommandPool {
         };
 
         if res == vks::vk::VK_SUCCESS {
+            let command_buffers: Vec<_> = self.0.command_buffers.lock().unwrap().iter().filter_map(Weak::upgrade).collect();
+            for resources in command_buffers {
+                let objects = resources.lock().unwrap().release(false);
+                drop(objects);
+            }
+
             Ok(())
         }
         else {
@@ -123,6 +146,19 @@ impl CommandPool {
         }
     }
 
+    /// See [`vkTrimCommandPool`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkTrimCommandPool)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the device doesn't support Vulkan 1.1.
+    pub fn trim(&self, flags: core::CommandPoolTrimFlags) -> Result<(), core::Error> {
+        self.0.device.physical_device().check_vulkan_1_1(self.0.device.loader_1_1().pfn_vkTrimCommandPool)?;
+
+        unsafe {
+            self.0.device.loader_1_1().vkTrimCommandPool(self.device_handle(), self.handle(), flags.bits());
+        }
+
+        Ok(())
+    }
+
     /// See [`vkAllocateCommandBuffers`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkAllocateCommandBuffers)
     pub fn allocate_command_buffers(allocate_info: &core::CommandBufferAllocateInfo) -> Result<Vec<CommandBuffer>, core::Error> {
         let command_pool = &allocate_info.command_pool;
@@ -149,6 +185,7 @@ struct Inner {
     owned: bool,
     device: Device,
     allocator: Option<AllocatorHelper>,
+    command_buffers: Mutex<Vec<Weak<Mutex<Resources>>>>,
 }
 
 impl Drop for Inner {
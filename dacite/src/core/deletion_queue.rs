@@ -0,0 +1,202 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use VulkanObject;
+use core::{self, Device, Fence};
+use std::any::Any;
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Condition, after which an object passed to
+/// [`DeletionQueue::destroy_deferred`](struct.DeletionQueue.html#method.destroy_deferred) is
+/// dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeletionCondition {
+    /// The object is dropped after the `Fence` has been observed to be signaled.
+    Fence(Fence),
+
+    /// The object is dropped after the frame has been completed with
+    /// [`DeletionQueue::complete_frame`](struct.DeletionQueue.html#method.complete_frame).
+    Frame(u64),
+}
+
+/// Objects waiting to be dropped, once the device is done with them.
+///
+/// The queue is polled after every successful [`Queue::submit`](struct.Queue.html#method.submit)
+/// on its `Device` and flushed by [`Device::wait_idle`](struct.Device.html#method.wait_idle). The
+/// `Device` refers to the queue only weakly, so the queue must be kept alive by the application.
+///
+/// Dropping the last clone of a `DeletionQueue` drops all pending objects immediately, regardless
+/// of their condition. Call `flush` before, if the device might still use them.
+#[derive(Debug, Clone)]
+pub struct DeletionQueue(Arc<Inner>);
+
+impl DeletionQueue {
+    /// Creates an empty deletion queue for objects of `device`.
+    pub fn new(device: Device) -> Self {
+        let queue = DeletionQueue(Arc::new(Inner {
+            device: device,
+            state: Mutex::new(State::default()),
+        }));
+
+        queue.0.device.register_deletion_queue(Arc::downgrade(&queue.0));
+        queue
+    }
+
+    /// Returns the `Device`, to which this queue belongs.
+    #[inline]
+    pub fn device(&self) -> &Device {
+        &self.0.device
+    }
+
+    /// Drops `object`, once `condition` is met.
+    pub fn destroy_deferred<T>(&self, object: T, condition: DeletionCondition)
+        where T: VulkanObject + Send + 'static
+    {
+        self.0.state.lock().unwrap().entries.push(Entry {
+            object: Box::new(object),
+            condition: condition,
+        });
+    }
+
+    /// Marks all frames up to and including `frame` as completed and polls the queue.
+    pub fn complete_frame(&self, frame: u64) {
+        {
+            let mut state = self.0.state.lock().unwrap();
+            if state.completed_frame.map_or(true, |completed_frame| frame > completed_frame) {
+                state.completed_frame = Some(frame);
+            }
+        }
+
+        self.poll();
+    }
+
+    /// Drops all objects, whose condition is met.
+    ///
+    /// Every distinct fence is queried only once. Errors are treated as if the fence was not yet
+    /// signaled.
+    pub fn poll(&self) {
+        self.0.poll();
+    }
+
+    /// Waits until the device is idle with
+    /// [`Device::wait_idle`](struct.Device.html#method.wait_idle) and drops all pending objects.
+    #[inline]
+    pub fn flush(&self) -> Result<(), core::Error> {
+        self.0.device.wait_idle()
+    }
+
+    /// Returns the number of objects, which have not been dropped yet.
+    pub fn pending_count(&self) -> usize {
+        self.0.state.lock().unwrap().entries.len()
+    }
+}
+
+struct Entry {
+    #[allow(dead_code)]
+    object: Box<Any + Send>,
+    condition: DeletionCondition,
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("condition", &self.condition)
+            .finish()
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: Vec<Entry>,
+    completed_frame: Option<u64>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Inner {
+    device: Device,
+    state: Mutex<State>,
+}
+
+impl Inner {
+    pub fn poll(&self) {
+        let entries = {
+            let mut state = self.state.lock().unwrap();
+            let completed_frame = state.completed_frame;
+            let mut fences: Vec<(Fence, bool)> = Vec::new();
+            let (ready, pending) = mem::replace(&mut state.entries, Vec::new()).into_iter().partition(|entry| {
+                match entry.condition {
+                    DeletionCondition::Fence(ref fence) => {
+                        if let Some(&(_, signaled)) = fences.iter().find(|f| f.0 == *fence) {
+                            return signaled;
+                        }
+
+                        let signaled = fence.get_status().unwrap_or(false);
+                        fences.push((fence.clone(), signaled));
+                        signaled
+                    }
+
+                    DeletionCondition::Frame(frame) => completed_frame.map_or(false, |completed_frame| frame <= completed_frame),
+                }
+            });
+
+            state.entries = pending;
+            ready
+        };
+
+        drop::<Vec<Entry>>(entries);
+    }
+
+    /// Drops all objects regardless of their condition.
+    pub fn clear(&self) {
+        let entries = mem::replace(&mut self.state.lock().unwrap().entries, Vec::new());
+        drop(entries);
+    }
+}
+
+/// Weak references to all `DeletionQueue`s of a `Device`.
+#[derive(Debug, Default)]
+pub(crate) struct DeletionQueues {
+    queues: Mutex<Vec<Weak<Inner>>>,
+}
+
+impl DeletionQueues {
+    pub fn register(&self, queue: Weak<Inner>) {
+        let mut queues = self.queues.lock().unwrap();
+        queues.retain(|queue| queue.upgrade().is_some());
+        queues.push(queue);
+    }
+
+    /// Returns all queues, which are still alive.
+    ///
+    /// The lock is released before returning, so that queues can be polled and dropped freely.
+    fn alive(&self) -> Vec<Arc<Inner>> {
+        let mut queues = self.queues.lock().unwrap();
+        queues.retain(|queue| queue.upgrade().is_some());
+        queues.iter().filter_map(Weak::upgrade).collect()
+    }
+
+    pub fn poll(&self) {
+        for queue in self.alive() {
+            queue.poll();
+        }
+    }
+
+    pub fn clear(&self) {
+        for queue in self.alive() {
+            queue.clear();
+        }
+    }
+}
@@ -16,17 +16,26 @@ use FromNativeObject;
 use TryDestroyError;
 use TryDestroyErrorKind;
 use VulkanObject;
-use core::{self, DescriptorPool};
+use core::{self, Buffer, BufferView, DescriptorPool, ImageView, Sampler};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::ptr;
+use std::sync::{Arc, Mutex};
 use vks;
 
 /// See [`VkDescriptorSet`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkDescriptorSet)
+///
+/// A `DescriptorSet` keeps the samplers, image views, buffers and buffer views written to it with
+/// [`update`](#method.update) alive, until they are overwritten or the set is freed with
+/// [`free`](#method.free). Resetting the `DescriptorPool` does not release them. Only clones share
+/// these references, not other `DescriptorSet`s created from the same handle with
+/// `from_native_object`.
 #[derive(Debug, Clone)]
 pub struct DescriptorSet {
     handle: vks::vk::VkDescriptorSet,
     descriptor_pool: DescriptorPool,
+    resources: Arc<Mutex<Resources>>,
 }
 
 impl VulkanObject for DescriptorSet {
@@ -94,6 +103,7 @@ impl DescriptorSet {
         DescriptorSet {
             handle: handle,
             descriptor_pool: descriptor_pool,
+            resources: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -113,6 +123,9 @@ impl DescriptorSet {
     }
 
     /// See [`vkUpdateDescriptorSets`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkUpdateDescriptorSets)
+    ///
+    /// The destination sets keep the written and copied resources alive, replacing the ones
+    /// previously written to the same array elements.
     pub fn update(writes: Option<&[core::WriteDescriptorSet]>, copies: Option<&[core::CopyDescriptorSet]>) {
         let (loader, device_handle) = match (writes, copies) {
             (Some(writes), _) if !writes.is_empty() => (writes[0].dst_set.loader(), writes[0].dst_set.device_handle()),
@@ -121,22 +134,22 @@ impl DescriptorSet {
         };
 
         #[allow(unused_variables)]
-        let (writes_count, writes_ptr, writes, writes_wrappers) = match writes {
+        let (writes_count, writes_ptr, vk_writes, writes_wrappers) = match writes {
             Some(writes) => {
                 let writes_wrappers: Vec<_> = writes.iter().map(|w| core::VkWriteDescriptorSetWrapper::new(w, true)).collect();
-                let writes: Vec<_> = writes_wrappers.iter().map(|w| w.vks_struct).collect();
-                (writes.len() as u32, writes.as_ptr(), Some(writes), Some(writes_wrappers))
+                let vk_writes: Vec<_> = writes_wrappers.iter().map(|w| w.vks_struct).collect();
+                (vk_writes.len() as u32, vk_writes.as_ptr(), Some(vk_writes), Some(writes_wrappers))
             }
 
             None => (0, ptr::null(), None, None),
         };
 
         #[allow(unused_variables)]
-        let (copies_count, copies_ptr, copies, copies_wrappers) = match copies {
+        let (copies_count, copies_ptr, vk_copies, copies_wrappers) = match copies {
             Some(copies) => {
                 let copies_wrappers: Vec<_> = copies.iter().map(|c| core::VkCopyDescriptorSetWrapper::new(c, true)).collect();
-                let copies: Vec<_> = copies_wrappers.iter().map(|c| c.vks_struct).collect();
-                (copies.len() as u32, copies.as_ptr(), Some(copies), Some(copies_wrappers))
+                let vk_copies: Vec<_> = copies_wrappers.iter().map(|c| c.vks_struct).collect();
+                (vk_copies.len() as u32, vk_copies.as_ptr(), Some(vk_copies), Some(copies_wrappers))
             }
 
             None => (0, ptr::null(), None, None),
@@ -145,6 +158,50 @@ impl DescriptorSet {
         unsafe {
             loader.vk.vkUpdateDescriptorSets(device_handle, writes_count, writes_ptr, copies_count, copies_ptr);
         }
+
+        for write in writes.into_iter().flat_map(|w| w.iter()) {
+            write.dst_set.keep_written(write);
+        }
+
+        for copy in copies.into_iter().flat_map(|c| c.iter()) {
+            copy.dst_set.keep_copied(copy);
+        }
+    }
+
+    fn keep_written(&self, write: &core::WriteDescriptorSet) {
+        let elements: Vec<Vec<Resource>> = match write.elements {
+            core::WriteDescriptorSetElements::ImageInfo(ref image_info) => {
+                image_info.iter().map(|i| {
+                    i.sampler.iter().cloned().map(Resource::Sampler)
+                        .chain(i.image_view.iter().cloned().map(Resource::ImageView))
+                        .collect()
+                }).collect()
+            }
+
+            core::WriteDescriptorSetElements::BufferInfo(ref buffer_info) => buffer_info.iter().map(|b| vec![Resource::Buffer(b.buffer.clone())]).collect(),
+            core::WriteDescriptorSetElements::TexelBufferView(ref texel_buffer_view) => texel_buffer_view.iter().map(|v| vec![Resource::BufferView(v.clone())]).collect(),
+        };
+
+        let mut resources = self.resources.lock().unwrap();
+        for (i, element) in elements.into_iter().enumerate() {
+            resources.insert((write.dst_binding, write.dst_array_element.wrapping_add(i as u32)), element);
+        }
+    }
+
+    fn keep_copied(&self, copy: &core::CopyDescriptorSet) {
+        let elements: Vec<Option<Vec<Resource>>> = {
+            let src_resources = copy.src_set.resources.lock().unwrap();
+            (0..copy.descriptor_count).map(|i| src_resources.get(&(copy.src_binding, copy.src_array_element.wrapping_add(i))).cloned()).collect()
+        };
+
+        let mut resources = self.resources.lock().unwrap();
+        for (i, element) in elements.into_iter().enumerate() {
+            let key = (copy.dst_binding, copy.dst_array_element.wrapping_add(i as u32));
+            match element {
+                Some(element) => resources.insert(key, element),
+                None => resources.remove(&key),
+            };
+        }
     }
 
     /// See [`vkFreeDescriptorSets`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkFreeDescriptorSets)
@@ -154,6 +211,7 @@ impl DescriptorSet {
         };
 
         if res == vks::vk::VK_SUCCESS {
+            self.resources.lock().unwrap().clear();
             Ok(())
         }
         else {
@@ -161,3 +219,15 @@ impl DescriptorSet {
         }
     }
 }
+
+/// Resources written to a descriptor set, indexed by binding and array element.
+type Resources = HashMap<(u32, u32), Vec<Resource>>;
+
+/// A resource written to a descriptor.
+#[derive(Debug, Clone)]
+enum Resource {
+    Sampler(Sampler),
+    ImageView(ImageView),
+    Buffer(Buffer),
+    BufferView(BufferView),
+}
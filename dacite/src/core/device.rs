@@ -13,6 +13,8 @@
 // PERFORMANCE OF THIS SOFTWARE.
 
 use core::allocator_helper::AllocatorHelper;
+use core::command_buffer::Submission;
+use core::deletion_queue::{self, DeletionQueues};
 use core::{
     self,
     Buffer,
@@ -26,7 +28,7 @@ use core::{
     Framebuffer,
     Image,
     ImageView,
-    Instance,
+    PhysicalDevice,
     Pipeline,
     PipelineCache,
     PipelineLayout,
@@ -40,10 +42,15 @@ use core::{
 use ext_debug_marker;
 use khr_swapchain;
 use std::cmp::Ordering;
+use std::fs;
 use std::hash::{Hash, Hasher};
+use std::io::{self, Read};
+use std::mem;
+use std::path::Path;
 use std::ptr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, Weak};
 use vks;
+use vks_1_1;
 use {TryDestroyError, TryDestroyErrorKind, VulkanObject};
 
 /// See [`VkDevice`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkDevice)
@@ -75,13 +82,19 @@ impl VulkanObject for Device {
 }
 
 impl Device {
-    pub(crate) fn new(handle: vks::vk::VkDevice, instance: Instance, allocator: Option<AllocatorHelper>, loader: vks::DeviceProcAddrLoader, enabled_extensions: core::DeviceExtensions) -> Self {
+    pub(crate) fn new(handle: vks::vk::VkDevice, physical_device: PhysicalDevice, allocator: Option<AllocatorHelper>, loader: vks::DeviceProcAddrLoader, loader_1_1: vks_1_1::DeviceFunctions, enabled_extensions: core::DeviceExtensions) -> Self {
+        let non_coherent_atom_size = physical_device.get_properties().limits.non_coherent_atom_size;
+
         Device(Arc::new(Inner {
             handle: handle,
-            instance: instance,
+            physical_device: physical_device,
+            non_coherent_atom_size: non_coherent_atom_size,
             allocator: allocator,
             loader: loader,
+            loader_1_1: loader_1_1,
             enabled_extensions: enabled_extensions,
+            deletion_queues: DeletionQueues::default(),
+            submissions: Mutex::new(Vec::new()),
         }))
     }
 
@@ -95,8 +108,24 @@ impl Device {
         &self.0.loader
     }
 
+    #[inline]
+    pub(crate) fn non_coherent_atom_size(&self) -> u64 {
+        self.0.non_coherent_atom_size
+    }
+
+    #[inline]
+    pub(crate) fn loader_1_1(&self) -> &vks_1_1::DeviceFunctions {
+        &self.0.loader_1_1
+    }
+
+    /// Returns the `PhysicalDevice`, from which this `Device` was created.
+    #[inline]
+    pub fn physical_device(&self) -> &PhysicalDevice {
+        &self.0.physical_device
+    }
+
     pub fn get_enabled_instance_extensions(&self) -> &core::InstanceExtensions {
-        self.0.instance.get_enabled_extensions()
+        self.0.physical_device.instance.get_enabled_extensions()
     }
 
     pub fn get_enabled_device_extensions(&self) -> &core::DeviceExtensions {
@@ -113,6 +142,21 @@ impl Device {
         Queue::new(queue, self.clone())
     }
 
+    /// See [`vkGetDeviceQueue2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetDeviceQueue2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the device doesn't support Vulkan 1.1.
+    pub fn get_queue2(&self, queue_info: &core::DeviceQueueInfo2) -> Result<Queue, core::Error> {
+        self.physical_device().check_vulkan_1_1(self.loader_1_1().pfn_vkGetDeviceQueue2)?;
+        let queue_info_wrapper = core::VkDeviceQueueInfo2Wrapper::new(queue_info, true);
+
+        let mut queue = ptr::null_mut();
+        unsafe {
+            self.loader_1_1().vkGetDeviceQueue2(self.handle(), &queue_info_wrapper.vks_struct, &mut queue);
+        }
+
+        Ok(Queue::new(queue, self.clone()))
+    }
+
     /// See [`vkCreateCommandPool`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCreateCommandPool)
     pub fn create_command_pool(&self, create_info: &core::CommandPoolCreateInfo, allocator: Option<Box<core::Allocator>>) -> Result<CommandPool, core::Error> {
         let create_info = core::VkCommandPoolCreateInfoWrapper::new(create_info, true);
@@ -235,24 +279,69 @@ impl Device {
 
     /// See [`vkCreateImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCreateImage)
     pub fn create_image(&self, create_info: &core::ImageCreateInfo, allocator: Option<Box<core::Allocator>>) -> Result<Image, core::Error> {
-        let create_info = core::VkImageCreateInfoWrapper::new(create_info, true);
+        let create_info_wrapper = core::VkImageCreateInfoWrapper::new(create_info, true);
 
         let allocator_helper = allocator.map(AllocatorHelper::new);
         let allocation_callbacks = allocator_helper.as_ref().map_or(ptr::null(), AllocatorHelper::callbacks);
 
         let mut image = Default::default();
         let res = unsafe {
-            self.loader().vk.vkCreateImage(self.handle(), &create_info.vks_struct, allocation_callbacks, &mut image)
+            self.loader().vk.vkCreateImage(self.handle(), &create_info_wrapper.vks_struct, allocation_callbacks, &mut image)
         };
 
         if res == vks::vk::VK_SUCCESS {
-            Ok(Image::new(image, true, self.clone(), allocator_helper))
+            let mut image = Image::new(image, true, self.clone(), allocator_helper);
+            image.set_create_info(create_info);
+            Ok(image)
         }
         else {
             Err(res.into())
         }
     }
 
+    /// Creates a buffer together with its own `DeviceMemory` and binds it.
+    ///
+    /// The memory type is chosen as described in
+    /// [`PhysicalDeviceMemoryProperties::find_memory_type`](struct.PhysicalDeviceMemoryProperties.html#method.find_memory_type).
+    /// The memory is owned by the returned buffer (see [`Buffer::memory`](struct.Buffer.html#method.memory)).
+    pub fn create_buffer_with_memory(&self, create_info: &core::BufferCreateInfo, required: core::MemoryPropertyFlags, preferred: core::MemoryPropertyFlags) -> Result<Buffer, core::AllocationError> {
+        let mut buffer = self.create_buffer(create_info, None)?;
+        let memory = self.allocate_memory_for(&buffer.get_memory_requirements(), required, preferred)?;
+        buffer.bind_memory(memory.clone(), 0)?;
+        buffer.set_memory(memory);
+
+        Ok(buffer)
+    }
+
+    /// Creates an image together with its own `DeviceMemory` and binds it.
+    ///
+    /// The memory type is chosen as described in
+    /// [`PhysicalDeviceMemoryProperties::find_memory_type`](struct.PhysicalDeviceMemoryProperties.html#method.find_memory_type).
+    /// The memory is owned by the returned image (see [`Image::memory`](struct.Image.html#method.memory)).
+    pub fn create_image_with_memory(&self, create_info: &core::ImageCreateInfo, required: core::MemoryPropertyFlags, preferred: core::MemoryPropertyFlags) -> Result<Image, core::AllocationError> {
+        let mut image = self.create_image(create_info, None)?;
+        let memory = self.allocate_memory_for(&image.get_memory_requirements(), required, preferred)?;
+        image.bind_memory(memory.clone(), 0)?;
+        image.set_memory(memory);
+
+        Ok(image)
+    }
+
+    fn allocate_memory_for(&self, requirements: &core::MemoryRequirements, required: core::MemoryPropertyFlags, preferred: core::MemoryPropertyFlags) -> Result<DeviceMemory, core::AllocationError> {
+        let memory_type_index = self.physical_device()
+            .get_memory_properties()
+            .find_memory_type(requirements.memory_type_bits, required, preferred)
+            .ok_or(core::AllocationError::NoSuitableMemoryType)?;
+
+        let allocate_info = core::MemoryAllocateInfo {
+            allocation_size: requirements.size,
+            memory_type_index: memory_type_index,
+            chain: None,
+        };
+
+        Ok(self.allocate_memory(&allocate_info, None)?)
+    }
+
     /// See [`vkCreateBufferView`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCreateBufferView)
     pub fn create_buffer_view(&self, create_info: &core::BufferViewCreateInfo, allocator: Option<Box<core::Allocator>>) -> Result<BufferView, core::Error> {
         let create_info_wrapper = core::VkBufferViewCreateInfoWrapper::new(create_info, true);
@@ -286,7 +375,9 @@ impl Device {
         };
 
         if res == vks::vk::VK_SUCCESS {
-            Ok(ImageView::new(image_view, true, self.clone(), allocator_helper))
+            let mut image_view = ImageView::new(image_view, true, self.clone(), allocator_helper);
+            image_view.set_image(create_info.image.clone(), create_info.subresource_range);
+            Ok(image_view)
         }
         else {
             Err(res.into())
@@ -333,6 +424,37 @@ impl Device {
         }
     }
 
+    /// Creates a pipeline cache, which is initialized from the file at `path`.
+    ///
+    /// The data is only used, if its header matches the vendor ID, device ID and pipeline cache UUID
+    /// of the physical device (see [`PipelineCacheHeader`](struct.PipelineCacheHeader.html)).
+    /// Otherwise, or if the file does not exist, an empty pipeline cache is created. Other errors
+    /// while reading the file are returned. Use
+    /// [`PipelineCache::store`](struct.PipelineCache.html#method.store) to write the cache back.
+    pub fn load_pipeline_cache<P: AsRef<Path>>(&self, path: P, allocator: Option<Box<core::Allocator>>) -> Result<PipelineCache, core::PipelineCacheError> {
+        let properties = self.physical_device().get_properties();
+        let mut data = Vec::new();
+        let initial_data = match fs::File::open(path).and_then(|mut file| file.read_to_end(&mut data)) {
+            Ok(_) => {
+                match core::PipelineCacheHeader::parse(&data) {
+                    Some(ref header) if header.is_compatible_with(&properties) => data,
+                    _ => vec![],
+                }
+            }
+
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+
+        let create_info = core::PipelineCacheCreateInfo {
+            flags: core::PipelineCacheCreateFlags::empty(),
+            initial_data: initial_data,
+            chain: None,
+        };
+
+        Ok(self.create_pipeline_cache(&create_info, allocator)?)
+    }
+
     /// See [`vkCreateSampler`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCreateSampler)
     pub fn create_sampler(&self, create_info: &core::SamplerCreateInfo, allocator: Option<Box<core::Allocator>>) -> Result<Sampler, core::Error> {
         let create_info = core::VkSamplerCreateInfoWrapper::new(create_info, true);
@@ -516,7 +638,9 @@ impl Device {
         };
 
         if res == vks::vk::VK_SUCCESS {
-            Ok(Framebuffer::new(framebuffer, true, self.clone(), allocator_helper))
+            let mut framebuffer = Framebuffer::new(framebuffer, true, self.clone(), allocator_helper);
+            framebuffer.set_attachments(create_info.attachments.clone());
+            Ok(framebuffer)
         }
         else {
             Err(res.into())
@@ -536,7 +660,9 @@ impl Device {
         };
 
         if res == vks::vk::VK_SUCCESS {
-            Ok(RenderPass::new(render_pass, true, self.clone(), allocator_helper))
+            let mut render_pass = RenderPass::new(render_pass, true, self.clone(), allocator_helper);
+            render_pass.set_final_layouts(create_info.attachments.iter().map(|a| a.final_layout).collect());
+            Ok(render_pass)
         }
         else {
             Err(res.into())
@@ -544,12 +670,18 @@ impl Device {
     }
 
     /// See [`vkDeviceWaitIdle`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkDeviceWaitIdle)
+    ///
+    /// On success, all pending objects of this device's
+    /// [`DeletionQueue`](struct.DeletionQueue.html)s are dropped, and all submitted command buffers
+    /// recorded with `ONE_TIME_SUBMIT` release the objects they reference.
     pub fn wait_idle(&self) -> Result<(), core::Error> {
         let res = unsafe {
             self.loader().vk.vkDeviceWaitIdle(self.handle())
         };
 
         if res == vks::vk::VK_SUCCESS {
+            self.complete_submissions(None);
+            self.0.deletion_queues.clear();
             Ok(())
         }
         else {
@@ -557,6 +689,92 @@ impl Device {
         }
     }
 
+    /// Remembers command buffer recordings submitted to `queue`, until they are completed.
+    pub(crate) fn add_submissions<I>(&self, queue: vks::vk::VkQueue, submissions: I)
+        where I: IntoIterator<Item = Submission>
+    {
+        let mut pending = self.0.submissions.lock().unwrap();
+        pending.retain(|(_, submission)| !submission.is_released());
+        pending.extend(submissions.into_iter().map(|submission| (queue, submission)));
+    }
+
+    /// Completes all submissions to `queue`, or to all queues if `queue` is `None`.
+    pub(crate) fn complete_submissions(&self, queue: Option<vks::vk::VkQueue>) {
+        let completed: Vec<_> = {
+            let mut pending = self.0.submissions.lock().unwrap();
+            let (completed, remaining) = mem::replace(&mut *pending, Vec::new()).into_iter().partition(|&(submission_queue, _)| queue.is_none() || (queue == Some(submission_queue)));
+            *pending = remaining;
+            completed
+        };
+
+        for (_, submission) in completed {
+            submission.completed();
+        }
+    }
+
+    pub(crate) fn register_deletion_queue(&self, queue: Weak<deletion_queue::Inner>) {
+        self.0.deletion_queues.register(queue);
+    }
+
+    /// Drops all objects of this device's `DeletionQueue`s, whose condition is met.
+    pub(crate) fn poll_deletion_queues(&self) {
+        self.0.deletion_queues.poll();
+    }
+
+    /// See [`vkBindBufferMemory2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkBindBufferMemory2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the device doesn't support Vulkan 1.1.
+    pub fn bind_buffer_memory2(&self, bind_infos: &[core::BindBufferMemoryInfo]) -> Result<(), core::Error> {
+        self.physical_device().check_vulkan_1_1(self.loader_1_1().pfn_vkBindBufferMemory2)?;
+        let bind_info_wrappers: Vec<_> = bind_infos.iter().map(|b| core::VkBindBufferMemoryInfoWrapper::new(b, true)).collect();
+        let vk_bind_infos: Vec<_> = bind_info_wrappers.iter().map(|b| b.vks_struct).collect();
+
+        let res = unsafe {
+            self.loader_1_1().vkBindBufferMemory2(self.handle(), vk_bind_infos.len() as u32, vk_bind_infos.as_ptr())
+        };
+
+        if res == vks::vk::VK_SUCCESS {
+            Ok(())
+        }
+        else {
+            Err(res.into())
+        }
+    }
+
+    /// See [`vkBindImageMemory2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkBindImageMemory2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the device doesn't support Vulkan 1.1.
+    pub fn bind_image_memory2(&self, bind_infos: &[core::BindImageMemoryInfo]) -> Result<(), core::Error> {
+        self.physical_device().check_vulkan_1_1(self.loader_1_1().pfn_vkBindImageMemory2)?;
+        let bind_info_wrappers: Vec<_> = bind_infos.iter().map(|b| core::VkBindImageMemoryInfoWrapper::new(b, true)).collect();
+        let vk_bind_infos: Vec<_> = bind_info_wrappers.iter().map(|b| b.vks_struct).collect();
+
+        let res = unsafe {
+            self.loader_1_1().vkBindImageMemory2(self.handle(), vk_bind_infos.len() as u32, vk_bind_infos.as_ptr())
+        };
+
+        if res == vks::vk::VK_SUCCESS {
+            Ok(())
+        }
+        else {
+            Err(res.into())
+        }
+    }
+
+    /// See [`vkGetDeviceGroupPeerMemoryFeatures`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetDeviceGroupPeerMemoryFeatures)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the device doesn't support Vulkan 1.1.
+    pub fn get_group_peer_memory_features(&self, heap_index: u32, local_device_index: u32, remote_device_index: u32) -> Result<core::PeerMemoryFeatureFlags, core::Error> {
+        self.physical_device().check_vulkan_1_1(self.loader_1_1().pfn_vkGetDeviceGroupPeerMemoryFeatures)?;
+
+        let mut peer_memory_features = 0;
+        unsafe {
+            self.loader_1_1().vkGetDeviceGroupPeerMemoryFeatures(self.handle(), heap_index, local_device_index, remote_device_index, &mut peer_memory_features);
+        }
+
+        Ok(core::PeerMemoryFeatureFlags::from_bits_truncate(peer_memory_features))
+    }
+
     /// See [`vkCreateSwapchainKHR`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCreateSwapchainKHR)
     /// and extension [`VK_KHR_swapchain`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_KHR_swapchain)
     pub fn create_swapchain_khr(&self, create_info: &khr_swapchain::SwapchainCreateInfoKhr, allocator: Option<Box<core::Allocator>>) -> Result<khr_swapchain::SwapchainKhr, core::Error> {
@@ -631,10 +849,14 @@ impl Device {
 #[derive(Debug)]
 struct Inner {
     handle: vks::vk::VkDevice,
-    instance: Instance,
+    physical_device: PhysicalDevice,
+    non_coherent_atom_size: u64,
     allocator: Option<AllocatorHelper>,
     loader: vks::DeviceProcAddrLoader,
+    loader_1_1: vks_1_1::DeviceFunctions,
     enabled_extensions: core::DeviceExtensions,
+    deletion_queues: DeletionQueues,
+    submissions: Mutex<Vec<(vks::vk::VkQueue, Submission)>>,
 }
 
 impl Drop for Inner {
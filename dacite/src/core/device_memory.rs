@@ -20,10 +20,13 @@ use core::allocator_helper::AllocatorHelper;
 use core::{self, Device};
 use libc::c_void;
 use nv_external_memory_capabilities;
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
+use std::error;
+use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::mem;
 use std::ptr;
+use std::slice;
 use std::sync::Arc;
 use vks;
 use win32_types;
@@ -133,10 +136,22 @@ impl DeviceMemory {
     }
 
     /// See [`vkMapMemory`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkMapMemory)
+    ///
+    /// If `offset` is not a multiple of `non_coherent_atom_size`, the memory is mapped starting at
+    /// the previous multiple, and the returned `MappedMemory` points to `offset`.
     pub fn map(&self, offset: u64, size: core::OptionalDeviceSize, flags: core::MemoryMapFlags) -> Result<MappedMemory, core::Error> {
+        // Mapping starts at a multiple of non_coherent_atom_size, so that every flushed and
+        // invalidated range can be aligned without leaving the mapping.
+        let non_coherent_atom_size = cmp::max(self.0.device.non_coherent_atom_size(), 1);
+        let map_offset = offset / non_coherent_atom_size * non_coherent_atom_size;
+        let map_size = match size {
+            core::OptionalDeviceSize::Size(size) => size + (offset - map_offset),
+            core::OptionalDeviceSize::WholeSize => vks::vk::VK_WHOLE_SIZE,
+        };
+
         let mut mapped = ptr::null_mut();
         let res = unsafe {
-            self.loader().vk.vkMapMemory(self.device_handle(), self.handle(), offset, size.into(), flags.bits(), &mut mapped)
+            self.loader().vk.vkMapMemory(self.device_handle(), self.handle(), map_offset, map_size, flags.bits(), &mut mapped)
         };
 
         if res == vks::vk::VK_SUCCESS {
@@ -147,9 +162,11 @@ impl DeviceMemory {
 
             Ok(MappedMemory {
                 memory: self.clone(),
-                mapped: mapped,
+                mapped: unsafe { (mapped as *mut u8).offset((offset - map_offset) as isize) as *mut c_void },
                 offset: offset,
                 size: size,
+                map_offset: map_offset,
+                non_coherent_atom_size: non_coherent_atom_size,
             })
         }
         else {
@@ -214,12 +231,117 @@ impl DeviceMemory {
     }
 }
 
+/// Indicates an error, which occurred while allocating device memory for a resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AllocationError {
+    /// None of the memory types allowed by the memory requirements has the required property
+    /// flags.
+    NoSuitableMemoryType,
+
+    /// The memory requirements have a size of 0.
+    ZeroSize,
+
+    /// A Vulkan error occurred.
+    VulkanError(core::Error),
+}
+
+impl From<core::Error> for AllocationError {
+    fn from(e: core::Error) -> Self {
+        AllocationError::VulkanError(e)
+    }
+}
+
+impl fmt::Display for AllocationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AllocationError::NoSuitableMemoryType => write!(f, "No suitable memory type found"),
+            AllocationError::ZeroSize => write!(f, "Allocation size is 0"),
+            AllocationError::VulkanError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for AllocationError {
+    fn description(&self) -> &str {
+        match *self {
+            AllocationError::NoSuitableMemoryType => "NoSuitableMemoryType",
+            AllocationError::ZeroSize => "ZeroSize",
+            AllocationError::VulkanError(ref e) => e.description(),
+        }
+    }
+}
+
+/// Error type for the typed accessors of `MappedMemory`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MappedMemoryError {
+    /// The accessed range is not fully inside the mapped range.
+    OutOfBounds,
+
+    /// The mapped memory is not suitably aligned for the requested type.
+    Misaligned,
+
+    /// A Vulkan error occurred.
+    VulkanError(core::Error),
+}
+
+impl From<core::Error> for MappedMemoryError {
+    fn from(e: core::Error) -> Self {
+        MappedMemoryError::VulkanError(e)
+    }
+}
+
+impl fmt::Display for MappedMemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MappedMemoryError::OutOfBounds => write!(f, "Access outside of the mapped range"),
+            MappedMemoryError::Misaligned => write!(f, "Mapped memory is not aligned for the requested type"),
+            MappedMemoryError::VulkanError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for MappedMemoryError {
+    fn description(&self) -> &str {
+        match *self {
+            MappedMemoryError::OutOfBounds => "OutOfBounds",
+            MappedMemoryError::Misaligned => "Misaligned",
+            MappedMemoryError::VulkanError(ref e) => e.description(),
+        }
+    }
+}
+
+/// Types, for which every bit pattern is a valid value.
+///
+/// The typed accessors of `MappedMemory` are restricted to these types, because mapped memory can
+/// contain arbitrary bytes. Implementing this trait for a type with invalid bit patterns (e.g.
+/// `bool`, `char`, references or most enums) or with padding bytes is undefined behaviour.
+pub unsafe trait Pod: Copy { }
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => ($(
+        unsafe impl Pod for $ty { }
+    )*)
+}
+
+macro_rules! impl_pod_array {
+    ($($len:expr),*) => ($(
+        unsafe impl<T: Pod> Pod for [T; $len] { }
+    )*)
+}
+
+impl_pod!((), u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+impl_pod_array!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 64, 128, 256);
+
 #[derive(Debug)]
 pub struct MappedMemory {
     memory: DeviceMemory,
     mapped: *mut c_void,
     offset: u64,
     size: u64,
+
+    /// Offset passed to `vkMapMemory`, which is `offset` rounded down to `non_coherent_atom_size`.
+    map_offset: u64,
+    non_coherent_atom_size: u64,
 }
 
 impl Drop for MappedMemory {
@@ -243,17 +365,131 @@ impl MappedMemory {
         self.size
     }
 
+    /// Returns the whole mapped range as a slice of `T`.
+    ///
+    /// The slice contains as many elements as fit completely into the mapped range.
+    pub fn as_slice<T: Pod>(&self) -> Result<&[T], MappedMemoryError> {
+        let len = self.slice_len::<T>()?;
+        Ok(unsafe { slice::from_raw_parts(self.mapped as *const T, len) })
+    }
+
+    /// Returns the whole mapped range as a mutable slice of `T`.
+    ///
+    /// The slice contains as many elements as fit completely into the mapped range.
+    pub fn as_mut_slice<T: Pod>(&mut self) -> Result<&mut [T], MappedMemoryError> {
+        let len = self.slice_len::<T>()?;
+        Ok(unsafe { slice::from_raw_parts_mut(self.mapped as *mut T, len) })
+    }
+
+    /// Copies `data` into the mapped memory, starting at `offset` bytes from the beginning of the
+    /// mapped range.
+    ///
+    /// `offset` does not need to be aligned for `T`.
+    pub fn write_at<T: Pod>(&mut self, offset: u64, data: &[T]) -> Result<(), MappedMemoryError> {
+        let size = mem::size_of_val(data);
+        self.check_bounds(offset, size)?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr() as *const u8, (self.mapped as *mut u8).offset(offset as isize), size);
+        }
+
+        Ok(())
+    }
+
+    /// Fills `data` with the contents of the mapped memory, starting at `offset` bytes from the
+    /// beginning of the mapped range.
+    ///
+    /// `offset` does not need to be aligned for `T`.
+    pub fn read_at<T: Pod>(&self, offset: u64, data: &mut [T]) -> Result<(), MappedMemoryError> {
+        let size = mem::size_of_val(data);
+        self.check_bounds(offset, size)?;
+
+        unsafe {
+            ptr::copy_nonoverlapping((self.mapped as *const u8).offset(offset as isize), data.as_mut_ptr() as *mut u8, size);
+        }
+
+        Ok(())
+    }
+
+    fn slice_len<T>(&self) -> Result<usize, MappedMemoryError> {
+        if (self.mapped as usize) % mem::align_of::<T>() != 0 {
+            return Err(MappedMemoryError::Misaligned);
+        }
+
+        match mem::size_of::<T>() {
+            0 => Ok(0),
+            size => Ok((self.size / size as u64) as usize),
+        }
+    }
+
+    fn check_bounds(&self, offset: u64, size: usize) -> Result<(), MappedMemoryError> {
+        match offset.checked_add(size as u64) {
+            Some(end) if end <= self.size => Ok(()),
+            _ => Err(MappedMemoryError::OutOfBounds),
+        }
+    }
+
+    /// Converts a range relative to the mapped range into a range of the `DeviceMemory`, which is
+    /// suitable for flushing and invalidating.
+    ///
+    /// The offset is rounded down and the size is rounded up to a multiple of
+    /// `non_coherent_atom_size`. Because `map` starts the actual mapping at a multiple of
+    /// `non_coherent_atom_size`, the rounded offset never leaves it. If the rounded range extends
+    /// beyond the end of the mapped range, `VK_WHOLE_SIZE` is used.
+    fn atom_aligned_range(&self, offset: u64, size: core::OptionalDeviceSize) -> Result<(u64, u64), MappedMemoryError> {
+        let mapped_end = self.offset + self.size;
+        let start = self.offset + offset;
+        let end = match size {
+            core::OptionalDeviceSize::Size(size) => start.checked_add(size).ok_or(MappedMemoryError::OutOfBounds)?,
+            core::OptionalDeviceSize::WholeSize => mapped_end,
+        };
+
+        if (offset > self.size) || (end > mapped_end) {
+            return Err(MappedMemoryError::OutOfBounds);
+        }
+
+        let atom_size = self.non_coherent_atom_size;
+        let start = start / atom_size * atom_size;
+        debug_assert!(start >= self.map_offset);
+        let end = (end + atom_size - 1) / atom_size * atom_size;
+
+        if end > mapped_end {
+            Ok((start, vks::vk::VK_WHOLE_SIZE))
+        }
+        else {
+            Ok((start, end - start))
+        }
+    }
+
+    /// Flushes the whole mapped range.
+    ///
     /// See [`vkFlushMappedMemoryRanges`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkFlushMappedMemoryRanges)
     pub fn flush(&self, chain: &Option<core::MappedMemoryRangeChain>) -> Result<(), core::Error> {
+        match self.flush_range(0, core::OptionalDeviceSize::WholeSize, chain) {
+            Ok(()) => Ok(()),
+            Err(MappedMemoryError::VulkanError(e)) => Err(e),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Flushes a part of the mapped range.
+    ///
+    /// `offset` is relative to the beginning of the mapped range. The range is extended as
+    /// necessary to satisfy `non_coherent_atom_size`. `MappedMemoryError::OutOfBounds` is returned,
+    /// if the range is not fully inside the mapped range.
+    ///
+    /// See [`vkFlushMappedMemoryRanges`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkFlushMappedMemoryRanges)
+    pub fn flush_range(&self, offset: u64, size: core::OptionalDeviceSize, chain: &Option<core::MappedMemoryRangeChain>) -> Result<(), MappedMemoryError> {
         #[allow(unused_variables)]
         let (pnext, chain_wrapper) = core::MappedMemoryRangeChainWrapper::new_optional(chain, true);
+        let (offset, size) = self.atom_aligned_range(offset, size)?;
 
         let range = vks::vk::VkMappedMemoryRange {
             sType: vks::vk::VK_STRUCTURE_TYPE_MAPPED_MEMORY_RANGE,
             pNext: pnext,
             memory: self.memory.handle(),
-            offset: self.offset,
-            size: vks::vk::VK_WHOLE_SIZE,
+            offset: offset,
+            size: size,
         };
 
         let res = unsafe {
@@ -264,21 +500,39 @@ impl MappedMemory {
             Ok(())
         }
         else {
-            Err(res.into())
+            Err(MappedMemoryError::VulkanError(res.into()))
         }
     }
 
+    /// Invalidates the whole mapped range.
+    ///
     /// See [`vkInvalidateMappedMemoryRanges`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkInvalidateMappedMemoryRanges)
     pub fn invalidate(&self, chain: &Option<core::MappedMemoryRangeChain>) -> Result<(), core::Error> {
+        match self.invalidate_range(0, core::OptionalDeviceSize::WholeSize, chain) {
+            Ok(()) => Ok(()),
+            Err(MappedMemoryError::VulkanError(e)) => Err(e),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Invalidates a part of the mapped range.
+    ///
+    /// `offset` is relative to the beginning of the mapped range. The range is extended as
+    /// necessary to satisfy `non_coherent_atom_size`. `MappedMemoryError::OutOfBounds` is returned,
+    /// if the range is not fully inside the mapped range.
+    ///
+    /// See [`vkInvalidateMappedMemoryRanges`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkInvalidateMappedMemoryRanges)
+    pub fn invalidate_range(&self, offset: u64, size: core::OptionalDeviceSize, chain: &Option<core::MappedMemoryRangeChain>) -> Result<(), MappedMemoryError> {
         #[allow(unused_variables)]
         let (pnext, chain_wrapper) = core::MappedMemoryRangeChainWrapper::new_optional(chain, true);
+        let (offset, size) = self.atom_aligned_range(offset, size)?;
 
         let range = vks::vk::VkMappedMemoryRange {
             sType: vks::vk::VK_STRUCTURE_TYPE_MAPPED_MEMORY_RANGE,
             pNext: pnext,
             memory: self.memory.handle(),
-            offset: self.offset,
-            size: vks::vk::VK_WHOLE_SIZE,
+            offset: offset,
+            size: size,
         };
 
         let res = unsafe {
@@ -289,7 +543,7 @@ impl MappedMemory {
             Ok(())
         }
         else {
-            Err(res.into())
+            Err(MappedMemoryError::VulkanError(res.into()))
         }
     }
 }
@@ -17,11 +17,13 @@ use TryDestroyError;
 use TryDestroyErrorKind;
 use VulkanObject;
 use core::allocator_helper::AllocatorHelper;
+use core::command_buffer::Submission;
 use core::{self, Device};
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
+use std::mem;
 use std::ptr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use utils;
 use vks;
 
@@ -92,6 +94,7 @@ impl Fence {
             owned: owned,
             device: device,
             allocator: allocator,
+            pending: Mutex::new(Vec::new()),
         }))
     }
 
@@ -110,18 +113,43 @@ impl Fence {
         self.0.device.handle()
     }
 
+    /// Associates submitted `CommandBuffer` recordings with this fence.
+    pub(crate) fn add_pending<I>(&self, submissions: I)
+        where I: IntoIterator<Item = Submission>
+    {
+        let mut pending = self.0.pending.lock().unwrap();
+        pending.retain(|submission| !submission.is_released());
+        pending.extend(submissions);
+    }
+
+    fn signaled(&self) {
+        let pending = mem::replace(&mut *self.0.pending.lock().unwrap(), Vec::new());
+        for submission in pending {
+            submission.completed();
+        }
+    }
+
     /// See [`vkWaitForFences`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkWaitForFences)
     pub fn wait_for_fences(fences: &[Self], wait_all: bool, timeout: core::Timeout) -> Result<bool, core::Error> {
         let loader = fences[0].loader();
         let device = fences[0].device_handle();
-        let fences: Vec<_> = fences.iter().map(Fence::handle).collect();
+        let vk_fences: Vec<_> = fences.iter().map(Fence::handle).collect();
 
         let res = unsafe {
-            loader.vk.vkWaitForFences(device, fences.len() as u32, fences.as_ptr(), utils::to_vk_bool(wait_all), timeout.as_nanoseconds())
+            loader.vk.vkWaitForFences(device, vk_fences.len() as u32, vk_fences.as_ptr(), utils::to_vk_bool(wait_all), timeout.as_nanoseconds())
         };
 
         match res {
-            vks::vk::VK_SUCCESS => Ok(true),
+            vks::vk::VK_SUCCESS => {
+                if wait_all || (fences.len() == 1) {
+                    for fence in fences {
+                        fence.signaled();
+                    }
+                }
+
+                Ok(true)
+            }
+
             vks::vk::VK_TIMEOUT => Ok(false),
             _ => Err(res.into()),
         }
@@ -137,13 +165,18 @@ impl Fence {
     pub fn reset_fences(fences: &[Self]) -> Result<(), core::Error> {
         let loader = fences[0].loader();
         let device = fences[0].device_handle();
-        let fences: Vec<_> = fences.iter().map(Fence::handle).collect();
+        let vk_fences: Vec<_> = fences.iter().map(Fence::handle).collect();
 
         let res = unsafe {
-            loader.vk.vkResetFences(device, fences.len() as u32, fences.as_ptr())
+            loader.vk.vkResetFences(device, vk_fences.len() as u32, vk_fences.as_ptr())
         };
 
         if res == vks::vk::VK_SUCCESS {
+            // Fences must not be reset while a queue submission is still pending.
+            for fence in fences {
+                fence.signaled();
+            }
+
             Ok(())
         }
         else {
@@ -164,7 +197,11 @@ impl Fence {
         };
 
         match res {
-            vks::vk::VK_SUCCESS => Ok(true),
+            vks::vk::VK_SUCCESS => {
+                self.signaled();
+                Ok(true)
+            }
+
             vks::vk::VK_NOT_READY => Ok(false),
             _ => Err(res.into()),
         }
@@ -177,6 +214,7 @@ struct Inner {
     owned: bool,
     device: Device,
     allocator: Option<AllocatorHelper>,
+    pending: Mutex<Vec<Submission>>,
 }
 
 impl Drop for Inner {
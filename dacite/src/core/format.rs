@@ -0,0 +1,530 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core::{Extent3D, Format, ImageAspectFlags};
+
+/// Number of bits of each component of an uncompressed format.
+///
+/// Components, which are not present in a format, have 0 bits. Padding bits and the shared
+/// exponent of `Format::E5B9G9R9_UFloat_Pack32` are not included.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ComponentBits {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+    pub alpha: u32,
+    pub depth: u32,
+    pub stencil: u32,
+}
+
+/// Numeric format of the components of a format.
+///
+/// See [Identification of Formats](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#formats-numericformat)
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NumericFormat {
+    UNorm,
+    SNorm,
+    UScaled,
+    SScaled,
+    UInt,
+    SInt,
+    UFloat,
+    SFloat,
+    sRGB,
+}
+
+impl NumericFormat {
+    /// Returns `true` for `UNorm`, `SNorm` and `sRGB`.
+    pub fn is_normalized(self) -> bool {
+        match self {
+            NumericFormat::UNorm | NumericFormat::SNorm | NumericFormat::sRGB => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` for `UInt` and `SInt`, which are accessed as integers in shaders.
+    pub fn is_integer(self) -> bool {
+        match self {
+            NumericFormat::UInt | NumericFormat::SInt => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` for `UFloat` and `SFloat`.
+    pub fn is_float(self) -> bool {
+        match self {
+            NumericFormat::UFloat | NumericFormat::SFloat => true,
+            _ => false,
+        }
+    }
+}
+
+/// Block compression scheme of a compressed format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FormatCompression {
+    /// BC1 to BC7, enabled by the `texture_compression_bc` feature.
+    Bc,
+
+    /// ETC2 and EAC, enabled by the `texture_compression_etc2` feature.
+    Etc2,
+
+    /// ASTC LDR, enabled by the `texture_compression_astc_ldr` feature.
+    Astc,
+
+    /// PVRTC, provided by the `VK_IMG_format_pvrtc` extension.
+    Pvrtc,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct FormatInfo {
+    block_size: u32,
+    block_extent: (u32, u32),
+    component_count: u32,
+    component_bits: Option<ComponentBits>,
+    numeric_format: NumericFormat,
+    compression: Option<FormatCompression>,
+}
+
+fn color(block_size: u32, bits: [u32; 4], numeric_format: NumericFormat) -> FormatInfo {
+    FormatInfo {
+        block_size: block_size,
+        block_extent: (1, 1),
+        component_count: bits.iter().filter(|&&bits| bits > 0).count() as u32,
+        component_bits: Some(ComponentBits {
+            red: bits[0],
+            green: bits[1],
+            blue: bits[2],
+            alpha: bits[3],
+            .. ComponentBits::default()
+        }),
+        numeric_format: numeric_format,
+        compression: None,
+    }
+}
+
+fn depth_stencil(block_size: u32, depth: u32, stencil: u32, numeric_format: NumericFormat) -> FormatInfo {
+    FormatInfo {
+        block_size: block_size,
+        block_extent: (1, 1),
+        component_count: (depth > 0) as u32 + (stencil > 0) as u32,
+        component_bits: Some(ComponentBits {
+            depth: depth,
+            stencil: stencil,
+            .. ComponentBits::default()
+        }),
+        numeric_format: numeric_format,
+        compression: None,
+    }
+}
+
+fn compressed(block_size: u32, width: u32, height: u32, component_count: u32, numeric_format: NumericFormat, compression: FormatCompression) -> FormatInfo {
+    FormatInfo {
+        block_size: block_size,
+        block_extent: (width, height),
+        component_count: component_count,
+        component_bits: None,
+        numeric_format: numeric_format,
+        compression: Some(compression),
+    }
+}
+
+impl Format {
+    fn info(self) -> Option<FormatInfo> {
+        let info = match self {
+            Format::R4G4_UNorm_Pack8 => color(1, [4, 4, 0, 0], NumericFormat::UNorm),
+            Format::R4G4B4A4_UNorm_Pack16 => color(2, [4, 4, 4, 4], NumericFormat::UNorm),
+            Format::B4G4R4A4_UNorm_Pack16 => color(2, [4, 4, 4, 4], NumericFormat::UNorm),
+            Format::R5G6B5_UNorm_Pack16 => color(2, [5, 6, 5, 0], NumericFormat::UNorm),
+            Format::B5G6R5_UNorm_Pack16 => color(2, [5, 6, 5, 0], NumericFormat::UNorm),
+            Format::R5G5B5A1_UNorm_Pack16 => color(2, [5, 5, 5, 1], NumericFormat::UNorm),
+            Format::B5G5R5A1_UNorm_Pack16 => color(2, [5, 5, 5, 1], NumericFormat::UNorm),
+            Format::A1R5G5B5_UNorm_Pack16 => color(2, [5, 5, 5, 1], NumericFormat::UNorm),
+            Format::R8_UNorm => color(1, [8, 0, 0, 0], NumericFormat::UNorm),
+            Format::R8_SNorm => color(1, [8, 0, 0, 0], NumericFormat::SNorm),
+            Format::R8_UScaled => color(1, [8, 0, 0, 0], NumericFormat::UScaled),
+            Format::R8_SScaled => color(1, [8, 0, 0, 0], NumericFormat::SScaled),
+            Format::R8_UInt => color(1, [8, 0, 0, 0], NumericFormat::UInt),
+            Format::R8_SInt => color(1, [8, 0, 0, 0], NumericFormat::SInt),
+            Format::R8_sRGB => color(1, [8, 0, 0, 0], NumericFormat::sRGB),
+            Format::R8G8_UNorm => color(2, [8, 8, 0, 0], NumericFormat::UNorm),
+            Format::R8G8_SNorm => color(2, [8, 8, 0, 0], NumericFormat::SNorm),
+            Format::R8G8_UScaled => color(2, [8, 8, 0, 0], NumericFormat::UScaled),
+            Format::R8G8_SScaled => color(2, [8, 8, 0, 0], NumericFormat::SScaled),
+            Format::R8G8_UInt => color(2, [8, 8, 0, 0], NumericFormat::UInt),
+            Format::R8G8_SInt => color(2, [8, 8, 0, 0], NumericFormat::SInt),
+            Format::R8G8_sRGB => color(2, [8, 8, 0, 0], NumericFormat::sRGB),
+            Format::R8G8B8_UNorm => color(3, [8, 8, 8, 0], NumericFormat::UNorm),
+            Format::R8G8B8_SNorm => color(3, [8, 8, 8, 0], NumericFormat::SNorm),
+            Format::R8G8B8_UScaled => color(3, [8, 8, 8, 0], NumericFormat::UScaled),
+            Format::R8G8B8_SScaled => color(3, [8, 8, 8, 0], NumericFormat::SScaled),
+            Format::R8G8B8_UInt => color(3, [8, 8, 8, 0], NumericFormat::UInt),
+            Format::R8G8B8_SInt => color(3, [8, 8, 8, 0], NumericFormat::SInt),
+            Format::R8G8B8_sRGB => color(3, [8, 8, 8, 0], NumericFormat::sRGB),
+            Format::B8G8R8_UNorm => color(3, [8, 8, 8, 0], NumericFormat::UNorm),
+            Format::B8G8R8_SNorm => color(3, [8, 8, 8, 0], NumericFormat::SNorm),
+            Format::B8G8R8_UScaled => color(3, [8, 8, 8, 0], NumericFormat::UScaled),
+            Format::B8G8R8_SScaled => color(3, [8, 8, 8, 0], NumericFormat::SScaled),
+            Format::B8G8R8_UInt => color(3, [8, 8, 8, 0], NumericFormat::UInt),
+            Format::B8G8R8_SInt => color(3, [8, 8, 8, 0], NumericFormat::SInt),
+            Format::B8G8R8_sRGB => color(3, [8, 8, 8, 0], NumericFormat::sRGB),
+            Format::R8G8B8A8_UNorm => color(4, [8, 8, 8, 8], NumericFormat::UNorm),
+            Format::R8G8B8A8_SNorm => color(4, [8, 8, 8, 8], NumericFormat::SNorm),
+            Format::R8G8B8A8_UScaled => color(4, [8, 8, 8, 8], NumericFormat::UScaled),
+            Format::R8G8B8A8_SScaled => color(4, [8, 8, 8, 8], NumericFormat::SScaled),
+            Format::R8G8B8A8_UInt => color(4, [8, 8, 8, 8], NumericFormat::UInt),
+            Format::R8G8B8A8_SInt => color(4, [8, 8, 8, 8], NumericFormat::SInt),
+            Format::R8G8B8A8_sRGB => color(4, [8, 8, 8, 8], NumericFormat::sRGB),
+            Format::B8G8R8A8_UNorm => color(4, [8, 8, 8, 8], NumericFormat::UNorm),
+            Format::B8G8R8A8_SNorm => color(4, [8, 8, 8, 8], NumericFormat::SNorm),
+            Format::B8G8R8A8_UScaled => color(4, [8, 8, 8, 8], NumericFormat::UScaled),
+            Format::B8G8R8A8_SScaled => color(4, [8, 8, 8, 8], NumericFormat::SScaled),
+            Format::B8G8R8A8_UInt => color(4, [8, 8, 8, 8], NumericFormat::UInt),
+            Format::B8G8R8A8_SInt => color(4, [8, 8, 8, 8], NumericFormat::SInt),
+            Format::B8G8R8A8_sRGB => color(4, [8, 8, 8, 8], NumericFormat::sRGB),
+            Format::A8B8G8R8_UNorm_Pack32 => color(4, [8, 8, 8, 8], NumericFormat::UNorm),
+            Format::A8B8G8R8_SNorm_Pack32 => color(4, [8, 8, 8, 8], NumericFormat::SNorm),
+            Format::A8B8G8R8_UScaled_Pack32 => color(4, [8, 8, 8, 8], NumericFormat::UScaled),
+            Format::A8B8G8R8_SScaled_Pack32 => color(4, [8, 8, 8, 8], NumericFormat::SScaled),
+            Format::A8B8G8R8_UInt_Pack32 => color(4, [8, 8, 8, 8], NumericFormat::UInt),
+            Format::A8B8G8R8_SInt_Pack32 => color(4, [8, 8, 8, 8], NumericFormat::SInt),
+            Format::A8B8G8R8_sRGB_Pack32 => color(4, [8, 8, 8, 8], NumericFormat::sRGB),
+            Format::A2R10G10B10_UNorm_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::UNorm),
+            Format::A2R10G10B10_SNorm_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::SNorm),
+            Format::A2R10G10B10_UScaled_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::UScaled),
+            Format::A2R10G10B10_SScaled_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::SScaled),
+            Format::A2R10G10B10_UInt_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::UInt),
+            Format::A2R10G10B10_SInt_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::SInt),
+            Format::A2B10G10R10_UNorm_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::UNorm),
+            Format::A2B10G10R10_SNorm_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::SNorm),
+            Format::A2B10G10R10_UScaled_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::UScaled),
+            Format::A2B10G10R10_SScaled_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::SScaled),
+            Format::A2B10G10R10_UInt_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::UInt),
+            Format::A2B10G10R10_SInt_Pack32 => color(4, [10, 10, 10, 2], NumericFormat::SInt),
+            Format::R16_UNorm => color(2, [16, 0, 0, 0], NumericFormat::UNorm),
+            Format::R16_SNorm => color(2, [16, 0, 0, 0], NumericFormat::SNorm),
+            Format::R16_UScaled => color(2, [16, 0, 0, 0], NumericFormat::UScaled),
+            Format::R16_SScaled => color(2, [16, 0, 0, 0], NumericFormat::SScaled),
+            Format::R16_UInt => color(2, [16, 0, 0, 0], NumericFormat::UInt),
+            Format::R16_SInt => color(2, [16, 0, 0, 0], NumericFormat::SInt),
+            Format::R16_SFloat => color(2, [16, 0, 0, 0], NumericFormat::SFloat),
+            Format::R16G16_UNorm => color(4, [16, 16, 0, 0], NumericFormat::UNorm),
+            Format::R16G16_SNorm => color(4, [16, 16, 0, 0], NumericFormat::SNorm),
+            Format::R16G16_UScaled => color(4, [16, 16, 0, 0], NumericFormat::UScaled),
+            Format::R16G16_SScaled => color(4, [16, 16, 0, 0], NumericFormat::SScaled),
+            Format::R16G16_UInt => color(4, [16, 16, 0, 0], NumericFormat::UInt),
+            Format::R16G16_SInt => color(4, [16, 16, 0, 0], NumericFormat::SInt),
+            Format::R16G16_SFloat => color(4, [16, 16, 0, 0], NumericFormat::SFloat),
+            Format::R16G16B16_UNorm => color(6, [16, 16, 16, 0], NumericFormat::UNorm),
+            Format::R16G16B16_SNorm => color(6, [16, 16, 16, 0], NumericFormat::SNorm),
+            Format::R16G16B16_UScaled => color(6, [16, 16, 16, 0], NumericFormat::UScaled),
+            Format::R16G16B16_SScaled => color(6, [16, 16, 16, 0], NumericFormat::SScaled),
+            Format::R16G16B16_UInt => color(6, [16, 16, 16, 0], NumericFormat::UInt),
+            Format::R16G16B16_SInt => color(6, [16, 16, 16, 0], NumericFormat::SInt),
+            Format::R16G16B16_SFloat => color(6, [16, 16, 16, 0], NumericFormat::SFloat),
+            Format::R16G16B16A16_UNorm => color(8, [16, 16, 16, 16], NumericFormat::UNorm),
+            Format::R16G16B16A16_SNorm => color(8, [16, 16, 16, 16], NumericFormat::SNorm),
+            Format::R16G16B16A16_UScaled => color(8, [16, 16, 16, 16], NumericFormat::UScaled),
+            Format::R16G16B16A16_SScaled => color(8, [16, 16, 16, 16], NumericFormat::SScaled),
+            Format::R16G16B16A16_UInt => color(8, [16, 16, 16, 16], NumericFormat::UInt),
+            Format::R16G16B16A16_SInt => color(8, [16, 16, 16, 16], NumericFormat::SInt),
+            Format::R16G16B16A16_SFloat => color(8, [16, 16, 16, 16], NumericFormat::SFloat),
+            Format::R32_UInt => color(4, [32, 0, 0, 0], NumericFormat::UInt),
+            Format::R32_SInt => color(4, [32, 0, 0, 0], NumericFormat::SInt),
+            Format::R32_SFloat => color(4, [32, 0, 0, 0], NumericFormat::SFloat),
+            Format::R32G32_UInt => color(8, [32, 32, 0, 0], NumericFormat::UInt),
+            Format::R32G32_SInt => color(8, [32, 32, 0, 0], NumericFormat::SInt),
+            Format::R32G32_SFloat => color(8, [32, 32, 0, 0], NumericFormat::SFloat),
+            Format::R32G32B32_UInt => color(12, [32, 32, 32, 0], NumericFormat::UInt),
+            Format::R32G32B32_SInt => color(12, [32, 32, 32, 0], NumericFormat::SInt),
+            Format::R32G32B32_SFloat => color(12, [32, 32, 32, 0], NumericFormat::SFloat),
+            Format::R32G32B32A32_UInt => color(16, [32, 32, 32, 32], NumericFormat::UInt),
+            Format::R32G32B32A32_SInt => color(16, [32, 32, 32, 32], NumericFormat::SInt),
+            Format::R32G32B32A32_SFloat => color(16, [32, 32, 32, 32], NumericFormat::SFloat),
+            Format::R64_UInt => color(8, [64, 0, 0, 0], NumericFormat::UInt),
+            Format::R64_SInt => color(8, [64, 0, 0, 0], NumericFormat::SInt),
+            Format::R64_SFloat => color(8, [64, 0, 0, 0], NumericFormat::SFloat),
+            Format::R64G64_UInt => color(16, [64, 64, 0, 0], NumericFormat::UInt),
+            Format::R64G64_SInt => color(16, [64, 64, 0, 0], NumericFormat::SInt),
+            Format::R64G64_SFloat => color(16, [64, 64, 0, 0], NumericFormat::SFloat),
+            Format::R64G64B64_UInt => color(24, [64, 64, 64, 0], NumericFormat::UInt),
+            Format::R64G64B64_SInt => color(24, [64, 64, 64, 0], NumericFormat::SInt),
+            Format::R64G64B64_SFloat => color(24, [64, 64, 64, 0], NumericFormat::SFloat),
+            Format::R64G64B64A64_UInt => color(32, [64, 64, 64, 64], NumericFormat::UInt),
+            Format::R64G64B64A64_SInt => color(32, [64, 64, 64, 64], NumericFormat::SInt),
+            Format::R64G64B64A64_SFloat => color(32, [64, 64, 64, 64], NumericFormat::SFloat),
+            Format::B10G11R11_UFloat_Pack32 => color(4, [11, 11, 10, 0], NumericFormat::UFloat),
+            Format::E5B9G9R9_UFloat_Pack32 => color(4, [9, 9, 9, 0], NumericFormat::UFloat),
+            Format::D16_UNorm => depth_stencil(2, 16, 0, NumericFormat::UNorm),
+            Format::X8_D24_UNorm_Pack32 => depth_stencil(4, 24, 0, NumericFormat::UNorm),
+            Format::D32_SFloat => depth_stencil(4, 32, 0, NumericFormat::SFloat),
+            Format::S8_UInt => depth_stencil(1, 0, 8, NumericFormat::UInt),
+            Format::D16_UNorm_S8_UInt => depth_stencil(3, 16, 8, NumericFormat::UNorm),
+            Format::D24_UNorm_S8_UInt => depth_stencil(4, 24, 8, NumericFormat::UNorm),
+            Format::D32_SFloat_S8_UInt => depth_stencil(5, 32, 8, NumericFormat::SFloat),
+            Format::BC1_RGB_UNorm_Block => compressed(8, 4, 4, 3, NumericFormat::UNorm, FormatCompression::Bc),
+            Format::BC1_RGB_sRGB_Block => compressed(8, 4, 4, 3, NumericFormat::sRGB, FormatCompression::Bc),
+            Format::BC1_RGBA_UNorm_Block => compressed(8, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Bc),
+            Format::BC1_RGBA_sRGB_Block => compressed(8, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Bc),
+            Format::BC2_UNorm_Block => compressed(16, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Bc),
+            Format::BC2_sRGB_Block => compressed(16, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Bc),
+            Format::BC3_UNorm_Block => compressed(16, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Bc),
+            Format::BC3_sRGB_Block => compressed(16, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Bc),
+            Format::BC4_UNorm_Block => compressed(8, 4, 4, 1, NumericFormat::UNorm, FormatCompression::Bc),
+            Format::BC4_SNorm_Block => compressed(8, 4, 4, 1, NumericFormat::SNorm, FormatCompression::Bc),
+            Format::BC5_UNorm_Block => compressed(16, 4, 4, 2, NumericFormat::UNorm, FormatCompression::Bc),
+            Format::BC5_SNorm_Block => compressed(16, 4, 4, 2, NumericFormat::SNorm, FormatCompression::Bc),
+            Format::BC6H_UFloat_Block => compressed(16, 4, 4, 3, NumericFormat::UFloat, FormatCompression::Bc),
+            Format::BC6H_SFloat_Block => compressed(16, 4, 4, 3, NumericFormat::SFloat, FormatCompression::Bc),
+            Format::BC7_UNorm_Block => compressed(16, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Bc),
+            Format::BC7_sRGB_Block => compressed(16, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Bc),
+            Format::ETC2_R8G8B8_UNorm_Block => compressed(8, 4, 4, 3, NumericFormat::UNorm, FormatCompression::Etc2),
+            Format::ETC2_R8G8B8_sRGB_Block => compressed(8, 4, 4, 3, NumericFormat::sRGB, FormatCompression::Etc2),
+            Format::ETC2_R8G8B8A1_UNorm_Block => compressed(8, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Etc2),
+            Format::ETC2_R8G8B8A1_sRGB_Block => compressed(8, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Etc2),
+            Format::ETC2_R8G8B8A8_UNorm_Block => compressed(16, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Etc2),
+            Format::ETC2_R8G8B8A8_sRGB_Block => compressed(16, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Etc2),
+            Format::EAC_R11_UNorm_Block => compressed(8, 4, 4, 1, NumericFormat::UNorm, FormatCompression::Etc2),
+            Format::EAC_R11_SNorm_Block => compressed(8, 4, 4, 1, NumericFormat::SNorm, FormatCompression::Etc2),
+            Format::EAC_R11G11_UNorm_Block => compressed(16, 4, 4, 2, NumericFormat::UNorm, FormatCompression::Etc2),
+            Format::EAC_R11G11_SNorm_Block => compressed(16, 4, 4, 2, NumericFormat::SNorm, FormatCompression::Etc2),
+            Format::ASTC_4x4_UNorm_Block => compressed(16, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_4x4_sRGB_Block => compressed(16, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_5x4_UNorm_Block => compressed(16, 5, 4, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_5x4_sRGB_Block => compressed(16, 5, 4, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_5x5_UNorm_Block => compressed(16, 5, 5, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_5x5_sRGB_Block => compressed(16, 5, 5, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_6x5_UNorm_Block => compressed(16, 6, 5, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_6x5_sRGB_Block => compressed(16, 6, 5, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_6x6_UNorm_Block => compressed(16, 6, 6, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_6x6_sRGB_Block => compressed(16, 6, 6, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_8x5_UNorm_Block => compressed(16, 8, 5, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_8x5_sRGB_Block => compressed(16, 8, 5, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_8x6_UNorm_Block => compressed(16, 8, 6, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_8x6_sRGB_Block => compressed(16, 8, 6, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_8x8_UNorm_Block => compressed(16, 8, 8, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_8x8_sRGB_Block => compressed(16, 8, 8, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_10x5_UNorm_Block => compressed(16, 10, 5, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_10x5_sRGB_Block => compressed(16, 10, 5, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_10x6_UNorm_Block => compressed(16, 10, 6, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_10x6_sRGB_Block => compressed(16, 10, 6, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_10x8_UNorm_Block => compressed(16, 10, 8, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_10x8_sRGB_Block => compressed(16, 10, 8, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_10x10_UNorm_Block => compressed(16, 10, 10, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_10x10_sRGB_Block => compressed(16, 10, 10, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_12x10_UNorm_Block => compressed(16, 12, 10, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_12x10_sRGB_Block => compressed(16, 12, 10, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::ASTC_12x12_UNorm_Block => compressed(16, 12, 12, 4, NumericFormat::UNorm, FormatCompression::Astc),
+            Format::ASTC_12x12_sRGB_Block => compressed(16, 12, 12, 4, NumericFormat::sRGB, FormatCompression::Astc),
+            Format::PVRTC1_2BPP_UNorm_Block_Img => compressed(8, 8, 4, 4, NumericFormat::UNorm, FormatCompression::Pvrtc),
+            Format::PVRTC1_4BPP_UNorm_Block_Img => compressed(8, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Pvrtc),
+            Format::PVRTC2_2BPP_UNorm_Block_Img => compressed(8, 8, 4, 4, NumericFormat::UNorm, FormatCompression::Pvrtc),
+            Format::PVRTC2_4BPP_UNorm_Block_Img => compressed(8, 4, 4, 4, NumericFormat::UNorm, FormatCompression::Pvrtc),
+            Format::PVRTC1_2BPP_sRGB_Block_Img => compressed(8, 8, 4, 4, NumericFormat::sRGB, FormatCompression::Pvrtc),
+            Format::PVRTC1_4BPP_sRGB_Block_Img => compressed(8, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Pvrtc),
+            Format::PVRTC2_2BPP_sRGB_Block_Img => compressed(8, 8, 4, 4, NumericFormat::sRGB, FormatCompression::Pvrtc),
+            Format::PVRTC2_4BPP_sRGB_Block_Img => compressed(8, 4, 4, 4, NumericFormat::sRGB, FormatCompression::Pvrtc),
+            Format::Undefined | Format::Unknown(_) => return None,
+        };
+
+        Some(info)
+    }
+
+    /// Returns the size of a texel block in bytes.
+    ///
+    /// A texel block is a single texel for uncompressed formats and a compressed block otherwise.
+    /// `None` is returned for `Format::Undefined` and unknown formats.
+    pub fn block_size(self) -> Option<u32> {
+        self.info().map(|info| info.block_size)
+    }
+
+    /// Returns the extent of a texel block in texels.
+    ///
+    /// This is 1 × 1 × 1 for uncompressed formats.
+    pub fn block_extent(self) -> Option<Extent3D> {
+        self.info().map(|info| Extent3D::new(info.block_extent.0, info.block_extent.1, 1))
+    }
+
+    /// Returns the number of bytes occupied by tightly packed texel data of a single aspect and
+    /// the given extent, as used by buffer to image copies.
+    ///
+    /// `aspect` must be exactly one of the aspects of this format. Copies of depth/stencil
+    /// formats operate on each aspect separately: depth texels occupy 2 bytes for
+    /// `D16_UNorm`-based formats and 4 bytes otherwise (including the 24 bit formats), and stencil
+    /// texels occupy 1 byte. `None` is returned for any other `aspect`.
+    ///
+    /// Partial texel blocks at the edges of `extent` are rounded up to whole blocks.
+    pub fn data_size(self, aspect: ImageAspectFlags, extent: Extent3D) -> Option<u64> {
+        let info = self.info()?;
+        if !self.aspect_mask().contains(aspect) {
+            return None;
+        }
+
+        let block_size = match aspect {
+            ImageAspectFlags::COLOR => info.block_size,
+            ImageAspectFlags::DEPTH if self.component_bits()?.depth == 16 => 2,
+            ImageAspectFlags::DEPTH => 4,
+            ImageAspectFlags::STENCIL => 1,
+            _ => return None,
+        };
+
+        let (block_width, block_height) = info.block_extent;
+        let blocks_x = ((extent.width + block_width - 1) / block_width) as u64;
+        let blocks_y = ((extent.height + block_height - 1) / block_height) as u64;
+        Some(blocks_x * blocks_y * extent.depth as u64 * block_size as u64)
+    }
+
+    /// Returns the number of components, including depth and stencil.
+    pub fn component_count(self) -> Option<u32> {
+        self.info().map(|info| info.component_count)
+    }
+
+    /// Returns the number of bits of each component.
+    ///
+    /// `None` is returned for compressed formats.
+    pub fn component_bits(self) -> Option<ComponentBits> {
+        self.info().and_then(|info| info.component_bits)
+    }
+
+    /// Returns the numeric format of the components.
+    ///
+    /// For combined depth/stencil formats, this is the numeric format of the depth component. The
+    /// stencil component is always `NumericFormat::UInt`.
+    pub fn numeric_format(self) -> Option<NumericFormat> {
+        self.info().map(|info| info.numeric_format)
+    }
+
+    /// Returns the block compression scheme, or `None` for uncompressed formats.
+    pub fn compression(self) -> Option<FormatCompression> {
+        self.info().and_then(|info| info.compression)
+    }
+
+    /// Returns `true`, if this is a block-compressed format.
+    pub fn is_compressed(self) -> bool {
+        self.compression().is_some()
+    }
+
+    /// Returns `true`, if color components use the sRGB nonlinear encoding.
+    pub fn is_srgb(self) -> bool {
+        self.numeric_format() == Some(NumericFormat::sRGB)
+    }
+
+    /// Returns `true`, if this format has a depth component.
+    pub fn has_depth(self) -> bool {
+        self.component_bits().map_or(false, |bits| bits.depth > 0)
+    }
+
+    /// Returns `true`, if this format has a stencil component.
+    pub fn has_stencil(self) -> bool {
+        self.component_bits().map_or(false, |bits| bits.stencil > 0)
+    }
+
+    /// Returns the aspects of images with this format.
+    pub fn aspect_mask(self) -> ImageAspectFlags {
+        let mut aspect_mask = ImageAspectFlags::empty();
+        if self.has_depth() {
+            aspect_mask |= ImageAspectFlags::DEPTH;
+        }
+
+        if self.has_stencil() {
+            aspect_mask |= ImageAspectFlags::STENCIL;
+        }
+
+        if aspect_mask.is_empty() {
+            aspect_mask = ImageAspectFlags::COLOR;
+        }
+
+        aspect_mask
+    }
+
+    /// Returns the sRGB format with the same layout as this `UNorm` format, if it exists.
+    pub fn srgb_counterpart(self) -> Option<Format> {
+        match self {
+            Format::R8_UNorm => Some(Format::R8_sRGB),
+            Format::R8G8_UNorm => Some(Format::R8G8_sRGB),
+            Format::R8G8B8_UNorm => Some(Format::R8G8B8_sRGB),
+            Format::B8G8R8_UNorm => Some(Format::B8G8R8_sRGB),
+            Format::R8G8B8A8_UNorm => Some(Format::R8G8B8A8_sRGB),
+            Format::B8G8R8A8_UNorm => Some(Format::B8G8R8A8_sRGB),
+            Format::A8B8G8R8_UNorm_Pack32 => Some(Format::A8B8G8R8_sRGB_Pack32),
+            Format::BC1_RGB_UNorm_Block => Some(Format::BC1_RGB_sRGB_Block),
+            Format::BC1_RGBA_UNorm_Block => Some(Format::BC1_RGBA_sRGB_Block),
+            Format::BC2_UNorm_Block => Some(Format::BC2_sRGB_Block),
+            Format::BC3_UNorm_Block => Some(Format::BC3_sRGB_Block),
+            Format::BC7_UNorm_Block => Some(Format::BC7_sRGB_Block),
+            Format::ETC2_R8G8B8_UNorm_Block => Some(Format::ETC2_R8G8B8_sRGB_Block),
+            Format::ETC2_R8G8B8A1_UNorm_Block => Some(Format::ETC2_R8G8B8A1_sRGB_Block),
+            Format::ETC2_R8G8B8A8_UNorm_Block => Some(Format::ETC2_R8G8B8A8_sRGB_Block),
+            Format::ASTC_4x4_UNorm_Block => Some(Format::ASTC_4x4_sRGB_Block),
+            Format::ASTC_5x4_UNorm_Block => Some(Format::ASTC_5x4_sRGB_Block),
+            Format::ASTC_5x5_UNorm_Block => Some(Format::ASTC_5x5_sRGB_Block),
+            Format::ASTC_6x5_UNorm_Block => Some(Format::ASTC_6x5_sRGB_Block),
+            Format::ASTC_6x6_UNorm_Block => Some(Format::ASTC_6x6_sRGB_Block),
+            Format::ASTC_8x5_UNorm_Block => Some(Format::ASTC_8x5_sRGB_Block),
+            Format::ASTC_8x6_UNorm_Block => Some(Format::ASTC_8x6_sRGB_Block),
+            Format::ASTC_8x8_UNorm_Block => Some(Format::ASTC_8x8_sRGB_Block),
+            Format::ASTC_10x5_UNorm_Block => Some(Format::ASTC_10x5_sRGB_Block),
+            Format::ASTC_10x6_UNorm_Block => Some(Format::ASTC_10x6_sRGB_Block),
+            Format::ASTC_10x8_UNorm_Block => Some(Format::ASTC_10x8_sRGB_Block),
+            Format::ASTC_10x10_UNorm_Block => Some(Format::ASTC_10x10_sRGB_Block),
+            Format::ASTC_12x10_UNorm_Block => Some(Format::ASTC_12x10_sRGB_Block),
+            Format::ASTC_12x12_UNorm_Block => Some(Format::ASTC_12x12_sRGB_Block),
+            Format::PVRTC1_2BPP_UNorm_Block_Img => Some(Format::PVRTC1_2BPP_sRGB_Block_Img),
+            Format::PVRTC1_4BPP_UNorm_Block_Img => Some(Format::PVRTC1_4BPP_sRGB_Block_Img),
+            Format::PVRTC2_2BPP_UNorm_Block_Img => Some(Format::PVRTC2_2BPP_sRGB_Block_Img),
+            Format::PVRTC2_4BPP_UNorm_Block_Img => Some(Format::PVRTC2_4BPP_sRGB_Block_Img),
+            _ => None,
+        }
+    }
+
+    /// Returns the `UNorm` format with the same layout as this sRGB format.
+    pub fn unorm_counterpart(self) -> Option<Format> {
+        match self {
+            Format::R8_sRGB => Some(Format::R8_UNorm),
+            Format::R8G8_sRGB => Some(Format::R8G8_UNorm),
+            Format::R8G8B8_sRGB => Some(Format::R8G8B8_UNorm),
+            Format::B8G8R8_sRGB => Some(Format::B8G8R8_UNorm),
+            Format::R8G8B8A8_sRGB => Some(Format::R8G8B8A8_UNorm),
+            Format::B8G8R8A8_sRGB => Some(Format::B8G8R8A8_UNorm),
+            Format::A8B8G8R8_sRGB_Pack32 => Some(Format::A8B8G8R8_UNorm_Pack32),
+            Format::BC1_RGB_sRGB_Block => Some(Format::BC1_RGB_UNorm_Block),
+            Format::BC1_RGBA_sRGB_Block => Some(Format::BC1_RGBA_UNorm_Block),
+            Format::BC2_sRGB_Block => Some(Format::BC2_UNorm_Block),
+            Format::BC3_sRGB_Block => Some(Format::BC3_UNorm_Block),
+            Format::BC7_sRGB_Block => Some(Format::BC7_UNorm_Block),
+            Format::ETC2_R8G8B8_sRGB_Block => Some(Format::ETC2_R8G8B8_UNorm_Block),
+            Format::ETC2_R8G8B8A1_sRGB_Block => Some(Format::ETC2_R8G8B8A1_UNorm_Block),
+            Format::ETC2_R8G8B8A8_sRGB_Block => Some(Format::ETC2_R8G8B8A8_UNorm_Block),
+            Format::ASTC_4x4_sRGB_Block => Some(Format::ASTC_4x4_UNorm_Block),
+            Format::ASTC_5x4_sRGB_Block => Some(Format::ASTC_5x4_UNorm_Block),
+            Format::ASTC_5x5_sRGB_Block => Some(Format::ASTC_5x5_UNorm_Block),
+            Format::ASTC_6x5_sRGB_Block => Some(Format::ASTC_6x5_UNorm_Block),
+            Format::ASTC_6x6_sRGB_Block => Some(Format::ASTC_6x6_UNorm_Block),
+            Format::ASTC_8x5_sRGB_Block => Some(Format::ASTC_8x5_UNorm_Block),
+            Format::ASTC_8x6_sRGB_Block => Some(Format::ASTC_8x6_UNorm_Block),
+            Format::ASTC_8x8_sRGB_Block => Some(Format::ASTC_8x8_UNorm_Block),
+            Format::ASTC_10x5_sRGB_Block => Some(Format::ASTC_10x5_UNorm_Block),
+            Format::ASTC_10x6_sRGB_Block => Some(Format::ASTC_10x6_UNorm_Block),
+            Format::ASTC_10x8_sRGB_Block => Some(Format::ASTC_10x8_UNorm_Block),
+            Format::ASTC_10x10_sRGB_Block => Some(Format::ASTC_10x10_UNorm_Block),
+            Format::ASTC_12x10_sRGB_Block => Some(Format::ASTC_12x10_UNorm_Block),
+            Format::ASTC_12x12_sRGB_Block => Some(Format::ASTC_12x12_UNorm_Block),
+            Format::PVRTC1_2BPP_sRGB_Block_Img => Some(Format::PVRTC1_2BPP_UNorm_Block_Img),
+            Format::PVRTC1_4BPP_sRGB_Block_Img => Some(Format::PVRTC1_4BPP_UNorm_Block_Img),
+            Format::PVRTC2_2BPP_sRGB_Block_Img => Some(Format::PVRTC2_2BPP_UNorm_Block_Img),
+            Format::PVRTC2_4BPP_sRGB_Block_Img => Some(Format::PVRTC2_4BPP_UNorm_Block_Img),
+            _ => None,
+        }
+    }
+}
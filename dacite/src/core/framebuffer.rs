@@ -17,7 +17,7 @@ use TryDestroyError;
 use TryDestroyErrorKind;
 use VulkanObject;
 use core::allocator_helper::AllocatorHelper;
-use core::{self, Device};
+use core::{self, Device, ImageView};
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 use std::ptr;
@@ -91,9 +91,22 @@ impl Framebuffer {
             owned: owned,
             device: device,
             allocator: allocator,
+            attachments: Vec::new(),
         }))
     }
 
+    /// Makes this framebuffer keep its attachments alive.
+    ///
+    /// Must only be called on newly created objects, which are not shared yet.
+    pub(crate) fn set_attachments(&mut self, attachments: Vec<ImageView>) {
+        Arc::get_mut(&mut self.0).unwrap().attachments = attachments;
+    }
+
+    #[inline]
+    pub(crate) fn attachments(&self) -> &[ImageView] {
+        &self.0.attachments
+    }
+
     #[inline]
     pub(crate) fn handle(&self) -> vks::vk::VkFramebuffer {
         self.0.handle
@@ -106,6 +119,7 @@ struct Inner {
     owned: bool,
     device: Device,
     allocator: Option<AllocatorHelper>,
+    attachments: Vec<ImageView>,
 }
 
 impl Drop for Inner {
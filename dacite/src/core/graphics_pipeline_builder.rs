@@ -0,0 +1,405 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core::{self, Device, Pipeline, PipelineCache, PipelineLayout, RenderPass, ShaderModule};
+use std::cmp;
+
+/// Builder for graphics pipelines with sensible defaults.
+///
+/// Only the pipeline layout, render pass and subpass must be specified. Everything else starts out
+/// with the following defaults:
+///
+/// - No vertex bindings or attributes.
+/// - Triangle lists without primitive restart.
+/// - Dynamic viewport and scissor.
+/// - Polygon mode fill, culling disabled, counter-clockwise front faces and a line width of 1.0.
+/// - Single sample, no sample shading.
+/// - Depth and stencil tests disabled. The depth stencil state is always specified, so that the
+///   pipeline can be used in subpasses with and without a depth stencil attachment.
+/// - One color attachment with blending disabled, which writes all components. Use
+///   `color_attachment_count(0)` for subpasses without color attachments.
+#[derive(Debug, Clone)]
+pub struct GraphicsPipelineBuilder {
+    flags: core::PipelineCreateFlags,
+    stages: Vec<core::PipelineShaderStageCreateInfo>,
+    vertex_binding_descriptions: Vec<core::VertexInputBindingDescription>,
+    vertex_attribute_descriptions: Vec<core::VertexInputAttributeDescription>,
+    topology: core::PrimitiveTopology,
+    primitive_restart_enable: bool,
+    patch_control_points: Option<u32>,
+    viewports: Vec<core::Viewport>,
+    scissors: Vec<core::Rect2D>,
+    rasterization_state: core::PipelineRasterizationStateCreateInfo,
+    multisample_state: core::PipelineMultisampleStateCreateInfo,
+    depth_stencil_state: core::PipelineDepthStencilStateCreateInfo,
+    color_attachment_count: u32,
+    color_blend_attachments: Option<Vec<core::PipelineColorBlendAttachmentState>>,
+    logic_op: Option<core::LogicOp>,
+    blend_constants: [f32; 4],
+    dynamic_states: Vec<core::DynamicState>,
+    layout: PipelineLayout,
+    render_pass: RenderPass,
+    subpass: u32,
+}
+
+impl GraphicsPipelineBuilder {
+    /// Creates a new builder for a pipeline, which will be used with `subpass` of `render_pass`.
+    pub fn new(layout: PipelineLayout, render_pass: RenderPass, subpass: u32) -> Self {
+        GraphicsPipelineBuilder {
+            flags: core::PipelineCreateFlags::empty(),
+            stages: Vec::new(),
+            vertex_binding_descriptions: Vec::new(),
+            vertex_attribute_descriptions: Vec::new(),
+            topology: core::PrimitiveTopology::TriangleList,
+            primitive_restart_enable: false,
+            patch_control_points: None,
+            viewports: Vec::new(),
+            scissors: Vec::new(),
+            rasterization_state: Default::default(),
+            multisample_state: Default::default(),
+            depth_stencil_state: Default::default(),
+            color_attachment_count: 1,
+            color_blend_attachments: None,
+            logic_op: None,
+            blend_constants: [0.0, 0.0, 0.0, 0.0],
+            dynamic_states: Vec::new(),
+            layout: layout,
+            render_pass: render_pass,
+            subpass: subpass,
+        }
+    }
+
+    /// Sets the pipeline creation flags.
+    pub fn flags(mut self, flags: core::PipelineCreateFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Adds a shader stage.
+    pub fn stage(mut self, stage: core::PipelineShaderStageCreateInfo) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Adds a shader stage, which uses the entry point `name` of `module`.
+    pub fn shader(self, stage: core::ShaderStageFlagBits, module: ShaderModule, name: &str) -> Self {
+        self.stage(core::PipelineShaderStageCreateInfo {
+            flags: core::PipelineShaderStageCreateFlags::empty(),
+            stage: stage,
+            module: module,
+            name: name.to_owned(),
+            specialization_info: None,
+            chain: None,
+        })
+    }
+
+    /// Adds a vertex shader stage.
+    pub fn vertex_shader(self, module: ShaderModule, name: &str) -> Self {
+        self.shader(core::ShaderStageFlagBits::Vertex, module, name)
+    }
+
+    /// Adds a fragment shader stage.
+    pub fn fragment_shader(self, module: ShaderModule, name: &str) -> Self {
+        self.shader(core::ShaderStageFlagBits::Fragment, module, name)
+    }
+
+    /// Adds a vertex input binding.
+    pub fn vertex_binding(mut self, binding: u32, stride: u32, input_rate: core::VertexInputRate) -> Self {
+        self.vertex_binding_descriptions.push(core::VertexInputBindingDescription {
+            binding: binding,
+            stride: stride,
+            input_rate: input_rate,
+        });
+
+        self
+    }
+
+    /// Adds a vertex input attribute.
+    pub fn vertex_attribute(mut self, location: u32, binding: u32, format: core::Format, offset: u32) -> Self {
+        self.vertex_attribute_descriptions.push(core::VertexInputAttributeDescription {
+            location: location,
+            binding: binding,
+            format: format,
+            offset: offset,
+        });
+
+        self
+    }
+
+    /// Sets the primitive topology.
+    pub fn topology(mut self, topology: core::PrimitiveTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Enables or disables primitive restart.
+    pub fn primitive_restart(mut self, enable: bool) -> Self {
+        self.primitive_restart_enable = enable;
+        self
+    }
+
+    /// Enables tessellation with `patch_control_points` control points per patch.
+    pub fn tessellation(mut self, patch_control_points: u32) -> Self {
+        self.patch_control_points = Some(patch_control_points);
+        self
+    }
+
+    /// Adds a static viewport.
+    ///
+    /// As soon as one viewport has been added, the viewport is no longer dynamic state. If scissor
+    /// rectangles are static as well, the same number of viewports and scissor rectangles must be
+    /// added.
+    pub fn viewport(mut self, viewport: core::Viewport) -> Self {
+        self.viewports.push(viewport);
+        self
+    }
+
+    /// Adds a static scissor rectangle.
+    ///
+    /// As soon as one scissor rectangle has been added, the scissor is no longer dynamic state. If
+    /// viewports are static as well, the same number of viewports and scissor rectangles must be
+    /// added.
+    pub fn scissor(mut self, scissor: core::Rect2D) -> Self {
+        self.scissors.push(scissor);
+        self
+    }
+
+    /// Sets the polygon mode.
+    pub fn polygon_mode(mut self, polygon_mode: core::PolygonMode) -> Self {
+        self.rasterization_state.polygon_mode = polygon_mode;
+        self
+    }
+
+    /// Sets the faces, which will be culled.
+    pub fn cull_mode(mut self, cull_mode: core::CullModeFlags) -> Self {
+        self.rasterization_state.cull_mode = cull_mode;
+        self
+    }
+
+    /// Sets the orientation of front-facing triangles.
+    pub fn front_face(mut self, front_face: core::FrontFace) -> Self {
+        self.rasterization_state.front_face = front_face;
+        self
+    }
+
+    /// Sets the width of rasterized lines.
+    pub fn line_width(mut self, line_width: f32) -> Self {
+        self.rasterization_state.line_width = line_width;
+        self
+    }
+
+    /// Enables depth bias with the specified parameters.
+    pub fn depth_bias(mut self, constant_factor: f32, clamp: f32, slope_factor: f32) -> Self {
+        self.rasterization_state.depth_bias_enable = true;
+        self.rasterization_state.depth_bias_constant_factor = constant_factor;
+        self.rasterization_state.depth_bias_clamp = clamp;
+        self.rasterization_state.depth_bias_slope_factor = slope_factor;
+        self
+    }
+
+    /// Enables or disables depth clamping.
+    pub fn depth_clamp(mut self, enable: bool) -> Self {
+        self.rasterization_state.depth_clamp_enable = enable;
+        self
+    }
+
+    /// Enables or disables discarding of all primitives before rasterization.
+    pub fn rasterizer_discard(mut self, enable: bool) -> Self {
+        self.rasterization_state.rasterizer_discard_enable = enable;
+        self
+    }
+
+    /// Sets the number of samples used in rasterization.
+    pub fn rasterization_samples(mut self, samples: core::SampleCountFlagBits) -> Self {
+        self.multisample_state.rasterization_samples = samples;
+        self
+    }
+
+    /// Enables sample shading with the specified minimum fraction.
+    pub fn sample_shading(mut self, min_sample_shading: f32) -> Self {
+        self.multisample_state.sample_shading_enable = true;
+        self.multisample_state.min_sample_shading = min_sample_shading;
+        self
+    }
+
+    /// Enables the depth test with `compare_op` and optionally depth writes.
+    pub fn depth_test(mut self, compare_op: core::CompareOp, write_enable: bool) -> Self {
+        self.depth_stencil_state.depth_test_enable = true;
+        self.depth_stencil_state.depth_write_enable = write_enable;
+        self.depth_stencil_state.depth_compare_op = compare_op;
+        self
+    }
+
+    /// Enables the depth bounds test.
+    pub fn depth_bounds_test(mut self, min_depth_bounds: f32, max_depth_bounds: f32) -> Self {
+        self.depth_stencil_state.depth_bounds_test_enable = true;
+        self.depth_stencil_state.min_depth_bounds = min_depth_bounds;
+        self.depth_stencil_state.max_depth_bounds = max_depth_bounds;
+        self
+    }
+
+    /// Enables the stencil test.
+    pub fn stencil_test(mut self, front: core::StencilOpState, back: core::StencilOpState) -> Self {
+        self.depth_stencil_state.stencil_test_enable = true;
+        self.depth_stencil_state.front = front;
+        self.depth_stencil_state.back = back;
+        self
+    }
+
+    /// Sets the number of color attachments, which use the default attachment state.
+    ///
+    /// This is ignored, if attachment states were added with `color_blend_attachment`.
+    pub fn color_attachment_count(mut self, count: u32) -> Self {
+        self.color_attachment_count = count;
+        self
+    }
+
+    /// Adds a color blend attachment state.
+    ///
+    /// The first call replaces the default attachment states.
+    pub fn color_blend_attachment(mut self, attachment: core::PipelineColorBlendAttachmentState) -> Self {
+        self.color_blend_attachments.get_or_insert_with(Vec::new).push(attachment);
+        self
+    }
+
+    /// Enables logical operations with `logic_op`.
+    pub fn logic_op(mut self, logic_op: core::LogicOp) -> Self {
+        self.logic_op = Some(logic_op);
+        self
+    }
+
+    /// Sets the blend constants.
+    pub fn blend_constants(mut self, blend_constants: [f32; 4]) -> Self {
+        self.blend_constants = blend_constants;
+        self
+    }
+
+    /// Adds a dynamic state.
+    pub fn dynamic_state(mut self, dynamic_state: core::DynamicState) -> Self {
+        if !self.dynamic_states.contains(&dynamic_state) {
+            self.dynamic_states.push(dynamic_state);
+        }
+
+        self
+    }
+
+    /// Returns the `GraphicsPipelineCreateInfo` described by this builder.
+    pub fn create_info(&self) -> core::GraphicsPipelineCreateInfo {
+        let mut dynamic_states = self.dynamic_states.clone();
+
+        // Even with dynamic viewport and scissor, their counts are specified at pipeline creation
+        // time and must be equal. The contents are ignored.
+        let count = cmp::max(cmp::max(self.viewports.len(), self.scissors.len()), 1);
+
+        let viewports = if self.viewports.is_empty() {
+            if !dynamic_states.contains(&core::DynamicState::Viewport) {
+                dynamic_states.push(core::DynamicState::Viewport);
+            }
+
+            vec![core::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: 1.0,
+                height: 1.0,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            }; count]
+        }
+        else {
+            self.viewports.clone()
+        };
+
+        let scissors = if self.scissors.is_empty() {
+            if !dynamic_states.contains(&core::DynamicState::Scissor) {
+                dynamic_states.push(core::DynamicState::Scissor);
+            }
+
+            vec![core::Rect2D::new(core::Offset2D::zero(), core::Extent2D::new(1, 1)); count]
+        }
+        else {
+            self.scissors.clone()
+        };
+
+        let attachments = match self.color_blend_attachments {
+            Some(ref attachments) => attachments.clone(),
+            None => vec![core::PipelineColorBlendAttachmentState {
+                blend_enable: false,
+                src_color_blend_factor: core::BlendFactor::One,
+                dst_color_blend_factor: core::BlendFactor::Zero,
+                color_blend_op: core::BlendOp::Add,
+                src_alpha_blend_factor: core::BlendFactor::One,
+                dst_alpha_blend_factor: core::BlendFactor::Zero,
+                alpha_blend_op: core::BlendOp::Add,
+                color_write_mask: core::ColorComponentFlags::R | core::ColorComponentFlags::G | core::ColorComponentFlags::B | core::ColorComponentFlags::A,
+            }; self.color_attachment_count as usize],
+        };
+
+        core::GraphicsPipelineCreateInfo {
+            flags: self.flags,
+            stages: self.stages.clone(),
+            vertex_input_state: core::PipelineVertexInputStateCreateInfo {
+                flags: core::PipelineVertexInputStateCreateFlags::empty(),
+                vertex_binding_descriptions: self.vertex_binding_descriptions.clone(),
+                vertex_attribute_descriptions: self.vertex_attribute_descriptions.clone(),
+                chain: None,
+            },
+            input_assembly_state: core::PipelineInputAssemblyStateCreateInfo {
+                flags: core::PipelineInputAssemblyStateCreateFlags::empty(),
+                topology: self.topology,
+                primitive_restart_enable: self.primitive_restart_enable,
+                chain: None,
+            },
+            tessellation_state: self.patch_control_points.map(|patch_control_points| core::PipelineTessellationStateCreateInfo {
+                flags: core::PipelineTessellationStateCreateFlags::empty(),
+                patch_control_points: patch_control_points,
+                chain: None,
+            }),
+            viewport_state: Some(core::PipelineViewportStateCreateInfo {
+                flags: core::PipelineViewportStateCreateFlags::empty(),
+                viewports: viewports,
+                scissors: scissors,
+                chain: None,
+            }),
+            rasterization_state: self.rasterization_state.clone(),
+            multisample_state: Some(self.multisample_state.clone()),
+            depth_stencil_state: Some(self.depth_stencil_state.clone()),
+            color_blend_state: Some(core::PipelineColorBlendStateCreateInfo {
+                flags: core::PipelineColorBlendStateCreateFlags::empty(),
+                logic_op_enable: self.logic_op.is_some(),
+                logic_op: self.logic_op.unwrap_or(core::LogicOp::Copy),
+                attachments: attachments,
+                blend_constants: self.blend_constants,
+                chain: None,
+            }),
+            dynamic_state: Some(core::PipelineDynamicStateCreateInfo {
+                flags: core::PipelineDynamicStateCreateFlags::empty(),
+                dynamic_states: dynamic_states,
+                chain: None,
+            }),
+            layout: self.layout.clone(),
+            render_pass: self.render_pass.clone(),
+            subpass: self.subpass,
+            base_pipeline: None,
+            base_pipeline_index: None,
+            chain: None,
+        }
+    }
+
+    /// Creates the pipeline.
+    pub fn build(&self, device: &Device, pipeline_cache: Option<&PipelineCache>) -> Result<Pipeline, core::Error> {
+        let create_info = self.create_info();
+        let mut pipelines = device.create_graphics_pipelines(pipeline_cache.cloned(), &[create_info], None).map_err(|(e, _)| e)?;
+        Ok(pipelines.remove(0))
+    }
+}
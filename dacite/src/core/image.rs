@@ -17,15 +17,47 @@ use TryDestroyError;
 use TryDestroyErrorKind;
 use VulkanObject;
 use core::allocator_helper::AllocatorHelper;
+use core::image_layout_tracker::ImageLayoutTracker;
 use core::{self, Device, DeviceMemory};
 use std::cmp::Ordering;
+use std::error;
+use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::mem;
 use std::ptr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
 use vks;
 
+/// Error type for image layout tracking.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LayoutTrackingError {
+    /// Layout tracking is not enabled for the image.
+    NotEnabled,
+
+    /// The format, mip levels and array layers of the image are unknown, because it was not
+    /// created with [`Device::create_image`](struct.Device.html#method.create_image).
+    UnknownSubresources,
+}
+
+impl fmt::Display for LayoutTrackingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LayoutTrackingError::NotEnabled => write!(f, "Layout tracking is not enabled for the image"),
+            LayoutTrackingError::UnknownSubresources => write!(f, "The subresources of the image are unknown"),
+        }
+    }
+}
+
+impl error::Error for LayoutTrackingError {
+    fn description(&self) -> &str {
+        match *self {
+            LayoutTrackingError::NotEnabled => "NotEnabled",
+            LayoutTrackingError::UnknownSubresources => "UnknownSubresources",
+        }
+    }
+}
+
 /// See [`VkImage`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkImage)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Image(Arc<Inner>);
@@ -98,9 +130,40 @@ impl Image {
             owned: owned,
             device: device,
             allocator: allocator,
+            memory: None,
+            properties: None,
+            layout_tracker: Mutex::new(None),
         }))
     }
 
+    /// Remembers the properties of `create_info` for layout tracking and mipmap generation.
+    ///
+    /// Must only be called on newly created objects, which are not shared yet.
+    pub(crate) fn set_create_info(&mut self, create_info: &core::ImageCreateInfo) {
+        Arc::get_mut(&mut self.0).unwrap().properties = Some(ImageProperties {
+            format: create_info.format,
+            extent: create_info.extent,
+            mip_levels: create_info.mip_levels,
+            array_layers: create_info.array_layers,
+            tiling: create_info.tiling,
+        });
+    }
+
+    /// Returns the properties of the `ImageCreateInfo`, with which this image was created.
+    ///
+    /// `None` is returned for images, which were not created by `Device::create_image`.
+    #[inline]
+    pub(crate) fn properties(&self) -> Option<ImageProperties> {
+        self.0.properties
+    }
+
+    /// Makes this image keep `memory` alive until it is destroyed.
+    ///
+    /// Must only be called on newly created objects, which are not shared yet.
+    pub(crate) fn set_memory(&mut self, memory: DeviceMemory) {
+        Arc::get_mut(&mut self.0).unwrap().memory = Some(memory);
+    }
+
     #[inline]
     pub(crate) fn handle(&self) -> vks::vk::VkImage {
         self.0.handle
@@ -116,6 +179,69 @@ impl Image {
         self.0.device.handle()
     }
 
+    /// Returns the `DeviceMemory` owned by this image.
+    ///
+    /// This is only the case for images created with
+    /// [`Device::create_image_with_memory`](struct.Device.html#method.create_image_with_memory).
+    pub fn memory(&self) -> Option<&DeviceMemory> {
+        self.0.memory.as_ref()
+    }
+
+    /// Enables tracking of the current layout of every mip level and array layer.
+    ///
+    /// The format, mip levels and array layers are taken from the `ImageCreateInfo`, with which
+    /// the image was created. `layout` must match the current state of the image. Tracking is
+    /// reset, if it was already enabled.
+    ///
+    /// Returns `LayoutTrackingError::UnknownSubresources` for images, which were not created with
+    /// [`Device::create_image`](struct.Device.html#method.create_image), e.g. swapchain images.
+    /// Use [`enable_layout_tracking_with`](#method.enable_layout_tracking_with) for these.
+    ///
+    /// Once enabled, image memory barriers recorded with
+    /// [`CommandBuffer::pipeline_barrier`](struct.CommandBuffer.html#method.pipeline_barrier) or
+    /// [`CommandBuffer::wait_events`](struct.CommandBuffer.html#method.wait_events) and the final
+    /// layouts of render pass attachments are recorded.
+    /// [`CommandBuffer::transition_to`](struct.CommandBuffer.html#method.transition_to) uses the
+    /// tracked layouts to emit barriers.
+    ///
+    /// The state is updated, when commands are recorded, not when they are executed. Command
+    /// buffers must therefore be submitted in the order, in which they were recorded.
+    pub fn enable_layout_tracking(&self, layout: core::ImageLayout) -> Result<(), LayoutTrackingError> {
+        let properties = self.0.properties.ok_or(LayoutTrackingError::UnknownSubresources)?;
+        self.enable_layout_tracking_with(properties.format, properties.mip_levels, properties.array_layers, layout);
+        Ok(())
+    }
+
+    /// Enables layout tracking for an image with the given format, mip levels and array layers.
+    ///
+    /// See [`enable_layout_tracking`](#method.enable_layout_tracking). `format`, `mip_levels` and
+    /// `array_layers` must match the values, with which the image was created.
+    pub fn enable_layout_tracking_with(&self, format: core::Format, mip_levels: u32, array_layers: u32, layout: core::ImageLayout) {
+        *self.layout_tracker() = Some(ImageLayoutTracker::new(format.aspect_mask(), mip_levels, array_layers, layout));
+    }
+
+    /// Disables layout tracking.
+    pub fn disable_layout_tracking(&self) {
+        *self.layout_tracker() = None;
+    }
+
+    /// Returns `true`, if layout tracking is enabled for this image.
+    pub fn is_layout_tracked(&self) -> bool {
+        self.layout_tracker().is_some()
+    }
+
+    /// Returns the tracked layout of a single mip level and array layer.
+    ///
+    /// `None` is returned, if layout tracking is not enabled or if the subresource does not exist.
+    pub fn layout(&self, mip_level: u32, array_layer: u32) -> Option<core::ImageLayout> {
+        self.layout_tracker().as_ref().and_then(|tracker| tracker.layout(mip_level, array_layer))
+    }
+
+    #[inline]
+    pub(crate) fn layout_tracker(&self) -> MutexGuard<Option<ImageLayoutTracker>> {
+        self.0.layout_tracker.lock().unwrap()
+    }
+
     /// See [`vkBindImageMemory`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkBindImageMemory)
     pub fn bind_memory(&self, memory: DeviceMemory, offset: u64) -> Result<(), core::Error> {
         let res = unsafe {
@@ -169,12 +295,28 @@ impl Image {
     }
 }
 
+/// Properties of an image created by `Device::create_image`.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ImageProperties {
+    pub format: core::Format,
+    pub extent: core::Extent3D,
+    pub mip_levels: u32,
+    pub array_layers: u32,
+    pub tiling: core::ImageTiling,
+}
+
 #[derive(Debug)]
 struct Inner {
     handle: vks::vk::VkImage,
     owned: bool,
     device: Device,
     allocator: Option<AllocatorHelper>,
+    memory: Option<DeviceMemory>,
+
+    /// Set, if the image was created by `Device::create_image`.
+    properties: Option<ImageProperties>,
+
+    layout_tracker: Mutex<Option<ImageLayoutTracker>>,
 }
 
 impl Drop for Inner {
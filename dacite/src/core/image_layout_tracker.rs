@@ -0,0 +1,179 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core;
+
+/// Layout and last access of a single subresource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct SubresourceState {
+    layout: core::ImageLayout,
+    stage_mask: core::PipelineStageFlags,
+    access_mask: core::AccessFlags,
+}
+
+/// Tracks the layout of every mip level and array layer of an `Image`.
+///
+/// The state is updated, when commands are recorded, not when they are executed. Command buffers
+/// must therefore be submitted in the same order, in which they were recorded.
+#[derive(Debug, Clone)]
+pub(crate) struct ImageLayoutTracker {
+    aspect_mask: core::ImageAspectFlags,
+    mip_levels: u32,
+    array_layers: u32,
+    states: Vec<SubresourceState>,
+}
+
+impl ImageLayoutTracker {
+    pub fn new(aspect_mask: core::ImageAspectFlags, mip_levels: u32, array_layers: u32, layout: core::ImageLayout) -> Self {
+        let state = SubresourceState {
+            layout: layout,
+            stage_mask: core::PipelineStageFlags::TOP_OF_PIPE,
+            access_mask: core::AccessFlags::empty(),
+        };
+
+        ImageLayoutTracker {
+            aspect_mask: aspect_mask,
+            mip_levels: mip_levels,
+            array_layers: array_layers,
+            states: vec![state; (mip_levels * array_layers) as usize],
+        }
+    }
+
+    pub fn layout(&self, mip_level: u32, array_layer: u32) -> Option<core::ImageLayout> {
+        if (mip_level < self.mip_levels) && (array_layer < self.array_layers) {
+            Some(self.states[self.index(mip_level, array_layer)].layout)
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn full_range(&self) -> core::ImageSubresourceRange {
+        core::ImageSubresourceRange {
+            aspect_mask: self.aspect_mask,
+            base_mip_level: 0,
+            level_count: core::OptionalMipLevels::MipLevels(self.mip_levels),
+            base_array_layer: 0,
+            layer_count: core::OptionalArrayLayers::ArrayLayers(self.array_layers),
+        }
+    }
+
+    /// Records, that `range` was transitioned to `layout` by a barrier or a render pass.
+    pub fn set(&mut self, range: &core::ImageSubresourceRange, layout: core::ImageLayout, stage_mask: core::PipelineStageFlags, access_mask: core::AccessFlags) {
+        let (mip_levels, array_layers) = self.resolve(range);
+        for mip_level in mip_levels.0..mip_levels.1 {
+            for array_layer in array_layers.0..array_layers.1 {
+                let index = self.index(mip_level, array_layer);
+                self.states[index] = SubresourceState {
+                    layout: layout,
+                    stage_mask: stage_mask,
+                    access_mask: access_mask,
+                };
+            }
+        }
+    }
+
+    /// Returns the source stage mask and the ranges, old layouts and source access masks of the
+    /// barriers, which transition `range` to `layout`.
+    ///
+    /// Subresources already in `layout` are skipped. Adjacent subresources with the same state
+    /// are combined into one barrier.
+    pub fn transition(&self, range: &core::ImageSubresourceRange, layout: core::ImageLayout) -> (core::PipelineStageFlags, Vec<(core::ImageSubresourceRange, core::ImageLayout, core::AccessFlags)>) {
+        let (mip_levels, array_layers) = self.resolve(range);
+
+        // Runs of array layers with the same state, one list per mip level.
+        let mut runs: Vec<(u32, u32, u32, SubresourceState)> = Vec::new();
+        for mip_level in mip_levels.0..mip_levels.1 {
+            for array_layer in array_layers.0..array_layers.1 {
+                let state = self.states[self.index(mip_level, array_layer)];
+                if state.layout == layout {
+                    continue;
+                }
+
+                if let Some(last) = runs.last_mut() {
+                    if (last.0 == mip_level) && (last.1 + last.2 == array_layer) && (last.3 == state) {
+                        last.2 += 1;
+                        continue;
+                    }
+                }
+
+                runs.push((mip_level, array_layer, 1, state));
+            }
+        }
+
+        // Merge runs covering the same array layers of consecutive mip levels.
+        let mut barriers: Vec<(u32, u32, u32, u32, SubresourceState)> = Vec::new();
+        for (mip_level, base_array_layer, layer_count, state) in runs {
+            if let Some(barrier) = barriers.iter_mut().find(|b| (b.0 + b.1 == mip_level) && (b.2 == base_array_layer) && (b.3 == layer_count) && (b.4 == state)) {
+                barrier.1 += 1;
+                continue;
+            }
+
+            barriers.push((mip_level, 1, base_array_layer, layer_count, state));
+        }
+
+        let mut src_stage_mask = core::PipelineStageFlags::empty();
+        let barriers = barriers.into_iter().map(|(base_mip_level, level_count, base_array_layer, layer_count, state)| {
+            src_stage_mask |= state.stage_mask;
+
+            let range = core::ImageSubresourceRange {
+                aspect_mask: self.aspect_mask,
+                base_mip_level: base_mip_level,
+                level_count: core::OptionalMipLevels::MipLevels(level_count),
+                base_array_layer: base_array_layer,
+                layer_count: core::OptionalArrayLayers::ArrayLayers(layer_count),
+            };
+
+            (range, state.layout, state.access_mask)
+        }).collect();
+
+        (src_stage_mask, barriers)
+    }
+
+    #[inline]
+    fn index(&self, mip_level: u32, array_layer: u32) -> usize {
+        (mip_level * self.array_layers + array_layer) as usize
+    }
+
+    /// Returns the half-open ranges of mip levels and array layers covered by `range`.
+    fn resolve(&self, range: &core::ImageSubresourceRange) -> ((u32, u32), (u32, u32)) {
+        let mip_levels_end = match range.level_count {
+            core::OptionalMipLevels::MipLevels(count) => range.base_mip_level + count,
+            core::OptionalMipLevels::Remaining => self.mip_levels,
+        };
+
+        let array_layers_end = match range.layer_count {
+            core::OptionalArrayLayers::ArrayLayers(count) => range.base_array_layer + count,
+            core::OptionalArrayLayers::Remaining => self.array_layers,
+        };
+
+        ((range.base_mip_level.min(self.mip_levels), mip_levels_end.min(self.mip_levels)),
+         (range.base_array_layer.min(self.array_layers), array_layers_end.min(self.array_layers)))
+    }
+}
+
+/// Returns the accesses, which are typically performed on images in `layout`.
+pub(crate) fn layout_access_mask(layout: core::ImageLayout) -> core::AccessFlags {
+    match layout {
+        core::ImageLayout::General => core::AccessFlags::MEMORY_READ | core::AccessFlags::MEMORY_WRITE,
+        core::ImageLayout::ColorAttachmentOptimal => core::AccessFlags::COLOR_ATTACHMENT_READ | core::AccessFlags::COLOR_ATTACHMENT_WRITE,
+        core::ImageLayout::DepthStencilAttachmentOptimal => core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+        core::ImageLayout::DepthStencilReadOnlyOptimal => core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | core::AccessFlags::SHADER_READ,
+        core::ImageLayout::ShaderReadOnlyOptimal => core::AccessFlags::SHADER_READ,
+        core::ImageLayout::TransferSrcOptimal => core::AccessFlags::TRANSFER_READ,
+        core::ImageLayout::TransferDstOptimal => core::AccessFlags::TRANSFER_WRITE,
+        core::ImageLayout::Preinitialized => core::AccessFlags::HOST_WRITE,
+        _ => core::AccessFlags::empty(),
+    }
+}
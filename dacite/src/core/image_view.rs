@@ -17,7 +17,7 @@ use TryDestroyError;
 use TryDestroyErrorKind;
 use VulkanObject;
 use core::allocator_helper::AllocatorHelper;
-use core::{self, Device};
+use core::{self, Device, Image};
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 use std::ptr;
@@ -91,9 +91,22 @@ impl ImageView {
             owned: owned,
             device: device,
             allocator: allocator,
+            image: None,
         }))
     }
 
+    /// Stores the image and subresource range viewed by this image view.
+    ///
+    /// Must only be called on newly created objects, which are not shared yet.
+    pub(crate) fn set_image(&mut self, image: Image, subresource_range: core::ImageSubresourceRange) {
+        Arc::get_mut(&mut self.0).unwrap().image = Some((image, subresource_range));
+    }
+
+    #[inline]
+    pub(crate) fn image(&self) -> Option<(&Image, &core::ImageSubresourceRange)> {
+        self.0.image.as_ref().map(|image| (&image.0, &image.1))
+    }
+
     #[inline]
     pub(crate) fn handle(&self) -> vks::vk::VkImageView {
         self.0.handle
@@ -106,6 +119,7 @@ struct Inner {
     owned: bool,
     device: Device,
     allocator: Option<AllocatorHelper>,
+    image: Option<(Image, core::ImageSubresourceRange)>,
 }
 
 impl Drop for Inner {
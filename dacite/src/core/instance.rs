@@ -24,7 +24,6 @@ use khr_wayland_surface;
 use khr_win32_surface;
 use khr_xcb_surface;
 use khr_xlib_surface;
-use libloading;
 use std::cmp::Ordering;
 use std::error;
 use std::ffi::CStr;
@@ -36,10 +35,9 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use utils;
 use vks;
+use vks_1_1;
 use {TryDestroyError, TryDestroyErrorKind, VulkanObject};
 
-const VK_GET_INSTANCE_PROC_ADDR: &'static str = "vkGetInstanceProcAddr";
-
 /// Indicates an error, which occurred before an Instance was created.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EarlyInstanceError {
@@ -118,6 +116,11 @@ impl Instance {
         &self.0.loader
     }
 
+    #[inline]
+    pub(crate) fn loader_1_1(&self) -> &vks_1_1::InstanceFunctions {
+        &self.0.loader_1_1
+    }
+
     pub(crate) fn add_display_mode_allocator(&self, allocator: AllocatorHelper) {
         self.0.display_mode_allocators.lock().unwrap().push(allocator);
     }
@@ -126,27 +129,26 @@ impl Instance {
         &self.0.enabled_extensions
     }
 
-    /// See [`vkCreateInstance`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCreateInstance)
-    pub fn create(create_info: &core::InstanceCreateInfo, allocator: Option<Box<core::Allocator>>) -> Result<Instance, EarlyInstanceError> {
-        let (library, vk_get_instance_proc_addr) = unsafe {
-            let library = libloading::Library::new(vks::VULKAN_LIBRARY_NAME)
-                .map_err(|_| EarlyInstanceError::LoadLibraryFailed(vks::VULKAN_LIBRARY_NAME.to_owned()))?;
-            let vk_get_instance_proc_addr = {
-                let vk_get_instance_proc_addr: libloading::Symbol<vks::vk::PFN_vkGetInstanceProcAddr> = library
-                    .get(VK_GET_INSTANCE_PROC_ADDR.as_bytes())
-                    .map_err(|_| EarlyInstanceError::SymbolNotFound(VK_GET_INSTANCE_PROC_ADDR.to_owned()))?;
-                *vk_get_instance_proc_addr
-            };
-            (library, vk_get_instance_proc_addr)
-        };
+    /// Returns the Vulkan version requested with `ApplicationInfo::api_version`.
+    ///
+    /// Version 1.0.0 is returned, if no version was requested.
+    pub fn get_api_version(&self) -> core::Version {
+        self.0.api_version
+    }
 
+    /// Returns `true`, if both the instance and `function` support Vulkan 1.1.
+    pub(crate) fn supports_vulkan_1_1<F>(&self, function: &Option<F>) -> bool {
+        function.is_some() && (self.0.api_version.as_api_version() >= ::DACITE_API_VERSION_1_1.as_api_version())
+    }
+
+    /// See [`vkCreateInstance`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCreateInstance)
+    pub fn create(loader: &core::Loader, create_info: &core::InstanceCreateInfo, allocator: Option<Box<core::Allocator>>) -> Result<Instance, EarlyInstanceError> {
         let allocator_helper = allocator.map(AllocatorHelper::new);
         let allocation_callbacks = allocator_helper.as_ref().map_or(ptr::null(), AllocatorHelper::callbacks);
 
-        let mut loader = vks::InstanceProcAddrLoader::from_get_instance_proc_addr(vk_get_instance_proc_addr);
-        unsafe {
-            loader.load_vk_global();
-        }
+        let vk_loader = loader;
+        let mut loader = vks::InstanceProcAddrLoader::from_get_instance_proc_addr(vk_loader.vk_get_instance_proc_addr());
+        loader.vk_global = *vk_loader.vk_global();
 
         let create_info_wrapper = core::VkInstanceCreateInfoWrapper::new(create_info, true);
         let mut instance = ptr::null_mut();
@@ -157,8 +159,10 @@ impl Instance {
             return Err(res.into());
         }
 
+        let mut loader_1_1 = vks_1_1::InstanceFunctions::new();
         unsafe {
             loader.load_vk(instance);
+            loader_1_1.load(vk_loader.vk_get_instance_proc_addr(), instance);
             create_info.enabled_extensions.load_instance(&mut loader, instance);
         }
 
@@ -174,12 +178,19 @@ impl Instance {
             None
         };
 
+        let api_version = match create_info.application_info {
+            Some(core::ApplicationInfo { api_version: Some(api_version), .. }) => api_version,
+            _ => ::DACITE_API_VERSION_1_0,
+        };
+
         Ok(Instance(Arc::new(Inner {
             handle: instance,
             allocator: allocator_helper,
             loader: loader,
-            library: library,
+            loader_1_1: loader_1_1,
+            vk_loader: vk_loader.clone(),
             enabled_extensions: create_info.enabled_extensions.clone(),
+            api_version: api_version,
             debug_report_callback: debug_report_callback,
             display_mode_allocators: Mutex::new(Vec::new()),
         })))
@@ -214,19 +225,75 @@ impl Instance {
         Ok(physical_devices)
     }
 
+    /// See [`vkEnumeratePhysicalDeviceGroups`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkEnumeratePhysicalDeviceGroups)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the instance doesn't support Vulkan 1.1 or was not
+    /// created for Vulkan 1.1.
+    pub fn enumerate_physical_device_groups<B>(&self, chain_query: Option<&core::PhysicalDeviceGroupPropertiesChainQuery>) -> Result<B, core::Error>
+        where B: FromIterator<core::PhysicalDeviceGroupProperties>
+    {
+        if !self.supports_vulkan_1_1(&self.loader_1_1().pfn_vkEnumeratePhysicalDeviceGroups) {
+            return Err(core::Error::FeatureNotPresent);
+        }
+
+        loop {
+            unsafe {
+                let mut num = 0;
+                let res = self.loader_1_1().vkEnumeratePhysicalDeviceGroups(self.handle(), &mut num, ptr::null_mut());
+                if res != vks::vk::VK_SUCCESS {
+                    return Err(res.into());
+                }
+
+                let mut chain_query_wrappers = Vec::with_capacity(num as usize);
+                for _ in 0..num {
+                    chain_query_wrappers.push(core::PhysicalDeviceGroupPropertiesChainQueryWrapper::new_optional(chain_query));
+                }
+
+                // The number of groups can change between both calls, in which case VK_INCOMPLETE
+                // is returned and the groups are enumerated again.
+                let mut vks_structs: Vec<_> = chain_query_wrappers.iter().map(|w| w.vks_struct).collect();
+                let res = self.loader_1_1().vkEnumeratePhysicalDeviceGroups(self.handle(), &mut num, vks_structs.as_mut_ptr());
+                if res == vks::vk::VK_INCOMPLETE {
+                    continue;
+                }
+                else if res != vks::vk::VK_SUCCESS {
+                    return Err(res.into());
+                }
+
+                return Ok(vks_structs.iter().take(num as usize).map(|p| core::PhysicalDeviceGroupProperties::from_vks(p, self, true)).collect());
+            }
+        }
+    }
+
+    /// See [`vkEnumerateInstanceVersion`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkEnumerateInstanceVersion)
+    ///
+    /// Vulkan 1.0 implementations do not provide `vkEnumerateInstanceVersion`. In that case,
+    /// version 1.0.0 is returned.
+    pub fn enumerate_instance_version(loader: &core::Loader) -> Result<core::Version, EarlyInstanceError> {
+        let vk_enumerate_instance_version = match loader.vk_enumerate_instance_version() {
+            Some(vk_enumerate_instance_version) => vk_enumerate_instance_version,
+            None => return Ok(::DACITE_API_VERSION_1_0),
+        };
+
+        let mut api_version = 0;
+        let res = unsafe {
+            vk_enumerate_instance_version(&mut api_version)
+        };
+
+        if res == vks::vk::VK_SUCCESS {
+            Ok(core::Version::from_api_version(api_version))
+        }
+        else {
+            Err(res.into())
+        }
+    }
+
     /// See [`vkEnumerateInstanceLayerProperties`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkEnumerateInstanceLayerProperties)
-    pub fn enumerate_instance_layer_properties<B>() -> Result<B, EarlyInstanceError>
+    pub fn enumerate_instance_layer_properties<B>(loader: &core::Loader) -> Result<B, EarlyInstanceError>
         where B: FromIterator<core::LayerProperties>
     {
         unsafe {
-            let library = libloading::Library::new(vks::VULKAN_LIBRARY_NAME)
-                .map_err(|_| EarlyInstanceError::LoadLibraryFailed(vks::VULKAN_LIBRARY_NAME.to_owned()))?;
-            let vk_get_instance_proc_addr: libloading::Symbol<vks::vk::PFN_vkGetInstanceProcAddr> = library
-                .get(VK_GET_INSTANCE_PROC_ADDR.as_bytes())
-                .map_err(|_| EarlyInstanceError::SymbolNotFound(VK_GET_INSTANCE_PROC_ADDR.to_owned()))?;
-
-            let mut loader = vks::instance_proc_addr_loader::VkGlobal::new();
-            loader.load(*vk_get_instance_proc_addr, ptr::null_mut());
+            let loader = loader.vk_global();
 
             let mut num_layer_properties = 0;
             let res = loader.vkEnumerateInstanceLayerProperties(&mut num_layer_properties, ptr::null_mut());
@@ -246,16 +313,9 @@ impl Instance {
     }
 
     /// See [`vkEnumerateInstanceExtensionProperties`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkEnumerateInstanceExtensionProperties)
-    pub fn get_instance_extension_properties(layer_name: Option<&str>) -> Result<core::InstanceExtensionsProperties, EarlyInstanceError> {
+    pub fn get_instance_extension_properties(loader: &core::Loader, layer_name: Option<&str>) -> Result<core::InstanceExtensionsProperties, EarlyInstanceError> {
         unsafe {
-            let library = libloading::Library::new(vks::VULKAN_LIBRARY_NAME)
-                .map_err(|_| EarlyInstanceError::LoadLibraryFailed(vks::VULKAN_LIBRARY_NAME.to_owned()))?;
-            let vk_get_instance_proc_addr: libloading::Symbol<vks::vk::PFN_vkGetInstanceProcAddr> = library
-                .get(VK_GET_INSTANCE_PROC_ADDR.as_bytes())
-                .map_err(|_| EarlyInstanceError::SymbolNotFound(VK_GET_INSTANCE_PROC_ADDR.to_owned()))?;
-
-            let mut loader = vks::instance_proc_addr_loader::VkGlobal::new();
-            loader.load(*vk_get_instance_proc_addr, ptr::null_mut());
+            let loader = loader.vk_global();
 
             let layer_name_cstr = utils::cstr_from_str(layer_name);
 
@@ -282,6 +342,24 @@ impl Instance {
         }
     }
 
+    /// Enumerates all instance layers together with the instance extensions provided by each layer.
+    ///
+    /// This is equivalent to calling `enumerate_instance_layer_properties` and then
+    /// `get_instance_extension_properties` for every layer.
+    pub fn enumerate_instance_layers<B>(loader: &core::Loader) -> Result<B, EarlyInstanceError>
+        where B: FromIterator<core::InstanceLayer>
+    {
+        let layers: Vec<core::LayerProperties> = Instance::enumerate_instance_layer_properties(loader)?;
+
+        layers.into_iter().map(|properties| {
+            let extensions = Instance::get_instance_extension_properties(loader, Some(&properties.layer_name))?;
+            Ok(core::InstanceLayer {
+                properties: properties,
+                extensions: extensions,
+            })
+        }).collect()
+    }
+
     /// See [`vkCreateDebugReportCallbackEXT`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkCreateDebugReportCallbackEXT)
     /// and extension [`VK_EXT_debug_report`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_EXT_debug_report)
     pub fn create_debug_report_callback_ext(&self, create_info: &ext_debug_report::DebugReportCallbackCreateInfoExt, allocator: Option<Box<core::Allocator>>) -> Result<ext_debug_report::DebugReportCallbackExt, core::Error> {
@@ -469,8 +547,10 @@ struct Inner {
     handle: vks::vk::VkInstance,
     allocator: Option<AllocatorHelper>,
     loader: vks::InstanceProcAddrLoader,
-    library: libloading::Library,
+    loader_1_1: vks_1_1::InstanceFunctions,
+    vk_loader: core::Loader,
     enabled_extensions: core::InstanceExtensions,
+    api_version: core::Version,
     debug_report_callback: Option<Arc<ext_debug_report::DebugReportCallbacksExt>>,
     display_mode_allocators: Mutex<Vec<AllocatorHelper>>,
 }
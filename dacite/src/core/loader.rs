@@ -0,0 +1,128 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core::EarlyInstanceError;
+use libloading;
+use std::env;
+use std::ffi::OsStr;
+use std::ptr;
+use std::mem;
+use std::sync::Arc;
+use vks;
+use vks_1_1;
+
+const VK_GET_INSTANCE_PROC_ADDR: &'static str = "vkGetInstanceProcAddr";
+
+/// Name of the environment variable, which overrides the default Vulkan library in
+/// [`Loader::new`](struct.Loader.html#method.new).
+pub const VULKAN_LIBRARY_ENV_VAR: &'static str = "DACITE_VULKAN_LIBRARY";
+
+/// Entry point to the Vulkan library.
+///
+/// A `Loader` owns the Vulkan library (if it was opened by dacite) and the `vkGetInstanceProcAddr`
+/// function pointer. It is cheap to clone and is kept alive by every `Instance` created from it.
+#[derive(Debug, Clone)]
+pub struct Loader(Arc<Inner>);
+
+impl Loader {
+    /// Loads the Vulkan library.
+    ///
+    /// If the environment variable `DACITE_VULKAN_LIBRARY` is set, its value is used as the path
+    /// of the library. Otherwise the platform's default Vulkan library is loaded.
+    pub fn new() -> Result<Self, EarlyInstanceError> {
+        match env::var_os(VULKAN_LIBRARY_ENV_VAR) {
+            Some(ref path) if !path.is_empty() => Loader::from_path(path),
+            _ => Loader::from_path(vks::VULKAN_LIBRARY_NAME),
+        }
+    }
+
+    /// Loads the Vulkan library from `path`.
+    pub fn from_path<P: AsRef<OsStr>>(path: P) -> Result<Self, EarlyInstanceError> {
+        let path = path.as_ref();
+
+        let library = libloading::Library::new(path)
+            .map_err(|_| EarlyInstanceError::LoadLibraryFailed(path.to_string_lossy().into_owned()))?;
+
+        let vk_get_instance_proc_addr = unsafe {
+            let vk_get_instance_proc_addr: libloading::Symbol<vks::vk::PFN_vkGetInstanceProcAddr> = library
+                .get(VK_GET_INSTANCE_PROC_ADDR.as_bytes())
+                .map_err(|_| EarlyInstanceError::SymbolNotFound(VK_GET_INSTANCE_PROC_ADDR.to_owned()))?;
+            *vk_get_instance_proc_addr
+        };
+
+        if vk_get_instance_proc_addr.is_none() {
+            return Err(EarlyInstanceError::SymbolNotFound(VK_GET_INSTANCE_PROC_ADDR.to_owned()));
+        }
+
+        Ok(unsafe { Loader::new_internal(Some(library), vk_get_instance_proc_addr) })
+    }
+
+    /// Creates a `Loader` from an existing `vkGetInstanceProcAddr` function pointer.
+    ///
+    /// No library is opened. The caller must ensure, that `vk_get_instance_proc_addr` stays valid
+    /// for as long as the `Loader` and all objects created from it are alive.
+    pub unsafe fn from_get_instance_proc_addr(vk_get_instance_proc_addr: vks::vk::PFN_vkGetInstanceProcAddr) -> Result<Self, EarlyInstanceError> {
+        if vk_get_instance_proc_addr.is_some() {
+            Ok(Loader::new_internal(None, vk_get_instance_proc_addr))
+        }
+        else {
+            Err(EarlyInstanceError::SymbolNotFound(VK_GET_INSTANCE_PROC_ADDR.to_owned()))
+        }
+    }
+
+    unsafe fn new_internal(library: Option<libloading::Library>, vk_get_instance_proc_addr: vks::vk::PFN_vkGetInstanceProcAddr) -> Self {
+        let mut vk_global = vks::instance_proc_addr_loader::VkGlobal::new();
+        vk_global.load(vk_get_instance_proc_addr, ptr::null_mut());
+
+        // vkEnumerateInstanceVersion is a global command, which is only available on Vulkan 1.1
+        // implementations.
+        let vk_enumerate_instance_version = (vk_get_instance_proc_addr.unwrap())(ptr::null_mut(), b"vkEnumerateInstanceVersion\x00".as_ptr() as *const _)
+            .map(|f| mem::transmute(f));
+
+        Loader(Arc::new(Inner {
+            library: library,
+            vk_get_instance_proc_addr: vk_get_instance_proc_addr,
+            vk_global: vk_global,
+            vk_enumerate_instance_version: vk_enumerate_instance_version,
+        }))
+    }
+
+    #[inline]
+    pub(crate) fn vk_get_instance_proc_addr(&self) -> vks::vk::PFN_vkGetInstanceProcAddr {
+        self.0.vk_get_instance_proc_addr
+    }
+
+    #[inline]
+    pub(crate) fn vk_global(&self) -> &vks::instance_proc_addr_loader::VkGlobal {
+        &self.0.vk_global
+    }
+
+    #[inline]
+    pub(crate) fn vk_enumerate_instance_version(&self) -> vks_1_1::PFN_vkEnumerateInstanceVersion {
+        self.0.vk_enumerate_instance_version
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    #[allow(dead_code)]
+    library: Option<libloading::Library>,
+    vk_get_instance_proc_addr: vks::vk::PFN_vkGetInstanceProcAddr,
+    vk_global: vks::instance_proc_addr_loader::VkGlobal,
+    vk_enumerate_instance_version: vks_1_1::PFN_vkEnumerateInstanceVersion,
+}
+
+unsafe impl Send for Inner { }
+
+unsafe impl Sync for Inner { }
@@ -20,7 +20,9 @@ mod extension_structs;
 mod buffer;
 mod buffer_view;
 mod command_buffer;
+mod command_buffer_recorder;
 mod command_pool;
+mod deletion_queue;
 mod descriptor_pool;
 mod descriptor_set;
 mod descriptor_set_layout;
@@ -28,10 +30,14 @@ mod device;
 mod device_memory;
 mod event;
 mod fence;
+mod format;
 mod framebuffer;
+mod graphics_pipeline_builder;
 mod image;
+mod image_layout_tracker;
 mod image_view;
 mod instance;
+mod loader;
 mod physical_device;
 mod pipeline;
 mod pipeline_cache;
@@ -54,6 +60,7 @@ use nv_dedicated_allocation;
 use nv_external_memory;
 use nv_external_memory_win32;
 use nv_win32_keyed_mutex;
+use spirv;
 use std::cmp;
 use std::collections::{HashMap, HashSet};
 use std::ffi::{CStr, CString};
@@ -64,25 +71,31 @@ use std::slice;
 use std::time::Duration;
 use utils;
 use vks;
+use vks_1_1;
 
 pub use self::buffer::{Buffer, FromNativeBufferParameters};
 pub use self::buffer_view::{BufferView, FromNativeBufferViewParameters};
-pub use self::command_buffer::{CommandBuffer, FromNativeCommandBufferParameters};
+pub use self::command_buffer::{CommandBuffer, FromNativeCommandBufferParameters, MipmapError};
+pub use self::command_buffer_recorder::{CommandBufferRecorder, RenderPassRecorder};
 pub use self::command_pool::{CommandPool, FromNativeCommandPoolParameters};
+pub use self::deletion_queue::{DeletionCondition, DeletionQueue};
 pub use self::descriptor_pool::{DescriptorPool, FromNativeDescriptorPoolParameters};
 pub use self::descriptor_set::DescriptorSet;
 pub use self::descriptor_set_layout::{DescriptorSetLayout, FromNativeDescriptorSetLayoutParameters};
 pub use self::device::Device;
-pub use self::device_memory::{DeviceMemory, MappedMemory, FromNativeDeviceMemoryParameters};
+pub use self::device_memory::{AllocationError, DeviceMemory, MappedMemory, MappedMemoryError, Pod, FromNativeDeviceMemoryParameters};
 pub use self::event::{Event, FromNativeEventParameters};
 pub use self::fence::{Fence, FromNativeFenceParameters};
+pub use self::format::{ComponentBits, FormatCompression, NumericFormat};
 pub use self::framebuffer::{Framebuffer, FromNativeFramebufferParameters};
-pub use self::image::{Image, FromNativeImageParameters};
+pub use self::graphics_pipeline_builder::GraphicsPipelineBuilder;
+pub use self::image::{Image, FromNativeImageParameters, LayoutTrackingError};
 pub use self::image_view::{ImageView, FromNativeImageViewParameters};
 pub use self::instance::{EarlyInstanceError, Instance};
-pub use self::physical_device::PhysicalDevice;
+pub use self::loader::{Loader, VULKAN_LIBRARY_ENV_VAR};
+pub use self::physical_device::{FindImageFormatError, FormatRejection, NoSupportedFormatError, PhysicalDevice};
 pub use self::pipeline::{Pipeline, FromNativePipelineParameters};
-pub use self::pipeline_cache::{PipelineCache, FromNativePipelineCacheParameters};
+pub use self::pipeline_cache::{PipelineCache, PipelineCacheError, FromNativePipelineCacheParameters};
 pub use self::pipeline_layout::{PipelineLayout, FromNativePipelineLayoutParameters};
 pub use self::query_pool::{QueryPool, FromNativeQueryPoolParameters};
 pub use self::queue::Queue;
@@ -161,6 +174,7 @@ dacite_bitflags! {
         const SPARSE_ALIASED [SparseAliased] = vks::vk::VK_IMAGE_CREATE_SPARSE_ALIASED_BIT;
         const MUTABLE_FORMAT [MutableFormat] = vks::vk::VK_IMAGE_CREATE_MUTABLE_FORMAT_BIT;
         const CUBE_COMPATIBLE [CubeCompatible] = vks::vk::VK_IMAGE_CREATE_CUBE_COMPATIBLE_BIT;
+        const PROTECTED [Protected] = vks_1_1::VK_IMAGE_CREATE_PROTECTED_BIT;
     }
 
     no_bits {}
@@ -196,6 +210,7 @@ dacite_bitflags! {
         const COMPUTE [Compute] = vks::vk::VK_QUEUE_COMPUTE_BIT;
         const TRANSFER [Transfer] = vks::vk::VK_QUEUE_TRANSFER_BIT;
         const SPARSE_BINDING [SparseBinding] = vks::vk::VK_QUEUE_SPARSE_BINDING_BIT;
+        const PROTECTED [Protected] = vks_1_1::VK_QUEUE_PROTECTED_BIT;
     }
 
     no_bits {}
@@ -213,6 +228,7 @@ dacite_bitflags! {
         const HOST_COHERENT [HostCoherent] = vks::vk::VK_MEMORY_PROPERTY_HOST_COHERENT_BIT;
         const HOST_CACHED [HostCached] = vks::vk::VK_MEMORY_PROPERTY_HOST_CACHED_BIT;
         const LAZILY_ALLOCATED [LazilyAllocated] = vks::vk::VK_MEMORY_PROPERTY_LAZILY_ALLOCATED_BIT;
+        const PROTECTED [Protected] = vks_1_1::VK_MEMORY_PROPERTY_PROTECTED_BIT;
     }
 
     no_bits {}
@@ -247,7 +263,9 @@ dacite_bitflags! {
     pub enum DeviceQueueCreateFlagBits: vks::vk::VkDeviceQueueCreateFlagBits;
     max_enum: vks::vk::VK_DEVICE_QUEUE_CREATE_FLAG_BITS_MAX_ENUM;
 
-    flags {}
+    flags {
+        const PROTECTED [Protected] = vks_1_1::VK_DEVICE_QUEUE_CREATE_PROTECTED_BIT;
+    }
     no_bits {}
 }
 
@@ -426,6 +444,7 @@ dacite_bitflags! {
         const SPARSE_BINDING [SparseBinding] = vks::vk::VK_BUFFER_CREATE_SPARSE_BINDING_BIT;
         const SPARSE_RESIDENCY [SparseResidency] = vks::vk::VK_BUFFER_CREATE_SPARSE_RESIDENCY_BIT;
         const SPARSE_ALIASED [SparseAliased] = vks::vk::VK_BUFFER_CREATE_SPARSE_ALIASED_BIT;
+        const PROTECTED [Protected] = vks_1_1::VK_BUFFER_CREATE_PROTECTED_BIT;
     }
 
     no_bits {}
@@ -808,6 +827,7 @@ dacite_bitflags! {
     flags {
         const TRANSIENT [Transient] = vks::vk::VK_COMMAND_POOL_CREATE_TRANSIENT_BIT;
         const RESET_COMMAND_BUFFER [ResetCommandBuffer] = vks::vk::VK_COMMAND_POOL_CREATE_RESET_COMMAND_BUFFER_BIT;
+        const PROTECTED [Protected] = vks_1_1::VK_COMMAND_POOL_CREATE_PROTECTED_BIT;
     }
 
     no_bits {}
@@ -826,6 +846,52 @@ dacite_bitflags! {
     no_bits {}
 }
 
+dacite_bitflags! {
+    /// See [`VkCommandPoolTrimFlags`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkCommandPoolTrimFlags)
+    pub struct CommandPoolTrimFlags: vks_1_1::VkCommandPoolTrimFlags;
+    pub enum CommandPoolTrimFlagBits: vks_1_1::VkCommandPoolTrimFlagBits;
+    max_enum: vks_1_1::VK_COMMAND_POOL_TRIM_FLAG_BITS_MAX_ENUM;
+
+    flags {}
+    no_bits {}
+}
+
+dacite_bitflags! {
+    /// See [`VkSubgroupFeatureFlagBits`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkSubgroupFeatureFlagBits)
+    pub struct SubgroupFeatureFlags: vks_1_1::VkSubgroupFeatureFlags;
+    pub enum SubgroupFeatureFlagBits: vks_1_1::VkSubgroupFeatureFlagBits;
+    max_enum: vks_1_1::VK_SUBGROUP_FEATURE_FLAG_BITS_MAX_ENUM;
+
+    flags {
+        const BASIC [Basic] = vks_1_1::VK_SUBGROUP_FEATURE_BASIC_BIT;
+        const VOTE [Vote] = vks_1_1::VK_SUBGROUP_FEATURE_VOTE_BIT;
+        const ARITHMETIC [Arithmetic] = vks_1_1::VK_SUBGROUP_FEATURE_ARITHMETIC_BIT;
+        const BALLOT [Ballot] = vks_1_1::VK_SUBGROUP_FEATURE_BALLOT_BIT;
+        const SHUFFLE [Shuffle] = vks_1_1::VK_SUBGROUP_FEATURE_SHUFFLE_BIT;
+        const SHUFFLE_RELATIVE [ShuffleRelative] = vks_1_1::VK_SUBGROUP_FEATURE_SHUFFLE_RELATIVE_BIT;
+        const CLUSTERED [Clustered] = vks_1_1::VK_SUBGROUP_FEATURE_CLUSTERED_BIT;
+        const QUAD [Quad] = vks_1_1::VK_SUBGROUP_FEATURE_QUAD_BIT;
+    }
+
+    no_bits {}
+}
+
+dacite_bitflags! {
+    /// See [`VkPeerMemoryFeatureFlagBits`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPeerMemoryFeatureFlagBits)
+    pub struct PeerMemoryFeatureFlags: vks_1_1::VkPeerMemoryFeatureFlags;
+    pub enum PeerMemoryFeatureFlagBits: vks_1_1::VkPeerMemoryFeatureFlagBits;
+    max_enum: vks_1_1::VK_PEER_MEMORY_FEATURE_FLAG_BITS_MAX_ENUM;
+
+    flags {
+        const COPY_SRC [CopySrc] = vks_1_1::VK_PEER_MEMORY_FEATURE_COPY_SRC_BIT;
+        const COPY_DST [CopyDst] = vks_1_1::VK_PEER_MEMORY_FEATURE_COPY_DST_BIT;
+        const GENERIC_SRC [GenericSrc] = vks_1_1::VK_PEER_MEMORY_FEATURE_GENERIC_SRC_BIT;
+        const GENERIC_DST [GenericDst] = vks_1_1::VK_PEER_MEMORY_FEATURE_GENERIC_DST_BIT;
+    }
+
+    no_bits {}
+}
+
 dacite_bitflags! {
     /// See [`VkCommandBufferUsageFlagBits`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkCommandBufferUsageFlagBits)
     pub struct CommandBufferUsageFlags: vks::vk::VkCommandBufferUsageFlags;
@@ -1080,6 +1146,24 @@ pub enum PipelineCacheHeaderVersion {
     Unknown(vks::vk::VkPipelineCacheHeaderVersion),
 }
 
+impl From<vks::vk::VkPipelineCacheHeaderVersion> for PipelineCacheHeaderVersion {
+    fn from(version: vks::vk::VkPipelineCacheHeaderVersion) -> Self {
+        match version {
+            vks::vk::VK_PIPELINE_CACHE_HEADER_VERSION_ONE => PipelineCacheHeaderVersion::One,
+            _ => PipelineCacheHeaderVersion::Unknown(version),
+        }
+    }
+}
+
+impl From<PipelineCacheHeaderVersion> for vks::vk::VkPipelineCacheHeaderVersion {
+    fn from(version: PipelineCacheHeaderVersion) -> Self {
+        match version {
+            PipelineCacheHeaderVersion::One => vks::vk::VK_PIPELINE_CACHE_HEADER_VERSION_ONE,
+            PipelineCacheHeaderVersion::Unknown(version) => version,
+        }
+    }
+}
+
 /// See [`VkResult`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkResult)
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Error {
@@ -2774,6 +2858,24 @@ pub struct InstanceCreateInfo {
     pub chain: Option<InstanceCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`InstanceCreateInfo`](struct.InstanceCreateInfo.html).
+    name: InstanceCreateInfo [InstanceCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: InstanceCreateFlags = InstanceCreateFlags::empty(),
+        /// Sets `application_info`.
+        application_info: Option<ApplicationInfo> = None,
+        /// Sets `enabled_layers`.
+        enabled_layers: Vec<String> = vec![],
+        /// Sets `enabled_extensions`.
+        enabled_extensions: InstanceExtensions = InstanceExtensions::new(),
+        /// Sets `chain`.
+        chain: Option<InstanceCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkInstanceCreateInfoWrapper {
     pub vks_struct: vks::vk::VkInstanceCreateInfo,
@@ -3361,6 +3463,67 @@ impl PhysicalDeviceFeatures {
         self.variable_multisample_rate |= other.variable_multisample_rate;
         self.inherited_queries |= other.inherited_queries;
     }
+
+    /// Returns the names of all features that are set to `true`.
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut res = Vec::new();
+        if self.robust_buffer_access { res.push("robust_buffer_access"); }
+        if self.full_draw_index_uint32 { res.push("full_draw_index_uint32"); }
+        if self.image_cube_array { res.push("image_cube_array"); }
+        if self.independent_blend { res.push("independent_blend"); }
+        if self.geometry_shader { res.push("geometry_shader"); }
+        if self.tessellation_shader { res.push("tessellation_shader"); }
+        if self.sample_rate_shading { res.push("sample_rate_shading"); }
+        if self.dual_src_blend { res.push("dual_src_blend"); }
+        if self.logic_op { res.push("logic_op"); }
+        if self.multi_draw_indirect { res.push("multi_draw_indirect"); }
+        if self.draw_indirect_first_instance { res.push("draw_indirect_first_instance"); }
+        if self.depth_clamp { res.push("depth_clamp"); }
+        if self.depth_bias_clamp { res.push("depth_bias_clamp"); }
+        if self.fill_mode_non_solid { res.push("fill_mode_non_solid"); }
+        if self.depth_bounds { res.push("depth_bounds"); }
+        if self.wide_lines { res.push("wide_lines"); }
+        if self.large_points { res.push("large_points"); }
+        if self.alpha_to_one { res.push("alpha_to_one"); }
+        if self.multi_viewport { res.push("multi_viewport"); }
+        if self.sampler_anisotropy { res.push("sampler_anisotropy"); }
+        if self.texture_compression_etc2 { res.push("texture_compression_etc2"); }
+        if self.texture_compression_astc_ldr { res.push("texture_compression_astc_ldr"); }
+        if self.texture_compression_bc { res.push("texture_compression_bc"); }
+        if self.occlusion_query_precise { res.push("occlusion_query_precise"); }
+        if self.pipeline_statistics_query { res.push("pipeline_statistics_query"); }
+        if self.vertex_pipeline_stores_and_atomics { res.push("vertex_pipeline_stores_and_atomics"); }
+        if self.fragment_stores_and_atomics { res.push("fragment_stores_and_atomics"); }
+        if self.shader_tessellation_and_geometry_point_size { res.push("shader_tessellation_and_geometry_point_size"); }
+        if self.shader_image_gather_extended { res.push("shader_image_gather_extended"); }
+        if self.shader_storage_image_extended_formats { res.push("shader_storage_image_extended_formats"); }
+        if self.shader_storage_image_multisample { res.push("shader_storage_image_multisample"); }
+        if self.shader_storage_image_read_without_format { res.push("shader_storage_image_read_without_format"); }
+        if self.shader_storage_image_write_without_format { res.push("shader_storage_image_write_without_format"); }
+        if self.shader_uniform_buffer_array_dynamic_indexing { res.push("shader_uniform_buffer_array_dynamic_indexing"); }
+        if self.shader_sampled_image_array_dynamic_indexing { res.push("shader_sampled_image_array_dynamic_indexing"); }
+        if self.shader_storage_buffer_array_dynamic_indexing { res.push("shader_storage_buffer_array_dynamic_indexing"); }
+        if self.shader_storage_image_array_dynamic_indexing { res.push("shader_storage_image_array_dynamic_indexing"); }
+        if self.shader_clip_distance { res.push("shader_clip_distance"); }
+        if self.shader_cull_distance { res.push("shader_cull_distance"); }
+        if self.shader_float64 { res.push("shader_float64"); }
+        if self.shader_int64 { res.push("shader_int64"); }
+        if self.shader_int16 { res.push("shader_int16"); }
+        if self.shader_resource_residency { res.push("shader_resource_residency"); }
+        if self.shader_resource_min_lod { res.push("shader_resource_min_lod"); }
+        if self.sparse_binding { res.push("sparse_binding"); }
+        if self.sparse_residency_buffer { res.push("sparse_residency_buffer"); }
+        if self.sparse_residency_image_2d { res.push("sparse_residency_image_2d"); }
+        if self.sparse_residency_image_3d { res.push("sparse_residency_image_3d"); }
+        if self.sparse_residency_2_samples { res.push("sparse_residency_2_samples"); }
+        if self.sparse_residency_4_samples { res.push("sparse_residency_4_samples"); }
+        if self.sparse_residency_8_samples { res.push("sparse_residency_8_samples"); }
+        if self.sparse_residency_16_samples { res.push("sparse_residency_16_samples"); }
+        if self.sparse_residency_aliased { res.push("sparse_residency_aliased"); }
+        if self.variable_multisample_rate { res.push("variable_multisample_rate"); }
+        if self.inherited_queries { res.push("inherited_queries"); }
+        res
+    }
 }
 
 /// See [`VkFormatProperties`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkFormatProperties)
@@ -4022,6 +4185,21 @@ impl<'a> From<&'a vks::vk::VkPhysicalDeviceMemoryProperties> for PhysicalDeviceM
     }
 }
 
+impl PhysicalDeviceMemoryProperties {
+    /// Finds a memory type, which is allowed by `memory_type_bits` and has at least the
+    /// `required` property flags.
+    ///
+    /// If multiple memory types qualify, the one with the most `preferred` property flags is
+    /// chosen. Ties are broken in favor of the lower index, as recommended by the specification.
+    pub fn find_memory_type(&self, memory_type_bits: u32, required: MemoryPropertyFlags, preferred: MemoryPropertyFlags) -> Option<u32> {
+        self.memory_types.iter()
+            .enumerate()
+            .filter(|&(index, memory_type)| (memory_type_bits & (1 << index) != 0) && memory_type.property_flags.contains(required))
+            .max_by_key(|&(index, memory_type)| ((memory_type.property_flags & preferred).bits().count_ones(), -(index as i32)))
+            .map(|(index, _)| index as u32)
+    }
+}
+
 impl<'a> From<&'a PhysicalDeviceMemoryProperties> for vks::vk::VkPhysicalDeviceMemoryProperties {
     fn from(properties: &'a PhysicalDeviceMemoryProperties) -> Self {
         debug_assert!(properties.memory_types.len() <= vks::vk::VK_MAX_MEMORY_TYPES);
@@ -4063,6 +4241,22 @@ pub struct DeviceQueueCreateInfo {
     pub chain: Option<DeviceQueueCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`DeviceQueueCreateInfo`](struct.DeviceQueueCreateInfo.html).
+    name: DeviceQueueCreateInfo [DeviceQueueCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: DeviceQueueCreateFlags = DeviceQueueCreateFlags::empty(),
+        /// Sets `queue_family_index`.
+        queue_family_index: u32 = 0,
+        /// Sets `queue_priorities`.
+        queue_priorities: Vec<f32> = vec![1.0],
+        /// Sets `chain`.
+        chain: Option<DeviceQueueCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkDeviceQueueCreateInfoWrapper {
     pub vks_struct: vks::vk::VkDeviceQueueCreateInfo,
@@ -4105,6 +4299,33 @@ gen_chain_struct! {
         vks: vks::khr_get_physical_device_properties2::VkPhysicalDeviceFeatures2KHR,
         stype: vks::vk::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2_KHR,
     }
+
+    physical_device_features2: PhysicalDeviceFeatures2 {
+        fn_add: add_physical_device_features2,
+        fn_has: has_physical_device_features2,
+        fn_get: get_physical_device_features2,
+        wrapper: VkPhysicalDeviceFeatures2Wrapper,
+        vks: vks_1_1::VkPhysicalDeviceFeatures2,
+        stype: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2,
+    }
+
+    physical_device_protected_memory_features: PhysicalDeviceProtectedMemoryFeatures {
+        fn_add: add_physical_device_protected_memory_features,
+        fn_has: has_physical_device_protected_memory_features,
+        fn_get: get_physical_device_protected_memory_features,
+        wrapper: VkPhysicalDeviceProtectedMemoryFeaturesWrapper,
+        vks: vks_1_1::VkPhysicalDeviceProtectedMemoryFeatures,
+        stype: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES,
+    }
+
+    device_group_device_create_info: DeviceGroupDeviceCreateInfo {
+        fn_add: add_device_group_device_create_info,
+        fn_has: has_device_group_device_create_info,
+        fn_get: get_device_group_device_create_info,
+        wrapper: VkDeviceGroupDeviceCreateInfoWrapper,
+        vks: vks_1_1::VkDeviceGroupDeviceCreateInfo,
+        stype: vks_1_1::VK_STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO,
+    }
 }
 
 /// See [`VkDeviceCreateInfo`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkDeviceCreateInfo)
@@ -4118,6 +4339,26 @@ pub struct DeviceCreateInfo {
     pub chain: Option<DeviceCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`DeviceCreateInfo`](struct.DeviceCreateInfo.html).
+    name: DeviceCreateInfo [DeviceCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: DeviceCreateFlags = DeviceCreateFlags::empty(),
+        /// Sets `queue_create_infos`.
+        queue_create_infos: Vec<DeviceQueueCreateInfo> = vec![],
+        /// Sets `enabled_layers`.
+        enabled_layers: Vec<String> = vec![],
+        /// Sets `enabled_extensions`.
+        enabled_extensions: DeviceExtensions = DeviceExtensions::new(),
+        /// Sets `enabled_features`.
+        enabled_features: Option<PhysicalDeviceFeatures> = None,
+        /// Sets `chain`.
+        chain: Option<DeviceCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkDeviceCreateInfoWrapper {
     pub vks_struct: vks::vk::VkDeviceCreateInfo,
@@ -4464,6 +4705,24 @@ impl<'a> From<&'a vks::vk::VkLayerProperties> for LayerProperties {
     }
 }
 
+/// An instance layer together with the instance extensions it provides.
+///
+/// See [`Instance::enumerate_instance_layers`](struct.Instance.html#method.enumerate_instance_layers).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceLayer {
+    pub properties: LayerProperties,
+    pub extensions: InstanceExtensionsProperties,
+}
+
+/// A device layer together with the device extensions it provides.
+///
+/// See [`PhysicalDevice::enumerate_device_layers`](struct.PhysicalDevice.html#method.enumerate_device_layers).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceLayer {
+    pub properties: LayerProperties,
+    pub extensions: DeviceExtensionsProperties,
+}
+
 gen_chain_struct! {
     name: SubmitInfoChain [SubmitInfoChainWrapper],
     query: SubmitInfoChainQuery [SubmitInfoChainQueryWrapper],
@@ -4479,6 +4738,15 @@ gen_chain_struct! {
         vks: vks::nv_win32_keyed_mutex::VkWin32KeyedMutexAcquireReleaseInfoNV,
         stype: vks::vk::VK_STRUCTURE_TYPE_WIN32_KEYED_MUTEX_ACQUIRE_RELEASE_INFO_NV,
     }
+
+    protected_submit_info: ProtectedSubmitInfo {
+        fn_add: add_protected_submit_info,
+        fn_has: has_protected_submit_info,
+        fn_get: get_protected_submit_info,
+        wrapper: VkProtectedSubmitInfoWrapper,
+        vks: vks_1_1::VkProtectedSubmitInfo,
+        stype: vks_1_1::VK_STRUCTURE_TYPE_PROTECTED_SUBMIT_INFO,
+    }
 }
 
 /// See [`VkSubmitInfo`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkSubmitInfo)
@@ -4855,6 +5123,21 @@ pub struct ImageSubresource {
     pub array_layer: u32,
 }
 
+impl ImageSubresource {
+    /// Creates an `ImageSubresource` with the aspects of `format`.
+    ///
+    /// Queries like `Image::get_subresource_layout` must select a single aspect of combined
+    /// depth/stencil formats instead.
+    #[inline]
+    pub fn new(format: Format, mip_level: u32, array_layer: u32) -> Self {
+        ImageSubresource {
+            aspect_mask: format.aspect_mask(),
+            mip_level: mip_level,
+            array_layer: array_layer,
+        }
+    }
+}
+
 impl<'a> From<&'a ImageSubresource> for vks::vk::VkImageSubresource {
     fn from(subresource: &'a ImageSubresource) -> Self {
         vks::vk::VkImageSubresource {
@@ -5115,6 +5398,18 @@ pub struct FenceCreateInfo {
     pub chain: Option<FenceCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`FenceCreateInfo`](struct.FenceCreateInfo.html).
+    name: FenceCreateInfo [FenceCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: FenceCreateFlags = FenceCreateFlags::empty(),
+        /// Sets `chain`.
+        chain: Option<FenceCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkFenceCreateInfoWrapper {
     pub vks_struct: vks::vk::VkFenceCreateInfo,
@@ -5151,6 +5446,18 @@ pub struct SemaphoreCreateInfo {
     pub chain: Option<SemaphoreCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`SemaphoreCreateInfo`](struct.SemaphoreCreateInfo.html).
+    name: SemaphoreCreateInfo [SemaphoreCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: SemaphoreCreateFlags = SemaphoreCreateFlags::empty(),
+        /// Sets `chain`.
+        chain: Option<SemaphoreCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkSemaphoreCreateInfoWrapper {
     pub vks_struct: vks::vk::VkSemaphoreCreateInfo,
@@ -5187,6 +5494,18 @@ pub struct EventCreateInfo {
     pub chain: Option<EventCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`EventCreateInfo`](struct.EventCreateInfo.html).
+    name: EventCreateInfo [EventCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: EventCreateFlags = EventCreateFlags::empty(),
+        /// Sets `chain`.
+        chain: Option<EventCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkEventCreateInfoWrapper {
     pub vks_struct: vks::vk::VkEventCreateInfo,
@@ -5226,6 +5545,27 @@ pub struct QueryPoolCreateInfo {
     pub chain: Option<QueryPoolCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`QueryPoolCreateInfo`](struct.QueryPoolCreateInfo.html).
+    name: QueryPoolCreateInfo [QueryPoolCreateInfoBuilder],
+
+    required {
+        /// Sets `query_type`.
+        query_type: QueryType,
+        /// Sets `query_count`.
+        query_count: u32,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: QueryPoolCreateFlags = QueryPoolCreateFlags::empty(),
+        /// Sets `pipeline_statistics`.
+        pipeline_statistics: QueryPipelineStatisticFlags = QueryPipelineStatisticFlags::empty(),
+        /// Sets `chain`.
+        chain: Option<QueryPoolCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkQueryPoolCreateInfoWrapper {
     pub vks_struct: vks::vk::VkQueryPoolCreateInfo,
@@ -5278,6 +5618,29 @@ pub struct BufferCreateInfo {
     pub chain: Option<BufferCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`BufferCreateInfo`](struct.BufferCreateInfo.html).
+    name: BufferCreateInfo [BufferCreateInfoBuilder],
+
+    required {
+        /// Sets `size`.
+        size: u64,
+        /// Sets `usage`.
+        usage: BufferUsageFlags,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: BufferCreateFlags = BufferCreateFlags::empty(),
+        /// Sets `sharing_mode`.
+        sharing_mode: SharingMode = SharingMode::Exclusive,
+        /// Sets `queue_family_indices`.
+        queue_family_indices: Vec<u32> = vec![],
+        /// Sets `chain`.
+        chain: Option<BufferCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkBufferCreateInfoWrapper {
     pub vks_struct: vks::vk::VkBufferCreateInfo,
@@ -5333,6 +5696,29 @@ pub struct BufferViewCreateInfo {
     pub chain: Option<BufferViewCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`BufferViewCreateInfo`](struct.BufferViewCreateInfo.html).
+    name: BufferViewCreateInfo [BufferViewCreateInfoBuilder],
+
+    required {
+        /// Sets `buffer`.
+        buffer: Buffer,
+        /// Sets `format`.
+        format: Format,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: BufferViewCreateFlags = BufferViewCreateFlags::empty(),
+        /// Sets `offset`.
+        offset: u64 = 0,
+        /// Sets `range`.
+        range: OptionalDeviceSize = OptionalDeviceSize::WholeSize,
+        /// Sets `chain`.
+        chain: Option<BufferViewCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkBufferViewCreateInfoWrapper {
     pub vks_struct: vks::vk::VkBufferViewCreateInfo,
@@ -5404,6 +5790,43 @@ pub struct ImageCreateInfo {
     pub chain: Option<ImageCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`ImageCreateInfo`](struct.ImageCreateInfo.html).
+    name: ImageCreateInfo [ImageCreateInfoBuilder],
+
+    required {
+        /// Sets `format`.
+        format: Format,
+        /// Sets `extent`.
+        extent: Extent3D,
+        /// Sets `usage`.
+        usage: ImageUsageFlags,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: ImageCreateFlags = ImageCreateFlags::empty(),
+        /// Sets `image_type`.
+        image_type: ImageType = ImageType::Type2D,
+        /// Sets `mip_levels`.
+        mip_levels: u32 = 1,
+        /// Sets `array_layers`.
+        array_layers: u32 = 1,
+        /// Sets `samples`.
+        samples: SampleCountFlagBits = SampleCountFlagBits::SampleCount1,
+        /// Sets `tiling`.
+        tiling: ImageTiling = ImageTiling::Optimal,
+        /// Sets `sharing_mode`.
+        sharing_mode: SharingMode = SharingMode::Exclusive,
+        /// Sets `queue_family_indices`.
+        queue_family_indices: Vec<u32> = vec![],
+        /// Sets `initial_layout`.
+        initial_layout: ImageLayout = ImageLayout::Undefined,
+        /// Sets `chain`.
+        chain: Option<ImageCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkImageCreateInfoWrapper {
     pub vks_struct: vks::vk::VkImageCreateInfo,
@@ -5513,6 +5936,38 @@ pub struct ImageSubresourceRange {
     pub layer_count: OptionalArrayLayers,
 }
 
+impl ImageSubresourceRange {
+    /// Creates an `ImageSubresourceRange` with the aspects of `format`.
+    #[inline]
+    pub fn new(format: Format, base_mip_level: u32, level_count: u32, base_array_layer: u32, layer_count: u32) -> Self {
+        ImageSubresourceRange {
+            aspect_mask: format.aspect_mask(),
+            base_mip_level: base_mip_level,
+            level_count: OptionalMipLevels::MipLevels(level_count),
+            base_array_layer: base_array_layer,
+            layer_count: OptionalArrayLayers::ArrayLayers(layer_count),
+        }
+    }
+
+    /// Creates an `ImageSubresourceRange` covering all aspects, mip levels and array layers of an
+    /// image with `format`.
+    ///
+    /// Instead of explicit counts, the range uses `OptionalMipLevels::Remaining` and
+    /// `OptionalArrayLayers::Remaining`. It therefore doesn't need the mip levels and array layers
+    /// of the image, and covers the whole image regardless of how it was created. Use
+    /// [`new`](#method.new) for explicit counts.
+    #[inline]
+    pub fn full(format: Format) -> Self {
+        ImageSubresourceRange {
+            aspect_mask: format.aspect_mask(),
+            base_mip_level: 0,
+            level_count: OptionalMipLevels::Remaining,
+            base_array_layer: 0,
+            layer_count: OptionalArrayLayers::Remaining,
+        }
+    }
+}
+
 impl<'a> From<&'a ImageSubresourceRange> for vks::vk::VkImageSubresourceRange {
     fn from(range: &'a ImageSubresourceRange) -> Self {
         vks::vk::VkImageSubresourceRange {
@@ -5545,6 +6000,47 @@ pub struct ImageViewCreateInfo {
     pub chain: Option<ImageViewCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`ImageViewCreateInfo`](struct.ImageViewCreateInfo.html).
+    name: ImageViewCreateInfo [ImageViewCreateInfoBuilder],
+
+    required {
+        /// Sets `image`.
+        image: Image,
+        /// Sets `view_type`.
+        view_type: ImageViewType,
+        /// Sets `format`.
+        format: Format,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: ImageViewCreateFlags = ImageViewCreateFlags::empty(),
+        /// Sets `components`.
+        components: ComponentMapping = ComponentMapping::identity(),
+        /// Sets `subresource_range`.
+        ///
+        /// Defaults to all aspects of `format`, all mip levels and all array layers.
+        subresource_range: ImageSubresourceRange = ImageSubresourceRange::full(format),
+        /// Sets `chain`.
+        chain: Option<ImageViewCreateInfoChain> = None,
+    }
+}
+
+impl ImageViewCreateInfo {
+    /// Creates an `ImageViewCreateInfo`, which views all aspects, mip levels and array layers of
+    /// `image`.
+    ///
+    /// For combined depth/stencil formats, the view has both the `DEPTH` and `STENCIL` aspects.
+    /// Such a view can be used as a framebuffer attachment, but not in a descriptor set, which
+    /// requires exactly one of both aspects. Set a `subresource_range` with only the `DEPTH` aspect
+    /// on the [`builder`](#method.builder) to sample the depth of such an image.
+    #[inline]
+    pub fn for_image(image: Image, view_type: ImageViewType, format: Format) -> Self {
+        ImageViewCreateInfo::builder(image, view_type, format).build()
+    }
+}
+
 #[derive(Debug)]
 struct VkImageViewCreateInfoWrapper {
     pub vks_struct: vks::vk::VkImageViewCreateInfo,
@@ -5585,32 +6081,74 @@ gen_chain_struct! {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ShaderModuleCreateInfo {
     pub flags: ShaderModuleCreateFlags,
-    pub code: Vec<u8>,
+
+    /// SPIR-V code in native byte order.
+    pub code: Vec<u32>,
     pub chain: Option<ShaderModuleCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`ShaderModuleCreateInfo`](struct.ShaderModuleCreateInfo.html).
+    name: ShaderModuleCreateInfo [ShaderModuleCreateInfoBuilder],
+
+    required {
+        /// Sets `code`.
+        code: Vec<u32>,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: ShaderModuleCreateFlags = ShaderModuleCreateFlags::empty(),
+        /// Sets `chain`.
+        chain: Option<ShaderModuleCreateInfoChain> = None,
+    }
+}
+
+impl ShaderModuleCreateInfo {
+    /// Creates a `ShaderModuleCreateInfo` from SPIR-V byte code in either byte order.
+    ///
+    /// The size, magic number and version of `code` are validated.
+    pub fn from_bytes(code: &[u8]) -> Result<Self, spirv::ParseError> {
+        let code = spirv::words_from_bytes(code)?;
+        spirv::parse_header(&code)?;
+
+        Ok(ShaderModuleCreateInfo {
+            flags: ShaderModuleCreateFlags::empty(),
+            code: code,
+            chain: None,
+        })
+    }
+
+    /// Creates a `ShaderModuleCreateInfo` from SPIR-V words.
+    ///
+    /// The magic number and version of `code` are validated. Byte-swapped words are converted to
+    /// native byte order in place.
+    pub fn from_words(mut code: Vec<u32>) -> Result<Self, spirv::ParseError> {
+        if code.first() == Some(&spirv::MAGIC_NUMBER.swap_bytes()) {
+            for word in &mut code {
+                *word = word.swap_bytes();
+            }
+        }
+
+        spirv::parse_header(&code)?;
+
+        Ok(ShaderModuleCreateInfo {
+            flags: ShaderModuleCreateFlags::empty(),
+            code: code,
+            chain: None,
+        })
+    }
+}
+
 #[derive(Debug)]
 struct VkShaderModuleCreateInfoWrapper {
     pub vks_struct: vks::vk::VkShaderModuleCreateInfo,
-    code: Vec<u32>,
     chain: Option<ShaderModuleCreateInfoChainWrapper>,
 }
 
 impl VkShaderModuleCreateInfoWrapper {
+    /// The code is not copied, so the wrapper must not outlive `create_info`.
     pub fn new(create_info: &ShaderModuleCreateInfo, with_chain: bool) -> Self {
-        let size_u32 = if create_info.code.len() % mem::size_of::<u32>() == 0 {
-            create_info.code.len() / mem::size_of::<u32>()
-        }
-        else {
-            1 + create_info.code.len() / mem::size_of::<u32>()
-        };
-
-        let mut code = Vec::with_capacity(size_u32);
-        unsafe {
-            code.set_len(size_u32);
-            ptr::copy_nonoverlapping(create_info.code.as_ptr(), code.as_mut_ptr() as *mut u8, create_info.code.len());
-        }
-
         let (pnext, chain) = ShaderModuleCreateInfoChainWrapper::new_optional(&create_info.chain, with_chain);
 
         VkShaderModuleCreateInfoWrapper {
@@ -5618,10 +6156,9 @@ impl VkShaderModuleCreateInfoWrapper {
                 sType: vks::vk::VK_STRUCTURE_TYPE_SHADER_MODULE_CREATE_INFO,
                 pNext: pnext,
                 flags: create_info.flags.bits(),
-                codeSize: create_info.code.len(),
-                pCode: code.as_ptr(),
+                codeSize: create_info.code.len() * mem::size_of::<u32>(),
+                pCode: create_info.code.as_ptr(),
             },
-            code: code,
             chain: chain,
         }
     }
@@ -5635,6 +6172,62 @@ gen_chain_struct! {
     output: false,
 }
 
+/// The header at the start of the data returned by
+/// [`PipelineCache::get_data`](struct.PipelineCache.html#method.get_data).
+///
+/// See [`vkGetPipelineCacheData`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkGetPipelineCacheData)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PipelineCacheHeader {
+    pub header_size: u32,
+    pub header_version: PipelineCacheHeaderVersion,
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub pipeline_cache_uuid: [u8; 16],
+}
+
+impl PipelineCacheHeader {
+    /// Size in bytes of a header with `PipelineCacheHeaderVersion::One`.
+    pub const SIZE: usize = 32;
+
+    /// Parses the header of pipeline cache data.
+    ///
+    /// Returns `None`, if `data` is too short or the header version is not
+    /// `PipelineCacheHeaderVersion::One`.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < Self::SIZE {
+            return None;
+        }
+
+        // All fields are stored in little-endian byte order.
+        let read_u32 = |offset: usize| {
+            (data[offset] as u32) | ((data[offset + 1] as u32) << 8) | ((data[offset + 2] as u32) << 16) | ((data[offset + 3] as u32) << 24)
+        };
+
+        let header_size = read_u32(0);
+        let header_version = PipelineCacheHeaderVersion::from(read_u32(4));
+        if (header_version != PipelineCacheHeaderVersion::One) || ((header_size as usize) < Self::SIZE) || ((header_size as usize) > data.len()) {
+            return None;
+        }
+
+        let mut pipeline_cache_uuid = [0; 16];
+        pipeline_cache_uuid.copy_from_slice(&data[16..32]);
+
+        Some(PipelineCacheHeader {
+            header_size: header_size,
+            header_version: header_version,
+            vendor_id: read_u32(8),
+            device_id: read_u32(12),
+            pipeline_cache_uuid: pipeline_cache_uuid,
+        })
+    }
+
+    /// Returns `true`, if pipeline cache data with this header can be used with a physical device
+    /// with the given properties.
+    pub fn is_compatible_with(&self, properties: &PhysicalDeviceProperties) -> bool {
+        (self.vendor_id == properties.vendor_id) && (self.device_id == properties.device_id) && (self.pipeline_cache_uuid == properties.pipeline_cache_uuid)
+    }
+}
+
 /// See [`VkPipelineCacheCreateInfo`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkPipelineCacheCreateInfo)
 #[derive(Debug, Clone, PartialEq)]
 pub struct PipelineCacheCreateInfo {
@@ -5643,6 +6236,20 @@ pub struct PipelineCacheCreateInfo {
     pub chain: Option<PipelineCacheCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineCacheCreateInfo`](struct.PipelineCacheCreateInfo.html).
+    name: PipelineCacheCreateInfo [PipelineCacheCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineCacheCreateFlags = PipelineCacheCreateFlags::empty(),
+        /// Sets `initial_data`.
+        initial_data: Vec<u8> = vec![],
+        /// Sets `chain`.
+        chain: Option<PipelineCacheCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineCacheCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineCacheCreateInfo,
@@ -5783,6 +6390,29 @@ pub struct PipelineShaderStageCreateInfo {
     pub chain: Option<PipelineShaderStageCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineShaderStageCreateInfo`](struct.PipelineShaderStageCreateInfo.html).
+    name: PipelineShaderStageCreateInfo [PipelineShaderStageCreateInfoBuilder],
+
+    required {
+        /// Sets `stage`.
+        stage: ShaderStageFlagBits,
+        /// Sets `module`.
+        module: ShaderModule,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineShaderStageCreateFlags = PipelineShaderStageCreateFlags::empty(),
+        /// Sets `name`.
+        name: String = "main".to_owned(),
+        /// Sets `specialization_info`.
+        specialization_info: Option<SpecializationInfo> = None,
+        /// Sets `chain`.
+        chain: Option<PipelineShaderStageCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineShaderStageCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineShaderStageCreateInfo,
@@ -5880,6 +6510,22 @@ pub struct PipelineVertexInputStateCreateInfo {
     pub chain: Option<PipelineVertexInputStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineVertexInputStateCreateInfo`](struct.PipelineVertexInputStateCreateInfo.html).
+    name: PipelineVertexInputStateCreateInfo [PipelineVertexInputStateCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineVertexInputStateCreateFlags = PipelineVertexInputStateCreateFlags::empty(),
+        /// Sets `vertex_binding_descriptions`.
+        vertex_binding_descriptions: Vec<VertexInputBindingDescription> = vec![],
+        /// Sets `vertex_attribute_descriptions`.
+        vertex_attribute_descriptions: Vec<VertexInputAttributeDescription> = vec![],
+        /// Sets `chain`.
+        chain: Option<PipelineVertexInputStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineVertexInputStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineVertexInputStateCreateInfo,
@@ -5942,6 +6588,22 @@ pub struct PipelineInputAssemblyStateCreateInfo {
     pub chain: Option<PipelineInputAssemblyStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineInputAssemblyStateCreateInfo`](struct.PipelineInputAssemblyStateCreateInfo.html).
+    name: PipelineInputAssemblyStateCreateInfo [PipelineInputAssemblyStateCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineInputAssemblyStateCreateFlags = PipelineInputAssemblyStateCreateFlags::empty(),
+        /// Sets `topology`.
+        topology: PrimitiveTopology = PrimitiveTopology::TriangleList,
+        /// Sets `primitive_restart_enable`.
+        primitive_restart_enable: bool = false,
+        /// Sets `chain`.
+        chain: Option<PipelineInputAssemblyStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineInputAssemblyStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineInputAssemblyStateCreateInfo,
@@ -5981,6 +6643,23 @@ pub struct PipelineTessellationStateCreateInfo {
     pub chain: Option<PipelineTessellationStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineTessellationStateCreateInfo`](struct.PipelineTessellationStateCreateInfo.html).
+    name: PipelineTessellationStateCreateInfo [PipelineTessellationStateCreateInfoBuilder],
+
+    required {
+        /// Sets `patch_control_points`.
+        patch_control_points: u32,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineTessellationStateCreateFlags = PipelineTessellationStateCreateFlags::empty(),
+        /// Sets `chain`.
+        chain: Option<PipelineTessellationStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineTessellationStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineTessellationStateCreateInfo,
@@ -6179,6 +6858,22 @@ pub struct PipelineViewportStateCreateInfo {
     pub chain: Option<PipelineViewportStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineViewportStateCreateInfo`](struct.PipelineViewportStateCreateInfo.html).
+    name: PipelineViewportStateCreateInfo [PipelineViewportStateCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineViewportStateCreateFlags = PipelineViewportStateCreateFlags::empty(),
+        /// Sets `viewports`.
+        viewports: Vec<Viewport> = vec![],
+        /// Sets `scissors`.
+        scissors: Vec<Rect2D> = vec![],
+        /// Sets `chain`.
+        chain: Option<PipelineViewportStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineViewportStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineViewportStateCreateInfo,
@@ -6244,6 +6939,38 @@ pub struct PipelineRasterizationStateCreateInfo {
     pub chain: Option<PipelineRasterizationStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineRasterizationStateCreateInfo`](struct.PipelineRasterizationStateCreateInfo.html).
+    name: PipelineRasterizationStateCreateInfo [PipelineRasterizationStateCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineRasterizationStateCreateFlags = PipelineRasterizationStateCreateFlags::empty(),
+        /// Sets `depth_clamp_enable`.
+        depth_clamp_enable: bool = false,
+        /// Sets `rasterizer_discard_enable`.
+        rasterizer_discard_enable: bool = false,
+        /// Sets `polygon_mode`.
+        polygon_mode: PolygonMode = PolygonMode::Fill,
+        /// Sets `cull_mode`.
+        cull_mode: CullModeFlags = CullModeFlags::NONE,
+        /// Sets `front_face`.
+        front_face: FrontFace = FrontFace::CounterClockwise,
+        /// Sets `depth_bias_enable`.
+        depth_bias_enable: bool = false,
+        /// Sets `depth_bias_constant_factor`.
+        depth_bias_constant_factor: f32 = 0.0,
+        /// Sets `depth_bias_clamp`.
+        depth_bias_clamp: f32 = 0.0,
+        /// Sets `depth_bias_slope_factor`.
+        depth_bias_slope_factor: f32 = 0.0,
+        /// Sets `line_width`.
+        line_width: f32 = 1.0,
+        /// Sets `chain`.
+        chain: Option<PipelineRasterizationStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineRasterizationStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineRasterizationStateCreateInfo,
@@ -6296,6 +7023,30 @@ pub struct PipelineMultisampleStateCreateInfo {
     pub chain: Option<PipelineMultisampleStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineMultisampleStateCreateInfo`](struct.PipelineMultisampleStateCreateInfo.html).
+    name: PipelineMultisampleStateCreateInfo [PipelineMultisampleStateCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineMultisampleStateCreateFlags = PipelineMultisampleStateCreateFlags::empty(),
+        /// Sets `rasterization_samples`.
+        rasterization_samples: SampleCountFlagBits = SampleCountFlagBits::SampleCount1,
+        /// Sets `sample_shading_enable`.
+        sample_shading_enable: bool = false,
+        /// Sets `min_sample_shading`.
+        min_sample_shading: f32 = 1.0,
+        /// Sets `sample_mask`.
+        sample_mask: Vec<u32> = vec![],
+        /// Sets `alpha_to_coverage_enable`.
+        alpha_to_coverage_enable: bool = false,
+        /// Sets `alpha_to_one_enable`.
+        alpha_to_one_enable: bool = false,
+        /// Sets `chain`.
+        chain: Option<PipelineMultisampleStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineMultisampleStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineMultisampleStateCreateInfo,
@@ -6383,6 +7134,52 @@ pub struct PipelineDepthStencilStateCreateInfo {
     pub chain: Option<PipelineDepthStencilStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineDepthStencilStateCreateInfo`](struct.PipelineDepthStencilStateCreateInfo.html).
+    name: PipelineDepthStencilStateCreateInfo [PipelineDepthStencilStateCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineDepthStencilStateCreateFlags = PipelineDepthStencilStateCreateFlags::empty(),
+        /// Sets `depth_test_enable`.
+        depth_test_enable: bool = false,
+        /// Sets `depth_write_enable`.
+        depth_write_enable: bool = false,
+        /// Sets `depth_compare_op`.
+        depth_compare_op: CompareOp = CompareOp::Less,
+        /// Sets `depth_bounds_test_enable`.
+        depth_bounds_test_enable: bool = false,
+        /// Sets `stencil_test_enable`.
+        stencil_test_enable: bool = false,
+        /// Sets `front`.
+        front: StencilOpState = StencilOpState {
+            fail_op: StencilOp::Keep,
+            pass_op: StencilOp::Keep,
+            depth_fail_op: StencilOp::Keep,
+            compare_op: CompareOp::Always,
+            compare_mask: 0,
+            write_mask: 0,
+            reference: 0,
+        },
+        /// Sets `back`.
+        back: StencilOpState = StencilOpState {
+            fail_op: StencilOp::Keep,
+            pass_op: StencilOp::Keep,
+            depth_fail_op: StencilOp::Keep,
+            compare_op: CompareOp::Always,
+            compare_mask: 0,
+            write_mask: 0,
+            reference: 0,
+        },
+        /// Sets `min_depth_bounds`.
+        min_depth_bounds: f32 = 0.0,
+        /// Sets `max_depth_bounds`.
+        max_depth_bounds: f32 = 1.0,
+        /// Sets `chain`.
+        chain: Option<PipelineDepthStencilStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineDepthStencilStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineDepthStencilStateCreateInfo,
@@ -6460,6 +7257,26 @@ pub struct PipelineColorBlendStateCreateInfo {
     pub chain: Option<PipelineColorBlendStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineColorBlendStateCreateInfo`](struct.PipelineColorBlendStateCreateInfo.html).
+    name: PipelineColorBlendStateCreateInfo [PipelineColorBlendStateCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineColorBlendStateCreateFlags = PipelineColorBlendStateCreateFlags::empty(),
+        /// Sets `logic_op_enable`.
+        logic_op_enable: bool = false,
+        /// Sets `logic_op`.
+        logic_op: LogicOp = LogicOp::Copy,
+        /// Sets `attachments`.
+        attachments: Vec<PipelineColorBlendAttachmentState> = vec![],
+        /// Sets `blend_constants`.
+        blend_constants: [f32; 4] = [0.0; 4],
+        /// Sets `chain`.
+        chain: Option<PipelineColorBlendStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineColorBlendStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineColorBlendStateCreateInfo,
@@ -6512,6 +7329,20 @@ pub struct PipelineDynamicStateCreateInfo {
     pub chain: Option<PipelineDynamicStateCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineDynamicStateCreateInfo`](struct.PipelineDynamicStateCreateInfo.html).
+    name: PipelineDynamicStateCreateInfo [PipelineDynamicStateCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineDynamicStateCreateFlags = PipelineDynamicStateCreateFlags::empty(),
+        /// Sets `dynamic_states`.
+        dynamic_states: Vec<DynamicState> = vec![],
+        /// Sets `chain`.
+        chain: Option<PipelineDynamicStateCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineDynamicStateCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineDynamicStateCreateInfo,
@@ -6573,6 +7404,51 @@ pub struct GraphicsPipelineCreateInfo {
     pub chain: Option<GraphicsPipelineCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`GraphicsPipelineCreateInfo`](struct.GraphicsPipelineCreateInfo.html).
+    name: GraphicsPipelineCreateInfo [GraphicsPipelineCreateInfoBuilder],
+
+    required {
+        /// Sets `stages`.
+        stages: Vec<PipelineShaderStageCreateInfo>,
+        /// Sets `layout`.
+        layout: PipelineLayout,
+        /// Sets `render_pass`.
+        render_pass: RenderPass,
+        /// Sets `subpass`.
+        subpass: u32,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineCreateFlags = PipelineCreateFlags::empty(),
+        /// Sets `vertex_input_state`.
+        vertex_input_state: PipelineVertexInputStateCreateInfo = Default::default(),
+        /// Sets `input_assembly_state`.
+        input_assembly_state: PipelineInputAssemblyStateCreateInfo = Default::default(),
+        /// Sets `tessellation_state`.
+        tessellation_state: Option<PipelineTessellationStateCreateInfo> = None,
+        /// Sets `viewport_state`.
+        viewport_state: Option<PipelineViewportStateCreateInfo> = None,
+        /// Sets `rasterization_state`.
+        rasterization_state: PipelineRasterizationStateCreateInfo = Default::default(),
+        /// Sets `multisample_state`.
+        multisample_state: Option<PipelineMultisampleStateCreateInfo> = None,
+        /// Sets `depth_stencil_state`.
+        depth_stencil_state: Option<PipelineDepthStencilStateCreateInfo> = None,
+        /// Sets `color_blend_state`.
+        color_blend_state: Option<PipelineColorBlendStateCreateInfo> = None,
+        /// Sets `dynamic_state`.
+        dynamic_state: Option<PipelineDynamicStateCreateInfo> = None,
+        /// Sets `base_pipeline`.
+        base_pipeline: Option<Pipeline> = None,
+        /// Sets `base_pipeline_index`.
+        base_pipeline_index: Option<u32> = None,
+        /// Sets `chain`.
+        chain: Option<GraphicsPipelineCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkGraphicsPipelineCreateInfoWrapper {
     pub vks_struct: vks::vk::VkGraphicsPipelineCreateInfo,
@@ -6728,6 +7604,29 @@ pub struct ComputePipelineCreateInfo {
     pub chain: Option<ComputePipelineCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`ComputePipelineCreateInfo`](struct.ComputePipelineCreateInfo.html).
+    name: ComputePipelineCreateInfo [ComputePipelineCreateInfoBuilder],
+
+    required {
+        /// Sets `stage`.
+        stage: PipelineShaderStageCreateInfo,
+        /// Sets `layout`.
+        layout: PipelineLayout,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineCreateFlags = PipelineCreateFlags::empty(),
+        /// Sets `base_pipeline`.
+        base_pipeline: Option<Pipeline> = None,
+        /// Sets `base_pipeline_index`.
+        base_pipeline_index: Option<u32> = None,
+        /// Sets `chain`.
+        chain: Option<ComputePipelineCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkComputePipelineCreateInfoWrapper {
     pub vks_struct: vks::vk::VkComputePipelineCreateInfo,
@@ -6806,6 +7705,22 @@ pub struct PipelineLayoutCreateInfo {
     pub chain: Option<PipelineLayoutCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`PipelineLayoutCreateInfo`](struct.PipelineLayoutCreateInfo.html).
+    name: PipelineLayoutCreateInfo [PipelineLayoutCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: PipelineLayoutCreateFlags = PipelineLayoutCreateFlags::empty(),
+        /// Sets `set_layouts`.
+        set_layouts: Vec<DescriptorSetLayout> = vec![],
+        /// Sets `push_constant_ranges`.
+        push_constant_ranges: Vec<PushConstantRange> = vec![],
+        /// Sets `chain`.
+        chain: Option<PipelineLayoutCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkPipelineLayoutCreateInfoWrapper {
     pub vks_struct: vks::vk::VkPipelineLayoutCreateInfo,
@@ -6884,6 +7799,48 @@ pub struct SamplerCreateInfo {
     pub chain: Option<SamplerCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`SamplerCreateInfo`](struct.SamplerCreateInfo.html).
+    name: SamplerCreateInfo [SamplerCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: SamplerCreateFlags = SamplerCreateFlags::empty(),
+        /// Sets `mag_filter`.
+        mag_filter: Filter = Filter::Nearest,
+        /// Sets `min_filter`.
+        min_filter: Filter = Filter::Nearest,
+        /// Sets `mipmap_mode`.
+        mipmap_mode: SamplerMipmapMode = SamplerMipmapMode::Nearest,
+        /// Sets `address_mode_u`.
+        address_mode_u: SamplerAddressMode = SamplerAddressMode::Repeat,
+        /// Sets `address_mode_v`.
+        address_mode_v: SamplerAddressMode = SamplerAddressMode::Repeat,
+        /// Sets `address_mode_w`.
+        address_mode_w: SamplerAddressMode = SamplerAddressMode::Repeat,
+        /// Sets `mip_lod_bias`.
+        mip_lod_bias: f32 = 0.0,
+        /// Sets `anisotropy_enable`.
+        anisotropy_enable: bool = false,
+        /// Sets `max_anisotropy`.
+        max_anisotropy: f32 = 1.0,
+        /// Sets `compare_enable`.
+        compare_enable: bool = false,
+        /// Sets `compare_op`.
+        compare_op: CompareOp = CompareOp::Never,
+        /// Sets `min_lod`.
+        min_lod: f32 = 0.0,
+        /// Sets `max_lod`.
+        max_lod: f32 = vks::vk::VK_LOD_CLAMP_NONE,
+        /// Sets `border_color`.
+        border_color: BorderColor = BorderColor::FloatTransparentBlack,
+        /// Sets `unnormalized_coordinates`.
+        unnormalized_coordinates: bool = false,
+        /// Sets `chain`.
+        chain: Option<SamplerCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkSamplerCreateInfoWrapper {
     pub vks_struct: vks::vk::VkSamplerCreateInfo,
@@ -6978,6 +7935,20 @@ pub struct DescriptorSetLayoutCreateInfo {
     pub chain: Option<DescriptorSetLayoutCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`DescriptorSetLayoutCreateInfo`](struct.DescriptorSetLayoutCreateInfo.html).
+    name: DescriptorSetLayoutCreateInfo [DescriptorSetLayoutCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: DescriptorSetLayoutCreateFlags = DescriptorSetLayoutCreateFlags::empty(),
+        /// Sets `bindings`.
+        bindings: Vec<DescriptorSetLayoutBinding> = vec![],
+        /// Sets `chain`.
+        chain: Option<DescriptorSetLayoutCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkDescriptorSetLayoutCreateInfoWrapper {
     pub vks_struct: vks::vk::VkDescriptorSetLayoutCreateInfo,
@@ -7047,6 +8018,25 @@ pub struct DescriptorPoolCreateInfo {
     pub chain: Option<DescriptorPoolCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`DescriptorPoolCreateInfo`](struct.DescriptorPoolCreateInfo.html).
+    name: DescriptorPoolCreateInfo [DescriptorPoolCreateInfoBuilder],
+
+    required {
+        /// Sets `max_sets`.
+        max_sets: u32,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: DescriptorPoolCreateFlags = DescriptorPoolCreateFlags::empty(),
+        /// Sets `pool_sizes`.
+        pool_sizes: Vec<DescriptorPoolSize> = vec![],
+        /// Sets `chain`.
+        chain: Option<DescriptorPoolCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkDescriptorPoolCreateInfoWrapper {
     pub vks_struct: vks::vk::VkDescriptorPoolCreateInfo,
@@ -7360,6 +8350,31 @@ pub struct FramebufferCreateInfo {
     pub chain: Option<FramebufferCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`FramebufferCreateInfo`](struct.FramebufferCreateInfo.html).
+    name: FramebufferCreateInfo [FramebufferCreateInfoBuilder],
+
+    required {
+        /// Sets `render_pass`.
+        render_pass: RenderPass,
+        /// Sets `width`.
+        width: u32,
+        /// Sets `height`.
+        height: u32,
+    }
+
+    defaults {
+        /// Sets `flags`.
+        flags: FramebufferCreateFlags = FramebufferCreateFlags::empty(),
+        /// Sets `attachments`.
+        attachments: Vec<ImageView> = vec![],
+        /// Sets `layers`.
+        layers: u32 = 1,
+        /// Sets `chain`.
+        chain: Option<FramebufferCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkFramebufferCreateInfoWrapper {
     pub vks_struct: vks::vk::VkFramebufferCreateInfo,
@@ -7670,6 +8685,24 @@ pub struct RenderPassCreateInfo {
     pub chain: Option<RenderPassCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`RenderPassCreateInfo`](struct.RenderPassCreateInfo.html).
+    name: RenderPassCreateInfo [RenderPassCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: RenderPassCreateFlags = RenderPassCreateFlags::empty(),
+        /// Sets `attachments`.
+        attachments: Vec<AttachmentDescription> = vec![],
+        /// Sets `subpasses`.
+        subpasses: Vec<SubpassDescription> = vec![],
+        /// Sets `dependencies`.
+        dependencies: Vec<SubpassDependency> = vec![],
+        /// Sets `chain`.
+        chain: Option<RenderPassCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkRenderPassCreateInfoWrapper {
     pub vks_struct: vks::vk::VkRenderPassCreateInfo,
@@ -7740,6 +8773,20 @@ pub struct CommandPoolCreateInfo {
     pub chain: Option<CommandPoolCreateInfoChain>,
 }
 
+gen_create_info_builder! {
+    /// Builder for [`CommandPoolCreateInfo`](struct.CommandPoolCreateInfo.html).
+    name: CommandPoolCreateInfo [CommandPoolCreateInfoBuilder],
+
+    defaults {
+        /// Sets `flags`.
+        flags: CommandPoolCreateFlags = CommandPoolCreateFlags::empty(),
+        /// Sets `queue_family_index`.
+        queue_family_index: u32 = 0,
+        /// Sets `chain`.
+        chain: Option<CommandPoolCreateInfoChain> = None,
+    }
+}
+
 #[derive(Debug)]
 struct VkCommandPoolCreateInfoWrapper {
     pub vks_struct: vks::vk::VkCommandPoolCreateInfo,
@@ -7940,6 +8987,22 @@ pub struct ImageSubresourceLayers {
     pub layer_count: u32,
 }
 
+impl ImageSubresourceLayers {
+    /// Creates an `ImageSubresourceLayers` with the aspects of `format`.
+    ///
+    /// Copies between buffers and images with combined depth/stencil formats must select a single
+    /// aspect instead.
+    #[inline]
+    pub fn new(format: Format, mip_level: u32, base_array_layer: u32, layer_count: u32) -> Self {
+        ImageSubresourceLayers {
+            aspect_mask: format.aspect_mask(),
+            mip_level: mip_level,
+            base_array_layer: base_array_layer,
+            layer_count: layer_count,
+        }
+    }
+}
+
 impl<'a> From<&'a ImageSubresourceLayers> for vks::vk::VkImageSubresourceLayers {
     fn from(layers: &'a ImageSubresourceLayers) -> Self {
         vks::vk::VkImageSubresourceLayers {
@@ -8354,3 +9417,663 @@ pub struct DrawIndirectCommand {
     pub first_vertex: u32,
     pub first_instance: u32,
 }
+
+gen_chain_struct! {
+    name: PhysicalDeviceFeatures2Chain [PhysicalDeviceFeatures2ChainWrapper],
+    query: PhysicalDeviceFeatures2ChainQuery [PhysicalDeviceFeatures2ChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceFeatures2,
+    input: true,
+    output: true,
+
+    physical_device_protected_memory_features: PhysicalDeviceProtectedMemoryFeatures {
+        fn_add: add_physical_device_protected_memory_features,
+        fn_has: has_physical_device_protected_memory_features,
+        fn_get: get_physical_device_protected_memory_features,
+        wrapper: VkPhysicalDeviceProtectedMemoryFeaturesWrapper,
+        vks: vks_1_1::VkPhysicalDeviceProtectedMemoryFeatures,
+        stype: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES,
+    }
+}
+
+/// See [`VkPhysicalDeviceFeatures2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceFeatures2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceFeatures2 {
+    pub features: PhysicalDeviceFeatures,
+    pub chain: Option<PhysicalDeviceFeatures2Chain>,
+}
+
+impl PhysicalDeviceFeatures2 {
+    pub(crate) unsafe fn from_vks(features: &vks_1_1::VkPhysicalDeviceFeatures2, with_chain: bool) -> Self {
+        PhysicalDeviceFeatures2 {
+            features: (&features.features).into(),
+            chain: PhysicalDeviceFeatures2Chain::from_optional_pnext(features.pNext, with_chain),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct VkPhysicalDeviceFeatures2Wrapper {
+    pub vks_struct: vks_1_1::VkPhysicalDeviceFeatures2,
+    chain: Option<PhysicalDeviceFeatures2ChainWrapper>,
+}
+
+impl VkPhysicalDeviceFeatures2Wrapper {
+    pub fn new(features: &PhysicalDeviceFeatures2, with_chain: bool) -> Self {
+        let (pnext, chain) = PhysicalDeviceFeatures2ChainWrapper::new_optional(&features.chain, with_chain);
+
+        VkPhysicalDeviceFeatures2Wrapper {
+            vks_struct: vks_1_1::VkPhysicalDeviceFeatures2 {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2,
+                pNext: pnext,
+                features: (&features.features).into(),
+            },
+            chain: chain,
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: PhysicalDeviceProperties2Chain [PhysicalDeviceProperties2ChainWrapper],
+    query: PhysicalDeviceProperties2ChainQuery [PhysicalDeviceProperties2ChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceProperties2,
+    input: false,
+    output: true,
+
+    physical_device_subgroup_properties: PhysicalDeviceSubgroupProperties {
+        fn_add: add_physical_device_subgroup_properties,
+        fn_has: has_physical_device_subgroup_properties,
+        fn_get: get_physical_device_subgroup_properties,
+        wrapper: VkPhysicalDeviceSubgroupPropertiesWrapper,
+        vks: vks_1_1::VkPhysicalDeviceSubgroupProperties,
+        stype: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES,
+    }
+
+    physical_device_protected_memory_properties: PhysicalDeviceProtectedMemoryProperties {
+        fn_add: add_physical_device_protected_memory_properties,
+        fn_has: has_physical_device_protected_memory_properties,
+        fn_get: get_physical_device_protected_memory_properties,
+        wrapper: VkPhysicalDeviceProtectedMemoryPropertiesWrapper,
+        vks: vks_1_1::VkPhysicalDeviceProtectedMemoryProperties,
+        stype: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_PROPERTIES,
+    }
+}
+
+/// See [`VkPhysicalDeviceProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceProperties2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceProperties2 {
+    pub properties: PhysicalDeviceProperties,
+    pub chain: Option<PhysicalDeviceProperties2Chain>,
+}
+
+impl PhysicalDeviceProperties2 {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkPhysicalDeviceProperties2, with_chain: bool) -> Self {
+        PhysicalDeviceProperties2 {
+            properties: (&properties.properties).into(),
+            chain: PhysicalDeviceProperties2Chain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: FormatProperties2Chain [FormatProperties2ChainWrapper],
+    query: FormatProperties2ChainQuery [FormatProperties2ChainQueryWrapper],
+    vks: vks_1_1::VkFormatProperties2,
+    input: false,
+    output: true,
+}
+
+/// See [`VkFormatProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkFormatProperties2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatProperties2 {
+    pub format_properties: FormatProperties,
+    pub chain: Option<FormatProperties2Chain>,
+}
+
+impl FormatProperties2 {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkFormatProperties2, with_chain: bool) -> Self {
+        FormatProperties2 {
+            format_properties: (&properties.formatProperties).into(),
+            chain: FormatProperties2Chain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: ImageFormatProperties2Chain [ImageFormatProperties2ChainWrapper],
+    query: ImageFormatProperties2ChainQuery [ImageFormatProperties2ChainQueryWrapper],
+    vks: vks_1_1::VkImageFormatProperties2,
+    input: false,
+    output: true,
+}
+
+/// See [`VkImageFormatProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkImageFormatProperties2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageFormatProperties2 {
+    pub image_format_properties: ImageFormatProperties,
+    pub chain: Option<ImageFormatProperties2Chain>,
+}
+
+impl ImageFormatProperties2 {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkImageFormatProperties2, with_chain: bool) -> Self {
+        ImageFormatProperties2 {
+            image_format_properties: (&properties.imageFormatProperties).into(),
+            chain: ImageFormatProperties2Chain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: PhysicalDeviceImageFormatInfo2Chain [PhysicalDeviceImageFormatInfo2ChainWrapper],
+    query: PhysicalDeviceImageFormatInfo2ChainQuery [PhysicalDeviceImageFormatInfo2ChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceImageFormatInfo2,
+    input: true,
+    output: false,
+}
+
+/// See [`VkPhysicalDeviceImageFormatInfo2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceImageFormatInfo2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceImageFormatInfo2 {
+    pub format: Format,
+    pub image_type: ImageType,
+    pub tiling: ImageTiling,
+    pub usage: ImageUsageFlags,
+    pub flags: ImageCreateFlags,
+    pub chain: Option<PhysicalDeviceImageFormatInfo2Chain>,
+}
+
+#[derive(Debug)]
+struct VkPhysicalDeviceImageFormatInfo2Wrapper {
+    pub vks_struct: vks_1_1::VkPhysicalDeviceImageFormatInfo2,
+    chain: Option<PhysicalDeviceImageFormatInfo2ChainWrapper>,
+}
+
+impl VkPhysicalDeviceImageFormatInfo2Wrapper {
+    pub fn new(info: &PhysicalDeviceImageFormatInfo2, with_chain: bool) -> Self {
+        let (pnext, chain) = PhysicalDeviceImageFormatInfo2ChainWrapper::new_optional(&info.chain, with_chain);
+
+        VkPhysicalDeviceImageFormatInfo2Wrapper {
+            vks_struct: vks_1_1::VkPhysicalDeviceImageFormatInfo2 {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_IMAGE_FORMAT_INFO_2,
+                pNext: pnext,
+                format: info.format.into(),
+                type_: info.image_type.into(),
+                tiling: info.tiling.into(),
+                usage: info.usage.bits(),
+                flags: info.flags.bits(),
+            },
+            chain: chain,
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: QueueFamilyProperties2Chain [QueueFamilyProperties2ChainWrapper],
+    query: QueueFamilyProperties2ChainQuery [QueueFamilyProperties2ChainQueryWrapper],
+    vks: vks_1_1::VkQueueFamilyProperties2,
+    input: false,
+    output: true,
+}
+
+/// See [`VkQueueFamilyProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkQueueFamilyProperties2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueFamilyProperties2 {
+    pub queue_family_properties: QueueFamilyProperties,
+    pub chain: Option<QueueFamilyProperties2Chain>,
+}
+
+impl QueueFamilyProperties2 {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkQueueFamilyProperties2, with_chain: bool) -> Self {
+        QueueFamilyProperties2 {
+            queue_family_properties: (&properties.queueFamilyProperties).into(),
+            chain: QueueFamilyProperties2Chain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: PhysicalDeviceMemoryProperties2Chain [PhysicalDeviceMemoryProperties2ChainWrapper],
+    query: PhysicalDeviceMemoryProperties2ChainQuery [PhysicalDeviceMemoryProperties2ChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceMemoryProperties2,
+    input: false,
+    output: true,
+}
+
+/// See [`VkPhysicalDeviceMemoryProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceMemoryProperties2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceMemoryProperties2 {
+    pub memory_properties: PhysicalDeviceMemoryProperties,
+    pub chain: Option<PhysicalDeviceMemoryProperties2Chain>,
+}
+
+impl PhysicalDeviceMemoryProperties2 {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkPhysicalDeviceMemoryProperties2, with_chain: bool) -> Self {
+        PhysicalDeviceMemoryProperties2 {
+            memory_properties: (&properties.memoryProperties).into(),
+            chain: PhysicalDeviceMemoryProperties2Chain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: SparseImageFormatProperties2Chain [SparseImageFormatProperties2ChainWrapper],
+    query: SparseImageFormatProperties2ChainQuery [SparseImageFormatProperties2ChainQueryWrapper],
+    vks: vks_1_1::VkSparseImageFormatProperties2,
+    input: false,
+    output: true,
+}
+
+/// See [`VkSparseImageFormatProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkSparseImageFormatProperties2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseImageFormatProperties2 {
+    pub properties: SparseImageFormatProperties,
+    pub chain: Option<SparseImageFormatProperties2Chain>,
+}
+
+impl SparseImageFormatProperties2 {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkSparseImageFormatProperties2, with_chain: bool) -> Self {
+        SparseImageFormatProperties2 {
+            properties: (&properties.properties).into(),
+            chain: SparseImageFormatProperties2Chain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: PhysicalDeviceSparseImageFormatInfo2Chain [PhysicalDeviceSparseImageFormatInfo2ChainWrapper],
+    query: PhysicalDeviceSparseImageFormatInfo2ChainQuery [PhysicalDeviceSparseImageFormatInfo2ChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceSparseImageFormatInfo2,
+    input: true,
+    output: false,
+}
+
+/// See [`VkPhysicalDeviceSparseImageFormatInfo2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceSparseImageFormatInfo2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceSparseImageFormatInfo2 {
+    pub format: Format,
+    pub image_type: ImageType,
+    pub samples: SampleCountFlagBits,
+    pub usage: ImageUsageFlags,
+    pub tiling: ImageTiling,
+    pub chain: Option<PhysicalDeviceSparseImageFormatInfo2Chain>,
+}
+
+#[derive(Debug)]
+struct VkPhysicalDeviceSparseImageFormatInfo2Wrapper {
+    pub vks_struct: vks_1_1::VkPhysicalDeviceSparseImageFormatInfo2,
+    chain: Option<PhysicalDeviceSparseImageFormatInfo2ChainWrapper>,
+}
+
+impl VkPhysicalDeviceSparseImageFormatInfo2Wrapper {
+    pub fn new(info: &PhysicalDeviceSparseImageFormatInfo2, with_chain: bool) -> Self {
+        let (pnext, chain) = PhysicalDeviceSparseImageFormatInfo2ChainWrapper::new_optional(&info.chain, with_chain);
+
+        VkPhysicalDeviceSparseImageFormatInfo2Wrapper {
+            vks_struct: vks_1_1::VkPhysicalDeviceSparseImageFormatInfo2 {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SPARSE_IMAGE_FORMAT_INFO_2,
+                pNext: pnext,
+                format: info.format.into(),
+                type_: info.image_type.into(),
+                samples: info.samples.bit(),
+                usage: info.usage.bits(),
+                tiling: info.tiling.into(),
+            },
+            chain: chain,
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: PhysicalDeviceSubgroupPropertiesChain [PhysicalDeviceSubgroupPropertiesChainWrapper],
+    query: PhysicalDeviceSubgroupPropertiesChainQuery [PhysicalDeviceSubgroupPropertiesChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceSubgroupProperties,
+    input: false,
+    output: true,
+}
+
+/// See [`VkPhysicalDeviceSubgroupProperties`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceSubgroupProperties)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceSubgroupProperties {
+    pub subgroup_size: u32,
+    pub supported_stages: ShaderStageFlags,
+    pub supported_operations: SubgroupFeatureFlags,
+    pub quad_operations_in_all_stages: bool,
+    pub chain: Option<PhysicalDeviceSubgroupPropertiesChain>,
+}
+
+impl PhysicalDeviceSubgroupProperties {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkPhysicalDeviceSubgroupProperties, with_chain: bool) -> Self {
+        PhysicalDeviceSubgroupProperties {
+            subgroup_size: properties.subgroupSize,
+            supported_stages: ShaderStageFlags::from_bits_truncate(properties.supportedStages),
+            supported_operations: SubgroupFeatureFlags::from_bits_truncate(properties.supportedOperations),
+            quad_operations_in_all_stages: utils::from_vk_bool(properties.quadOperationsInAllStages),
+            chain: PhysicalDeviceSubgroupPropertiesChain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: PhysicalDeviceProtectedMemoryFeaturesChain [PhysicalDeviceProtectedMemoryFeaturesChainWrapper],
+    query: PhysicalDeviceProtectedMemoryFeaturesChainQuery [PhysicalDeviceProtectedMemoryFeaturesChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceProtectedMemoryFeatures,
+    input: true,
+    output: true,
+}
+
+/// See [`VkPhysicalDeviceProtectedMemoryFeatures`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceProtectedMemoryFeatures)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceProtectedMemoryFeatures {
+    pub protected_memory: bool,
+    pub chain: Option<PhysicalDeviceProtectedMemoryFeaturesChain>,
+}
+
+impl PhysicalDeviceProtectedMemoryFeatures {
+    pub(crate) unsafe fn from_vks(features: &vks_1_1::VkPhysicalDeviceProtectedMemoryFeatures, with_chain: bool) -> Self {
+        PhysicalDeviceProtectedMemoryFeatures {
+            protected_memory: utils::from_vk_bool(features.protectedMemory),
+            chain: PhysicalDeviceProtectedMemoryFeaturesChain::from_optional_pnext(features.pNext, with_chain),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct VkPhysicalDeviceProtectedMemoryFeaturesWrapper {
+    pub vks_struct: vks_1_1::VkPhysicalDeviceProtectedMemoryFeatures,
+    chain: Option<PhysicalDeviceProtectedMemoryFeaturesChainWrapper>,
+}
+
+impl VkPhysicalDeviceProtectedMemoryFeaturesWrapper {
+    pub fn new(features: &PhysicalDeviceProtectedMemoryFeatures, with_chain: bool) -> Self {
+        let (pnext, chain) = PhysicalDeviceProtectedMemoryFeaturesChainWrapper::new_optional(&features.chain, with_chain);
+
+        VkPhysicalDeviceProtectedMemoryFeaturesWrapper {
+            vks_struct: vks_1_1::VkPhysicalDeviceProtectedMemoryFeatures {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES,
+                pNext: pnext,
+                protectedMemory: utils::to_vk_bool(features.protected_memory),
+            },
+            chain: chain,
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: PhysicalDeviceProtectedMemoryPropertiesChain [PhysicalDeviceProtectedMemoryPropertiesChainWrapper],
+    query: PhysicalDeviceProtectedMemoryPropertiesChainQuery [PhysicalDeviceProtectedMemoryPropertiesChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceProtectedMemoryProperties,
+    input: false,
+    output: true,
+}
+
+/// See [`VkPhysicalDeviceProtectedMemoryProperties`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceProtectedMemoryProperties)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceProtectedMemoryProperties {
+    pub protected_no_fault: bool,
+    pub chain: Option<PhysicalDeviceProtectedMemoryPropertiesChain>,
+}
+
+impl PhysicalDeviceProtectedMemoryProperties {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkPhysicalDeviceProtectedMemoryProperties, with_chain: bool) -> Self {
+        PhysicalDeviceProtectedMemoryProperties {
+            protected_no_fault: utils::from_vk_bool(properties.protectedNoFault),
+            chain: PhysicalDeviceProtectedMemoryPropertiesChain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: PhysicalDeviceGroupPropertiesChain [PhysicalDeviceGroupPropertiesChainWrapper],
+    query: PhysicalDeviceGroupPropertiesChainQuery [PhysicalDeviceGroupPropertiesChainQueryWrapper],
+    vks: vks_1_1::VkPhysicalDeviceGroupProperties,
+    input: false,
+    output: true,
+}
+
+/// See [`VkPhysicalDeviceGroupProperties`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceGroupProperties)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalDeviceGroupProperties {
+    pub physical_devices: Vec<PhysicalDevice>,
+    pub subset_allocation: bool,
+    pub chain: Option<PhysicalDeviceGroupPropertiesChain>,
+}
+
+impl PhysicalDeviceGroupProperties {
+    pub(crate) unsafe fn from_vks(properties: &vks_1_1::VkPhysicalDeviceGroupProperties, instance: &Instance, with_chain: bool) -> Self {
+        let physical_devices = properties.physicalDevices[..properties.physicalDeviceCount as usize]
+            .iter()
+            .map(|&d| PhysicalDevice::new(d, instance.clone()))
+            .collect();
+
+        PhysicalDeviceGroupProperties {
+            physical_devices: physical_devices,
+            subset_allocation: utils::from_vk_bool(properties.subsetAllocation),
+            chain: PhysicalDeviceGroupPropertiesChain::from_optional_pnext(properties.pNext, with_chain),
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: DeviceGroupDeviceCreateInfoChain [DeviceGroupDeviceCreateInfoChainWrapper],
+    query: DeviceGroupDeviceCreateInfoChainQuery [DeviceGroupDeviceCreateInfoChainQueryWrapper],
+    vks: vks_1_1::VkDeviceGroupDeviceCreateInfo,
+    input: true,
+    output: false,
+}
+
+/// See [`VkDeviceGroupDeviceCreateInfo`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkDeviceGroupDeviceCreateInfo)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceGroupDeviceCreateInfo {
+    pub physical_devices: Vec<PhysicalDevice>,
+    pub chain: Option<DeviceGroupDeviceCreateInfoChain>,
+}
+
+gen_create_info_builder! {
+    /// Builder for [`DeviceGroupDeviceCreateInfo`](struct.DeviceGroupDeviceCreateInfo.html).
+    name: DeviceGroupDeviceCreateInfo [DeviceGroupDeviceCreateInfoBuilder],
+
+    defaults {
+        /// Sets `physical_devices`.
+        physical_devices: Vec<PhysicalDevice> = vec![],
+        /// Sets `chain`.
+        chain: Option<DeviceGroupDeviceCreateInfoChain> = None,
+    }
+}
+
+#[derive(Debug)]
+struct VkDeviceGroupDeviceCreateInfoWrapper {
+    pub vks_struct: vks_1_1::VkDeviceGroupDeviceCreateInfo,
+    physical_devices: Vec<PhysicalDevice>,
+    vk_physical_devices: Vec<vks::vk::VkPhysicalDevice>,
+    chain: Option<DeviceGroupDeviceCreateInfoChainWrapper>,
+}
+
+impl VkDeviceGroupDeviceCreateInfoWrapper {
+    pub fn new(create_info: &DeviceGroupDeviceCreateInfo, with_chain: bool) -> Self {
+        let physical_devices = create_info.physical_devices.clone();
+        let vk_physical_devices: Vec<_> = physical_devices.iter().map(|d| d.handle).collect();
+        let vk_physical_devices_ptr = if !vk_physical_devices.is_empty() {
+            vk_physical_devices.as_ptr()
+        }
+        else {
+            ptr::null()
+        };
+
+        let (pnext, chain) = DeviceGroupDeviceCreateInfoChainWrapper::new_optional(&create_info.chain, with_chain);
+
+        VkDeviceGroupDeviceCreateInfoWrapper {
+            vks_struct: vks_1_1::VkDeviceGroupDeviceCreateInfo {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO,
+                pNext: pnext,
+                physicalDeviceCount: vk_physical_devices.len() as u32,
+                pPhysicalDevices: vk_physical_devices_ptr,
+            },
+            physical_devices: physical_devices,
+            vk_physical_devices: vk_physical_devices,
+            chain: chain,
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: DeviceQueueInfo2Chain [DeviceQueueInfo2ChainWrapper],
+    query: DeviceQueueInfo2ChainQuery [DeviceQueueInfo2ChainQueryWrapper],
+    vks: vks_1_1::VkDeviceQueueInfo2,
+    input: true,
+    output: false,
+}
+
+/// See [`VkDeviceQueueInfo2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkDeviceQueueInfo2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceQueueInfo2 {
+    pub flags: DeviceQueueCreateFlags,
+    pub queue_family_index: u32,
+    pub queue_index: u32,
+    pub chain: Option<DeviceQueueInfo2Chain>,
+}
+
+#[derive(Debug)]
+struct VkDeviceQueueInfo2Wrapper {
+    pub vks_struct: vks_1_1::VkDeviceQueueInfo2,
+    chain: Option<DeviceQueueInfo2ChainWrapper>,
+}
+
+impl VkDeviceQueueInfo2Wrapper {
+    pub fn new(queue_info: &DeviceQueueInfo2, with_chain: bool) -> Self {
+        let (pnext, chain) = DeviceQueueInfo2ChainWrapper::new_optional(&queue_info.chain, with_chain);
+
+        VkDeviceQueueInfo2Wrapper {
+            vks_struct: vks_1_1::VkDeviceQueueInfo2 {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_DEVICE_QUEUE_INFO_2,
+                pNext: pnext,
+                flags: queue_info.flags.bits(),
+                queueFamilyIndex: queue_info.queue_family_index,
+                queueIndex: queue_info.queue_index,
+            },
+            chain: chain,
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: BindBufferMemoryInfoChain [BindBufferMemoryInfoChainWrapper],
+    query: BindBufferMemoryInfoChainQuery [BindBufferMemoryInfoChainQueryWrapper],
+    vks: vks_1_1::VkBindBufferMemoryInfo,
+    input: true,
+    output: false,
+}
+
+/// See [`VkBindBufferMemoryInfo`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkBindBufferMemoryInfo)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindBufferMemoryInfo {
+    pub buffer: Buffer,
+    pub memory: DeviceMemory,
+    pub memory_offset: u64,
+    pub chain: Option<BindBufferMemoryInfoChain>,
+}
+
+#[derive(Debug)]
+struct VkBindBufferMemoryInfoWrapper {
+    pub vks_struct: vks_1_1::VkBindBufferMemoryInfo,
+    buffer: Buffer,
+    memory: DeviceMemory,
+    chain: Option<BindBufferMemoryInfoChainWrapper>,
+}
+
+impl VkBindBufferMemoryInfoWrapper {
+    pub fn new(bind_info: &BindBufferMemoryInfo, with_chain: bool) -> Self {
+        let (pnext, chain) = BindBufferMemoryInfoChainWrapper::new_optional(&bind_info.chain, with_chain);
+
+        VkBindBufferMemoryInfoWrapper {
+            vks_struct: vks_1_1::VkBindBufferMemoryInfo {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_BIND_BUFFER_MEMORY_INFO,
+                pNext: pnext,
+                buffer: bind_info.buffer.handle(),
+                memory: bind_info.memory.handle(),
+                memoryOffset: bind_info.memory_offset,
+            },
+            buffer: bind_info.buffer.clone(),
+            memory: bind_info.memory.clone(),
+            chain: chain,
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: BindImageMemoryInfoChain [BindImageMemoryInfoChainWrapper],
+    query: BindImageMemoryInfoChainQuery [BindImageMemoryInfoChainQueryWrapper],
+    vks: vks_1_1::VkBindImageMemoryInfo,
+    input: true,
+    output: false,
+}
+
+/// See [`VkBindImageMemoryInfo`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkBindImageMemoryInfo)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindImageMemoryInfo {
+    pub image: Image,
+    pub memory: DeviceMemory,
+    pub memory_offset: u64,
+    pub chain: Option<BindImageMemoryInfoChain>,
+}
+
+#[derive(Debug)]
+struct VkBindImageMemoryInfoWrapper {
+    pub vks_struct: vks_1_1::VkBindImageMemoryInfo,
+    image: Image,
+    memory: DeviceMemory,
+    chain: Option<BindImageMemoryInfoChainWrapper>,
+}
+
+impl VkBindImageMemoryInfoWrapper {
+    pub fn new(bind_info: &BindImageMemoryInfo, with_chain: bool) -> Self {
+        let (pnext, chain) = BindImageMemoryInfoChainWrapper::new_optional(&bind_info.chain, with_chain);
+
+        VkBindImageMemoryInfoWrapper {
+            vks_struct: vks_1_1::VkBindImageMemoryInfo {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_BIND_IMAGE_MEMORY_INFO,
+                pNext: pnext,
+                image: bind_info.image.handle(),
+                memory: bind_info.memory.handle(),
+                memoryOffset: bind_info.memory_offset,
+            },
+            image: bind_info.image.clone(),
+            memory: bind_info.memory.clone(),
+            chain: chain,
+        }
+    }
+}
+
+gen_chain_struct! {
+    name: ProtectedSubmitInfoChain [ProtectedSubmitInfoChainWrapper],
+    query: ProtectedSubmitInfoChainQuery [ProtectedSubmitInfoChainQueryWrapper],
+    vks: vks_1_1::VkProtectedSubmitInfo,
+    input: true,
+    output: false,
+}
+
+/// See [`VkProtectedSubmitInfo`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkProtectedSubmitInfo)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectedSubmitInfo {
+    pub protected_submit: bool,
+    pub chain: Option<ProtectedSubmitInfoChain>,
+}
+
+#[derive(Debug)]
+struct VkProtectedSubmitInfoWrapper {
+    pub vks_struct: vks_1_1::VkProtectedSubmitInfo,
+    chain: Option<ProtectedSubmitInfoChainWrapper>,
+}
+
+impl VkProtectedSubmitInfoWrapper {
+    pub fn new(submit_info: &ProtectedSubmitInfo, with_chain: bool) -> Self {
+        let (pnext, chain) = ProtectedSubmitInfoChainWrapper::new_optional(&submit_info.chain, with_chain);
+
+        VkProtectedSubmitInfoWrapper {
+            vks_struct: vks_1_1::VkProtectedSubmitInfo {
+                sType: vks_1_1::VK_STRUCTURE_TYPE_PROTECTED_SUBMIT_INFO,
+                pNext: pnext,
+                protectedSubmit: utils::to_vk_bool(submit_info.protected_submit),
+            },
+            chain: chain,
+        }
+    }
+}
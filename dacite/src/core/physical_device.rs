@@ -24,22 +24,140 @@ use khr_surface;
 use mir_types;
 use nv_external_memory_capabilities;
 use std::cmp::Ordering;
+use std::error;
 use std::ffi::CStr;
+use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::mem;
 use std::ptr;
 use utils;
 use vks;
+use vks_1_1;
 use wayland_types;
 use xcb_types;
 use xlib_types;
 
+/// Reason, why a candidate format was rejected by
+/// [`PhysicalDevice::find_supported_format`](struct.PhysicalDevice.html#method.find_supported_format)
+/// or
+/// [`PhysicalDevice::find_supported_image_format`](struct.PhysicalDevice.html#method.find_supported_image_format).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FormatRejection {
+    /// The format lacks these required features for the requested tiling.
+    MissingFeatures(core::FormatFeatureFlags),
+
+    /// `get_image_format_properties` failed with `Error::FormatNotSupported`.
+    ImageNotSupported,
+
+    /// The extent exceeds the maximum extent of the format.
+    ExtentTooLarge(core::Extent3D),
+
+    /// The number of mip levels exceeds the maximum of the format.
+    TooManyMipLevels(u32),
+
+    /// The number of array layers exceeds the maximum of the format.
+    TooManyArrayLayers(u32),
+
+    /// The sample count is not among the supported sample counts of the format.
+    SampleCountNotSupported(core::SampleCountFlags),
+}
+
+impl fmt::Display for FormatRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FormatRejection::MissingFeatures(features) => write!(f, "missing features {:?}", features),
+            FormatRejection::ImageNotSupported => write!(f, "image not supported"),
+            FormatRejection::ExtentTooLarge(max_extent) => write!(f, "extent exceeds {}x{}x{}", max_extent.width, max_extent.height, max_extent.depth),
+            FormatRejection::TooManyMipLevels(max_mip_levels) => write!(f, "more than {} mip levels", max_mip_levels),
+            FormatRejection::TooManyArrayLayers(max_array_layers) => write!(f, "more than {} array layers", max_array_layers),
+            FormatRejection::SampleCountNotSupported(sample_counts) => write!(f, "sample count not in {:?}", sample_counts),
+        }
+    }
+}
+
+/// None of the candidate formats passed to
+/// [`PhysicalDevice::find_supported_format`](struct.PhysicalDevice.html#method.find_supported_format)
+/// or
+/// [`PhysicalDevice::find_supported_image_format`](struct.PhysicalDevice.html#method.find_supported_image_format)
+/// is supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoSupportedFormatError {
+    /// Every candidate format in order, together with the reason, why it was rejected.
+    pub rejections: Vec<(core::Format, FormatRejection)>,
+}
+
+impl fmt::Display for NoSupportedFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.rejections.is_empty() {
+            return write!(f, "No candidate formats given");
+        }
+
+        write!(f, "No supported format found:")?;
+        for (i, &(format, ref rejection)) in self.rejections.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{}{:?} ({})", separator, format, rejection)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for NoSupportedFormatError {
+    fn description(&self) -> &str {
+        "NoSupportedFormat"
+    }
+}
+
+/// Error type of
+/// [`PhysicalDevice::find_supported_image_format`](struct.PhysicalDevice.html#method.find_supported_image_format).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FindImageFormatError {
+    /// None of the candidate formats is supported.
+    NoSupportedFormat(NoSupportedFormatError),
+
+    /// `get_image_format_properties` failed with an error other than `Error::FormatNotSupported`.
+    VulkanError(core::Error),
+}
+
+impl From<NoSupportedFormatError> for FindImageFormatError {
+    fn from(e: NoSupportedFormatError) -> Self {
+        FindImageFormatError::NoSupportedFormat(e)
+    }
+}
+
+impl From<core::Error> for FindImageFormatError {
+    fn from(e: core::Error) -> Self {
+        FindImageFormatError::VulkanError(e)
+    }
+}
+
+impl fmt::Display for FindImageFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FindImageFormatError::NoSupportedFormat(ref e) => e.fmt(f),
+            FindImageFormatError::VulkanError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for FindImageFormatError {
+    fn description(&self) -> &str {
+        match *self {
+            FindImageFormatError::NoSupportedFormat(ref e) => e.description(),
+            FindImageFormatError::VulkanError(ref e) => e.description(),
+        }
+    }
+}
+
 /// See [`VkPhysicalDevice`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkPhysicalDevice)
 #[derive(Debug, Clone)]
 pub struct PhysicalDevice {
     pub(crate) handle: vks::vk::VkPhysicalDevice,
     pub(crate) instance: Instance,
+
+    /// Raw `apiVersion` of the physical device, queried once on creation.
+    api_version: u32,
 }
 
 unsafe impl Send for PhysicalDevice { }
@@ -104,9 +222,16 @@ impl FromNativeObject for PhysicalDevice {
 
 impl PhysicalDevice {
     pub(crate) fn new(handle: vks::vk::VkPhysicalDevice, instance: Instance) -> Self {
+        let api_version = unsafe {
+            let mut properties = mem::uninitialized::<vks::vk::VkPhysicalDeviceProperties>();
+            instance.loader().vk.vkGetPhysicalDeviceProperties(handle, &mut properties);
+            properties.apiVersion
+        };
+
         PhysicalDevice {
             handle: handle,
             instance: instance,
+            api_version: api_version,
         }
     }
 
@@ -115,6 +240,25 @@ impl PhysicalDevice {
         self.instance.loader()
     }
 
+    #[inline]
+    pub(crate) fn loader_1_1(&self) -> &vks_1_1::InstanceFunctions {
+        self.instance.loader_1_1()
+    }
+
+    /// Returns `Error::FeatureNotPresent`, unless `function` was loaded and both the instance and
+    /// the physical device support Vulkan 1.1.
+    ///
+    /// The instance supports Vulkan 1.1 only, if it was created with an `api_version` of at least
+    /// 1.1. The api version of the physical device is cached when it is enumerated.
+    pub(crate) fn check_vulkan_1_1<F>(&self, function: Option<F>) -> Result<(), core::Error> {
+        if self.instance.supports_vulkan_1_1(&function) && (self.api_version >= ::DACITE_API_VERSION_1_1.as_api_version()) {
+            Ok(())
+        }
+        else {
+            Err(core::Error::FeatureNotPresent)
+        }
+    }
+
     /// See [`vkGetPhysicalDeviceProperties`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkGetPhysicalDeviceProperties)
     pub fn get_properties(&self) -> core::PhysicalDeviceProperties {
         unsafe {
@@ -183,6 +327,24 @@ impl PhysicalDevice {
         }
     }
 
+    /// Enumerates all device layers together with the device extensions provided by each layer.
+    ///
+    /// This is equivalent to calling `enumerate_device_layer_properties` and then
+    /// `get_device_extension_properties` for every layer.
+    pub fn enumerate_device_layers<B>(&self) -> Result<B, core::Error>
+        where B: FromIterator<core::DeviceLayer>
+    {
+        let layers: Vec<core::LayerProperties> = self.enumerate_device_layer_properties()?;
+
+        layers.into_iter().map(|properties| {
+            let extensions = self.get_device_extension_properties(Some(&properties.layer_name))?;
+            Ok(core::DeviceLayer {
+                properties: properties,
+                extensions: extensions,
+            })
+        }).collect()
+    }
+
     /// See [`vkGetPhysicalDeviceFormatProperties`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkGetPhysicalDeviceFormatProperties)
     pub fn get_format_properties(&self, format: core::Format) -> core::FormatProperties {
         let mut properties = unsafe { mem::uninitialized() };
@@ -210,6 +372,96 @@ impl PhysicalDevice {
         }
     }
 
+    /// Returns the first format of `candidates`, which supports `features` with `tiling`.
+    ///
+    /// If none of the candidates is supported, the returned error lists why each one was rejected.
+    pub fn find_supported_format(&self, candidates: &[core::Format], tiling: core::ImageTiling, features: core::FormatFeatureFlags) -> Result<core::Format, NoSupportedFormatError> {
+        let mut rejections = Vec::new();
+        for &format in candidates {
+            match self.check_format_features(format, tiling, features) {
+                Ok(()) => return Ok(format),
+                Err(rejection) => rejections.push((format, rejection)),
+            }
+        }
+
+        Err(NoSupportedFormatError {
+            rejections: rejections,
+        })
+    }
+
+    /// Returns the first format of `candidates`, with which an image described by `create_info`
+    /// can be created, and which supports `features`.
+    ///
+    /// The `format` and `initial_layout` of `create_info` are ignored. All other parameters are
+    /// checked against
+    /// [`get_image_format_properties`](#method.get_image_format_properties), including the extent,
+    /// the number of mip levels and array layers and the sample count.
+    ///
+    /// If none of the candidates is supported, the returned error lists why each one was rejected.
+    /// Errors of `get_image_format_properties` other than `Error::FormatNotSupported` are returned
+    /// immediately.
+    pub fn find_supported_image_format(&self, candidates: &[core::Format], create_info: &core::ImageCreateInfo, features: core::FormatFeatureFlags) -> Result<core::Format, FindImageFormatError> {
+        let mut rejections = Vec::new();
+        for &format in candidates {
+            match self.check_image_format(format, create_info, features)? {
+                None => return Ok(format),
+                Some(rejection) => rejections.push((format, rejection)),
+            }
+        }
+
+        Err(NoSupportedFormatError {
+            rejections: rejections,
+        }.into())
+    }
+
+    fn check_format_features(&self, format: core::Format, tiling: core::ImageTiling, features: core::FormatFeatureFlags) -> Result<(), FormatRejection> {
+        let properties = self.get_format_properties(format);
+        let supported = match tiling {
+            core::ImageTiling::Linear => properties.linear_tiling_features,
+            _ => properties.optimal_tiling_features,
+        };
+
+        if supported.contains(features) {
+            Ok(())
+        }
+        else {
+            Err(FormatRejection::MissingFeatures(features - supported))
+        }
+    }
+
+    /// Returns `None`, if `format` is suitable, or the reason why it was rejected.
+    fn check_image_format(&self, format: core::Format, create_info: &core::ImageCreateInfo, features: core::FormatFeatureFlags) -> Result<Option<FormatRejection>, core::Error> {
+        if let Err(rejection) = self.check_format_features(format, create_info.tiling, features) {
+            return Ok(Some(rejection));
+        }
+
+        let properties = match self.get_image_format_properties(format, create_info.image_type, create_info.tiling, create_info.usage, create_info.flags) {
+            Ok(properties) => properties,
+            Err(core::Error::FormatNotSupported) => return Ok(Some(FormatRejection::ImageNotSupported)),
+            Err(e) => return Err(e),
+        };
+
+        let extent = &create_info.extent;
+        let max_extent = properties.max_extent;
+        if (extent.width > max_extent.width) || (extent.height > max_extent.height) || (extent.depth > max_extent.depth) {
+            return Ok(Some(FormatRejection::ExtentTooLarge(max_extent)));
+        }
+
+        if create_info.mip_levels > properties.max_mip_levels {
+            return Ok(Some(FormatRejection::TooManyMipLevels(properties.max_mip_levels)));
+        }
+
+        if create_info.array_layers > properties.max_array_layers {
+            return Ok(Some(FormatRejection::TooManyArrayLayers(properties.max_array_layers)));
+        }
+
+        if !properties.sample_counts.contains(create_info.samples.into()) {
+            return Ok(Some(FormatRejection::SampleCountNotSupported(properties.sample_counts)));
+        }
+
+        Ok(None)
+    }
+
     /// See [`vkGetPhysicalDeviceSparseImageFormatProperties`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkGetPhysicalDeviceSparseImageFormatProperties)
     pub fn get_sparse_image_format_properties<B>(&self, format: core::Format, image_type: core::ImageType, samples: core::SampleCountFlagBits, usage: core::ImageUsageFlags, tiling: core::ImageTiling) -> B
         where B: FromIterator<core::SparseImageFormatProperties>
@@ -270,20 +522,139 @@ impl PhysicalDevice {
 
         if res == vks::vk::VK_SUCCESS {
             let mut loader = vks::DeviceProcAddrLoader::from_get_device_proc_addr(self.loader().vk.pfn_vkGetDeviceProcAddr);
+            let mut loader_1_1 = vks_1_1::DeviceFunctions::new();
 
             unsafe {
                 loader.load_vk(device);
+                loader_1_1.load(self.loader().vk.pfn_vkGetDeviceProcAddr, device);
                 create_info.enabled_extensions.load_device(&mut loader, device);
                 self.instance.get_enabled_extensions().load_device(&mut loader, device);
             }
 
-            Ok(Device::new(device, self.instance.clone(), allocator_helper, loader, create_info.enabled_extensions.clone()))
+            Ok(Device::new(device, self.clone(), allocator_helper, loader, loader_1_1, create_info.enabled_extensions.clone()))
         }
         else {
             Err(res.into())
         }
     }
 
+    /// See [`vkGetPhysicalDeviceFeatures2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetPhysicalDeviceFeatures2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the physical device doesn't support Vulkan 1.1.
+    pub fn get_features2(&self, chain_query: Option<&core::PhysicalDeviceFeatures2ChainQuery>) -> Result<core::PhysicalDeviceFeatures2, core::Error> {
+        self.check_vulkan_1_1(self.loader_1_1().pfn_vkGetPhysicalDeviceFeatures2)?;
+        let mut chain_query_wrapper = core::PhysicalDeviceFeatures2ChainQueryWrapper::new_optional(chain_query);
+        unsafe {
+            self.loader_1_1().vkGetPhysicalDeviceFeatures2(self.handle, &mut chain_query_wrapper.vks_struct);
+            Ok(core::PhysicalDeviceFeatures2::from_vks(&chain_query_wrapper.vks_struct, true))
+        }
+    }
+
+    /// See [`vkGetPhysicalDeviceProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetPhysicalDeviceProperties2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the physical device doesn't support Vulkan 1.1.
+    pub fn get_properties2(&self, chain_query: Option<&core::PhysicalDeviceProperties2ChainQuery>) -> Result<core::PhysicalDeviceProperties2, core::Error> {
+        self.check_vulkan_1_1(self.loader_1_1().pfn_vkGetPhysicalDeviceProperties2)?;
+        let mut chain_query_wrapper = core::PhysicalDeviceProperties2ChainQueryWrapper::new_optional(chain_query);
+        unsafe {
+            self.loader_1_1().vkGetPhysicalDeviceProperties2(self.handle, &mut chain_query_wrapper.vks_struct);
+            Ok(core::PhysicalDeviceProperties2::from_vks(&chain_query_wrapper.vks_struct, true))
+        }
+    }
+
+    /// See [`vkGetPhysicalDeviceFormatProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetPhysicalDeviceFormatProperties2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the physical device doesn't support Vulkan 1.1.
+    pub fn get_format_properties2(&self, format: core::Format, chain_query: Option<&core::FormatProperties2ChainQuery>) -> Result<core::FormatProperties2, core::Error> {
+        self.check_vulkan_1_1(self.loader_1_1().pfn_vkGetPhysicalDeviceFormatProperties2)?;
+        let mut chain_query_wrapper = core::FormatProperties2ChainQueryWrapper::new_optional(chain_query);
+        unsafe {
+            self.loader_1_1().vkGetPhysicalDeviceFormatProperties2(self.handle, format.into(), &mut chain_query_wrapper.vks_struct);
+            Ok(core::FormatProperties2::from_vks(&chain_query_wrapper.vks_struct, true))
+        }
+    }
+
+    /// See [`vkGetPhysicalDeviceImageFormatProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetPhysicalDeviceImageFormatProperties2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the physical device doesn't support Vulkan 1.1.
+    pub fn get_image_format_properties2(&self, image_format_info: &core::PhysicalDeviceImageFormatInfo2, chain_query: Option<&core::ImageFormatProperties2ChainQuery>) -> Result<core::ImageFormatProperties2, core::Error> {
+        self.check_vulkan_1_1(self.loader_1_1().pfn_vkGetPhysicalDeviceImageFormatProperties2)?;
+        let image_format_info_wrapper = core::VkPhysicalDeviceImageFormatInfo2Wrapper::new(image_format_info, true);
+        let mut chain_query_wrapper = core::ImageFormatProperties2ChainQueryWrapper::new_optional(chain_query);
+
+        unsafe {
+            let res = self.loader_1_1().vkGetPhysicalDeviceImageFormatProperties2(self.handle, &image_format_info_wrapper.vks_struct, &mut chain_query_wrapper.vks_struct);
+
+            if res == vks::vk::VK_SUCCESS {
+                Ok(core::ImageFormatProperties2::from_vks(&chain_query_wrapper.vks_struct, true))
+            }
+            else {
+                Err(res.into())
+            }
+        }
+    }
+
+    /// See [`vkGetPhysicalDeviceQueueFamilyProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetPhysicalDeviceQueueFamilyProperties2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the physical device doesn't support Vulkan 1.1.
+    pub fn get_queue_family_properties2<B>(&self, chain_query: Option<&core::QueueFamilyProperties2ChainQuery>) -> Result<B, core::Error>
+        where B: FromIterator<core::QueueFamilyProperties2>
+    {
+        self.check_vulkan_1_1(self.loader_1_1().pfn_vkGetPhysicalDeviceQueueFamilyProperties2)?;
+
+        unsafe {
+            let mut num = 0;
+            self.loader_1_1().vkGetPhysicalDeviceQueueFamilyProperties2(self.handle, &mut num, ptr::null_mut());
+
+            let mut chain_query_wrappers = Vec::with_capacity(num as usize);
+            for _ in 0..num {
+                chain_query_wrappers.push(core::QueueFamilyProperties2ChainQueryWrapper::new_optional(chain_query));
+            }
+
+            let mut vks_structs: Vec<_> = chain_query_wrappers.iter().map(|w| w.vks_struct).collect();
+            self.loader_1_1().vkGetPhysicalDeviceQueueFamilyProperties2(self.handle, &mut num, vks_structs.as_mut_ptr());
+
+            Ok(vks_structs.iter().take(num as usize).map(|p| core::QueueFamilyProperties2::from_vks(p, true)).collect())
+        }
+    }
+
+    /// See [`vkGetPhysicalDeviceMemoryProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetPhysicalDeviceMemoryProperties2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the physical device doesn't support Vulkan 1.1.
+    pub fn get_memory_properties2(&self, chain_query: Option<&core::PhysicalDeviceMemoryProperties2ChainQuery>) -> Result<core::PhysicalDeviceMemoryProperties2, core::Error> {
+        self.check_vulkan_1_1(self.loader_1_1().pfn_vkGetPhysicalDeviceMemoryProperties2)?;
+        let mut chain_query_wrapper = core::PhysicalDeviceMemoryProperties2ChainQueryWrapper::new_optional(chain_query);
+        unsafe {
+            self.loader_1_1().vkGetPhysicalDeviceMemoryProperties2(self.handle, &mut chain_query_wrapper.vks_struct);
+            Ok(core::PhysicalDeviceMemoryProperties2::from_vks(&chain_query_wrapper.vks_struct, true))
+        }
+    }
+
+    /// See [`vkGetPhysicalDeviceSparseImageFormatProperties2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#vkGetPhysicalDeviceSparseImageFormatProperties2)
+    ///
+    /// Returns `Error::FeatureNotPresent`, if the physical device doesn't support Vulkan 1.1.
+    pub fn get_sparse_image_format_properties2<B>(&self, format_info: &core::PhysicalDeviceSparseImageFormatInfo2, chain_query: Option<&core::SparseImageFormatProperties2ChainQuery>) -> Result<B, core::Error>
+        where B: FromIterator<core::SparseImageFormatProperties2>
+    {
+        self.check_vulkan_1_1(self.loader_1_1().pfn_vkGetPhysicalDeviceSparseImageFormatProperties2)?;
+        let format_info_wrapper = core::VkPhysicalDeviceSparseImageFormatInfo2Wrapper::new(format_info, true);
+
+        unsafe {
+            let mut num = 0;
+            self.loader_1_1().vkGetPhysicalDeviceSparseImageFormatProperties2(self.handle, &format_info_wrapper.vks_struct, &mut num, ptr::null_mut());
+
+            let mut chain_query_wrappers = Vec::with_capacity(num as usize);
+            for _ in 0..num {
+                chain_query_wrappers.push(core::SparseImageFormatProperties2ChainQueryWrapper::new_optional(chain_query));
+            }
+
+            let mut vks_structs: Vec<_> = chain_query_wrappers.iter().map(|w| w.vks_struct).collect();
+            self.loader_1_1().vkGetPhysicalDeviceSparseImageFormatProperties2(self.handle, &format_info_wrapper.vks_struct, &mut num, vks_structs.as_mut_ptr());
+
+            Ok(vks_structs.iter().take(num as usize).map(|p| core::SparseImageFormatProperties2::from_vks(p, true)).collect())
+        }
+    }
+
     /// See [`vkGetPhysicalDeviceSurfaceSupportKHR`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkGetPhysicalDeviceSurfaceSupportKHR)
     /// and extension [`VK_KHR_surface`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VK_KHR_surface)
     pub fn get_surface_support_khr(&self, queue_family_index: u32, surface: &khr_surface::SurfaceKhr) -> Result<bool, core::Error> {
@@ -20,11 +20,62 @@ use core::allocator_helper::AllocatorHelper;
 use core::{self, Device};
 use libc::c_void;
 use std::cmp::Ordering;
+use std::error;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
 use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
 use std::ptr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 use vks;
 
+/// Counter for the names of temporary files written by `PipelineCache::store`.
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Error type for loading and storing pipeline caches.
+#[derive(Debug)]
+pub enum PipelineCacheError {
+    /// Reading or writing the file failed.
+    Io(io::Error),
+
+    /// A Vulkan function returned an error.
+    VulkanError(core::Error),
+}
+
+impl fmt::Display for PipelineCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PipelineCacheError::Io(ref e) => e.fmt(f),
+            PipelineCacheError::VulkanError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for PipelineCacheError {
+    fn description(&self) -> &str {
+        match *self {
+            PipelineCacheError::Io(ref e) => e.description(),
+            PipelineCacheError::VulkanError(ref e) => e.description(),
+        }
+    }
+}
+
+impl From<io::Error> for PipelineCacheError {
+    fn from(e: io::Error) -> Self {
+        PipelineCacheError::Io(e)
+    }
+}
+
+impl From<core::Error> for PipelineCacheError {
+    fn from(e: core::Error) -> Self {
+        PipelineCacheError::VulkanError(e)
+    }
+}
+
 /// See [`VkPipelineCache`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#VkPipelineCache)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PipelineCache(Arc<Inner>);
@@ -126,6 +177,34 @@ impl PipelineCache {
         }
     }
 
+    /// Writes the data of this pipeline cache to `path`.
+    ///
+    /// The data is first written to a temporary file next to `path`, which then replaces `path`.
+    /// Readers will therefore never observe a partially written cache. The name of the temporary
+    /// file contains the process ID and a counter, so that concurrent writers don't collide.
+    pub fn store<P: AsRef<Path>>(&self, path: P) -> Result<(), PipelineCacheError> {
+        let path = path.as_ref();
+        let data = self.get_data(None)?;
+
+        let pid = process::id();
+        let counter = TEMP_FILE_COUNTER.fetch_add(1, AtomicOrdering::Relaxed);
+        let mut temp_file_name = path.file_name().map(OsString::from).unwrap_or_default();
+        temp_file_name.push(format!(".{}.{}.tmp", pid, counter));
+        let temp_path = path.with_file_name(temp_file_name);
+
+        let res = fs::File::create(&temp_path).and_then(|mut file| {
+            file.write_all(&data)?;
+            file.sync_all()
+        }).and_then(|_| fs::rename(&temp_path, path));
+
+        if let Err(e) = res {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
     /// See [`vkGetPipelineCacheData`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkGetPipelineCacheData)
     pub fn get_data(&self, max_size: Option<usize>) -> Result<Vec<u8>, core::Error> {
         if let Some(mut max_size) = max_size {
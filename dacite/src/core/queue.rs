@@ -16,7 +16,7 @@ use FromNativeObject;
 use TryDestroyError;
 use TryDestroyErrorKind;
 use VulkanObject;
-use core::{self, Device, Fence};
+use core::{self, CommandBuffer, Device, Fence};
 use khr_swapchain;
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
@@ -102,6 +102,9 @@ impl Queue {
         self.device.loader()
     }
 
+    /// After a successful submission, all [`DeletionQueue`](struct.DeletionQueue.html)s of the
+    /// `Device` are polled.
+    ///
     /// See [`vkQueueSubmit`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkQueueSubmit)
     pub fn submit(&self, submits: Option<&[core::SubmitInfo]>, fence: Option<&Fence>) -> Result<(), core::Error> {
         #[allow(unused_variables)]
@@ -115,13 +118,23 @@ impl Queue {
             None => (0, ptr::null(), None, None),
         };
 
-        let fence = fence.map_or(Default::default(), Fence::handle);
+        let vk_fence = fence.map_or(Default::default(), Fence::handle);
 
         let res = unsafe {
-            self.loader().vk.vkQueueSubmit(self.handle, submits_count, vk_submits_ptr, fence)
+            self.loader().vk.vkQueueSubmit(self.handle, submits_count, vk_submits_ptr, vk_fence)
         };
 
         if res == vks::vk::VK_SUCCESS {
+            if let Some(submits) = submits {
+                let submissions: Vec<_> = submits.iter().flat_map(|s| s.command_buffers.iter()).filter_map(CommandBuffer::submission).collect();
+                if let Some(fence) = fence {
+                    fence.add_pending(submissions.iter().cloned());
+                }
+
+                self.device.add_submissions(self.handle, submissions);
+            }
+
+            self.device.poll_deletion_queues();
             Ok(())
         }
         else {
@@ -129,6 +142,9 @@ impl Queue {
         }
     }
 
+    /// Command buffers submitted to this queue with `ONE_TIME_SUBMIT` release the objects they
+    /// reference.
+    ///
     /// See [`vkQueueWaitIdle`](https://www.khronos.org/registry/vulkan/specs/1.0-extensions/html/vkspec.html#vkQueueWaitIdle)
     pub fn wait_idle(&self) -> Result<(), core::Error> {
         let res = unsafe {
@@ -136,6 +152,7 @@ impl Queue {
         };
 
         if res == vks::vk::VK_SUCCESS {
+            self.device.complete_submissions(Some(self.handle));
             Ok(())
         }
         else {
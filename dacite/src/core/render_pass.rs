@@ -92,9 +92,22 @@ impl RenderPass {
             owned: owned,
             device: device,
             allocator: allocator,
+            final_layouts: Vec::new(),
         }))
     }
 
+    /// Stores the final layouts of all attachments.
+    ///
+    /// Must only be called on newly created objects, which are not shared yet.
+    pub(crate) fn set_final_layouts(&mut self, final_layouts: Vec<core::ImageLayout>) {
+        Arc::get_mut(&mut self.0).unwrap().final_layouts = final_layouts;
+    }
+
+    #[inline]
+    pub(crate) fn final_layouts(&self) -> &[core::ImageLayout] {
+        &self.0.final_layouts
+    }
+
     #[inline]
     pub(crate) fn handle(&self) -> vks::vk::VkRenderPass {
         self.0.handle
@@ -126,6 +139,7 @@ struct Inner {
     owned: bool,
     device: Device,
     allocator: Option<AllocatorHelper>,
+    final_layouts: Vec<core::ImageLayout>,
 }
 
 impl Drop for Inner {
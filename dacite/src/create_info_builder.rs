@@ -0,0 +1,128 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+/// Generates a builder for a create info struct.
+///
+/// If the struct has no `required` fields, `Default` is implemented for it with the values given
+/// in `defaults`. Otherwise the builder's constructor takes the required fields as parameters.
+///
+/// Doc comments on the fields are attached to the generated setters.
+macro_rules! gen_create_info_builder {
+    (
+        $( #[$attr:meta] )*
+        name: $name:ident [$builder:ident],
+
+        defaults {
+            $( $( #[$field_attr:meta] )* $field:ident: $field_ty:ty = $default:expr, )*
+        }
+    ) => {
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $( $field: $default, )*
+                }
+            }
+        }
+
+        impl $name {
+            /// Returns a builder, which starts out with the default values.
+            #[inline]
+            pub fn builder() -> $builder {
+                $builder::new()
+            }
+        }
+
+        $( #[$attr] )*
+        #[derive(Debug, Clone, Default, PartialEq)]
+        pub struct $builder($name);
+
+        impl $builder {
+            #[inline]
+            pub fn new() -> Self {
+                $builder(Default::default())
+            }
+
+            gen_create_info_builder!(@setters $name, $( $( #[$field_attr] )* $field: $field_ty, )*);
+        }
+
+        impl From<$builder> for $name {
+            #[inline]
+            fn from(builder: $builder) -> Self {
+                builder.0
+            }
+        }
+    };
+
+    (
+        $( #[$attr:meta] )*
+        name: $name:ident [$builder:ident],
+
+        required {
+            $( $( #[$required_field_attr:meta] )* $required_field:ident: $required_field_ty:ty, )*
+        }
+
+        defaults {
+            $( $( #[$field_attr:meta] )* $field:ident: $field_ty:ty = $default:expr, )*
+        }
+    ) => {
+        impl $name {
+            /// Returns a builder, which starts out with the required fields and default values for
+            /// all other fields.
+            #[inline]
+            pub fn builder($( $required_field: $required_field_ty ),*) -> $builder {
+                $builder::new($( $required_field ),*)
+            }
+        }
+
+        $( #[$attr] )*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $builder($name);
+
+        impl $builder {
+            #[inline]
+            pub fn new($( $required_field: $required_field_ty ),*) -> Self {
+                $builder($name {
+                    $( $required_field: $required_field, )*
+                    $( $field: $default, )*
+                })
+            }
+
+            gen_create_info_builder!(@setters $name, $( $( #[$required_field_attr] )* $required_field: $required_field_ty, )* $( $( #[$field_attr] )* $field: $field_ty, )*);
+        }
+
+        impl From<$builder> for $name {
+            #[inline]
+            fn from(builder: $builder) -> Self {
+                builder.0
+            }
+        }
+    };
+
+    (@setters $name:ident, $( $( #[$field_attr:meta] )* $field:ident: $field_ty:ty, )*) => {
+        $(
+            $( #[$field_attr] )*
+            #[inline]
+            pub fn $field(mut self, $field: $field_ty) -> Self {
+                self.0.$field = $field;
+                self
+            }
+        )*
+
+        /// Returns the finished create info.
+        #[inline]
+        pub fn build(self) -> $name {
+            self.0
+        }
+    };
+}
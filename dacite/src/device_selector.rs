@@ -0,0 +1,399 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! Selection of a physical device, which meets the requirements of an application.
+//!
+//! A [`PhysicalDeviceSelector`](struct.PhysicalDeviceSelector.html) checks every physical device of
+//! an instance against a set of requirements: extensions, features, queue capabilities,
+//! presentation support and the API version. Suitable devices are ranked by their type and the
+//! number of supported optional extensions. Devices, which are not suitable, are reported together
+//! with all reasons for their rejection.
+
+use core::{self, Instance, PhysicalDevice};
+use khr_surface::SurfaceKhr;
+use std::cmp;
+use std::error;
+use std::fmt;
+
+/// Reason, why a physical device was rejected by a
+/// [`PhysicalDeviceSelector`](struct.PhysicalDeviceSelector.html).
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectionReason {
+    /// The API version of the device is lower than the required version.
+    ApiVersionTooLow {
+        required: core::Version,
+        supported: core::Version,
+    },
+
+    /// Some required extensions are not supported.
+    MissingExtensions(core::DeviceExtensions),
+
+    /// Some required features are not supported.
+    MissingFeatures(core::PhysicalDeviceFeatures),
+
+    /// No queue family supports all of the flags.
+    MissingQueueFlags(core::QueueFlags),
+
+    /// No queue family supports presentation to the surface.
+    PresentationNotSupported,
+
+    /// A Vulkan function returned an error while the device was queried.
+    VulkanError(core::Error),
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RejectionReason::ApiVersionTooLow { required, supported } => write!(f, "API version {} is lower than {}", supported, required),
+            RejectionReason::MissingExtensions(ref extensions) => write!(f, "missing extensions {}", extensions.names().join(", ")),
+            RejectionReason::MissingFeatures(ref features) => write!(f, "missing features {}", features.names().join(", ")),
+            RejectionReason::MissingQueueFlags(flags) => write!(f, "no queue family supports {:?}", flags),
+            RejectionReason::PresentationNotSupported => write!(f, "no queue family supports presentation"),
+            RejectionReason::VulkanError(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// A physical device, which does not meet the requirements of a
+/// [`PhysicalDeviceSelector`](struct.PhysicalDeviceSelector.html).
+#[derive(Debug, Clone)]
+pub struct RejectedPhysicalDevice {
+    pub physical_device: PhysicalDevice,
+    pub properties: core::PhysicalDeviceProperties,
+
+    /// All requirements, which are not met by the device.
+    pub reasons: Vec<RejectionReason>,
+}
+
+impl fmt::Display for RejectedPhysicalDevice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.properties.device_name)?;
+
+        for (i, reason) in self.reasons.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+
+            write!(f, "{}", reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error type for [`PhysicalDeviceSelector::select`](struct.PhysicalDeviceSelector.html#method.select).
+#[derive(Debug, Clone)]
+pub enum SelectionError {
+    /// No physical device meets the requirements.
+    NoSuitableDevice(Vec<RejectedPhysicalDevice>),
+
+    /// Enumerating the physical devices failed.
+    VulkanError(core::Error),
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SelectionError::NoSuitableDevice(ref rejections) => {
+                write!(f, "No suitable physical device found")?;
+
+                for (i, rejection) in rejections.iter().enumerate() {
+                    if i == 0 {
+                        write!(f, ": {}", rejection)?;
+                    }
+                    else {
+                        write!(f, ", {}", rejection)?;
+                    }
+                }
+
+                Ok(())
+            }
+
+            SelectionError::VulkanError(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for SelectionError {
+    fn description(&self) -> &str {
+        match *self {
+            SelectionError::NoSuitableDevice(_) => "NoSuitableDevice",
+            SelectionError::VulkanError(ref e) => e.description(),
+        }
+    }
+}
+
+impl From<core::Error> for SelectionError {
+    fn from(e: core::Error) -> Self {
+        SelectionError::VulkanError(e)
+    }
+}
+
+/// A physical device, which meets the requirements of a
+/// [`PhysicalDeviceSelector`](struct.PhysicalDeviceSelector.html).
+#[derive(Debug, Clone)]
+pub struct SelectedPhysicalDevice {
+    pub physical_device: PhysicalDevice,
+    pub properties: core::PhysicalDeviceProperties,
+    pub queue_family_properties: Vec<core::QueueFamilyProperties>,
+
+    /// The required extensions and all supported optional extensions.
+    pub enabled_extensions: core::DeviceExtensions,
+
+    /// The required features.
+    pub enabled_features: core::PhysicalDeviceFeatures,
+
+    /// For every call of [`queue_flags`](struct.PhysicalDeviceSelector.html#method.queue_flags),
+    /// the index of the first queue family, which supports the flags.
+    pub queue_family_indices: Vec<u32>,
+
+    /// The index of a queue family, which supports presentation to the surface, if one was set.
+    ///
+    /// Families from `queue_family_indices` are preferred.
+    pub present_queue_family_index: Option<u32>,
+}
+
+/// Selects and ranks physical devices by a set of requirements.
+///
+/// Without any requirements, every physical device is suitable. By default, discrete GPUs are
+/// preferred over integrated GPUs, virtual GPUs and CPUs, in that order.
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceSelector {
+    required_extensions: core::DeviceExtensions,
+    optional_extensions: core::DeviceExtensions,
+    required_features: core::PhysicalDeviceFeatures,
+    queue_flags: Vec<core::QueueFlags>,
+    surface: Option<SurfaceKhr>,
+    min_api_version: Option<core::Version>,
+    preferred_device_types: Vec<core::PhysicalDeviceType>,
+}
+
+impl Default for PhysicalDeviceSelector {
+    fn default() -> Self {
+        PhysicalDeviceSelector::new()
+    }
+}
+
+impl PhysicalDeviceSelector {
+    /// Creates a new selector without any requirements.
+    pub fn new() -> Self {
+        PhysicalDeviceSelector {
+            required_extensions: core::DeviceExtensions::new(),
+            optional_extensions: core::DeviceExtensions::new(),
+            required_features: core::PhysicalDeviceFeatures::empty(),
+            queue_flags: Vec::new(),
+            surface: None,
+            min_api_version: None,
+            preferred_device_types: vec![
+                core::PhysicalDeviceType::DiscreteGpu,
+                core::PhysicalDeviceType::IntegratedGpu,
+                core::PhysicalDeviceType::VirtualGpu,
+                core::PhysicalDeviceType::Cpu,
+            ],
+        }
+    }
+
+    /// Sets the extensions, which must be supported.
+    pub fn required_extensions(mut self, extensions: core::DeviceExtensions) -> Self {
+        self.required_extensions = extensions;
+        self
+    }
+
+    /// Sets the extensions, which are enabled if they are supported.
+    ///
+    /// Devices supporting more of these extensions are ranked higher among devices of the same
+    /// type.
+    pub fn optional_extensions(mut self, extensions: core::DeviceExtensions) -> Self {
+        self.optional_extensions = extensions;
+        self
+    }
+
+    /// Sets the features, which must be supported.
+    pub fn required_features(mut self, features: core::PhysicalDeviceFeatures) -> Self {
+        self.required_features = features;
+        self
+    }
+
+    /// Requires a queue family, which supports all of `flags`.
+    ///
+    /// This can be called multiple times. Each requirement can be met by a different queue family.
+    pub fn queue_flags(mut self, flags: core::QueueFlags) -> Self {
+        self.queue_flags.push(flags);
+        self
+    }
+
+    /// Requires a queue family, which supports presentation to `surface`.
+    pub fn surface(mut self, surface: SurfaceKhr) -> Self {
+        self.surface = Some(surface);
+        self
+    }
+
+    /// Sets the minimum API version, which must be supported.
+    pub fn min_api_version(mut self, version: core::Version) -> Self {
+        self.min_api_version = Some(version);
+        self
+    }
+
+    /// Sets the order, in which device types are preferred.
+    ///
+    /// Devices of types not contained in `device_types` are still suitable, but ranked last.
+    pub fn preferred_device_types(mut self, device_types: Vec<core::PhysicalDeviceType>) -> Self {
+        self.preferred_device_types = device_types;
+        self
+    }
+
+    /// Selects the highest ranked physical device of `instance`.
+    pub fn select(&self, instance: &Instance) -> Result<SelectedPhysicalDevice, SelectionError> {
+        let physical_devices = instance.enumerate_physical_devices()?;
+        let (mut selected, rejected) = self.rank(physical_devices);
+
+        if !selected.is_empty() {
+            Ok(selected.remove(0))
+        }
+        else {
+            Err(SelectionError::NoSuitableDevice(rejected))
+        }
+    }
+
+    /// Checks all `physical_devices` against the requirements.
+    ///
+    /// Returns the suitable devices, ordered from highest to lowest rank, and the rejected devices
+    /// in their original order. Devices of equal rank retain their original order.
+    pub fn rank(&self, physical_devices: Vec<PhysicalDevice>) -> (Vec<SelectedPhysicalDevice>, Vec<RejectedPhysicalDevice>) {
+        let mut selected = Vec::new();
+        let mut rejected = Vec::new();
+
+        for physical_device in physical_devices {
+            match self.check(physical_device) {
+                Ok(device) => {
+                    let type_rank = self.type_rank(device.properties.device_type);
+                    let optional_count = device.enabled_extensions.intersection(&self.optional_extensions).len();
+                    selected.push((cmp::Reverse((type_rank, optional_count)), device));
+                }
+
+                Err(device) => rejected.push(device),
+            }
+        }
+
+        selected.sort_by_key(|&(rank, _)| rank);
+        let selected = selected.into_iter().map(|(_, device)| device).collect();
+
+        (selected, rejected)
+    }
+
+    fn type_rank(&self, device_type: core::PhysicalDeviceType) -> usize {
+        match self.preferred_device_types.iter().position(|&t| t == device_type) {
+            Some(position) => self.preferred_device_types.len() - position,
+            None => 0,
+        }
+    }
+
+    fn check(&self, physical_device: PhysicalDevice) -> Result<SelectedPhysicalDevice, RejectedPhysicalDevice> {
+        let properties = physical_device.get_properties();
+        let queue_family_properties: Vec<core::QueueFamilyProperties> = physical_device.get_queue_family_properties();
+        let mut reasons = Vec::new();
+
+        if let Some(required) = self.min_api_version {
+            if properties.api_version.as_api_version() < required.as_api_version() {
+                reasons.push(RejectionReason::ApiVersionTooLow {
+                    required: required,
+                    supported: properties.api_version,
+                });
+            }
+        }
+
+        let mut enabled_extensions = self.required_extensions.clone();
+        match physical_device.get_device_extension_properties(None) {
+            Ok(available_extensions) => {
+                let available_extensions = available_extensions.to_extensions();
+
+                let missing_extensions = self.required_extensions.difference(&available_extensions);
+                if !missing_extensions.is_empty() {
+                    reasons.push(RejectionReason::MissingExtensions(missing_extensions));
+                }
+
+                enabled_extensions = enabled_extensions.union(&self.optional_extensions.intersection(&available_extensions));
+            }
+
+            Err(e) => reasons.push(RejectionReason::VulkanError(e)),
+        }
+
+        let mut missing_features = self.required_features;
+        missing_features.difference(&physical_device.get_features());
+        if !missing_features.is_empty() {
+            reasons.push(RejectionReason::MissingFeatures(missing_features));
+        }
+
+        let mut queue_family_indices = Vec::with_capacity(self.queue_flags.len());
+        for &flags in &self.queue_flags {
+            let index = queue_family_properties.iter().position(|properties| {
+                (properties.queue_count > 0) && properties.queue_flags.contains(flags)
+            });
+
+            match index {
+                Some(index) => queue_family_indices.push(index as u32),
+                None => reasons.push(RejectionReason::MissingQueueFlags(flags)),
+            }
+        }
+
+        let mut present_queue_family_index = None;
+        if let Some(ref surface) = self.surface {
+            match self.find_present_queue_family(&physical_device, surface, &queue_family_properties, &queue_family_indices) {
+                Ok(Some(index)) => present_queue_family_index = Some(index),
+                Ok(None) => reasons.push(RejectionReason::PresentationNotSupported),
+                Err(e) => reasons.push(RejectionReason::VulkanError(e)),
+            }
+        }
+
+        if reasons.is_empty() {
+            Ok(SelectedPhysicalDevice {
+                physical_device: physical_device,
+                properties: properties,
+                queue_family_properties: queue_family_properties,
+                enabled_extensions: enabled_extensions,
+                enabled_features: self.required_features,
+                queue_family_indices: queue_family_indices,
+                present_queue_family_index: present_queue_family_index,
+            })
+        }
+        else {
+            Err(RejectedPhysicalDevice {
+                physical_device: physical_device,
+                properties: properties,
+                reasons: reasons,
+            })
+        }
+    }
+
+    fn find_present_queue_family(&self, physical_device: &PhysicalDevice, surface: &SurfaceKhr, queue_family_properties: &[core::QueueFamilyProperties], preferred: &[u32]) -> Result<Option<u32>, core::Error> {
+        let mut present_queue_family_index = None;
+
+        for (index, properties) in queue_family_properties.iter().enumerate() {
+            let index = index as u32;
+            if (properties.queue_count == 0) || !physical_device.get_surface_support_khr(index, surface)? {
+                continue;
+            }
+
+            if preferred.contains(&index) {
+                return Ok(Some(index));
+            }
+
+            if present_queue_family_index.is_none() {
+                present_queue_family_index = Some(index);
+            }
+        }
+
+        Ok(present_queue_family_index)
+    }
+}
@@ -22,14 +22,19 @@ extern crate vks;
 #[macro_use]
 mod chain;
 
+#[macro_use]
+mod create_info_builder;
+
 #[macro_use]
 mod dacite_bitflags;
 
 mod utils;
+mod vks_1_1;
 mod vulkan_object;
 
 pub mod core;
 pub mod amd_rasterization_order;
+pub mod device_selector;
 pub mod ext_debug_marker;
 pub mod ext_debug_report;
 pub mod ext_validation_flags;
@@ -44,11 +49,17 @@ pub mod khr_wayland_surface;
 pub mod khr_win32_surface;
 pub mod khr_xcb_surface;
 pub mod khr_xlib_surface;
+pub mod memory_allocator;
 pub mod nv_dedicated_allocation;
 pub mod nv_external_memory;
 pub mod nv_external_memory_capabilities;
 pub mod nv_external_memory_win32;
 pub mod nv_win32_keyed_mutex;
+pub mod queue_planner;
+pub mod render_graph;
+pub mod spirv;
+pub mod sync;
+pub mod upload;
 
 pub use vks::android_types;
 pub use vks::mir_types;
@@ -64,6 +75,9 @@ pub use vulkan_object::{
     FromNativeObject,
 };
 
+/// The Vulkan version, whose core API is provided by dacite.
+///
+/// Extensions, which were added after Vulkan 1.0.30, are not (yet) provided.
 pub const DACITE_API_VERSION: core::Version = core::Version {
     major: DACITE_API_VERSION_MAJOR,
     minor: DACITE_API_VERSION_MINOR,
@@ -71,11 +85,17 @@ pub const DACITE_API_VERSION: core::Version = core::Version {
 };
 
 pub const DACITE_API_VERSION_MAJOR: u32 = 1;
-pub const DACITE_API_VERSION_MINOR: u32 = 0;
-pub const DACITE_API_VERSION_PATCH: u32 = 30;
+pub const DACITE_API_VERSION_MINOR: u32 = 1;
+pub const DACITE_API_VERSION_PATCH: u32 = 70;
 
 pub const DACITE_API_VERSION_1_0: core::Version = core::Version {
     major: 1,
     minor: 0,
     patch: 0,
 };
+
+pub const DACITE_API_VERSION_1_1: core::Version = core::Version {
+    major: 1,
+    minor: 1,
+    patch: 0,
+};
@@ -0,0 +1,145 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core::DeviceMemory;
+use memory_allocator::ResourceKind;
+use std::sync::Mutex;
+
+/// A single `DeviceMemory` object, from which sub-ranges are handed out.
+#[derive(Debug)]
+pub(crate) struct Block {
+    memory: DeviceMemory,
+    dedicated: bool,
+    suballocations: Mutex<Vec<Suballocation>>,
+}
+
+/// A used range of a `Block`.
+///
+/// Suballocations are kept sorted by their offset. Free space is implicitly given by the gaps
+/// between them.
+#[derive(Debug, Copy, Clone)]
+struct Suballocation {
+    offset: u64,
+    size: u64,
+    kind: ResourceKind,
+}
+
+impl Block {
+    pub fn new(memory: DeviceMemory) -> Self {
+        Block {
+            memory: memory,
+            dedicated: false,
+            suballocations: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn new_dedicated(memory: DeviceMemory, kind: ResourceKind) -> Self {
+        let suballocation = Suballocation {
+            offset: 0,
+            size: memory.size(),
+            kind: kind,
+        };
+
+        Block {
+            memory: memory,
+            dedicated: true,
+            suballocations: Mutex::new(vec![suballocation]),
+        }
+    }
+
+    #[inline]
+    pub fn memory(&self) -> &DeviceMemory {
+        &self.memory
+    }
+
+    #[inline]
+    pub fn is_dedicated(&self) -> bool {
+        self.dedicated
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.suballocations.lock().unwrap().is_empty()
+    }
+
+    /// Reserves `size` bytes aligned to `alignment` and returns the offset of the range.
+    ///
+    /// Neighbouring linear and non-linear resources are never placed on the same page of size
+    /// `granularity` (see `bufferImageGranularity`).
+    pub fn allocate(&self, size: u64, alignment: u64, kind: ResourceKind, granularity: u64) -> Option<u64> {
+        let mut suballocations = self.suballocations.lock().unwrap();
+        let block_size = self.memory.size();
+
+        for index in 0..(suballocations.len() + 1) {
+            let prev = if index > 0 { Some(suballocations[index - 1]) } else { None };
+            let next = suballocations.get(index).cloned();
+
+            let gap_start = prev.map_or(0, |prev| prev.offset + prev.size);
+            let gap_end = next.map_or(block_size, |next| next.offset);
+
+            let mut offset = align_up(gap_start, alignment);
+            if let Some(prev) = prev {
+                if prev.kind.conflicts_with(kind) && same_page(prev.offset + prev.size - 1, offset, granularity) {
+                    offset = align_up(offset, granularity);
+                }
+            }
+
+            if (offset + size) > gap_end {
+                continue;
+            }
+
+            if let Some(next) = next {
+                if next.kind.conflicts_with(kind) && same_page(offset + size - 1, next.offset, granularity) {
+                    continue;
+                }
+            }
+
+            suballocations.insert(index, Suballocation {
+                offset: offset,
+                size: size,
+                kind: kind,
+            });
+
+            return Some(offset);
+        }
+
+        None
+    }
+
+    /// Returns the range starting at `offset` back to the block.
+    ///
+    /// Returns `true`, if the block is empty afterwards.
+    pub fn free(&self, offset: u64) -> bool {
+        let mut suballocations = self.suballocations.lock().unwrap();
+        if let Ok(index) = suballocations.binary_search_by_key(&offset, |s| s.offset) {
+            suballocations.remove(index);
+        }
+
+        suballocations.is_empty()
+    }
+}
+
+#[inline]
+fn align_up(value: u64, alignment: u64) -> u64 {
+    if alignment > 1 {
+        (value + alignment - 1) / alignment * alignment
+    }
+    else {
+        value
+    }
+}
+
+#[inline]
+fn same_page(a: u64, b: u64, page_size: u64) -> bool {
+    (page_size > 1) && (a / page_size == b / page_size)
+}
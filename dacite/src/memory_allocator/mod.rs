@@ -0,0 +1,297 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! Sub-allocation of device memory.
+//!
+//! Drivers limit the number of `DeviceMemory` objects, which can exist at the same time (see
+//! `max_memory_allocation_count`). A [`MemoryAllocator`](struct.MemoryAllocator.html) allocates
+//! large blocks of device memory and hands out aligned sub-ranges of them.
+
+mod block;
+
+use core::{self, AllocationError};
+use self::block::Block;
+use std::sync::{Arc, Mutex};
+
+/// Default size of the blocks allocated by a `MemoryAllocator`.
+pub const DEFAULT_BLOCK_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Heaps of at most this size use smaller blocks of one eighth of the heap size.
+const SMALL_HEAP_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// The kind of resource, which is bound to an allocation.
+///
+/// Linear and non-linear resources must be separated by `buffer_image_granularity`, if they are
+/// placed next to each other in the same `DeviceMemory`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    /// A `Buffer`.
+    Buffer,
+
+    /// An `Image` created with `ImageTiling::Linear`.
+    LinearImage,
+
+    /// An `Image` created with `ImageTiling::Optimal`.
+    OptimalImage,
+}
+
+impl ResourceKind {
+    /// Returns the `ResourceKind` of images with the given tiling.
+    pub fn from_image_tiling(tiling: core::ImageTiling) -> Self {
+        match tiling {
+            core::ImageTiling::Linear => ResourceKind::LinearImage,
+            _ => ResourceKind::OptimalImage,
+        }
+    }
+
+    #[inline]
+    fn is_linear(&self) -> bool {
+        *self != ResourceKind::OptimalImage
+    }
+
+    #[inline]
+    fn conflicts_with(&self, other: ResourceKind) -> bool {
+        self.is_linear() != other.is_linear()
+    }
+}
+
+/// A general-purpose device memory sub-allocator.
+///
+/// Blocks are allocated per memory type. Requests larger than half a block get their own
+/// `DeviceMemory`. Empty blocks are freed, except for the last one of each memory type.
+///
+/// `MemoryAllocator` is cheap to clone and can be shared between threads.
+#[derive(Debug, Clone)]
+pub struct MemoryAllocator(Arc<Inner>);
+
+impl MemoryAllocator {
+    /// Creates a new `MemoryAllocator`, which uses the default block size.
+    pub fn new(device: &core::Device, physical_device: &core::PhysicalDevice) -> Self {
+        MemoryAllocator::with_block_size(device, physical_device, DEFAULT_BLOCK_SIZE)
+    }
+
+    /// Creates a new `MemoryAllocator` with a custom block size.
+    pub fn with_block_size(device: &core::Device, physical_device: &core::PhysicalDevice, block_size: u64) -> Self {
+        let memory_properties = physical_device.get_memory_properties();
+        let limits = physical_device.get_properties().limits;
+        let pools = memory_properties.memory_types.iter().map(|_| Mutex::new(Vec::new())).collect();
+
+        MemoryAllocator(Arc::new(Inner {
+            device: device.clone(),
+            memory_properties: memory_properties,
+            buffer_image_granularity: limits.buffer_image_granularity,
+            block_size: block_size,
+            pools: pools,
+        }))
+    }
+
+    /// Returns the memory properties of the physical device.
+    #[inline]
+    pub fn memory_properties(&self) -> &core::PhysicalDeviceMemoryProperties {
+        &self.0.memory_properties
+    }
+
+    /// Returns the number of `DeviceMemory` objects currently owned by this allocator.
+    ///
+    /// Allocations with their own `DeviceMemory` are not included.
+    pub fn block_count(&self) -> usize {
+        self.0.pools.iter().map(|pool| pool.lock().unwrap().len()).sum()
+    }
+
+    /// Allocates memory for a resource with the given memory requirements.
+    ///
+    /// The memory type is chosen as described in
+    /// [`PhysicalDeviceMemoryProperties::find_memory_type`](../core/struct.PhysicalDeviceMemoryProperties.html#method.find_memory_type).
+    /// If a memory type runs out of memory, the next best one is tried. If no other memory type is
+    /// suitable, the out of memory error is returned.
+    ///
+    /// Returns `AllocationError::ZeroSize`, if `requirements.size` is 0.
+    pub fn allocate(&self, requirements: &core::MemoryRequirements, kind: ResourceKind, required: core::MemoryPropertyFlags, preferred: core::MemoryPropertyFlags) -> Result<Allocation, AllocationError> {
+        if requirements.size == 0 {
+            return Err(AllocationError::ZeroSize);
+        }
+
+        let mut memory_type_bits = requirements.memory_type_bits;
+        let mut out_of_memory = None;
+
+        loop {
+            let memory_type_index = match self.0.memory_properties.find_memory_type(memory_type_bits, required, preferred) {
+                Some(memory_type_index) => memory_type_index,
+                None => return Err(out_of_memory.map_or(AllocationError::NoSuitableMemoryType, From::from)),
+            };
+
+            match self.allocate_from_type(memory_type_index, requirements, kind) {
+                Err(e @ core::Error::OutOfDeviceMemory) | Err(e @ core::Error::OutOfHostMemory) => {
+                    memory_type_bits &= !(1 << memory_type_index);
+                    out_of_memory = Some(e);
+                }
+
+                res => return res.map_err(From::from),
+            }
+        }
+    }
+
+    /// Allocates memory for `buffer` and binds it.
+    ///
+    /// The returned `Allocation` must be kept alive for as long as `buffer` is in use.
+    pub fn allocate_for_buffer(&self, buffer: &core::Buffer, required: core::MemoryPropertyFlags, preferred: core::MemoryPropertyFlags) -> Result<Allocation, AllocationError> {
+        let requirements = buffer.get_memory_requirements();
+        let allocation = self.allocate(&requirements, ResourceKind::Buffer, required, preferred)?;
+        buffer.bind_memory(allocation.memory().clone(), allocation.offset())?;
+        Ok(allocation)
+    }
+
+    /// Allocates memory for `image` and binds it.
+    ///
+    /// `tiling` must be the tiling `image` was created with. The returned `Allocation` must be kept
+    /// alive for as long as `image` is in use.
+    pub fn allocate_for_image(&self, image: &core::Image, tiling: core::ImageTiling, required: core::MemoryPropertyFlags, preferred: core::MemoryPropertyFlags) -> Result<Allocation, AllocationError> {
+        let requirements = image.get_memory_requirements();
+        let allocation = self.allocate(&requirements, ResourceKind::from_image_tiling(tiling), required, preferred)?;
+        image.bind_memory(allocation.memory().clone(), allocation.offset())?;
+        Ok(allocation)
+    }
+
+    fn allocate_from_type(&self, memory_type_index: u32, requirements: &core::MemoryRequirements, kind: ResourceKind) -> Result<Allocation, core::Error> {
+        let block_size = self.block_size(memory_type_index);
+        if requirements.size > block_size / 2 {
+            let memory = self.allocate_device_memory(memory_type_index, requirements.size)?;
+            let block = Arc::new(Block::new_dedicated(memory, kind));
+            return Ok(Allocation::new(self.clone(), block, memory_type_index, 0, requirements.size));
+        }
+
+        let mut pool = self.0.pools[memory_type_index as usize].lock().unwrap();
+        let granularity = self.0.buffer_image_granularity;
+
+        for block in pool.iter() {
+            if let Some(offset) = block.allocate(requirements.size, requirements.alignment, kind, granularity) {
+                return Ok(Allocation::new(self.clone(), block.clone(), memory_type_index, offset, requirements.size));
+            }
+        }
+
+        let memory = self.allocate_device_memory(memory_type_index, block_size)?;
+        let block = Arc::new(Block::new(memory));
+        let offset = block.allocate(requirements.size, requirements.alignment, kind, granularity).unwrap();
+        pool.push(block.clone());
+
+        Ok(Allocation::new(self.clone(), block, memory_type_index, offset, requirements.size))
+    }
+
+    fn allocate_device_memory(&self, memory_type_index: u32, size: u64) -> Result<core::DeviceMemory, core::Error> {
+        let allocate_info = core::MemoryAllocateInfo {
+            allocation_size: size,
+            memory_type_index: memory_type_index,
+            chain: None,
+        };
+
+        self.0.device.allocate_memory(&allocate_info, None)
+    }
+
+    fn block_size(&self, memory_type_index: u32) -> u64 {
+        let heap_index = self.0.memory_properties.memory_types[memory_type_index as usize].heap_index;
+        let heap_size = self.0.memory_properties.memory_heaps[heap_index as usize].size;
+
+        if heap_size <= SMALL_HEAP_SIZE {
+            ::std::cmp::min(self.0.block_size, heap_size / 8)
+        }
+        else {
+            self.0.block_size
+        }
+    }
+
+    fn free(&self, memory_type_index: u32, block: &Arc<Block>, offset: u64) {
+        if block.is_dedicated() || !block.free(offset) {
+            return;
+        }
+
+        let mut pool = self.0.pools[memory_type_index as usize].lock().unwrap();
+        let empty_blocks = pool.iter().filter(|b| b.is_empty()).count();
+        if empty_blocks > 1 {
+            // Check again under the pool lock, because another thread might have allocated from
+            // the block in the meantime.
+            if let Some(index) = pool.iter().position(|b| Arc::ptr_eq(b, block) && b.is_empty()) {
+                pool.remove(index);
+            }
+        }
+    }
+}
+
+/// A range of device memory handed out by a `MemoryAllocator`.
+///
+/// The range is returned to the allocator, when the `Allocation` is dropped.
+#[derive(Debug)]
+pub struct Allocation {
+    allocator: MemoryAllocator,
+    block: Arc<Block>,
+    memory_type_index: u32,
+    offset: u64,
+    size: u64,
+}
+
+impl Drop for Allocation {
+    fn drop(&mut self) {
+        self.allocator.free(self.memory_type_index, &self.block, self.offset);
+    }
+}
+
+impl Allocation {
+    fn new(allocator: MemoryAllocator, block: Arc<Block>, memory_type_index: u32, offset: u64, size: u64) -> Self {
+        Allocation {
+            allocator: allocator,
+            block: block,
+            memory_type_index: memory_type_index,
+            offset: offset,
+            size: size,
+        }
+    }
+
+    /// The `DeviceMemory` this allocation is part of.
+    #[inline]
+    pub fn memory(&self) -> &core::DeviceMemory {
+        self.block.memory()
+    }
+
+    /// The offset in bytes of this allocation inside `memory()`.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The size in bytes of this allocation.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The index of the memory type of `memory()`.
+    #[inline]
+    pub fn memory_type_index(&self) -> u32 {
+        self.memory_type_index
+    }
+
+    /// Returns `true`, if this allocation has its own `DeviceMemory`.
+    #[inline]
+    pub fn is_dedicated(&self) -> bool {
+        self.block.is_dedicated()
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    device: core::Device,
+    memory_properties: core::PhysicalDeviceMemoryProperties,
+    buffer_image_granularity: u64,
+    block_size: u64,
+    pools: Vec<Mutex<Vec<Arc<Block>>>>,
+}
@@ -0,0 +1,319 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! Assignment of queue families and queues to the roles, in which an application uses them.
+//!
+//! A [`QueuePlanner`](struct.QueuePlanner.html) collects the number of queues requested for every
+//! [`QueueRole`](enum.QueueRole.html) and picks a queue family for each role from the
+//! `QueueFamilyProperties` of a physical device. Dedicated families are preferred for compute and
+//! transfer queues, so that work on them can run concurrently to graphics work. Presentation
+//! prefers the graphics family, so that no ownership transfers are needed for swapchain images.
+//!
+//! The resulting [`QueuePlan`](struct.QueuePlan.html) provides the `DeviceQueueCreateInfo`s for
+//! `DeviceCreateInfo` and, after the device has been created, the `Queue` objects of every role.
+
+use core::{self, Device, PhysicalDevice, Queue};
+use khr_surface::SurfaceKhr;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+/// The role, in which a queue is used.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum QueueRole {
+    /// Queues supporting graphics operations.
+    Graphics,
+
+    /// Queues supporting compute operations. Families without graphics support are preferred.
+    Compute,
+
+    /// Queues supporting transfer operations. Families without graphics and compute support are
+    /// preferred.
+    Transfer,
+
+    /// Queues supporting presentation to the surface of the
+    /// [`QueuePlanner`](struct.QueuePlanner.html). The graphics family is preferred.
+    Present,
+}
+
+impl fmt::Display for QueueRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            QueueRole::Graphics => write!(f, "graphics"),
+            QueueRole::Compute => write!(f, "compute"),
+            QueueRole::Transfer => write!(f, "transfer"),
+            QueueRole::Present => write!(f, "present"),
+        }
+    }
+}
+
+/// Error type for [`QueuePlanner::plan`](struct.QueuePlanner.html#method.plan).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QueuePlanError {
+    /// No queue family supports the role.
+    NoSuitableQueueFamily(QueueRole),
+
+    /// Present queues were requested, but no surface was set.
+    NoSurface,
+
+    /// A Vulkan function returned an error.
+    VulkanError(core::Error),
+}
+
+impl fmt::Display for QueuePlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            QueuePlanError::NoSuitableQueueFamily(role) => write!(f, "no queue family supports {} queues", role),
+            QueuePlanError::NoSurface => write!(f, "present queues require a surface"),
+            QueuePlanError::VulkanError(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for QueuePlanError {
+    fn description(&self) -> &str {
+        match *self {
+            QueuePlanError::NoSuitableQueueFamily(_) => "NoSuitableQueueFamily",
+            QueuePlanError::NoSurface => "NoSurface",
+            QueuePlanError::VulkanError(ref e) => e.description(),
+        }
+    }
+}
+
+impl From<core::Error> for QueuePlanError {
+    fn from(e: core::Error) -> Self {
+        QueuePlanError::VulkanError(e)
+    }
+}
+
+/// Plans the queues of a device by their roles.
+///
+/// Every role is served by a single queue family. Within a family, each requested queue gets its
+/// own queue index as long as the family has queues left. After that, queue indices are shared
+/// between requests in a round-robin fashion.
+#[derive(Debug, Clone, Default)]
+pub struct QueuePlanner {
+    requests: Vec<(QueueRole, u32)>,
+    surface: Option<SurfaceKhr>,
+}
+
+impl QueuePlanner {
+    /// Creates a new planner without any requested queues.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Requests `count` queues for `role`.
+    ///
+    /// Requests are served in the order, in which they were made. Requesting the same role
+    /// multiple times adds up the counts.
+    pub fn request(mut self, role: QueueRole, count: u32) -> Self {
+        if let Some(request) = self.requests.iter_mut().find(|request| request.0 == role) {
+            request.1 += count;
+            return self;
+        }
+
+        self.requests.push((role, count));
+        self
+    }
+
+    /// Sets the surface, which present queues must support.
+    pub fn surface(mut self, surface: SurfaceKhr) -> Self {
+        self.surface = Some(surface);
+        self
+    }
+
+    /// Assigns a queue family and queue indices to all requested queues of `physical_device`.
+    pub fn plan(&self, physical_device: &PhysicalDevice) -> Result<QueuePlan, QueuePlanError> {
+        let queue_family_properties: Vec<core::QueueFamilyProperties> = physical_device.get_queue_family_properties();
+
+        let mut families = HashMap::new();
+        for &(role, count) in &self.requests {
+            if count == 0 {
+                continue;
+            }
+
+            let family = match role {
+                QueueRole::Graphics => find_family(&queue_family_properties, core::QueueFlags::GRAPHICS, &[core::QueueFlags::empty()]),
+                QueueRole::Compute => find_family(&queue_family_properties, core::QueueFlags::COMPUTE, &[core::QueueFlags::GRAPHICS, core::QueueFlags::empty()]),
+                QueueRole::Transfer => find_transfer_family(&queue_family_properties),
+                QueueRole::Present => continue,
+            };
+
+            match family {
+                Some(family) => { families.insert(role, family); }
+                None => return Err(QueuePlanError::NoSuitableQueueFamily(role)),
+            }
+        }
+
+        // Present queues are resolved last, because they prefer the graphics family and then any
+        // other family, which is already in use.
+        if self.requests.iter().any(|&(role, count)| (role == QueueRole::Present) && (count > 0)) {
+            let surface = match self.surface {
+                Some(ref surface) => surface,
+                None => return Err(QueuePlanError::NoSurface),
+            };
+
+            let mut preferred = Vec::new();
+            if let Some(&family) = families.get(&QueueRole::Graphics) {
+                preferred.push(family);
+            }
+            for &(role, _) in &self.requests {
+                if let Some(&family) = families.get(&role) {
+                    preferred.push(family);
+                }
+            }
+
+            match find_present_family(physical_device, surface, &queue_family_properties, &preferred)? {
+                Some(family) => { families.insert(QueueRole::Present, family); }
+                None => return Err(QueuePlanError::NoSuitableQueueFamily(QueueRole::Present)),
+            }
+        }
+
+        let mut queue_counts: HashMap<u32, u32> = HashMap::new();
+        let mut next_indices: HashMap<u32, u32> = HashMap::new();
+        let mut family_order = Vec::new();
+        let mut assignments = HashMap::new();
+
+        for &(role, count) in &self.requests {
+            let family = match families.get(&role) {
+                Some(&family) => family,
+                None => continue,
+            };
+
+            let available = queue_family_properties[family as usize].queue_count;
+            let mut queues = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let next_index = next_indices.entry(family).or_insert(0);
+                let index = *next_index % available;
+                *next_index += 1;
+
+                let queue_count = queue_counts.entry(family).or_insert_with(|| {
+                    family_order.push(family);
+                    0
+                });
+                if index >= *queue_count {
+                    *queue_count = index + 1;
+                }
+
+                queues.push((family, index));
+            }
+
+            assignments.insert(role, queues);
+        }
+
+        let queue_create_infos = family_order.into_iter().map(|family| {
+            core::DeviceQueueCreateInfo::builder()
+                .queue_family_index(family)
+                .queue_priorities(vec![1.0; queue_counts[&family] as usize])
+                .build()
+        }).collect();
+
+        Ok(QueuePlan {
+            queue_create_infos: queue_create_infos,
+            assignments: assignments,
+        })
+    }
+}
+
+/// The queue families and queue indices assigned to the roles of a
+/// [`QueuePlanner`](struct.QueuePlanner.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuePlan {
+    queue_create_infos: Vec<core::DeviceQueueCreateInfo>,
+    assignments: HashMap<QueueRole, Vec<(u32, u32)>>,
+}
+
+impl QueuePlan {
+    /// Returns the `DeviceQueueCreateInfo`s, which must be used to create the device.
+    ///
+    /// There is one `DeviceQueueCreateInfo` for every used queue family.
+    #[inline]
+    pub fn queue_create_infos(&self) -> &[core::DeviceQueueCreateInfo] {
+        &self.queue_create_infos
+    }
+
+    /// Returns the queue family and queue index of all queues requested for `role`.
+    ///
+    /// The slice is empty, if no queues were requested for `role`.
+    pub fn queues_for(&self, role: QueueRole) -> &[(u32, u32)] {
+        match self.assignments.get(&role) {
+            Some(queues) => queues,
+            None => &[],
+        }
+    }
+
+    /// Returns the queue family assigned to `role`, if any queues were requested for it.
+    pub fn queue_family_index(&self, role: QueueRole) -> Option<u32> {
+        self.queues_for(role).first().map(|&(family, _)| family)
+    }
+
+    /// Returns the queues of all roles.
+    ///
+    /// `device` must have been created with the `DeviceQueueCreateInfo`s of this plan.
+    pub fn get_queues(&self, device: &Device) -> HashMap<QueueRole, Vec<Queue>> {
+        self.assignments
+            .iter()
+            .map(|(&role, queues)| {
+                let queues = queues.iter().map(|&(family, index)| device.get_queue(family, index)).collect();
+                (role, queues)
+            })
+            .collect()
+    }
+}
+
+/// Finds a queue family, which supports `flags`.
+///
+/// Families are searched once for every entry of `excluded`, skipping families, which support any
+/// of the excluded flags.
+fn find_family(queue_family_properties: &[core::QueueFamilyProperties], flags: core::QueueFlags, excluded: &[core::QueueFlags]) -> Option<u32> {
+    for &excluded in excluded {
+        let family = queue_family_properties.iter().position(|properties| {
+            (properties.queue_count > 0) && properties.queue_flags.contains(flags) && !properties.queue_flags.intersects(excluded)
+        });
+
+        if let Some(family) = family {
+            return Some(family as u32);
+        }
+    }
+
+    None
+}
+
+/// Finds a queue family for transfer queues.
+///
+/// Graphics and compute families implicitly support transfer operations, but dedicated transfer
+/// families are preferred.
+fn find_transfer_family(queue_family_properties: &[core::QueueFamilyProperties]) -> Option<u32> {
+    find_family(queue_family_properties, core::QueueFlags::TRANSFER, &[core::QueueFlags::GRAPHICS | core::QueueFlags::COMPUTE, core::QueueFlags::GRAPHICS, core::QueueFlags::empty()])
+        .or_else(|| find_family(queue_family_properties, core::QueueFlags::COMPUTE, &[core::QueueFlags::empty()]))
+        .or_else(|| find_family(queue_family_properties, core::QueueFlags::GRAPHICS, &[core::QueueFlags::empty()]))
+}
+
+fn find_present_family(physical_device: &PhysicalDevice, surface: &SurfaceKhr, queue_family_properties: &[core::QueueFamilyProperties], preferred: &[u32]) -> Result<Option<u32>, core::Error> {
+    for &family in preferred {
+        if physical_device.get_surface_support_khr(family, surface)? {
+            return Ok(Some(family));
+        }
+    }
+
+    for (family, properties) in queue_family_properties.iter().enumerate() {
+        let family = family as u32;
+        if (properties.queue_count > 0) && !preferred.contains(&family) && physical_device.get_surface_support_khr(family, surface)? {
+            return Ok(Some(family));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,159 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core;
+
+/// A barrier between two accesses of a resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Transition {
+    pub src_stage_mask: core::PipelineStageFlags,
+    pub src_access_mask: core::AccessFlags,
+    pub dst_stage_mask: core::PipelineStageFlags,
+    pub dst_access_mask: core::AccessFlags,
+    pub old_layout: core::ImageLayout,
+    pub new_layout: core::ImageLayout,
+}
+
+/// Tracks accesses of a single resource and computes the barriers between them.
+///
+/// Buffers are tracked with `ImageLayout::Undefined`, which never changes.
+#[derive(Debug, Copy, Clone)]
+pub struct ResourceState {
+    layout: core::ImageLayout,
+
+    /// Stages and accesses of the last write (or layout transition).
+    write_stage_mask: core::PipelineStageFlags,
+    write_access_mask: core::AccessFlags,
+
+    /// Stages, which read the resource since the last write.
+    read_stage_mask: core::PipelineStageFlags,
+
+    /// Stages and accesses, to which the last write has been made visible.
+    visible_stage_mask: core::PipelineStageFlags,
+    visible_access_mask: core::AccessFlags,
+}
+
+impl ResourceState {
+    /// Creates a new state from the last access before the render graph.
+    pub fn new(stage_mask: core::PipelineStageFlags, access_mask: core::AccessFlags, layout: core::ImageLayout) -> Self {
+        let write_access_mask = write_access(access_mask);
+        let (write_stage_mask, read_stage_mask) = if write_access_mask.is_empty() {
+            (core::PipelineStageFlags::empty(), stage_mask)
+        }
+        else {
+            (stage_mask, core::PipelineStageFlags::empty())
+        };
+
+        ResourceState {
+            layout: layout,
+            write_stage_mask: write_stage_mask,
+            write_access_mask: write_access_mask,
+            read_stage_mask: read_stage_mask,
+            visible_stage_mask: core::PipelineStageFlags::empty(),
+            visible_access_mask: core::AccessFlags::empty(),
+        }
+    }
+
+    /// Records an access and returns the barrier, which must precede it.
+    ///
+    /// If `discard` is `true`, the previous contents are not preserved.
+    pub fn access(&mut self, stage_mask: core::PipelineStageFlags, access_mask: core::AccessFlags, layout: core::ImageLayout, write: bool, discard: bool) -> Option<Transition> {
+        let layout_change = layout != self.layout;
+
+        if write || layout_change {
+            let src_stage_mask = self.write_stage_mask | self.read_stage_mask;
+            let transition = if layout_change || !src_stage_mask.is_empty() {
+                Some(Transition {
+                    src_stage_mask: if src_stage_mask.is_empty() { core::PipelineStageFlags::TOP_OF_PIPE } else { src_stage_mask },
+                    src_access_mask: self.write_access_mask,
+                    dst_stage_mask: stage_mask,
+                    dst_access_mask: access_mask,
+                    old_layout: if discard { core::ImageLayout::Undefined } else { self.layout },
+                    new_layout: layout,
+                })
+            }
+            else {
+                None
+            };
+
+            self.layout = layout;
+            self.write_stage_mask = stage_mask;
+            if write {
+                self.write_access_mask = write_access(access_mask);
+                self.read_stage_mask = core::PipelineStageFlags::empty();
+                self.visible_stage_mask = core::PipelineStageFlags::empty();
+                self.visible_access_mask = core::AccessFlags::empty();
+            }
+            else {
+                // A layout transition for a read. The transition is made visible to this access.
+                self.write_access_mask = core::AccessFlags::empty();
+                self.read_stage_mask = stage_mask;
+                self.visible_stage_mask = stage_mask;
+                self.visible_access_mask = access_mask;
+            }
+
+            return transition;
+        }
+
+        let visible = self.visible_stage_mask.contains(stage_mask) && self.visible_access_mask.contains(access_mask);
+        let transition = if !self.write_stage_mask.is_empty() && !visible {
+            self.visible_stage_mask |= stage_mask;
+            self.visible_access_mask |= access_mask;
+
+            Some(Transition {
+                src_stage_mask: self.write_stage_mask,
+                src_access_mask: self.write_access_mask,
+                dst_stage_mask: stage_mask,
+                dst_access_mask: access_mask,
+                old_layout: self.layout,
+                new_layout: layout,
+            })
+        }
+        else {
+            None
+        };
+
+        self.read_stage_mask |= stage_mask;
+        transition
+    }
+
+    /// Returns the barrier, which transitions the resource to `layout` at the end of the render
+    /// graph.
+    pub fn finish(&self, layout: core::ImageLayout) -> Option<Transition> {
+        if layout == self.layout {
+            return None;
+        }
+
+        let src_stage_mask = self.write_stage_mask | self.read_stage_mask;
+        Some(Transition {
+            src_stage_mask: if src_stage_mask.is_empty() { core::PipelineStageFlags::TOP_OF_PIPE } else { src_stage_mask },
+            src_access_mask: self.write_access_mask,
+            dst_stage_mask: core::PipelineStageFlags::BOTTOM_OF_PIPE,
+            dst_access_mask: core::AccessFlags::empty(),
+            old_layout: self.layout,
+            new_layout: layout,
+        })
+    }
+}
+
+fn write_access(access_mask: core::AccessFlags) -> core::AccessFlags {
+    access_mask & (
+        core::AccessFlags::SHADER_WRITE |
+        core::AccessFlags::COLOR_ATTACHMENT_WRITE |
+        core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE |
+        core::AccessFlags::TRANSFER_WRITE |
+        core::AccessFlags::HOST_WRITE |
+        core::AccessFlags::MEMORY_WRITE
+    )
+}
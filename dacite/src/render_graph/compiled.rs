@@ -0,0 +1,713 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core::{self, Buffer, CommandBuffer, Framebuffer, Image, ImageView, RenderPass};
+use std::fmt;
+use super::barriers::{ResourceState, Transition};
+use super::{Attachment, AttachmentLoad, BufferId, BufferUse, ExecuteFn, ImageId, ImageResource, ImageUse, PassId, RenderGraph, RenderGraphError};
+
+/// Barriers, which are recorded with a single `pipeline_barrier` command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassBarriers {
+    pub src_stage_mask: core::PipelineStageFlags,
+    pub dst_stage_mask: core::PipelineStageFlags,
+    pub buffer_memory_barriers: Vec<core::BufferMemoryBarrier>,
+    pub image_memory_barriers: Vec<core::ImageMemoryBarrier>,
+}
+
+impl PassBarriers {
+    fn new() -> Self {
+        PassBarriers {
+            src_stage_mask: core::PipelineStageFlags::empty(),
+            dst_stage_mask: core::PipelineStageFlags::empty(),
+            buffer_memory_barriers: Vec::new(),
+            image_memory_barriers: Vec::new(),
+        }
+    }
+
+    fn add_image(&mut self, transition: Transition, image: Image, format: core::Format) {
+        self.src_stage_mask |= transition.src_stage_mask;
+        self.dst_stage_mask |= transition.dst_stage_mask;
+        self.image_memory_barriers.push(core::ImageMemoryBarrier {
+            src_access_mask: transition.src_access_mask,
+            dst_access_mask: transition.dst_access_mask,
+            old_layout: transition.old_layout,
+            new_layout: transition.new_layout,
+            src_queue_family_index: core::QueueFamilyIndex::Ignored,
+            dst_queue_family_index: core::QueueFamilyIndex::Ignored,
+            image: image,
+            subresource_range: core::ImageSubresourceRange::full(format),
+            chain: None,
+        });
+    }
+
+    fn add_buffer(&mut self, transition: Transition, buffer: Buffer) {
+        self.src_stage_mask |= transition.src_stage_mask;
+        self.dst_stage_mask |= transition.dst_stage_mask;
+        self.buffer_memory_barriers.push(core::BufferMemoryBarrier {
+            src_access_mask: transition.src_access_mask,
+            dst_access_mask: transition.dst_access_mask,
+            src_queue_family_index: core::QueueFamilyIndex::Ignored,
+            dst_queue_family_index: core::QueueFamilyIndex::Ignored,
+            buffer: buffer,
+            offset: 0,
+            size: core::OptionalDeviceSize::WholeSize,
+            chain: None,
+        });
+    }
+
+    fn into_option(self) -> Option<Self> {
+        if self.buffer_memory_barriers.is_empty() && self.image_memory_barriers.is_empty() {
+            None
+        }
+        else {
+            Some(self)
+        }
+    }
+
+    fn record(&self, command_buffer: &CommandBuffer) {
+        let buffer_memory_barriers = if !self.buffer_memory_barriers.is_empty() { Some(&self.buffer_memory_barriers[..]) } else { None };
+        let image_memory_barriers = if !self.image_memory_barriers.is_empty() { Some(&self.image_memory_barriers[..]) } else { None };
+        command_buffer.pipeline_barrier(self.src_stage_mask, self.dst_stage_mask, core::DependencyFlags::empty(), None, buffer_memory_barriers, image_memory_barriers);
+    }
+}
+
+/// Gives the function of a pass access to the command buffer and the resources of the graph.
+#[derive(Debug)]
+pub struct PassContext<'a> {
+    command_buffer: &'a CommandBuffer,
+    render_pass: Option<&'a RenderPass>,
+    render_area: Option<core::Rect2D>,
+    images: &'a [Option<Image>],
+    views: &'a [Option<ImageView>],
+    buffers: &'a [Buffer],
+}
+
+impl<'a> PassContext<'a> {
+    /// Returns the command buffer, into which the pass records its commands.
+    #[inline]
+    pub fn command_buffer(&self) -> &CommandBuffer {
+        self.command_buffer
+    }
+
+    /// Returns the render pass, which is active while the pass is executed.
+    ///
+    /// Only passes with attachments are executed inside of a render pass.
+    #[inline]
+    pub fn render_pass(&self) -> Option<&RenderPass> {
+        self.render_pass
+    }
+
+    /// Returns the area covered by the attachments of the pass.
+    #[inline]
+    pub fn render_area(&self) -> Option<core::Rect2D> {
+        self.render_area
+    }
+
+    /// Returns the `Image` backing `image`.
+    ///
+    /// `None` is returned for transient images, which are not used by any pass.
+    #[inline]
+    pub fn image(&self, image: ImageId) -> Option<&Image> {
+        self.images[image.0].as_ref()
+    }
+
+    /// Returns the `ImageView`, which is used for `image` in framebuffers.
+    ///
+    /// `None` is returned for images, which are not used as an attachment by any pass.
+    #[inline]
+    pub fn image_view(&self, image: ImageId) -> Option<&ImageView> {
+        self.views[image.0].as_ref()
+    }
+
+    /// Returns the imported `Buffer` `buffer`.
+    #[inline]
+    pub fn buffer(&self, buffer: BufferId) -> &Buffer {
+        &self.buffers[buffer.0]
+    }
+}
+
+#[derive(Debug)]
+struct RenderPassInfo {
+    render_pass: RenderPass,
+    framebuffer: Framebuffer,
+    render_area: core::Rect2D,
+    clear_values: Vec<core::ClearValue>,
+}
+
+struct CompiledPass {
+    id: PassId,
+    barriers: Option<PassBarriers>,
+    render_pass: Option<RenderPassInfo>,
+    execute: ExecuteFn,
+}
+
+impl fmt::Debug for CompiledPass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CompiledPass")
+            .field("id", &self.id)
+            .field("barriers", &self.barriers)
+            .field("render_pass", &self.render_pass)
+            .finish()
+    }
+}
+
+/// A render graph, which is ready to be recorded into command buffers.
+///
+/// Created by [`RenderGraph::compile`](struct.RenderGraph.html#method.compile). Transient images
+/// and all render passes and framebuffers are owned by the compiled graph.
+#[derive(Debug)]
+pub struct CompiledRenderGraph {
+    passes: Vec<CompiledPass>,
+    final_barriers: Option<PassBarriers>,
+    images: Vec<Option<Image>>,
+    buffers: Vec<Buffer>,
+    transient_image_count: usize,
+    views: Vec<Option<ImageView>>,
+}
+
+impl CompiledRenderGraph {
+    /// Returns the passes in the order, in which they are recorded.
+    pub fn pass_order(&self) -> Vec<PassId> {
+        self.passes.iter().map(|pass| pass.id).collect()
+    }
+
+    /// Returns the barriers recorded before `pass`, if any.
+    pub fn barriers(&self, pass: PassId) -> Option<&PassBarriers> {
+        self.passes.iter().find(|p| p.id == pass).and_then(|p| p.barriers.as_ref())
+    }
+
+    /// Returns the barriers recorded after the last pass, which transition imported images to
+    /// their final layouts.
+    pub fn final_barriers(&self) -> Option<&PassBarriers> {
+        self.final_barriers.as_ref()
+    }
+
+    /// Returns the render pass created for `pass`.
+    ///
+    /// Pipelines used by the pass must be compatible with this render pass.
+    pub fn render_pass(&self, pass: PassId) -> Option<&RenderPass> {
+        self.passes.iter().find(|p| p.id == pass).and_then(|p| p.render_pass.as_ref()).map(|r| &r.render_pass)
+    }
+
+    /// Returns the `Image` backing `image`.
+    ///
+    /// `None` is returned for transient images, which are not used by any pass.
+    pub fn image(&self, image: ImageId) -> Option<&Image> {
+        self.images[image.0].as_ref()
+    }
+
+    /// Returns the `ImageView`, which is used for `image` in framebuffers.
+    ///
+    /// `None` is returned for images, which are not used as an attachment by any pass.
+    pub fn image_view(&self, image: ImageId) -> Option<&ImageView> {
+        self.views[image.0].as_ref()
+    }
+
+    /// Returns the imported `Buffer` `buffer`.
+    pub fn buffer(&self, buffer: BufferId) -> &Buffer {
+        &self.buffers[buffer.0]
+    }
+
+    /// Returns the number of images created by the render graph.
+    ///
+    /// This can be less than the number of transient images declared, because images with
+    /// non-overlapping lifetimes are shared.
+    pub fn transient_image_count(&self) -> usize {
+        self.transient_image_count
+    }
+
+    /// Records all passes into `command_buffer`, which must be in the recording state.
+    pub fn record(&mut self, command_buffer: &CommandBuffer) {
+        for pass in &mut self.passes {
+            if let Some(ref barriers) = pass.barriers {
+                barriers.record(command_buffer);
+            }
+
+            if let Some(ref render_pass) = pass.render_pass {
+                command_buffer.begin_render_pass(&core::RenderPassBeginInfo {
+                    render_pass: render_pass.render_pass.clone(),
+                    framebuffer: render_pass.framebuffer.clone(),
+                    render_area: render_pass.render_area,
+                    clear_values: render_pass.clear_values.clone(),
+                    chain: None,
+                }, core::SubpassContents::Inline);
+            }
+
+            (pass.execute)(&PassContext {
+                command_buffer: command_buffer,
+                render_pass: pass.render_pass.as_ref().map(|r| &r.render_pass),
+                render_area: pass.render_pass.as_ref().map(|r| r.render_area),
+                images: &self.images,
+                views: &self.views,
+                buffers: &self.buffers,
+            });
+
+            if pass.render_pass.is_some() {
+                command_buffer.end_render_pass();
+            }
+        }
+
+        if let Some(ref barriers) = self.final_barriers {
+            barriers.record(command_buffer);
+        }
+    }
+}
+
+/// Merges multiple uses of the same image by a pass.
+fn merge_image_uses(name: &str, uses: &[ImageUse]) -> Result<Vec<ImageUse>, RenderGraphError> {
+    let mut merged: Vec<ImageUse> = Vec::with_capacity(uses.len());
+
+    for image_use in uses {
+        if let Some(existing) = merged.iter_mut().find(|u| u.image == image_use.image) {
+            if existing.access.layout != image_use.access.layout {
+                return Err(RenderGraphError::ConflictingImageLayouts {
+                    pass: name.to_owned(),
+                    image: image_use.image,
+                });
+            }
+
+            existing.access.stage_mask |= image_use.access.stage_mask;
+            existing.access.access_mask |= image_use.access.access_mask;
+            existing.write |= image_use.write;
+            existing.discard &= image_use.discard;
+            continue;
+        }
+
+        merged.push(*image_use);
+    }
+
+    Ok(merged)
+}
+
+fn merge_buffer_uses(uses: &[BufferUse]) -> Vec<BufferUse> {
+    let mut merged: Vec<BufferUse> = Vec::with_capacity(uses.len());
+
+    for buffer_use in uses {
+        if let Some(existing) = merged.iter_mut().find(|u| u.buffer == buffer_use.buffer) {
+            existing.access.stage_mask |= buffer_use.access.stage_mask;
+            existing.access.access_mask |= buffer_use.access.access_mask;
+            existing.write |= buffer_use.write;
+            continue;
+        }
+
+        merged.push(*buffer_use);
+    }
+
+    merged
+}
+
+/// Tracks the passes, which last wrote and read a resource, in declaration order.
+#[derive(Debug, Clone, Default)]
+struct Dependencies {
+    last_writer: Option<usize>,
+    readers: Vec<usize>,
+}
+
+impl Dependencies {
+    fn add(&mut self, pass: usize, write: bool, dependencies: &mut Vec<usize>) {
+        dependencies.extend(self.last_writer);
+
+        if write {
+            dependencies.append(&mut self.readers);
+            self.last_writer = Some(pass);
+        }
+        else {
+            self.readers.push(pass);
+        }
+    }
+}
+
+/// Orders the passes topologically.
+///
+/// Among the passes, whose dependencies are satisfied, the first one added to the graph, which
+/// does not depend on the previously scheduled pass, is preferred. This gives the GPU more work
+/// to overlap between dependent passes.
+fn schedule(dependencies: &[Vec<usize>]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = dependencies.iter().map(Vec::len).collect();
+    let mut dependents = vec![Vec::new(); dependencies.len()];
+    for (pass, pass_dependencies) in dependencies.iter().enumerate() {
+        for &dependency in pass_dependencies {
+            dependents[dependency].push(pass);
+        }
+    }
+
+    let mut ready: Vec<usize> = (0..dependencies.len()).filter(|&pass| remaining[pass] == 0).collect();
+    let mut order = Vec::with_capacity(dependencies.len());
+
+    while !ready.is_empty() {
+        let index = {
+            let previous = order.last();
+            ready.iter().position(|pass| match previous {
+                Some(previous) => !dependencies[*pass].contains(previous),
+                None => true,
+            }).unwrap_or(0)
+        };
+
+        let pass = ready.remove(index);
+        order.push(pass);
+
+        for &dependent in &dependents[pass] {
+            remaining[dependent] -= 1;
+            if remaining[dependent] == 0 {
+                let position = ready.iter().position(|&p| p > dependent).unwrap_or(ready.len());
+                ready.insert(position, dependent);
+            }
+        }
+    }
+
+    order
+}
+
+#[derive(Debug)]
+struct PhysicalImage {
+    format: core::Format,
+    extent: core::Extent2D,
+    samples: core::SampleCountFlagBits,
+    usage: core::ImageUsageFlags,
+    last_use: usize,
+}
+
+pub fn compile(graph: RenderGraph, device: &core::Device) -> Result<CompiledRenderGraph, RenderGraphError> {
+    let RenderGraph { images: resources, buffers, passes } = graph;
+
+    let mut image_uses = Vec::with_capacity(passes.len());
+    let mut buffer_uses = Vec::with_capacity(passes.len());
+    for pass in &passes {
+        image_uses.push(merge_image_uses(&pass.name, &pass.images)?);
+        buffer_uses.push(merge_buffer_uses(&pass.buffers));
+
+        let mut extents = pass.attachments.iter().map(|attachment| match *attachment {
+            Attachment::Color(image, _) | Attachment::DepthStencil(image, _) | Attachment::Input(image) => resources[image.0].extent(),
+        });
+
+        if let Some(first) = extents.next() {
+            if extents.any(|extent| extent != first) {
+                return Err(RenderGraphError::AttachmentExtentMismatch { pass: pass.name.clone() });
+            }
+        }
+    }
+
+    // Dependencies between passes in declaration order.
+    let mut image_dependencies = vec![Dependencies::default(); resources.len()];
+    let mut buffer_dependencies = vec![Dependencies::default(); buffers.len()];
+    let mut dependencies = Vec::with_capacity(passes.len());
+    for (index, pass) in passes.iter().enumerate() {
+        let mut pass_dependencies = Vec::new();
+
+        for image_use in &image_uses[index] {
+            let image_dependencies = &mut image_dependencies[image_use.image.0];
+            if !resources[image_use.image.0].is_imported() && !image_use.write && image_dependencies.last_writer.is_none() {
+                return Err(RenderGraphError::UninitializedImage {
+                    pass: pass.name.clone(),
+                    image: image_use.image,
+                });
+            }
+
+            image_dependencies.add(index, image_use.write, &mut pass_dependencies);
+        }
+
+        for buffer_use in &buffer_uses[index] {
+            buffer_dependencies[buffer_use.buffer.0].add(index, buffer_use.write, &mut pass_dependencies);
+        }
+
+        pass_dependencies.sort();
+        pass_dependencies.dedup();
+        dependencies.push(pass_dependencies);
+    }
+
+    let order = schedule(&dependencies);
+    let mut position = vec![0; passes.len()];
+    for (i, &pass) in order.iter().enumerate() {
+        position[pass] = i;
+    }
+
+    // Lifetimes and usage of all images in scheduled order.
+    let mut first_use = vec![None; resources.len()];
+    let mut last_use = vec![0; resources.len()];
+    let mut usage = vec![core::ImageUsageFlags::empty(); resources.len()];
+    for (i, &pass) in order.iter().enumerate() {
+        for image_use in &image_uses[pass] {
+            let image = image_use.image.0;
+            if first_use[image].is_none() {
+                first_use[image] = Some(i);
+            }
+
+            last_use[image] = i;
+            usage[image] |= image_use.access.usage();
+        }
+    }
+
+    // Assign transient images to physical images.
+    let mut by_first_use: Vec<usize> = (0..resources.len()).filter(|&image| first_use[image].is_some()).collect();
+    by_first_use.sort_by_key(|&image| first_use[image]);
+
+    let mut physical_images: Vec<PhysicalImage> = Vec::new();
+    let mut physical = vec![None; resources.len()];
+    for image in by_first_use {
+        let transient = match resources[image] {
+            ImageResource::Transient(ref transient) => *transient,
+            ImageResource::Imported(_) => continue,
+        };
+
+        let first = first_use[image].unwrap();
+        let index = physical_images.iter().position(|p| {
+            p.format == transient.format && p.extent == transient.extent && p.samples == transient.samples && p.usage == usage[image] && p.last_use < first
+        });
+
+        let index = match index {
+            Some(index) => index,
+            None => {
+                physical_images.push(PhysicalImage {
+                    format: transient.format,
+                    extent: transient.extent,
+                    samples: transient.samples,
+                    usage: usage[image],
+                    last_use: 0,
+                });
+
+                physical_images.len() - 1
+            }
+        };
+
+        physical_images[index].last_use = last_use[image];
+        physical[image] = Some(index);
+    }
+
+    let attachment_usage = core::ImageUsageFlags::COLOR_ATTACHMENT | core::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | core::ImageUsageFlags::INPUT_ATTACHMENT;
+    let mut transient_images = Vec::with_capacity(physical_images.len());
+    for physical_image in &physical_images {
+        let (usage, preferred) = if attachment_usage.contains(physical_image.usage) {
+            (physical_image.usage | core::ImageUsageFlags::TRANSIENT_ATTACHMENT, core::MemoryPropertyFlags::LAZILY_ALLOCATED)
+        }
+        else {
+            (physical_image.usage, core::MemoryPropertyFlags::empty())
+        };
+
+        let create_info = core::ImageCreateInfo::builder(physical_image.format, core::Extent3D::from_2d(&physical_image.extent, 1), usage)
+            .samples(physical_image.samples)
+            .build();
+
+        transient_images.push(device.create_image_with_memory(&create_info, core::MemoryPropertyFlags::DEVICE_LOCAL, preferred)?);
+    }
+
+    let mut images = Vec::with_capacity(resources.len());
+    let mut physical_ids = Vec::with_capacity(resources.len());
+    for (image, resource) in resources.iter().enumerate() {
+        match *resource {
+            ImageResource::Imported(ref imported) => {
+                physical_ids.push(Some(transient_images.len() + image));
+                images.push(Some(imported.image.clone()));
+            }
+
+            ImageResource::Transient(_) => {
+                physical_ids.push(physical[image]);
+                images.push(physical[image].map(|index| transient_images[index].clone()));
+            }
+        }
+    }
+
+    // States are tracked per physical image, so that barriers between aliased images are
+    // inserted as well.
+    let mut image_states: Vec<ResourceState> = physical_images.iter()
+        .map(|_| ResourceState::new(core::PipelineStageFlags::empty(), core::AccessFlags::empty(), core::ImageLayout::Undefined))
+        .collect();
+
+    for resource in &resources {
+        match *resource {
+            ImageResource::Imported(ref imported) => {
+                let initial = imported.initial_access;
+                image_states.push(ResourceState::new(initial.stage_mask, initial.access_mask, initial.layout));
+            }
+
+            ImageResource::Transient(_) => {
+                image_states.push(ResourceState::new(core::PipelineStageFlags::empty(), core::AccessFlags::empty(), core::ImageLayout::Undefined));
+            }
+        }
+    }
+
+    let mut buffer_states: Vec<ResourceState> = buffers.iter()
+        .map(|_| ResourceState::new(core::PipelineStageFlags::empty(), core::AccessFlags::empty(), core::ImageLayout::Undefined))
+        .collect();
+
+    let mut passes: Vec<Option<_>> = passes.into_iter().map(Some).collect();
+    let mut compiled_passes = Vec::with_capacity(order.len());
+    let mut views: Vec<Option<ImageView>> = vec![None; resources.len()];
+    for (i, &index) in order.iter().enumerate() {
+        let pass = passes[index].take().unwrap();
+
+        let mut barriers = PassBarriers::new();
+        for image_use in &image_uses[index] {
+            let image = image_use.image.0;
+            let state = &mut image_states[physical_ids[image].unwrap()];
+            let access = image_use.access;
+            if let Some(transition) = state.access(access.stage_mask, access.access_mask, access.layout, image_use.write, image_use.discard) {
+                barriers.add_image(transition, images[image].clone().unwrap(), resources[image].format());
+            }
+        }
+
+        for buffer_use in &buffer_uses[index] {
+            let buffer = buffer_use.buffer.0;
+            let access = buffer_use.access;
+            if let Some(transition) = buffer_states[buffer].access(access.stage_mask, access.access_mask, core::ImageLayout::Undefined, buffer_use.write, false) {
+                barriers.add_buffer(transition, buffers[buffer].clone());
+            }
+        }
+
+        let render_pass = if !pass.attachments.is_empty() {
+            Some(create_render_pass(device, &pass.attachments, &resources, &images, &mut views, |image| resources[image.0].is_imported() || last_use[image.0] > i)?)
+        }
+        else {
+            None
+        };
+
+        compiled_passes.push(CompiledPass {
+            id: PassId(index),
+            barriers: barriers.into_option(),
+            render_pass: render_pass,
+            execute: pass.execute.unwrap(),
+        });
+    }
+
+    let mut final_barriers = PassBarriers::new();
+    for (image, resource) in resources.iter().enumerate() {
+        if let ImageResource::Imported(ref imported) = *resource {
+            if let Some(final_layout) = imported.final_layout {
+                if let Some(transition) = image_states[physical_ids[image].unwrap()].finish(final_layout) {
+                    final_barriers.add_image(transition, images[image].clone().unwrap(), imported.format);
+                }
+            }
+        }
+    }
+
+    Ok(CompiledRenderGraph {
+        passes: compiled_passes,
+        final_barriers: final_barriers.into_option(),
+        images: images,
+        buffers: buffers,
+        transient_image_count: transient_images.len(),
+        views: views,
+    })
+}
+
+fn create_render_pass<F>(device: &core::Device, attachments: &[Attachment], resources: &[ImageResource], images: &[Option<Image>], views: &mut [Option<ImageView>], store: F) -> Result<RenderPassInfo, RenderGraphError>
+    where F: Fn(ImageId) -> bool
+{
+    let mut descriptions = Vec::with_capacity(attachments.len());
+    let mut input_attachments = Vec::new();
+    let mut color_attachments = Vec::new();
+    let mut depth_stencil_attachment = None;
+    let mut clear_values = Vec::with_capacity(attachments.len());
+    let mut attachment_views = Vec::with_capacity(attachments.len());
+
+    for (index, attachment) in attachments.iter().enumerate() {
+        let (image, load, layout) = match *attachment {
+            Attachment::Color(image, load) => {
+                color_attachments.push(core::AttachmentReference {
+                    attachment: core::AttachmentIndex::Index(index as u32),
+                    layout: core::ImageLayout::ColorAttachmentOptimal,
+                });
+
+                (image, load, core::ImageLayout::ColorAttachmentOptimal)
+            }
+
+            Attachment::DepthStencil(image, load) => {
+                depth_stencil_attachment = Some(core::AttachmentReference {
+                    attachment: core::AttachmentIndex::Index(index as u32),
+                    layout: core::ImageLayout::DepthStencilAttachmentOptimal,
+                });
+
+                (image, load, core::ImageLayout::DepthStencilAttachmentOptimal)
+            }
+
+            Attachment::Input(image) => {
+                input_attachments.push(core::AttachmentReference {
+                    attachment: core::AttachmentIndex::Index(index as u32),
+                    layout: core::ImageLayout::ShaderReadOnlyOptimal,
+                });
+
+                (image, AttachmentLoad::Load, core::ImageLayout::ShaderReadOnlyOptimal)
+            }
+        };
+
+        let resource = &resources[image.0];
+        let aspect_mask = resource.format().aspect_mask();
+        let store_op = if store(image) { core::AttachmentStoreOp::Store } else { core::AttachmentStoreOp::DontCare };
+        let (stencil_load_op, stencil_store_op) = if aspect_mask.contains(core::ImageAspectFlags::STENCIL) {
+            (load.load_op(), store_op)
+        }
+        else {
+            (core::AttachmentLoadOp::DontCare, core::AttachmentStoreOp::DontCare)
+        };
+
+        descriptions.push(core::AttachmentDescription {
+            flags: core::AttachmentDescriptionFlags::empty(),
+            format: resource.format(),
+            samples: resource.samples(),
+            load_op: load.load_op(),
+            store_op: store_op,
+            stencil_load_op: stencil_load_op,
+            stencil_store_op: stencil_store_op,
+            initial_layout: layout,
+            final_layout: layout,
+        });
+
+        clear_values.push(match load {
+            AttachmentLoad::Clear(clear_value) => clear_value,
+            _ => core::ClearValue::Color(core::ClearColorValue::Float32([0.0; 4])),
+        });
+
+        if views[image.0].is_none() {
+            let create_info = core::ImageViewCreateInfo::builder(images[image.0].clone().unwrap(), core::ImageViewType::Type2D, resource.format())
+                .subresource_range(core::ImageSubresourceRange::new(resource.format(), 0, 1, 0, 1))
+                .build();
+
+            views[image.0] = Some(device.create_image_view(&create_info, None)?);
+        }
+
+        attachment_views.push(views[image.0].clone().unwrap());
+    }
+
+    let create_info = core::RenderPassCreateInfo::builder()
+        .attachments(descriptions)
+        .subpasses(vec![core::SubpassDescription {
+            flags: core::SubpassDescriptionFlags::empty(),
+            pipeline_bind_point: core::PipelineBindPoint::Graphics,
+            input_attachments: input_attachments,
+            color_attachments: color_attachments,
+            resolve_attachments: vec![],
+            depth_stencil_attachment: depth_stencil_attachment,
+            preserve_attachments: vec![],
+        }])
+        .build();
+
+    let render_pass = device.create_render_pass(&create_info, None)?;
+
+    let extent = match attachments[0] {
+        Attachment::Color(image, _) | Attachment::DepthStencil(image, _) | Attachment::Input(image) => resources[image.0].extent(),
+    };
+
+    let create_info = core::FramebufferCreateInfo::builder(render_pass.clone(), extent.width, extent.height)
+        .attachments(attachment_views)
+        .build();
+
+    let framebuffer = device.create_framebuffer(&create_info, None)?;
+
+    Ok(RenderPassInfo {
+        render_pass: render_pass,
+        framebuffer: framebuffer,
+        render_area: core::Rect2D::new(core::Offset2D::zero(), extent),
+        clear_values: clear_values,
+    })
+}
@@ -0,0 +1,613 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! A render graph on top of `CommandBuffer`, `RenderPass` and `Framebuffer`.
+//!
+//! Passes declare which images and buffers they read and write. From these declarations, the
+//! [`RenderGraph`](struct.RenderGraph.html) orders the passes, creates transient attachments,
+//! render passes and framebuffers, and inserts `pipeline_barrier` calls with minimal stage and
+//! access masks.
+//!
+//! Dependencies follow the order in which passes are added: a pass, which reads a resource,
+//! depends on the last pass added before it, which writes that resource.
+//!
+//! A typical graph is built once per swapchain image or whenever attachments are resized:
+//!
+//! 1. Import external images and buffers and declare transient images.
+//! 2. Add passes with [`RenderGraph::add_pass`](struct.RenderGraph.html#method.add_pass).
+//! 3. [`compile`](struct.RenderGraph.html#method.compile) the graph.
+//! 4. [`record`](struct.CompiledRenderGraph.html#method.record) it into a command buffer.
+
+mod barriers;
+mod compiled;
+
+pub use self::compiled::{CompiledRenderGraph, PassBarriers, PassContext};
+
+use core::{self, AllocationError, Buffer, Image};
+use std::error;
+use std::fmt;
+
+/// Identifies an image in a `RenderGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ImageId(usize);
+
+/// Identifies a buffer in a `RenderGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BufferId(usize);
+
+/// Identifies a pass in a `RenderGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PassId(usize);
+
+/// Error type for compiling render graphs.
+#[derive(Debug)]
+pub enum RenderGraphError {
+    /// A transient image is read by a pass, but no earlier pass writes it.
+    UninitializedImage {
+        pass: String,
+        image: ImageId,
+    },
+
+    /// A pass uses the same image in two different layouts.
+    ConflictingImageLayouts {
+        pass: String,
+        image: ImageId,
+    },
+
+    /// The attachments of a pass don't have the same extent.
+    AttachmentExtentMismatch {
+        pass: String,
+    },
+
+    /// Allocating memory for a transient image failed.
+    AllocationError(AllocationError),
+
+    /// A Vulkan function returned an error.
+    VulkanError(core::Error),
+}
+
+impl fmt::Display for RenderGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RenderGraphError::UninitializedImage { ref pass, image } => write!(f, "Pass \"{}\" reads image {}, which is not written by an earlier pass", pass, image.0),
+            RenderGraphError::ConflictingImageLayouts { ref pass, image } => write!(f, "Pass \"{}\" uses image {} in different layouts", pass, image.0),
+            RenderGraphError::AttachmentExtentMismatch { ref pass } => write!(f, "The attachments of pass \"{}\" have different extents", pass),
+            RenderGraphError::AllocationError(ref e) => e.fmt(f),
+            RenderGraphError::VulkanError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for RenderGraphError {
+    fn description(&self) -> &str {
+        match *self {
+            RenderGraphError::UninitializedImage { .. } => "Image read before it is written",
+            RenderGraphError::ConflictingImageLayouts { .. } => "Image used in different layouts by the same pass",
+            RenderGraphError::AttachmentExtentMismatch { .. } => "Attachments have different extents",
+            RenderGraphError::AllocationError(ref e) => e.description(),
+            RenderGraphError::VulkanError(ref e) => e.description(),
+        }
+    }
+}
+
+impl From<AllocationError> for RenderGraphError {
+    fn from(e: AllocationError) -> Self {
+        RenderGraphError::AllocationError(e)
+    }
+}
+
+impl From<core::Error> for RenderGraphError {
+    fn from(e: core::Error) -> Self {
+        RenderGraphError::VulkanError(e)
+    }
+}
+
+/// Describes how a pass accesses an image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ImageAccess {
+    /// Pipeline stages, which access the image.
+    pub stage_mask: core::PipelineStageFlags,
+
+    /// Types of memory accesses.
+    pub access_mask: core::AccessFlags,
+
+    /// Layout, which the image must be in.
+    pub layout: core::ImageLayout,
+}
+
+impl ImageAccess {
+    /// Creates a new `ImageAccess`.
+    pub fn new(stage_mask: core::PipelineStageFlags, access_mask: core::AccessFlags, layout: core::ImageLayout) -> Self {
+        ImageAccess {
+            stage_mask: stage_mask,
+            access_mask: access_mask,
+            layout: layout,
+        }
+    }
+
+    /// The image is not accessed and its contents are undefined.
+    pub fn undefined() -> Self {
+        ImageAccess::new(core::PipelineStageFlags::TOP_OF_PIPE, core::AccessFlags::empty(), core::ImageLayout::Undefined)
+    }
+
+    /// The image is sampled in shaders of the given stages.
+    pub fn sampled(stage_mask: core::PipelineStageFlags) -> Self {
+        ImageAccess::new(stage_mask, core::AccessFlags::SHADER_READ, core::ImageLayout::ShaderReadOnlyOptimal)
+    }
+
+    /// The image is read as a storage image in shaders of the given stages.
+    pub fn storage_read(stage_mask: core::PipelineStageFlags) -> Self {
+        ImageAccess::new(stage_mask, core::AccessFlags::SHADER_READ, core::ImageLayout::General)
+    }
+
+    /// The image is written (and possibly read) as a storage image in shaders of the given stages.
+    pub fn storage_write(stage_mask: core::PipelineStageFlags) -> Self {
+        ImageAccess::new(stage_mask, core::AccessFlags::SHADER_READ | core::AccessFlags::SHADER_WRITE, core::ImageLayout::General)
+    }
+
+    /// The image is the source of a transfer command.
+    pub fn transfer_src() -> Self {
+        ImageAccess::new(core::PipelineStageFlags::TRANSFER, core::AccessFlags::TRANSFER_READ, core::ImageLayout::TransferSrcOptimal)
+    }
+
+    /// The image is the destination of a transfer command.
+    pub fn transfer_dst() -> Self {
+        ImageAccess::new(core::PipelineStageFlags::TRANSFER, core::AccessFlags::TRANSFER_WRITE, core::ImageLayout::TransferDstOptimal)
+    }
+
+    /// The image is presented to a swapchain.
+    pub fn present() -> Self {
+        ImageAccess::new(core::PipelineStageFlags::BOTTOM_OF_PIPE, core::AccessFlags::empty(), core::ImageLayout::PresentSrcKhr)
+    }
+
+    fn color_attachment(load: bool) -> Self {
+        let mut access_mask = core::AccessFlags::COLOR_ATTACHMENT_WRITE;
+        if load {
+            access_mask |= core::AccessFlags::COLOR_ATTACHMENT_READ;
+        }
+
+        ImageAccess::new(core::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT, access_mask, core::ImageLayout::ColorAttachmentOptimal)
+    }
+
+    fn depth_stencil_attachment() -> Self {
+        ImageAccess::new(
+            core::PipelineStageFlags::EARLY_FRAGMENT_TESTS | core::PipelineStageFlags::LATE_FRAGMENT_TESTS,
+            core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+            core::ImageLayout::DepthStencilAttachmentOptimal,
+        )
+    }
+
+    fn input_attachment() -> Self {
+        ImageAccess::new(core::PipelineStageFlags::FRAGMENT_SHADER, core::AccessFlags::INPUT_ATTACHMENT_READ, core::ImageLayout::ShaderReadOnlyOptimal)
+    }
+
+    fn usage(&self) -> core::ImageUsageFlags {
+        let mut usage = core::ImageUsageFlags::empty();
+
+        if self.access_mask.intersects(core::AccessFlags::SHADER_READ | core::AccessFlags::SHADER_WRITE) {
+            if self.layout == core::ImageLayout::General {
+                usage |= core::ImageUsageFlags::STORAGE;
+            }
+            else {
+                usage |= core::ImageUsageFlags::SAMPLED;
+            }
+        }
+
+        if self.access_mask.intersects(core::AccessFlags::INPUT_ATTACHMENT_READ) {
+            usage |= core::ImageUsageFlags::INPUT_ATTACHMENT;
+        }
+
+        if self.access_mask.intersects(core::AccessFlags::COLOR_ATTACHMENT_READ | core::AccessFlags::COLOR_ATTACHMENT_WRITE) {
+            usage |= core::ImageUsageFlags::COLOR_ATTACHMENT;
+        }
+
+        if self.access_mask.intersects(core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | core::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE) {
+            usage |= core::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT;
+        }
+
+        if self.access_mask.intersects(core::AccessFlags::TRANSFER_READ) {
+            usage |= core::ImageUsageFlags::TRANSFER_SRC;
+        }
+
+        if self.access_mask.intersects(core::AccessFlags::TRANSFER_WRITE) {
+            usage |= core::ImageUsageFlags::TRANSFER_DST;
+        }
+
+        usage
+    }
+}
+
+/// Describes how a pass accesses a buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BufferAccess {
+    /// Pipeline stages, which access the buffer.
+    pub stage_mask: core::PipelineStageFlags,
+
+    /// Types of memory accesses.
+    pub access_mask: core::AccessFlags,
+}
+
+impl BufferAccess {
+    /// Creates a new `BufferAccess`.
+    pub fn new(stage_mask: core::PipelineStageFlags, access_mask: core::AccessFlags) -> Self {
+        BufferAccess {
+            stage_mask: stage_mask,
+            access_mask: access_mask,
+        }
+    }
+
+    /// The buffer is used as a vertex buffer.
+    pub fn vertex_buffer() -> Self {
+        BufferAccess::new(core::PipelineStageFlags::VERTEX_INPUT, core::AccessFlags::VERTEX_ATTRIBUTE_READ)
+    }
+
+    /// The buffer is used as an index buffer.
+    pub fn index_buffer() -> Self {
+        BufferAccess::new(core::PipelineStageFlags::VERTEX_INPUT, core::AccessFlags::INDEX_READ)
+    }
+
+    /// The buffer contains parameters of indirect draw or dispatch commands.
+    pub fn indirect_buffer() -> Self {
+        BufferAccess::new(core::PipelineStageFlags::DRAW_INDIRECT, core::AccessFlags::INDIRECT_COMMAND_READ)
+    }
+
+    /// The buffer is read as a uniform buffer in shaders of the given stages.
+    pub fn uniform_buffer(stage_mask: core::PipelineStageFlags) -> Self {
+        BufferAccess::new(stage_mask, core::AccessFlags::UNIFORM_READ)
+    }
+
+    /// The buffer is read as a storage buffer in shaders of the given stages.
+    pub fn storage_read(stage_mask: core::PipelineStageFlags) -> Self {
+        BufferAccess::new(stage_mask, core::AccessFlags::SHADER_READ)
+    }
+
+    /// The buffer is written (and possibly read) as a storage buffer in shaders of the given
+    /// stages.
+    pub fn storage_write(stage_mask: core::PipelineStageFlags) -> Self {
+        BufferAccess::new(stage_mask, core::AccessFlags::SHADER_READ | core::AccessFlags::SHADER_WRITE)
+    }
+
+    /// The buffer is the source of a transfer command.
+    pub fn transfer_src() -> Self {
+        BufferAccess::new(core::PipelineStageFlags::TRANSFER, core::AccessFlags::TRANSFER_READ)
+    }
+
+    /// The buffer is the destination of a transfer command.
+    pub fn transfer_dst() -> Self {
+        BufferAccess::new(core::PipelineStageFlags::TRANSFER, core::AccessFlags::TRANSFER_WRITE)
+    }
+}
+
+/// Specifies what happens to the contents of an attachment at the beginning of a pass.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AttachmentLoad {
+    /// The previous contents are preserved.
+    Load,
+
+    /// The attachment is cleared.
+    Clear(core::ClearValue),
+
+    /// The previous contents are not needed.
+    DontCare,
+}
+
+impl AttachmentLoad {
+    fn load_op(&self) -> core::AttachmentLoadOp {
+        match *self {
+            AttachmentLoad::Load => core::AttachmentLoadOp::Load,
+            AttachmentLoad::Clear(_) => core::AttachmentLoadOp::Clear,
+            AttachmentLoad::DontCare => core::AttachmentLoadOp::DontCare,
+        }
+    }
+}
+
+/// An image, which is created and owned by the render graph.
+///
+/// Transient images are 2D images with a single mip level and array layer. Their usage flags are
+/// derived from the passes, which use them. Images, which are only used as attachments, are
+/// created with `ImageUsageFlags::TRANSIENT_ATTACHMENT` and preferably in lazily allocated
+/// memory. Transient images with the same description and non-overlapping lifetimes share the
+/// same `Image`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TransientImage {
+    pub format: core::Format,
+    pub extent: core::Extent2D,
+    pub samples: core::SampleCountFlagBits,
+}
+
+/// An image, which is created outside of the render graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedImage {
+    pub image: Image,
+    pub format: core::Format,
+    pub extent: core::Extent2D,
+    pub samples: core::SampleCountFlagBits,
+
+    /// The last access before the render graph is executed.
+    pub initial_access: ImageAccess,
+
+    /// If set, the image is transitioned to this layout at the end of the render graph.
+    pub final_layout: Option<core::ImageLayout>,
+}
+
+impl ImportedImage {
+    /// Creates a new single-sampled `ImportedImage` with undefined initial contents and no final
+    /// layout.
+    pub fn new(image: Image, format: core::Format, extent: core::Extent2D) -> Self {
+        ImportedImage {
+            image: image,
+            format: format,
+            extent: extent,
+            samples: core::SampleCountFlagBits::SampleCount1,
+            initial_access: ImageAccess::undefined(),
+            final_layout: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ImageResource {
+    Transient(TransientImage),
+    Imported(ImportedImage),
+}
+
+impl ImageResource {
+    fn format(&self) -> core::Format {
+        match *self {
+            ImageResource::Transient(ref image) => image.format,
+            ImageResource::Imported(ref image) => image.format,
+        }
+    }
+
+    fn extent(&self) -> core::Extent2D {
+        match *self {
+            ImageResource::Transient(ref image) => image.extent,
+            ImageResource::Imported(ref image) => image.extent,
+        }
+    }
+
+    fn samples(&self) -> core::SampleCountFlagBits {
+        match *self {
+            ImageResource::Transient(ref image) => image.samples,
+            ImageResource::Imported(ref image) => image.samples,
+        }
+    }
+
+    fn is_imported(&self) -> bool {
+        match *self {
+            ImageResource::Transient(_) => false,
+            ImageResource::Imported(_) => true,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Attachment {
+    Color(ImageId, AttachmentLoad),
+    DepthStencil(ImageId, AttachmentLoad),
+    Input(ImageId),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct ImageUse {
+    image: ImageId,
+    access: ImageAccess,
+    write: bool,
+
+    /// The previous contents are not needed.
+    discard: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct BufferUse {
+    buffer: BufferId,
+    access: BufferAccess,
+    write: bool,
+}
+
+type ExecuteFn = Box<FnMut(&PassContext)>;
+
+struct Pass {
+    name: String,
+    attachments: Vec<Attachment>,
+    images: Vec<ImageUse>,
+    buffers: Vec<BufferUse>,
+    execute: Option<ExecuteFn>,
+}
+
+impl fmt::Debug for Pass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pass")
+            .field("name", &self.name)
+            .field("attachments", &self.attachments)
+            .field("images", &self.images)
+            .field("buffers", &self.buffers)
+            .finish()
+    }
+}
+
+/// A graph of passes and the resources they use.
+///
+/// See the [module documentation](index.html) for an overview.
+#[derive(Debug, Default)]
+pub struct RenderGraph {
+    images: Vec<ImageResource>,
+    buffers: Vec<Buffer>,
+    passes: Vec<Pass>,
+}
+
+impl RenderGraph {
+    /// Creates an empty render graph.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Declares a transient image, which is created by the render graph.
+    pub fn create_image(&mut self, image: TransientImage) -> ImageId {
+        self.images.push(ImageResource::Transient(image));
+        ImageId(self.images.len() - 1)
+    }
+
+    /// Imports an external image.
+    pub fn import_image(&mut self, image: ImportedImage) -> ImageId {
+        self.images.push(ImageResource::Imported(image));
+        ImageId(self.images.len() - 1)
+    }
+
+    /// Imports an external buffer.
+    ///
+    /// All writes to the buffer before the render graph is executed must already be visible, e.g.
+    /// because they were performed by the host before the command buffer was submitted.
+    pub fn import_buffer(&mut self, buffer: Buffer) -> BufferId {
+        self.buffers.push(buffer);
+        BufferId(self.buffers.len() - 1)
+    }
+
+    /// Adds a new pass.
+    ///
+    /// The pass is added to the graph, when [`PassBuilder::execute`](struct.PassBuilder.html#method.execute)
+    /// is called.
+    pub fn add_pass<'a>(&'a mut self, name: &str) -> PassBuilder<'a> {
+        PassBuilder {
+            graph: self,
+            pass: Pass {
+                name: name.to_owned(),
+                attachments: Vec::new(),
+                images: Vec::new(),
+                buffers: Vec::new(),
+                execute: None,
+            },
+        }
+    }
+
+    /// Returns the number of passes in the graph.
+    pub fn pass_count(&self) -> usize {
+        self.passes.len()
+    }
+
+    /// Orders the passes, creates all transient images, render passes and framebuffers and
+    /// computes the barriers between passes.
+    pub fn compile(self, device: &core::Device) -> Result<CompiledRenderGraph, RenderGraphError> {
+        compiled::compile(self, device)
+    }
+}
+
+/// Declares the resources used by a pass.
+///
+/// Returned by [`RenderGraph::add_pass`](struct.RenderGraph.html#method.add_pass).
+#[derive(Debug)]
+pub struct PassBuilder<'a> {
+    graph: &'a mut RenderGraph,
+    pass: Pass,
+}
+
+impl<'a> PassBuilder<'a> {
+    /// Adds a color attachment.
+    ///
+    /// Passes with attachments are executed inside of a render pass with a single subpass, which
+    /// uses the color and input attachments in the order they were added.
+    pub fn color_attachment(mut self, image: ImageId, load: AttachmentLoad) -> Self {
+        self.pass.attachments.push(Attachment::Color(image, load));
+        self.add_image_use(image, ImageAccess::color_attachment(load == AttachmentLoad::Load), true, load != AttachmentLoad::Load)
+    }
+
+    /// Sets the depth/stencil attachment.
+    ///
+    /// A previously set depth/stencil attachment is replaced.
+    pub fn depth_stencil_attachment(mut self, image: ImageId, load: AttachmentLoad) -> Self {
+        let previous = self.pass.attachments.iter().position(|attachment| match *attachment {
+            Attachment::DepthStencil(..) => true,
+            _ => false,
+        });
+
+        if let Some(index) = previous {
+            if let Attachment::DepthStencil(previous_image, previous_load) = self.pass.attachments.remove(index) {
+                let previous_use = ImageUse {
+                    image: previous_image,
+                    access: ImageAccess::depth_stencil_attachment(),
+                    write: true,
+                    discard: previous_load != AttachmentLoad::Load,
+                };
+
+                if let Some(index) = self.pass.images.iter().position(|image_use| *image_use == previous_use) {
+                    self.pass.images.remove(index);
+                }
+            }
+        }
+
+        self.pass.attachments.push(Attachment::DepthStencil(image, load));
+        self.add_image_use(image, ImageAccess::depth_stencil_attachment(), true, load != AttachmentLoad::Load)
+    }
+
+    /// Adds an input attachment, which is read in fragment shaders.
+    ///
+    /// The contents of the image are preserved. It must not be used in any other layout by the same
+    /// pass, which rules out using it as a color or depth/stencil attachment as well. The view,
+    /// which must be written to the input attachment descriptor, is returned by
+    /// [`PassContext::image_view`](struct.PassContext.html#method.image_view).
+    pub fn input_attachment(mut self, image: ImageId) -> Self {
+        self.pass.attachments.push(Attachment::Input(image));
+        self.add_image_use(image, ImageAccess::input_attachment(), false, false)
+    }
+
+    /// Declares, that the pass reads an image.
+    pub fn read_image(self, image: ImageId, access: ImageAccess) -> Self {
+        self.add_image_use(image, access, false, false)
+    }
+
+    /// Declares, that the pass writes an image.
+    pub fn write_image(self, image: ImageId, access: ImageAccess) -> Self {
+        self.add_image_use(image, access, true, false)
+    }
+
+    /// Declares, that the pass reads a buffer.
+    pub fn read_buffer(self, buffer: BufferId, access: BufferAccess) -> Self {
+        self.add_buffer_use(buffer, access, false)
+    }
+
+    /// Declares, that the pass writes a buffer.
+    pub fn write_buffer(self, buffer: BufferId, access: BufferAccess) -> Self {
+        self.add_buffer_use(buffer, access, true)
+    }
+
+    /// Sets the function, which records the commands of the pass, and adds the pass to the graph.
+    pub fn execute<F>(mut self, execute: F) -> PassId
+        where F: FnMut(&PassContext) + 'static
+    {
+        self.pass.execute = Some(Box::new(execute));
+        self.graph.passes.push(self.pass);
+        PassId(self.graph.passes.len() - 1)
+    }
+
+    fn add_image_use(mut self, image: ImageId, access: ImageAccess, write: bool, discard: bool) -> Self {
+        self.pass.images.push(ImageUse {
+            image: image,
+            access: access,
+            write: write,
+            discard: discard,
+        });
+
+        self
+    }
+
+    fn add_buffer_use(mut self, buffer: BufferId, access: BufferAccess, write: bool) -> Self {
+        self.pass.buffers.push(BufferUse {
+            buffer: buffer,
+            access: access,
+            write: write,
+        });
+
+        self
+    }
+}
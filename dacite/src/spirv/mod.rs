@@ -0,0 +1,347 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! SPIR-V parsing and reflection.
+//!
+//! [`ShaderReflection`](struct.ShaderReflection.html) lists the entry points, descriptor bindings,
+//! push constant ranges and vertex inputs of a SPIR-V module. The descriptor set and pipeline
+//! layouts of a pipeline can be generated from the reflections of all its shader modules with
+//! [`descriptor_set_layout_create_infos`](fn.descriptor_set_layout_create_infos.html) and
+//! [`pipeline_layout_create_info`](fn.pipeline_layout_create_info.html).
+
+mod parser;
+
+use core;
+use std::cmp;
+use std::error;
+use std::fmt;
+
+/// The magic number, which every SPIR-V module starts with.
+pub const MAGIC_NUMBER: u32 = 0x0723_0203;
+
+/// Highest supported SPIR-V major version.
+pub const MAX_MAJOR_VERSION: u32 = 1;
+
+/// Highest supported SPIR-V minor version.
+pub const MAX_MINOR_VERSION: u32 = 3;
+
+/// Number of words in the header of a SPIR-V module.
+pub const HEADER_WORDS: usize = 5;
+
+/// Error type for parsing SPIR-V modules.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// The size in bytes is either not a multiple of 4 or too small for the header.
+    InvalidSize(usize),
+
+    /// The first word is not the SPIR-V magic number in either byte order.
+    InvalidMagicNumber(u32),
+
+    /// The module uses an unsupported SPIR-V version (major, minor).
+    UnsupportedVersion(u32, u32),
+
+    /// The instruction at the given word offset is truncated or malformed, or declares a type,
+    /// which is cyclic, nested too deeply or too large.
+    InvalidInstruction(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::InvalidSize(size) => write!(f, "Invalid SPIR-V module size of {} bytes", size),
+            ParseError::InvalidMagicNumber(magic) => write!(f, "Invalid SPIR-V magic number 0x{:08x}", magic),
+            ParseError::UnsupportedVersion(major, minor) => write!(f, "Unsupported SPIR-V version {}.{}", major, minor),
+            ParseError::InvalidInstruction(offset) => write!(f, "Invalid SPIR-V instruction at word {}", offset),
+        }
+    }
+}
+
+impl error::Error for ParseError {
+    fn description(&self) -> &str {
+        match *self {
+            ParseError::InvalidSize(_) => "InvalidSize",
+            ParseError::InvalidMagicNumber(_) => "InvalidMagicNumber",
+            ParseError::UnsupportedVersion(_, _) => "UnsupportedVersion",
+            ParseError::InvalidInstruction(_) => "InvalidInstruction",
+        }
+    }
+}
+
+/// Error type for generating layouts from multiple shader modules.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LayoutError {
+    /// The same binding is declared with different descriptor types or counts.
+    ConflictingBinding {
+        set: u32,
+        binding: u32,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LayoutError::ConflictingBinding { set, binding } => write!(f, "Conflicting declarations of set {} binding {}", set, binding),
+        }
+    }
+}
+
+impl error::Error for LayoutError {
+    fn description(&self) -> &str {
+        match *self {
+            LayoutError::ConflictingBinding { .. } => "ConflictingBinding",
+        }
+    }
+}
+
+/// The header of a SPIR-V module.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Header {
+    pub major_version: u32,
+    pub minor_version: u32,
+    pub generator: u32,
+    pub bound: u32,
+}
+
+/// Converts SPIR-V byte code to words in native byte order.
+///
+/// The byte order of `code` is detected from the magic number.
+pub fn words_from_bytes(code: &[u8]) -> Result<Vec<u32>, ParseError> {
+    if (code.len() % 4 != 0) || (code.len() < HEADER_WORDS * 4) {
+        return Err(ParseError::InvalidSize(code.len()));
+    }
+
+    let little_endian = |b: &[u8]| (b[0] as u32) | ((b[1] as u32) << 8) | ((b[2] as u32) << 16) | ((b[3] as u32) << 24);
+    let big_endian = |b: &[u8]| (b[3] as u32) | ((b[2] as u32) << 8) | ((b[1] as u32) << 16) | ((b[0] as u32) << 24);
+
+    if little_endian(code) == MAGIC_NUMBER {
+        Ok(code.chunks(4).map(little_endian).collect())
+    }
+    else if big_endian(code) == MAGIC_NUMBER {
+        Ok(code.chunks(4).map(big_endian).collect())
+    }
+    else {
+        Err(ParseError::InvalidMagicNumber(little_endian(code)))
+    }
+}
+
+/// Parses and validates the header of a SPIR-V module.
+///
+/// The magic number of `words` must be in native byte order.
+pub fn parse_header(words: &[u32]) -> Result<Header, ParseError> {
+    if words.len() < HEADER_WORDS {
+        return Err(ParseError::InvalidSize(words.len() * 4));
+    }
+
+    if words[0] != MAGIC_NUMBER {
+        return Err(ParseError::InvalidMagicNumber(words[0]));
+    }
+
+    let major_version = (words[1] >> 16) & 0xff;
+    let minor_version = (words[1] >> 8) & 0xff;
+    if (major_version != MAX_MAJOR_VERSION) || (minor_version > MAX_MINOR_VERSION) {
+        return Err(ParseError::UnsupportedVersion(major_version, minor_version));
+    }
+
+    Ok(Header {
+        major_version: major_version,
+        minor_version: minor_version,
+        generator: words[2],
+        bound: words[3],
+    })
+}
+
+/// An entry point of a SPIR-V module.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntryPoint {
+    pub name: String,
+
+    /// The shader stage of this entry point, or empty for execution models not supported by
+    /// Vulkan.
+    pub stage: core::ShaderStageFlags,
+}
+
+/// A descriptor binding declared by a SPIR-V module.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DescriptorBinding {
+    pub set: u32,
+    pub binding: u32,
+    pub descriptor_type: core::DescriptorType,
+
+    /// Number of descriptors. Runtime arrays are reported with a count of 1.
+    pub descriptor_count: u32,
+
+    /// The number of descriptors depends on specialization constants.
+    ///
+    /// `descriptor_count` is then computed from their default values. Lengths computed by
+    /// `OpSpecConstantOp` or `OpSpecConstantComposite` count as 1.
+    pub specialized_count: bool,
+
+    /// The stages of all entry points of the module.
+    pub stage_flags: core::ShaderStageFlags,
+    pub name: Option<String>,
+}
+
+/// A vertex shader input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VertexInput {
+    pub location: u32,
+
+    /// The format matching the type of the input, if there is one.
+    pub format: Option<core::Format>,
+    pub name: Option<String>,
+}
+
+/// Reflection data of a SPIR-V module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderReflection {
+    header: Header,
+    entry_points: Vec<EntryPoint>,
+    descriptor_bindings: Vec<DescriptorBinding>,
+    push_constant_ranges: Vec<core::PushConstantRange>,
+    vertex_inputs: Vec<VertexInput>,
+}
+
+impl ShaderReflection {
+    /// Parses SPIR-V byte code in either byte order.
+    pub fn new(code: &[u8]) -> Result<Self, ParseError> {
+        ShaderReflection::from_words(&words_from_bytes(code)?)
+    }
+
+    /// Parses SPIR-V words in native byte order.
+    pub fn from_words(words: &[u32]) -> Result<Self, ParseError> {
+        let header = parse_header(words)?;
+        parser::parse(header, words)
+    }
+
+    /// Returns the header of the module.
+    #[inline]
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns all entry points of the module.
+    #[inline]
+    pub fn entry_points(&self) -> &[EntryPoint] {
+        &self.entry_points
+    }
+
+    /// Returns the union of the stages of all entry points.
+    pub fn stage_flags(&self) -> core::ShaderStageFlags {
+        self.entry_points.iter().fold(core::ShaderStageFlags::empty(), |stages, entry_point| stages | entry_point.stage)
+    }
+
+    /// Returns all descriptor bindings sorted by set and binding.
+    #[inline]
+    pub fn descriptor_bindings(&self) -> &[DescriptorBinding] {
+        &self.descriptor_bindings
+    }
+
+    /// Returns the push constant ranges of the module.
+    ///
+    /// A module contains at most one push constant block, so this is either empty or contains
+    /// exactly one range.
+    #[inline]
+    pub fn push_constant_ranges(&self) -> &[core::PushConstantRange] {
+        &self.push_constant_ranges
+    }
+
+    /// Returns the inputs of all vertex shader entry points sorted by location.
+    #[inline]
+    pub fn vertex_inputs(&self) -> &[VertexInput] {
+        &self.vertex_inputs
+    }
+}
+
+/// Generates one `DescriptorSetLayoutCreateInfo` per descriptor set used by `modules`.
+///
+/// The returned `Vec` is indexed by set number. Unused sets below the highest used set get an
+/// empty layout. Bindings declared by multiple modules are merged, if their descriptor types and
+/// counts match.
+pub fn descriptor_set_layout_create_infos(modules: &[ShaderReflection]) -> Result<Vec<core::DescriptorSetLayoutCreateInfo>, LayoutError> {
+    let mut sets: Vec<Vec<core::DescriptorSetLayoutBinding>> = Vec::new();
+
+    for binding in modules.iter().flat_map(|m| m.descriptor_bindings.iter()) {
+        let set = binding.set as usize;
+        while sets.len() <= set {
+            sets.push(Vec::new());
+        }
+
+        if let Some(existing) = sets[set].iter_mut().find(|b| b.binding == binding.binding) {
+            if (existing.descriptor_type != binding.descriptor_type) || (existing.descriptor_count != binding.descriptor_count) {
+                return Err(LayoutError::ConflictingBinding {
+                    set: binding.set,
+                    binding: binding.binding,
+                });
+            }
+
+            existing.stage_flags |= binding.stage_flags;
+            continue;
+        }
+
+        sets[set].push(core::DescriptorSetLayoutBinding {
+            binding: binding.binding,
+            descriptor_type: binding.descriptor_type,
+            descriptor_count: binding.descriptor_count,
+            stage_flags: binding.stage_flags,
+            immutable_samplers: vec![],
+        });
+    }
+
+    Ok(sets.into_iter().map(|mut bindings| {
+        bindings.sort_by_key(|b| b.binding);
+        core::DescriptorSetLayoutCreateInfo {
+            flags: core::DescriptorSetLayoutCreateFlags::empty(),
+            bindings: bindings,
+            chain: None,
+        }
+    }).collect())
+}
+
+/// Merges the push constant ranges of `modules`.
+///
+/// All ranges are combined into a single range, which covers all of them and is accessible from
+/// all stages declaring push constants.
+pub fn push_constant_ranges(modules: &[ShaderReflection]) -> Vec<core::PushConstantRange> {
+    let merged = modules.iter().flat_map(|m| m.push_constant_ranges.iter()).fold(None, |merged: Option<core::PushConstantRange>, range| {
+        Some(match merged {
+            Some(merged) => {
+                let start = cmp::min(merged.offset, range.offset);
+                let end = cmp::max(merged.offset + merged.size, range.offset + range.size);
+                core::PushConstantRange {
+                    stage_flags: merged.stage_flags | range.stage_flags,
+                    offset: start,
+                    size: end - start,
+                }
+            }
+
+            None => *range,
+        })
+    });
+
+    merged.into_iter().collect()
+}
+
+/// Generates a `PipelineLayoutCreateInfo` for `modules`.
+///
+/// `set_layouts` must have been created from the result of
+/// [`descriptor_set_layout_create_infos`](fn.descriptor_set_layout_create_infos.html) for the same
+/// modules.
+pub fn pipeline_layout_create_info(modules: &[ShaderReflection], set_layouts: Vec<core::DescriptorSetLayout>) -> core::PipelineLayoutCreateInfo {
+    core::PipelineLayoutCreateInfo {
+        flags: core::PipelineLayoutCreateFlags::empty(),
+        set_layouts: set_layouts,
+        push_constant_ranges: push_constant_ranges(modules),
+        chain: None,
+    }
+}
@@ -0,0 +1,647 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+use core;
+use spirv::{DescriptorBinding, EntryPoint, Header, HEADER_WORDS, ParseError, ShaderReflection, VertexInput};
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::HashMap;
+
+const OP_NAME: u32 = 5;
+const OP_ENTRY_POINT: u32 = 15;
+const OP_TYPE_BOOL: u32 = 20;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_MATRIX: u32 = 24;
+const OP_TYPE_IMAGE: u32 = 25;
+const OP_TYPE_SAMPLER: u32 = 26;
+const OP_TYPE_SAMPLED_IMAGE: u32 = 27;
+const OP_TYPE_ARRAY: u32 = 28;
+const OP_TYPE_RUNTIME_ARRAY: u32 = 29;
+const OP_TYPE_STRUCT: u32 = 30;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_CONSTANT: u32 = 43;
+const OP_SPEC_CONSTANT_TRUE: u32 = 48;
+const OP_SPEC_CONSTANT_FALSE: u32 = 49;
+const OP_SPEC_CONSTANT: u32 = 50;
+const OP_SPEC_CONSTANT_COMPOSITE: u32 = 51;
+const OP_SPEC_CONSTANT_OP: u32 = 52;
+const OP_VARIABLE: u32 = 59;
+const OP_DECORATE: u32 = 71;
+const OP_MEMBER_DECORATE: u32 = 72;
+
+const DECORATION_BLOCK: u32 = 2;
+const DECORATION_BUFFER_BLOCK: u32 = 3;
+const DECORATION_ARRAY_STRIDE: u32 = 6;
+const DECORATION_MATRIX_STRIDE: u32 = 7;
+const DECORATION_BUILT_IN: u32 = 11;
+const DECORATION_LOCATION: u32 = 30;
+const DECORATION_BINDING: u32 = 33;
+const DECORATION_DESCRIPTOR_SET: u32 = 34;
+const DECORATION_OFFSET: u32 = 35;
+
+const STORAGE_CLASS_UNIFORM_CONSTANT: u32 = 0;
+const STORAGE_CLASS_INPUT: u32 = 1;
+const STORAGE_CLASS_UNIFORM: u32 = 2;
+const STORAGE_CLASS_PUSH_CONSTANT: u32 = 9;
+const STORAGE_CLASS_STORAGE_BUFFER: u32 = 12;
+
+const DIM_BUFFER: u32 = 5;
+const DIM_SUBPASS_DATA: u32 = 6;
+
+/// Maximum nesting depth of types. Deeper (or cyclic) types are rejected.
+const MAX_TYPE_DEPTH: u32 = 64;
+
+#[derive(Debug, Clone)]
+enum Type {
+    Bool,
+    Int { width: u32, signed: bool },
+    Float { width: u32 },
+    Vector { component: u32, count: u32 },
+    Matrix { column: u32, count: u32 },
+    Image { dim: u32, sampled: u32 },
+    Sampler,
+    SampledImage,
+    Array { element: u32, length: ArrayLength },
+    RuntimeArray { element: u32 },
+    Struct { members: Vec<u32> },
+    Pointer { pointee: u32 },
+}
+
+/// Value of a constant, which can be used as an array length.
+#[derive(Debug, Copy, Clone)]
+enum Constant {
+    Value(u32),
+
+    /// A specialization constant with its default value, if it has a scalar one.
+    Specialized(Option<u32>),
+}
+
+/// Length of an array type.
+#[derive(Debug, Copy, Clone)]
+struct ArrayLength {
+    /// The length, or 1 if it is computed by `OpSpecConstantOp` or `OpSpecConstantComposite`.
+    value: u32,
+
+    /// The length can be changed by specialization constants.
+    specialized: bool,
+}
+
+impl From<Constant> for ArrayLength {
+    fn from(constant: Constant) -> Self {
+        match constant {
+            Constant::Value(value) => ArrayLength { value: value, specialized: false },
+            Constant::Specialized(value) => ArrayLength { value: value.unwrap_or(1), specialized: true },
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Decorations {
+    block: bool,
+    buffer_block: bool,
+    built_in: bool,
+    array_stride: Option<u32>,
+    location: Option<u32>,
+    binding: Option<u32>,
+    descriptor_set: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct MemberDecorations {
+    offset: Option<u32>,
+    matrix_stride: Option<u32>,
+}
+
+#[derive(Debug)]
+struct Variable {
+    id: u32,
+    pointer_type: u32,
+    storage_class: u32,
+}
+
+#[derive(Debug, Default)]
+struct Module {
+    names: HashMap<u32, String>,
+    decorations: HashMap<u32, Decorations>,
+    member_decorations: HashMap<(u32, u32), MemberDecorations>,
+    types: HashMap<u32, Type>,
+    type_offsets: HashMap<u32, usize>,
+    sizes: RefCell<HashMap<(u32, Option<u32>), u32>>,
+    constants: HashMap<u32, Constant>,
+    variables: Vec<Variable>,
+    entry_points: Vec<(EntryPoint, Vec<u32>)>,
+}
+
+/// Operands of a single instruction.
+struct Operands<'a> {
+    words: &'a [u32],
+    offset: usize,
+}
+
+impl<'a> Operands<'a> {
+    fn get(&self, index: usize) -> Result<u32, ParseError> {
+        self.words.get(index).cloned().ok_or(ParseError::InvalidInstruction(self.offset))
+    }
+
+    /// Reads a literal string starting at `index` and returns it together with the number of words
+    /// it occupies.
+    fn string(&self, index: usize) -> Result<(String, usize), ParseError> {
+        let mut bytes = Vec::new();
+        for (i, word) in self.words.iter().skip(index).enumerate() {
+            for shift in 0..4 {
+                let byte = ((word >> (shift * 8)) & 0xff) as u8;
+                if byte == 0 {
+                    let string = String::from_utf8(bytes).map_err(|_| ParseError::InvalidInstruction(self.offset))?;
+                    return Ok((string, i + 1));
+                }
+
+                bytes.push(byte);
+            }
+        }
+
+        Err(ParseError::InvalidInstruction(self.offset))
+    }
+}
+
+pub fn parse(header: Header, words: &[u32]) -> Result<ShaderReflection, ParseError> {
+    let mut module = Module::default();
+
+    let mut offset = HEADER_WORDS;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let opcode = words[offset] & 0xffff;
+        if (word_count == 0) || (offset + word_count > words.len()) {
+            return Err(ParseError::InvalidInstruction(offset));
+        }
+
+        let operands = Operands {
+            words: &words[offset + 1..offset + word_count],
+            offset: offset,
+        };
+
+        module.parse_instruction(opcode, &operands)?;
+        offset += word_count;
+    }
+
+    module.reflect(header)
+}
+
+impl Module {
+    fn parse_instruction(&mut self, opcode: u32, operands: &Operands) -> Result<(), ParseError> {
+        match opcode {
+            OP_NAME => {
+                let (name, _) = operands.string(1)?;
+                self.names.insert(operands.get(0)?, name);
+            }
+
+            OP_ENTRY_POINT => {
+                let (name, name_words) = operands.string(2)?;
+                let entry_point = EntryPoint {
+                    name: name,
+                    stage: stage_from_execution_model(operands.get(0)?),
+                };
+
+                let interface = operands.words[2 + name_words..].to_vec();
+                self.entry_points.push((entry_point, interface));
+            }
+
+            OP_TYPE_BOOL => {
+                self.insert_type(operands, Type::Bool)?;
+            }
+
+            OP_TYPE_INT => {
+                self.insert_type(operands, Type::Int {
+                    width: operands.get(1)?,
+                    signed: operands.get(2)? != 0,
+                })?;
+            }
+
+            OP_TYPE_FLOAT => {
+                self.insert_type(operands, Type::Float { width: operands.get(1)? })?;
+            }
+
+            OP_TYPE_VECTOR => {
+                self.insert_type(operands, Type::Vector {
+                    component: operands.get(1)?,
+                    count: operands.get(2)?,
+                })?;
+            }
+
+            OP_TYPE_MATRIX => {
+                self.insert_type(operands, Type::Matrix {
+                    column: operands.get(1)?,
+                    count: operands.get(2)?,
+                })?;
+            }
+
+            OP_TYPE_IMAGE => {
+                self.insert_type(operands, Type::Image {
+                    dim: operands.get(2)?,
+                    sampled: operands.get(6)?,
+                })?;
+            }
+
+            OP_TYPE_SAMPLER => {
+                self.insert_type(operands, Type::Sampler)?;
+            }
+
+            OP_TYPE_SAMPLED_IMAGE => {
+                self.insert_type(operands, Type::SampledImage)?;
+            }
+
+            OP_TYPE_ARRAY => {
+                let length = operands.get(2)?;
+                let length = self.constants.get(&length).cloned().ok_or(ParseError::InvalidInstruction(operands.offset))?;
+                self.insert_type(operands, Type::Array {
+                    element: operands.get(1)?,
+                    length: length.into(),
+                })?;
+            }
+
+            OP_TYPE_RUNTIME_ARRAY => {
+                self.insert_type(operands, Type::RuntimeArray { element: operands.get(1)? })?;
+            }
+
+            OP_TYPE_STRUCT => {
+                operands.get(0)?;
+                let members = operands.words.get(1..).unwrap_or(&[]).to_vec();
+                self.insert_type(operands, Type::Struct { members: members })?;
+            }
+
+            OP_TYPE_POINTER => {
+                self.insert_type(operands, Type::Pointer { pointee: operands.get(2)? })?;
+            }
+
+            OP_CONSTANT => {
+                self.constants.insert(operands.get(1)?, Constant::Value(operands.get(2)?));
+            }
+
+            OP_SPEC_CONSTANT => {
+                self.constants.insert(operands.get(1)?, Constant::Specialized(Some(operands.get(2)?)));
+            }
+
+            OP_SPEC_CONSTANT_TRUE => {
+                self.constants.insert(operands.get(1)?, Constant::Specialized(Some(1)));
+            }
+
+            OP_SPEC_CONSTANT_FALSE => {
+                self.constants.insert(operands.get(1)?, Constant::Specialized(Some(0)));
+            }
+
+            OP_SPEC_CONSTANT_COMPOSITE | OP_SPEC_CONSTANT_OP => {
+                self.constants.insert(operands.get(1)?, Constant::Specialized(None));
+            }
+
+            OP_VARIABLE => {
+                self.variables.push(Variable {
+                    pointer_type: operands.get(0)?,
+                    id: operands.get(1)?,
+                    storage_class: operands.get(2)?,
+                });
+            }
+
+            OP_DECORATE => {
+                let decorations = self.decorations.entry(operands.get(0)?).or_insert_with(Default::default);
+                match operands.get(1)? {
+                    DECORATION_BLOCK => decorations.block = true,
+                    DECORATION_BUFFER_BLOCK => decorations.buffer_block = true,
+                    DECORATION_BUILT_IN => decorations.built_in = true,
+                    DECORATION_ARRAY_STRIDE => decorations.array_stride = Some(operands.get(2)?),
+                    DECORATION_LOCATION => decorations.location = Some(operands.get(2)?),
+                    DECORATION_BINDING => decorations.binding = Some(operands.get(2)?),
+                    DECORATION_DESCRIPTOR_SET => decorations.descriptor_set = Some(operands.get(2)?),
+                    _ => { }
+                }
+            }
+
+            OP_MEMBER_DECORATE => {
+                let key = (operands.get(0)?, operands.get(1)?);
+                let decorations = self.member_decorations.entry(key).or_insert_with(Default::default);
+                match operands.get(2)? {
+                    DECORATION_OFFSET => decorations.offset = Some(operands.get(3)?),
+                    DECORATION_MATRIX_STRIDE => decorations.matrix_stride = Some(operands.get(3)?),
+                    _ => { }
+                }
+            }
+
+            _ => { }
+        }
+
+        Ok(())
+    }
+
+    fn insert_type(&mut self, operands: &Operands, ty: Type) -> Result<(), ParseError> {
+        let id = operands.get(0)?;
+        self.types.insert(id, ty);
+        self.type_offsets.insert(id, operands.offset);
+        Ok(())
+    }
+
+    /// Returns an error for a type, which is nested too deeply, cyclic or too large.
+    fn invalid_type(&self, type_id: u32) -> ParseError {
+        ParseError::InvalidInstruction(self.type_offsets.get(&type_id).cloned().unwrap_or(HEADER_WORDS))
+    }
+
+    fn reflect(self, header: Header) -> Result<ShaderReflection, ParseError> {
+        let stage_flags = self.entry_points.iter().fold(core::ShaderStageFlags::empty(), |stages, &(ref entry_point, _)| stages | entry_point.stage);
+
+        let mut descriptor_bindings = Vec::new();
+        for variable in &self.variables {
+            if let Some(descriptor_binding) = self.descriptor_binding(variable, stage_flags)? {
+                descriptor_bindings.push(descriptor_binding);
+            }
+        }
+        descriptor_bindings.sort_by_key(|b| (b.set, b.binding));
+
+        let mut push_constant_ranges = Vec::new();
+        for variable in self.variables.iter().filter(|variable| variable.storage_class == STORAGE_CLASS_PUSH_CONSTANT) {
+            if let Some(push_constant_range) = self.push_constant_range(variable, stage_flags)? {
+                push_constant_ranges.push(push_constant_range);
+            }
+        }
+
+        let mut vertex_inputs = Vec::new();
+        for &(ref entry_point, ref interface) in &self.entry_points {
+            if entry_point.stage != core::ShaderStageFlags::VERTEX {
+                continue;
+            }
+
+            for variable in self.variables.iter().filter(|v| interface.contains(&v.id)) {
+                self.vertex_inputs(variable, &mut vertex_inputs)?;
+            }
+        }
+        vertex_inputs.sort_by_key(|i| i.location);
+        vertex_inputs.dedup_by_key(|i| i.location);
+
+        Ok(ShaderReflection {
+            header: header,
+            entry_points: self.entry_points.into_iter().map(|(entry_point, _)| entry_point).collect(),
+            descriptor_bindings: descriptor_bindings,
+            push_constant_ranges: push_constant_ranges,
+            vertex_inputs: vertex_inputs,
+        })
+    }
+
+    fn decorations(&self, id: u32) -> Decorations {
+        self.decorations.get(&id).cloned().unwrap_or_default()
+    }
+
+    fn pointee(&self, pointer_type: u32) -> Option<u32> {
+        match self.types.get(&pointer_type) {
+            Some(&Type::Pointer { pointee }) => Some(pointee),
+            _ => None,
+        }
+    }
+
+    fn descriptor_binding(&self, variable: &Variable, stage_flags: core::ShaderStageFlags) -> Result<Option<DescriptorBinding>, ParseError> {
+        match variable.storage_class {
+            STORAGE_CLASS_UNIFORM_CONSTANT | STORAGE_CLASS_UNIFORM | STORAGE_CLASS_STORAGE_BUFFER => { }
+            _ => return Ok(None),
+        }
+
+        let mut type_id = match self.pointee(variable.pointer_type) {
+            Some(type_id) => type_id,
+            None => return Ok(None),
+        };
+
+        let mut descriptor_count: u32 = 1;
+        let mut specialized_count = false;
+        let mut depth = 0;
+        let ty = loop {
+            let ty = match self.types.get(&type_id) {
+                Some(ty) => ty,
+                None => return Ok(None),
+            };
+
+            let element = match *ty {
+                Type::Array { element, length } => {
+                    descriptor_count = descriptor_count.checked_mul(length.value).ok_or_else(|| self.invalid_type(type_id))?;
+                    specialized_count |= length.specialized;
+                    element
+                }
+
+                Type::RuntimeArray { element } => element,
+                _ => break ty,
+            };
+
+            depth += 1;
+            if depth > MAX_TYPE_DEPTH {
+                return Err(self.invalid_type(type_id));
+            }
+
+            type_id = element;
+        };
+
+        let descriptor_type = match *ty {
+            Type::Sampler => core::DescriptorType::Sampler,
+            Type::SampledImage => core::DescriptorType::CombinedImageSampler,
+            Type::Image { dim: DIM_BUFFER, sampled: 2 } => core::DescriptorType::StorageTexelBuffer,
+            Type::Image { dim: DIM_BUFFER, .. } => core::DescriptorType::UniformTexelBuffer,
+            Type::Image { dim: DIM_SUBPASS_DATA, .. } => core::DescriptorType::InputAttachment,
+            Type::Image { sampled: 2, .. } => core::DescriptorType::StorageImage,
+            Type::Image { .. } => core::DescriptorType::SampledImage,
+            Type::Struct { .. } => {
+                let decorations = self.decorations(type_id);
+                if (variable.storage_class == STORAGE_CLASS_STORAGE_BUFFER) || decorations.buffer_block {
+                    core::DescriptorType::StorageBuffer
+                }
+                else if decorations.block {
+                    core::DescriptorType::UniformBuffer
+                }
+                else {
+                    return Ok(None);
+                }
+            }
+
+            _ => return Ok(None),
+        };
+
+        let decorations = self.decorations(variable.id);
+        Ok(Some(DescriptorBinding {
+            set: decorations.descriptor_set.unwrap_or(0),
+            binding: decorations.binding.unwrap_or(0),
+            descriptor_type: descriptor_type,
+            descriptor_count: descriptor_count,
+            specialized_count: specialized_count,
+            stage_flags: stage_flags,
+            name: self.names.get(&variable.id).cloned(),
+        }))
+    }
+
+    fn push_constant_range(&self, variable: &Variable, stage_flags: core::ShaderStageFlags) -> Result<Option<core::PushConstantRange>, ParseError> {
+        let type_id = match self.pointee(variable.pointer_type) {
+            Some(type_id) => type_id,
+            None => return Ok(None),
+        };
+
+        let members = match self.types.get(&type_id) {
+            Some(&Type::Struct { ref members }) => members,
+            _ => return Ok(None),
+        };
+
+        let mut start = u32::max_value();
+        let mut end = 0;
+        for (index, &member) in members.iter().enumerate() {
+            let decorations = self.member_decorations.get(&(type_id, index as u32)).cloned().unwrap_or_default();
+            let offset = decorations.offset.unwrap_or(0);
+            let size = self.size_of(member, decorations.matrix_stride)?;
+            start = cmp::min(start, offset);
+            end = cmp::max(end, offset.checked_add(size).ok_or_else(|| self.invalid_type(type_id))?);
+        }
+
+        if start >= end {
+            return Ok(None);
+        }
+
+        let size = (end - start).checked_add(3).ok_or_else(|| self.invalid_type(type_id))? & !3;
+        Ok(Some(core::PushConstantRange {
+            stage_flags: stage_flags,
+            offset: start,
+            size: size,
+        }))
+    }
+
+    /// Returns the size in bytes of an explicitly laid out type.
+    fn size_of(&self, type_id: u32, matrix_stride: Option<u32>) -> Result<u32, ParseError> {
+        self.size_of_nested(type_id, matrix_stride, 0)
+    }
+
+    fn size_of_nested(&self, type_id: u32, matrix_stride: Option<u32>, depth: u32) -> Result<u32, ParseError> {
+        if depth > MAX_TYPE_DEPTH {
+            return Err(self.invalid_type(type_id));
+        }
+
+        if let Some(&size) = self.sizes.borrow().get(&(type_id, matrix_stride)) {
+            return Ok(size);
+        }
+
+        let overflow = || self.invalid_type(type_id);
+        let size = match self.types.get(&type_id) {
+            Some(&Type::Bool) => 4,
+            Some(&Type::Int { width, .. }) | Some(&Type::Float { width }) => width / 8,
+            Some(&Type::Vector { component, count }) => count.checked_mul(self.size_of_nested(component, None, depth + 1)?).ok_or_else(overflow)?,
+            Some(&Type::Matrix { column, count }) => {
+                let stride = match matrix_stride {
+                    Some(matrix_stride) => matrix_stride,
+                    None => self.size_of_nested(column, None, depth + 1)?,
+                };
+
+                count.checked_mul(stride).ok_or_else(overflow)?
+            }
+
+            Some(&Type::Array { element, length }) => {
+                let stride = match self.decorations(type_id).array_stride {
+                    Some(array_stride) => array_stride,
+                    None => self.size_of_nested(element, matrix_stride, depth + 1)?,
+                };
+
+                length.value.checked_mul(stride).ok_or_else(overflow)?
+            }
+
+            Some(&Type::Struct { ref members }) => {
+                let mut size = 0;
+                for (index, &member) in members.iter().enumerate() {
+                    let decorations = self.member_decorations.get(&(type_id, index as u32)).cloned().unwrap_or_default();
+                    let member_size = self.size_of_nested(member, decorations.matrix_stride, depth + 1)?;
+                    size = cmp::max(size, decorations.offset.unwrap_or(0).checked_add(member_size).ok_or_else(overflow)?);
+                }
+
+                size
+            }
+
+            _ => 0,
+        };
+
+        self.sizes.borrow_mut().insert((type_id, matrix_stride), size);
+        Ok(size)
+    }
+
+    fn vertex_inputs(&self, variable: &Variable, vertex_inputs: &mut Vec<VertexInput>) -> Result<(), ParseError> {
+        let decorations = self.decorations(variable.id);
+        if (variable.storage_class != STORAGE_CLASS_INPUT) || decorations.built_in {
+            return Ok(());
+        }
+
+        let location = match decorations.location {
+            Some(location) => location,
+            None => return Ok(()),
+        };
+
+        let type_id = match self.pointee(variable.pointer_type) {
+            Some(type_id) => type_id,
+            None => return Ok(()),
+        };
+
+        // Matrices and arrays occupy consecutive locations.
+        let (element, count) = match self.types.get(&type_id) {
+            Some(&Type::Matrix { column, count }) => (column, count),
+            Some(&Type::Array { element, length }) => (element, length.value),
+            _ => (type_id, 1),
+        };
+
+        let locations = self.location_count(element);
+        for i in 0..count {
+            let location = i.checked_mul(locations).and_then(|offset| location.checked_add(offset)).ok_or_else(|| self.invalid_type(type_id))?;
+            vertex_inputs.push(VertexInput {
+                location: location,
+                format: self.format_of(element),
+                name: self.names.get(&variable.id).cloned(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of locations occupied by a scalar or vector type.
+    fn location_count(&self, type_id: u32) -> u32 {
+        match self.types.get(&type_id) {
+            Some(&Type::Vector { component, count }) if count > 2 && self.size_of(component, None) == Ok(8) => 2,
+            _ => 1,
+        }
+    }
+
+    fn format_of(&self, type_id: u32) -> Option<core::Format> {
+        let (component, count) = match self.types.get(&type_id) {
+            Some(&Type::Vector { component, count }) => (component, count),
+            _ => (type_id, 1),
+        };
+
+        let formats = match self.types.get(&component) {
+            Some(&Type::Float { width: 16 }) => [core::Format::R16_SFloat, core::Format::R16G16_SFloat, core::Format::R16G16B16_SFloat, core::Format::R16G16B16A16_SFloat],
+            Some(&Type::Float { width: 32 }) => [core::Format::R32_SFloat, core::Format::R32G32_SFloat, core::Format::R32G32B32_SFloat, core::Format::R32G32B32A32_SFloat],
+            Some(&Type::Float { width: 64 }) => [core::Format::R64_SFloat, core::Format::R64G64_SFloat, core::Format::R64G64B64_SFloat, core::Format::R64G64B64A64_SFloat],
+            Some(&Type::Int { width: 32, signed: true }) => [core::Format::R32_SInt, core::Format::R32G32_SInt, core::Format::R32G32B32_SInt, core::Format::R32G32B32A32_SInt],
+            Some(&Type::Int { width: 32, signed: false }) => [core::Format::R32_UInt, core::Format::R32G32_UInt, core::Format::R32G32B32_UInt, core::Format::R32G32B32A32_UInt],
+            Some(&Type::Int { width: 64, signed: true }) => [core::Format::R64_SInt, core::Format::R64G64_SInt, core::Format::R64G64B64_SInt, core::Format::R64G64B64A64_SInt],
+            Some(&Type::Int { width: 64, signed: false }) => [core::Format::R64_UInt, core::Format::R64G64_UInt, core::Format::R64G64B64_UInt, core::Format::R64G64B64A64_UInt],
+            _ => return None,
+        };
+
+        formats.get(count.wrapping_sub(1) as usize).cloned()
+    }
+}
+
+fn stage_from_execution_model(execution_model: u32) -> core::ShaderStageFlags {
+    match execution_model {
+        0 => core::ShaderStageFlags::VERTEX,
+        1 => core::ShaderStageFlags::TESSELLATION_CONTROL,
+        2 => core::ShaderStageFlags::TESSELLATION_EVALUATION,
+        3 => core::ShaderStageFlags::GEOMETRY,
+        4 => core::ShaderStageFlags::FRAGMENT,
+        5 => core::ShaderStageFlags::COMPUTE,
+        _ => core::ShaderStageFlags::empty(),
+    }
+}
@@ -0,0 +1,523 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! Simplified pipeline barriers.
+//!
+//! Instead of filling `core::ImageMemoryBarrier` and `core::BufferMemoryBarrier` by hand,
+//! barriers are described by the kind of accesses before and after them (e.g.
+//! `AccessType::TransferWrite` followed by
+//! `AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer`). Pipeline stages, access
+//! masks and image layouts are derived from the access types.
+//!
+//! Barriers are recorded with
+//! [`CommandBuffer::barrier`](../core/struct.CommandBuffer.html#method.barrier).
+//!
+//! The approach follows the
+//! [simple Vulkan synchronization](https://github.com/Tobski/simple_vulkan_synchronization)
+//! library.
+
+use core;
+
+/// A kind of access to a buffer or image.
+///
+/// Variants up to and including `Present` only read, all others write.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AccessType {
+    /// No access. Useful as the previous access of newly created resources.
+    None,
+
+    /// Read as an indirect buffer for drawing or dispatch.
+    IndirectBuffer,
+
+    /// Read as an index buffer.
+    IndexBuffer,
+
+    /// Read as a vertex buffer.
+    VertexBuffer,
+
+    /// Read as a uniform buffer in a vertex shader.
+    VertexShaderReadUniformBuffer,
+
+    /// Read as a sampled image or uniform texel buffer in a vertex shader.
+    VertexShaderReadSampledImageOrUniformTexelBuffer,
+
+    /// Read as any other resource in a vertex shader.
+    VertexShaderReadOther,
+
+    /// Read as a uniform buffer in a tessellation control shader.
+    TessellationControlShaderReadUniformBuffer,
+
+    /// Read as a sampled image or uniform texel buffer in a tessellation control shader.
+    TessellationControlShaderReadSampledImageOrUniformTexelBuffer,
+
+    /// Read as any other resource in a tessellation control shader.
+    TessellationControlShaderReadOther,
+
+    /// Read as a uniform buffer in a tessellation evaluation shader.
+    TessellationEvaluationShaderReadUniformBuffer,
+
+    /// Read as a sampled image or uniform texel buffer in a tessellation evaluation shader.
+    TessellationEvaluationShaderReadSampledImageOrUniformTexelBuffer,
+
+    /// Read as any other resource in a tessellation evaluation shader.
+    TessellationEvaluationShaderReadOther,
+
+    /// Read as a uniform buffer in a geometry shader.
+    GeometryShaderReadUniformBuffer,
+
+    /// Read as a sampled image or uniform texel buffer in a geometry shader.
+    GeometryShaderReadSampledImageOrUniformTexelBuffer,
+
+    /// Read as any other resource in a geometry shader.
+    GeometryShaderReadOther,
+
+    /// Read as a uniform buffer in a fragment shader.
+    FragmentShaderReadUniformBuffer,
+
+    /// Read as a sampled image or uniform texel buffer in a fragment shader.
+    FragmentShaderReadSampledImageOrUniformTexelBuffer,
+
+    /// Read as a color input attachment in a fragment shader.
+    FragmentShaderReadColorInputAttachment,
+
+    /// Read as a depth/stencil input attachment in a fragment shader.
+    FragmentShaderReadDepthStencilInputAttachment,
+
+    /// Read as any other resource in a fragment shader.
+    FragmentShaderReadOther,
+
+    /// Read as a color attachment, e.g. for blending.
+    ColorAttachmentRead,
+
+    /// Read as a depth/stencil attachment during depth and stencil tests.
+    DepthStencilAttachmentRead,
+
+    /// Read as a uniform buffer in a compute shader.
+    ComputeShaderReadUniformBuffer,
+
+    /// Read as a sampled image or uniform texel buffer in a compute shader.
+    ComputeShaderReadSampledImageOrUniformTexelBuffer,
+
+    /// Read as any other resource in a compute shader.
+    ComputeShaderReadOther,
+
+    /// Read as a uniform buffer in any shader.
+    AnyShaderReadUniformBuffer,
+
+    /// Read as a uniform buffer in any shader or as a vertex buffer.
+    AnyShaderReadUniformBufferOrVertexBuffer,
+
+    /// Read as a sampled image or uniform texel buffer in any shader.
+    AnyShaderReadSampledImageOrUniformTexelBuffer,
+
+    /// Read as any other resource in any shader.
+    AnyShaderReadOther,
+
+    /// Read as the source of a transfer command.
+    TransferRead,
+
+    /// Read on the host.
+    HostRead,
+
+    /// Read by the presentation engine.
+    Present,
+
+    /// Written in a vertex shader.
+    VertexShaderWrite,
+
+    /// Written in a tessellation control shader.
+    TessellationControlShaderWrite,
+
+    /// Written in a tessellation evaluation shader.
+    TessellationEvaluationShaderWrite,
+
+    /// Written in a geometry shader.
+    GeometryShaderWrite,
+
+    /// Written in a fragment shader.
+    FragmentShaderWrite,
+
+    /// Written as a color attachment.
+    ColorAttachmentWrite,
+
+    /// Written as a depth/stencil attachment.
+    DepthStencilAttachmentWrite,
+
+    /// Written in a compute shader.
+    ComputeShaderWrite,
+
+    /// Written in any shader.
+    AnyShaderWrite,
+
+    /// Written as the destination of a transfer command.
+    TransferWrite,
+
+    /// Written on the host before the image is first used by the device.
+    HostPreinitialized,
+
+    /// Written on the host.
+    HostWrite,
+
+    /// Read and written as a color attachment, e.g. for blending.
+    ColorAttachmentReadWrite,
+
+    /// Any read or write. This is a very conservative access type and should be avoided.
+    General,
+}
+
+/// Pipeline stages, access mask and image layout of an `AccessType`.
+struct AccessInfo {
+    stage_mask: core::PipelineStageFlags,
+    access_mask: core::AccessFlags,
+    layout: core::ImageLayout,
+}
+
+impl AccessType {
+    fn info(self) -> AccessInfo {
+        use core::AccessFlags as A;
+        use core::ImageLayout as L;
+        use core::PipelineStageFlags as S;
+
+        let (stage_mask, access_mask, layout) = match self {
+            AccessType::None => (S::empty(), A::empty(), L::Undefined),
+            AccessType::IndirectBuffer => (S::DRAW_INDIRECT, A::INDIRECT_COMMAND_READ, L::Undefined),
+            AccessType::IndexBuffer => (S::VERTEX_INPUT, A::INDEX_READ, L::Undefined),
+            AccessType::VertexBuffer => (S::VERTEX_INPUT, A::VERTEX_ATTRIBUTE_READ, L::Undefined),
+            AccessType::VertexShaderReadUniformBuffer => (S::VERTEX_SHADER, A::UNIFORM_READ, L::Undefined),
+            AccessType::VertexShaderReadSampledImageOrUniformTexelBuffer => (S::VERTEX_SHADER, A::SHADER_READ, L::ShaderReadOnlyOptimal),
+            AccessType::VertexShaderReadOther => (S::VERTEX_SHADER, A::SHADER_READ, L::General),
+            AccessType::TessellationControlShaderReadUniformBuffer => (S::TESSELLATION_CONTROL_SHADER, A::UNIFORM_READ, L::Undefined),
+            AccessType::TessellationControlShaderReadSampledImageOrUniformTexelBuffer => (S::TESSELLATION_CONTROL_SHADER, A::SHADER_READ, L::ShaderReadOnlyOptimal),
+            AccessType::TessellationControlShaderReadOther => (S::TESSELLATION_CONTROL_SHADER, A::SHADER_READ, L::General),
+            AccessType::TessellationEvaluationShaderReadUniformBuffer => (S::TESSELLATION_EVALUATION_SHADER, A::UNIFORM_READ, L::Undefined),
+            AccessType::TessellationEvaluationShaderReadSampledImageOrUniformTexelBuffer => (S::TESSELLATION_EVALUATION_SHADER, A::SHADER_READ, L::ShaderReadOnlyOptimal),
+            AccessType::TessellationEvaluationShaderReadOther => (S::TESSELLATION_EVALUATION_SHADER, A::SHADER_READ, L::General),
+            AccessType::GeometryShaderReadUniformBuffer => (S::GEOMETRY_SHADER, A::UNIFORM_READ, L::Undefined),
+            AccessType::GeometryShaderReadSampledImageOrUniformTexelBuffer => (S::GEOMETRY_SHADER, A::SHADER_READ, L::ShaderReadOnlyOptimal),
+            AccessType::GeometryShaderReadOther => (S::GEOMETRY_SHADER, A::SHADER_READ, L::General),
+            AccessType::FragmentShaderReadUniformBuffer => (S::FRAGMENT_SHADER, A::UNIFORM_READ, L::Undefined),
+            AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer => (S::FRAGMENT_SHADER, A::SHADER_READ, L::ShaderReadOnlyOptimal),
+            AccessType::FragmentShaderReadColorInputAttachment => (S::FRAGMENT_SHADER, A::INPUT_ATTACHMENT_READ, L::ShaderReadOnlyOptimal),
+            AccessType::FragmentShaderReadDepthStencilInputAttachment => (S::FRAGMENT_SHADER, A::INPUT_ATTACHMENT_READ, L::DepthStencilReadOnlyOptimal),
+            AccessType::FragmentShaderReadOther => (S::FRAGMENT_SHADER, A::SHADER_READ, L::General),
+            AccessType::ColorAttachmentRead => (S::COLOR_ATTACHMENT_OUTPUT, A::COLOR_ATTACHMENT_READ, L::ColorAttachmentOptimal),
+            AccessType::DepthStencilAttachmentRead => (S::EARLY_FRAGMENT_TESTS | S::LATE_FRAGMENT_TESTS, A::DEPTH_STENCIL_ATTACHMENT_READ, L::DepthStencilReadOnlyOptimal),
+            AccessType::ComputeShaderReadUniformBuffer => (S::COMPUTE_SHADER, A::UNIFORM_READ, L::Undefined),
+            AccessType::ComputeShaderReadSampledImageOrUniformTexelBuffer => (S::COMPUTE_SHADER, A::SHADER_READ, L::ShaderReadOnlyOptimal),
+            AccessType::ComputeShaderReadOther => (S::COMPUTE_SHADER, A::SHADER_READ, L::General),
+            AccessType::AnyShaderReadUniformBuffer => (S::ALL_COMMANDS, A::UNIFORM_READ, L::Undefined),
+            AccessType::AnyShaderReadUniformBufferOrVertexBuffer => (S::ALL_COMMANDS, A::UNIFORM_READ | A::VERTEX_ATTRIBUTE_READ, L::Undefined),
+            AccessType::AnyShaderReadSampledImageOrUniformTexelBuffer => (S::ALL_COMMANDS, A::SHADER_READ, L::ShaderReadOnlyOptimal),
+            AccessType::AnyShaderReadOther => (S::ALL_COMMANDS, A::SHADER_READ, L::General),
+            AccessType::TransferRead => (S::TRANSFER, A::TRANSFER_READ, L::TransferSrcOptimal),
+            AccessType::HostRead => (S::HOST, A::HOST_READ, L::General),
+            AccessType::Present => (S::empty(), A::empty(), L::PresentSrcKhr),
+            AccessType::VertexShaderWrite => (S::VERTEX_SHADER, A::SHADER_WRITE, L::General),
+            AccessType::TessellationControlShaderWrite => (S::TESSELLATION_CONTROL_SHADER, A::SHADER_WRITE, L::General),
+            AccessType::TessellationEvaluationShaderWrite => (S::TESSELLATION_EVALUATION_SHADER, A::SHADER_WRITE, L::General),
+            AccessType::GeometryShaderWrite => (S::GEOMETRY_SHADER, A::SHADER_WRITE, L::General),
+            AccessType::FragmentShaderWrite => (S::FRAGMENT_SHADER, A::SHADER_WRITE, L::General),
+            AccessType::ColorAttachmentWrite => (S::COLOR_ATTACHMENT_OUTPUT, A::COLOR_ATTACHMENT_WRITE, L::ColorAttachmentOptimal),
+            AccessType::DepthStencilAttachmentWrite => (S::EARLY_FRAGMENT_TESTS | S::LATE_FRAGMENT_TESTS, A::DEPTH_STENCIL_ATTACHMENT_WRITE, L::DepthStencilAttachmentOptimal),
+            AccessType::ComputeShaderWrite => (S::COMPUTE_SHADER, A::SHADER_WRITE, L::General),
+            AccessType::AnyShaderWrite => (S::ALL_COMMANDS, A::SHADER_WRITE, L::General),
+            AccessType::TransferWrite => (S::TRANSFER, A::TRANSFER_WRITE, L::TransferDstOptimal),
+            AccessType::HostPreinitialized => (S::HOST, A::HOST_WRITE, L::Preinitialized),
+            AccessType::HostWrite => (S::HOST, A::HOST_WRITE, L::General),
+            AccessType::ColorAttachmentReadWrite => (S::COLOR_ATTACHMENT_OUTPUT, A::COLOR_ATTACHMENT_READ | A::COLOR_ATTACHMENT_WRITE, L::ColorAttachmentOptimal),
+            AccessType::General => (S::ALL_COMMANDS, A::MEMORY_READ | A::MEMORY_WRITE, L::General),
+        };
+
+        AccessInfo {
+            stage_mask: stage_mask,
+            access_mask: access_mask,
+            layout: layout,
+        }
+    }
+
+    /// Returns the pipeline stages, in which the access happens.
+    pub fn stage_mask(self) -> core::PipelineStageFlags {
+        self.info().stage_mask
+    }
+
+    /// Returns the access mask of the access.
+    pub fn access_mask(self) -> core::AccessFlags {
+        self.info().access_mask
+    }
+
+    /// Returns the optimal image layout for the access.
+    ///
+    /// `ImageLayout::Undefined` is returned for access types, which apply only to buffers.
+    pub fn image_layout(self) -> core::ImageLayout {
+        self.info().layout
+    }
+
+    /// Returns `true`, if the access writes.
+    pub fn is_write(self) -> bool {
+        match self {
+            AccessType::VertexShaderWrite |
+            AccessType::TessellationControlShaderWrite |
+            AccessType::TessellationEvaluationShaderWrite |
+            AccessType::GeometryShaderWrite |
+            AccessType::FragmentShaderWrite |
+            AccessType::ColorAttachmentWrite |
+            AccessType::DepthStencilAttachmentWrite |
+            AccessType::ComputeShaderWrite |
+            AccessType::AnyShaderWrite |
+            AccessType::TransferWrite |
+            AccessType::HostPreinitialized |
+            AccessType::HostWrite |
+            AccessType::ColorAttachmentReadWrite |
+            AccessType::General => true,
+            _ => false,
+        }
+    }
+}
+
+/// Selects between optimal layouts and `ImageLayout::General` for image barriers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ImageLayoutHint {
+    /// Use the optimal layout of each access type.
+    Optimal,
+
+    /// Use `ImageLayout::General` for all access types except `AccessType::None`,
+    /// `AccessType::Present` and `AccessType::HostPreinitialized`.
+    General,
+}
+
+impl Default for ImageLayoutHint {
+    fn default() -> Self {
+        ImageLayoutHint::Optimal
+    }
+}
+
+/// A barrier, which applies to all resources.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GlobalBarrier {
+    pub prev_accesses: Vec<AccessType>,
+    pub next_accesses: Vec<AccessType>,
+}
+
+impl GlobalBarrier {
+    /// Creates a global barrier between `prev_accesses` and `next_accesses`.
+    pub fn new(prev_accesses: &[AccessType], next_accesses: &[AccessType]) -> Self {
+        GlobalBarrier {
+            prev_accesses: prev_accesses.to_vec(),
+            next_accesses: next_accesses.to_vec(),
+        }
+    }
+}
+
+/// A barrier for a range of a buffer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferBarrier {
+    pub prev_accesses: Vec<AccessType>,
+    pub next_accesses: Vec<AccessType>,
+    pub src_queue_family_index: core::QueueFamilyIndex,
+    pub dst_queue_family_index: core::QueueFamilyIndex,
+    pub buffer: core::Buffer,
+    pub offset: u64,
+    pub size: core::OptionalDeviceSize,
+}
+
+impl BufferBarrier {
+    /// Creates a barrier for the whole `buffer` without a queue family ownership transfer.
+    pub fn new(buffer: core::Buffer, prev_accesses: &[AccessType], next_accesses: &[AccessType]) -> Self {
+        BufferBarrier {
+            prev_accesses: prev_accesses.to_vec(),
+            next_accesses: next_accesses.to_vec(),
+            src_queue_family_index: core::QueueFamilyIndex::Ignored,
+            dst_queue_family_index: core::QueueFamilyIndex::Ignored,
+            buffer: buffer,
+            offset: 0,
+            size: core::OptionalDeviceSize::WholeSize,
+        }
+    }
+}
+
+/// A barrier for a subresource range of an image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageBarrier {
+    pub prev_accesses: Vec<AccessType>,
+    pub next_accesses: Vec<AccessType>,
+    pub prev_layout: ImageLayoutHint,
+    pub next_layout: ImageLayoutHint,
+
+    /// If `true`, the contents of the image are not preserved and the old layout is
+    /// `ImageLayout::Undefined`.
+    pub discard_contents: bool,
+
+    pub src_queue_family_index: core::QueueFamilyIndex,
+    pub dst_queue_family_index: core::QueueFamilyIndex,
+    pub image: core::Image,
+    pub subresource_range: core::ImageSubresourceRange,
+}
+
+impl ImageBarrier {
+    /// Creates a barrier for `subresource_range` of `image` with optimal layouts and without a
+    /// queue family ownership transfer.
+    pub fn new(image: core::Image, subresource_range: core::ImageSubresourceRange, prev_accesses: &[AccessType], next_accesses: &[AccessType]) -> Self {
+        ImageBarrier {
+            prev_accesses: prev_accesses.to_vec(),
+            next_accesses: next_accesses.to_vec(),
+            prev_layout: ImageLayoutHint::Optimal,
+            next_layout: ImageLayoutHint::Optimal,
+            discard_contents: false,
+            src_queue_family_index: core::QueueFamilyIndex::Ignored,
+            dst_queue_family_index: core::QueueFamilyIndex::Ignored,
+            image: image,
+            subresource_range: subresource_range,
+        }
+    }
+}
+
+/// The parameters of a `pipeline_barrier` call derived from simplified barriers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineBarrier {
+    pub src_stage_mask: core::PipelineStageFlags,
+    pub dst_stage_mask: core::PipelineStageFlags,
+    pub memory_barrier: Option<core::MemoryBarrier>,
+    pub buffer_memory_barriers: Vec<core::BufferMemoryBarrier>,
+    pub image_memory_barriers: Vec<core::ImageMemoryBarrier>,
+}
+
+impl PipelineBarrier {
+    /// Derives pipeline stages, access masks and image layouts from the given barriers.
+    ///
+    /// Memory dependencies are only created for previous accesses, which write. Reads are only
+    /// covered by execution dependencies. A global memory barrier is emitted only if a memory
+    /// dependency is required.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the previous or next accesses of an image barrier require different layouts.
+    pub fn new(global_barrier: Option<&GlobalBarrier>, buffer_barriers: &[BufferBarrier], image_barriers: &[ImageBarrier]) -> Self {
+        let mut src_stage_mask = core::PipelineStageFlags::empty();
+        let mut dst_stage_mask = core::PipelineStageFlags::empty();
+
+        let memory_barrier = global_barrier.and_then(|barrier| {
+            let (src_access_mask, dst_access_mask) = access_masks(&barrier.prev_accesses, &barrier.next_accesses, false, &mut src_stage_mask, &mut dst_stage_mask);
+            if !src_access_mask.is_empty() || !dst_access_mask.is_empty() {
+                Some(core::MemoryBarrier {
+                    src_access_mask: src_access_mask,
+                    dst_access_mask: dst_access_mask,
+                    chain: None,
+                })
+            }
+            else {
+                None
+            }
+        });
+
+        let buffer_memory_barriers = buffer_barriers.iter().map(|barrier| {
+            let (src_access_mask, dst_access_mask) = access_masks(&barrier.prev_accesses, &barrier.next_accesses, false, &mut src_stage_mask, &mut dst_stage_mask);
+
+            core::BufferMemoryBarrier {
+                src_access_mask: src_access_mask,
+                dst_access_mask: dst_access_mask,
+                src_queue_family_index: barrier.src_queue_family_index,
+                dst_queue_family_index: barrier.dst_queue_family_index,
+                buffer: barrier.buffer.clone(),
+                offset: barrier.offset,
+                size: barrier.size,
+                chain: None,
+            }
+        }).collect();
+
+        let image_memory_barriers = image_barriers.iter().map(|barrier| {
+            let old_layout = if barrier.discard_contents {
+                core::ImageLayout::Undefined
+            }
+            else {
+                image_layout(&barrier.prev_accesses, barrier.prev_layout)
+            };
+            let new_layout = image_layout(&barrier.next_accesses, barrier.next_layout);
+            let (src_access_mask, dst_access_mask) = access_masks(&barrier.prev_accesses, &barrier.next_accesses, old_layout != new_layout, &mut src_stage_mask, &mut dst_stage_mask);
+
+            core::ImageMemoryBarrier {
+                src_access_mask: src_access_mask,
+                dst_access_mask: dst_access_mask,
+                old_layout: old_layout,
+                new_layout: new_layout,
+                src_queue_family_index: barrier.src_queue_family_index,
+                dst_queue_family_index: barrier.dst_queue_family_index,
+                image: barrier.image.clone(),
+                subresource_range: barrier.subresource_range,
+                chain: None,
+            }
+        }).collect();
+
+        if src_stage_mask.is_empty() {
+            src_stage_mask = core::PipelineStageFlags::TOP_OF_PIPE;
+        }
+
+        if dst_stage_mask.is_empty() {
+            dst_stage_mask = core::PipelineStageFlags::BOTTOM_OF_PIPE;
+        }
+
+        PipelineBarrier {
+            src_stage_mask: src_stage_mask,
+            dst_stage_mask: dst_stage_mask,
+            memory_barrier: memory_barrier,
+            buffer_memory_barriers: buffer_memory_barriers,
+            image_memory_barriers: image_memory_barriers,
+        }
+    }
+}
+
+/// Accumulates the stages of all accesses and returns the source and destination access masks.
+///
+/// Only writes are made available. Visibility is only required, if there was a write or if a
+/// layout transition takes place.
+fn access_masks(prev_accesses: &[AccessType], next_accesses: &[AccessType], layout_transition: bool, src_stage_mask: &mut core::PipelineStageFlags, dst_stage_mask: &mut core::PipelineStageFlags) -> (core::AccessFlags, core::AccessFlags) {
+    let mut src_access_mask = core::AccessFlags::empty();
+    for &access in prev_accesses {
+        let info = access.info();
+        *src_stage_mask |= info.stage_mask;
+        if access.is_write() {
+            src_access_mask |= info.access_mask;
+        }
+    }
+
+    let mut dst_access_mask = core::AccessFlags::empty();
+    for &access in next_accesses {
+        let info = access.info();
+        *dst_stage_mask |= info.stage_mask;
+        if !src_access_mask.is_empty() || layout_transition {
+            dst_access_mask |= info.access_mask;
+        }
+    }
+
+    (src_access_mask, dst_access_mask)
+}
+
+fn image_layout(accesses: &[AccessType], hint: ImageLayoutHint) -> core::ImageLayout {
+    let mut layout = None;
+    for &access in accesses {
+        let access_layout = match (access, hint) {
+            (AccessType::None, _) |
+            (AccessType::Present, _) |
+            (AccessType::HostPreinitialized, _) |
+            (_, ImageLayoutHint::Optimal) => access.info().layout,
+            (_, ImageLayoutHint::General) => core::ImageLayout::General,
+        };
+
+        match layout {
+            Some(layout) => assert_eq!(layout, access_layout, "access types require different image layouts"),
+            None => layout = Some(access_layout),
+        }
+    }
+
+    layout.unwrap_or(core::ImageLayout::Undefined)
+}
@@ -0,0 +1,712 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! Uploads of data to device-local buffers and images through a staging buffer.
+//!
+//! An [`UploadManager`](struct.UploadManager.html) owns a host-visible staging buffer, which is
+//! used as a ring buffer. Uploads are copied into the ring and recorded into a command buffer,
+//! which is submitted by [`flush`](struct.UploadManager.html#method.flush) or when the ring runs
+//! out of space. Space is reclaimed as soon as the fence of a submission is signalled.
+//!
+//! Uploads are either submitted to the queue, on which the resources are used, or to a dedicated
+//! transfer queue. In the latter case, `flush` returns a semaphore, which must be waited on before
+//! the uploaded resources are used. If the transfer queue belongs to a different queue family,
+//! ownership of the resources is released on the transfer queue and must be acquired with
+//! [`record_acquire_barriers`](struct.UploadManager.html#method.record_acquire_barriers).
+
+use core::{self, Buffer, CommandBuffer, CommandPool, Device, Fence, Image, MappedMemory, Queue, Semaphore};
+use std::collections::VecDeque;
+use std::error;
+use std::fmt;
+use std::ptr;
+use sync::AccessType;
+
+/// Alignment of image uploads in the staging buffer.
+///
+/// The offset of a buffer to image copy must be a multiple of the texel block size of the
+/// format. 96 is a multiple of all texel block sizes up to 32 bytes, including 3 byte formats.
+const IMAGE_ALIGNMENT: u64 = 96;
+
+/// Alignment of buffer uploads in the staging buffer.
+const BUFFER_ALIGNMENT: u64 = 4;
+
+/// Error type for uploads.
+#[derive(Debug)]
+pub enum UploadError {
+    /// The data of an image upload is larger than the staging buffer.
+    TooLarge {
+        size: u64,
+        capacity: u64,
+    },
+
+    /// The requested staging buffer size is smaller than the minimum size.
+    StagingSizeTooSmall {
+        size: u64,
+        minimum: u64,
+    },
+
+    /// Allocating the staging buffer failed.
+    AllocationError(core::AllocationError),
+
+    /// Flushing the staging buffer failed.
+    MappedMemoryError(core::MappedMemoryError),
+
+    /// A Vulkan function returned an error.
+    VulkanError(core::Error),
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UploadError::TooLarge { size, capacity } => write!(f, "upload of {} bytes exceeds the staging buffer size of {} bytes", size, capacity),
+            UploadError::StagingSizeTooSmall { size, minimum } => write!(f, "staging buffer size of {} bytes is smaller than the minimum of {} bytes", size, minimum),
+            UploadError::AllocationError(ref e) => write!(f, "{}", e),
+            UploadError::MappedMemoryError(ref e) => write!(f, "{}", e),
+            UploadError::VulkanError(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for UploadError {
+    fn description(&self) -> &str {
+        match *self {
+            UploadError::TooLarge { .. } => "TooLarge",
+            UploadError::StagingSizeTooSmall { .. } => "StagingSizeTooSmall",
+            UploadError::AllocationError(ref e) => e.description(),
+            UploadError::MappedMemoryError(ref e) => e.description(),
+            UploadError::VulkanError(ref e) => e.description(),
+        }
+    }
+}
+
+impl From<core::AllocationError> for UploadError {
+    fn from(e: core::AllocationError) -> Self {
+        UploadError::AllocationError(e)
+    }
+}
+
+impl From<core::MappedMemoryError> for UploadError {
+    fn from(e: core::MappedMemoryError) -> Self {
+        UploadError::MappedMemoryError(e)
+    }
+}
+
+impl From<core::Error> for UploadError {
+    fn from(e: core::Error) -> Self {
+        UploadError::VulkanError(e)
+    }
+}
+
+/// A semaphore returned by [`UploadManager::flush`](struct.UploadManager.html#method.flush), if
+/// uploads are submitted to a dedicated transfer queue.
+#[derive(Debug, Clone)]
+pub struct UploadSemaphore {
+    /// Signalled, when all flushed uploads have completed.
+    pub semaphore: Semaphore,
+
+    /// The stages, at which the semaphore must be waited on. These are the stages of all next
+    /// accesses of the flushed uploads.
+    pub wait_dst_stage_mask: core::PipelineStageFlags,
+}
+
+/// A command buffer and fence, which are recorded or in flight.
+#[derive(Debug)]
+struct Batch {
+    command_buffer: CommandBuffer,
+    fence: Fence,
+
+    /// Position of the ring head after the last allocation of this batch.
+    end: u64,
+}
+
+/// Barriers, which acquire ownership of uploaded resources on the destination queue family.
+#[derive(Debug, Default)]
+struct AcquireBarriers {
+    dst_stage_mask: core::PipelineStageFlags,
+    buffer_memory_barriers: Vec<core::BufferMemoryBarrier>,
+    image_memory_barriers: Vec<core::ImageMemoryBarrier>,
+}
+
+/// Uploads data to buffers and images through a staging ring buffer.
+#[derive(Debug)]
+pub struct UploadManager {
+    device: Device,
+    queue: Queue,
+    queue_family_index: u32,
+
+    /// Queue family, on which the uploaded resources are used, if uploads are submitted to a
+    /// dedicated transfer queue.
+    dst_queue_family_index: Option<u32>,
+
+    command_pool: CommandPool,
+    staging_buffer: Buffer,
+    mapped: MappedMemory,
+    capacity: u64,
+
+    /// Ring positions. Both grow monotonically, offsets are taken modulo `capacity`.
+    head: u64,
+    tail: u64,
+
+    recording: Option<Batch>,
+    in_flight: VecDeque<Batch>,
+    free: Vec<(CommandBuffer, Fence)>,
+
+    /// `true`, if uploads were submitted to a dedicated transfer queue since the last semaphore.
+    unsignalled: bool,
+    wait_dst_stage_mask: core::PipelineStageFlags,
+    acquire_barriers: AcquireBarriers,
+}
+
+impl UploadManager {
+    /// Creates an upload manager with a staging buffer of `staging_size` bytes.
+    ///
+    /// `staging_size` must be at least 4 bytes, the alignment of buffer uploads.
+    ///
+    /// Uploads are submitted to `queue`, which must belong to `queue_family_index` and on which
+    /// the uploaded resources are used.
+    pub fn new(device: &Device, queue: Queue, queue_family_index: u32, staging_size: u64) -> Result<Self, UploadError> {
+        UploadManager::create(device, queue, queue_family_index, None, staging_size)
+    }
+
+    /// Creates an upload manager, which submits uploads to a dedicated transfer queue.
+    ///
+    /// `transfer_queue` must belong to `transfer_queue_family_index`. The uploaded resources are
+    /// used on a queue of `dst_queue_family_index`. If the two families differ, ownership of
+    /// uploaded resources is transferred, and the resources must have been created with
+    /// `SharingMode::Exclusive`.
+    ///
+    /// Image uploads discard the previous contents of the uploaded subresources, because
+    /// ownership is only transferred from the transfer queue, but never to it.
+    pub fn with_transfer_queue(device: &Device, transfer_queue: Queue, transfer_queue_family_index: u32, dst_queue_family_index: u32, staging_size: u64) -> Result<Self, UploadError> {
+        UploadManager::create(device, transfer_queue, transfer_queue_family_index, Some(dst_queue_family_index), staging_size)
+    }
+
+    fn create(device: &Device, queue: Queue, queue_family_index: u32, dst_queue_family_index: Option<u32>, staging_size: u64) -> Result<Self, UploadError> {
+        if staging_size < BUFFER_ALIGNMENT {
+            return Err(UploadError::StagingSizeTooSmall {
+                size: staging_size,
+                minimum: BUFFER_ALIGNMENT,
+            });
+        }
+
+        let create_info = core::BufferCreateInfo::builder(staging_size, core::BufferUsageFlags::TRANSFER_SRC).build();
+        let staging_buffer = device.create_buffer_with_memory(&create_info, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::HOST_COHERENT)?;
+        let mapped = staging_buffer.memory().unwrap().map(0, core::OptionalDeviceSize::WholeSize, core::MemoryMapFlags::empty())?;
+
+        let create_info = core::CommandPoolCreateInfo {
+            flags: core::CommandPoolCreateFlags::TRANSIENT | core::CommandPoolCreateFlags::RESET_COMMAND_BUFFER,
+            queue_family_index: queue_family_index,
+            chain: None,
+        };
+        let command_pool = device.create_command_pool(&create_info, None)?;
+
+        Ok(UploadManager {
+            device: device.clone(),
+            queue: queue,
+            queue_family_index: queue_family_index,
+            dst_queue_family_index: dst_queue_family_index,
+            command_pool: command_pool,
+            staging_buffer: staging_buffer,
+            mapped: mapped,
+            capacity: staging_size,
+            head: 0,
+            tail: 0,
+            recording: None,
+            in_flight: VecDeque::new(),
+            free: Vec::new(),
+            unsignalled: false,
+            wait_dst_stage_mask: core::PipelineStageFlags::empty(),
+            acquire_barriers: AcquireBarriers::default(),
+        })
+    }
+
+    /// Returns the size of the staging buffer.
+    #[inline]
+    pub fn staging_size(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Returns the staging buffer.
+    #[inline]
+    pub fn staging_buffer(&self) -> &Buffer {
+        &self.staging_buffer
+    }
+
+    /// Returns the number of submissions, which have not completed yet.
+    #[inline]
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Uploads `data` to `buffer` at `offset`.
+    ///
+    /// `next_access` is the first access to the buffer after the upload. Data larger than the
+    /// staging buffer is split into multiple copies.
+    ///
+    /// `buffer` must not be accessed by commands, which were submitted before, but have not
+    /// completed yet.
+    pub fn upload_buffer(&mut self, data: &[u8], buffer: &Buffer, offset: u64, next_access: AccessType) -> Result<(), UploadError> {
+        let chunk_size = self.capacity - self.capacity % BUFFER_ALIGNMENT;
+        let mut written = 0;
+        for chunk in data.chunks(chunk_size as usize) {
+            let staging_offset = self.write(chunk, BUFFER_ALIGNMENT)?;
+            let command_buffer = &self.recording.as_ref().unwrap().command_buffer;
+            command_buffer.copy_buffer(&self.staging_buffer, buffer, &[core::BufferCopy {
+                src_offset: staging_offset,
+                dst_offset: offset + written,
+                size: chunk.len() as u64,
+            }]);
+
+            written += chunk.len() as u64;
+        }
+
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let (src_queue_family_index, dst_queue_family_index) = self.queue_family_indices();
+        let barrier = core::BufferMemoryBarrier {
+            src_access_mask: core::AccessFlags::TRANSFER_WRITE,
+            dst_access_mask: next_access.access_mask(),
+            src_queue_family_index: src_queue_family_index,
+            dst_queue_family_index: dst_queue_family_index,
+            buffer: buffer.clone(),
+            offset: offset,
+            size: core::OptionalDeviceSize::Size(data.len() as u64),
+            chain: None,
+        };
+
+        if self.dst_queue_family_index.is_some() {
+            self.wait_dst_stage_mask |= stage_mask(next_access);
+        }
+
+        if self.transfers_ownership() {
+            let release = core::BufferMemoryBarrier {
+                dst_access_mask: core::AccessFlags::empty(),
+                .. barrier.clone()
+            };
+            self.release(Some(release), None);
+
+            let acquire = core::BufferMemoryBarrier {
+                src_access_mask: core::AccessFlags::empty(),
+                .. barrier
+            };
+            self.acquire_barriers.dst_stage_mask |= stage_mask(next_access);
+            self.acquire_barriers.buffer_memory_barriers.push(acquire);
+        }
+        else if self.dst_queue_family_index.is_some() {
+            // The semaphore makes the data available and visible on the other queue.
+        }
+        else {
+            self.recording.as_ref().unwrap().command_buffer.pipeline_barrier(core::PipelineStageFlags::TRANSFER, stage_mask(next_access), core::DependencyFlags::empty(), None, Some(&[barrier]), None);
+        }
+
+        Ok(())
+    }
+
+    /// Uploads `data` to a region of an image subresource.
+    ///
+    /// `data` must be tightly packed. `next_access` is the first access to the image after the
+    /// upload and determines its layout afterwards.
+    ///
+    /// If layout tracking is enabled for `image`, the subresource is transitioned from its
+    /// tracked layout, otherwise its previous contents are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `next_access` doesn't apply to images.
+    pub fn upload_image(&mut self, data: &[u8], image: &Image, subresource: core::ImageSubresourceLayers, offset: core::Offset3D, extent: core::Extent3D, next_access: AccessType) -> Result<(), UploadError> {
+        let new_layout = next_access.image_layout();
+        assert!(new_layout != core::ImageLayout::Undefined, "next access of an image upload must apply to images");
+
+        if data.len() as u64 > self.capacity {
+            return Err(UploadError::TooLarge {
+                size: data.len() as u64,
+                capacity: self.capacity,
+            });
+        }
+
+        let staging_offset = self.write(data, IMAGE_ALIGNMENT)?;
+
+        let subresource_range = core::ImageSubresourceRange {
+            aspect_mask: subresource.aspect_mask,
+            base_mip_level: subresource.mip_level,
+            level_count: core::OptionalMipLevels::MipLevels(1),
+            base_array_layer: subresource.base_array_layer,
+            layer_count: core::OptionalArrayLayers::ArrayLayers(subresource.layer_count),
+        };
+
+        // Array layers may be in different layouts. Layers with the same layout are transitioned
+        // by a single barrier.
+        let mut old_layouts: Vec<(core::ImageLayout, u32, u32)> = Vec::new();
+        for array_layer in subresource.base_array_layer..(subresource.base_array_layer + subresource.layer_count) {
+            let old_layout = match self.dst_queue_family_index {
+                Some(_) => core::ImageLayout::Undefined,
+                None => image.layout(subresource.mip_level, array_layer).unwrap_or(core::ImageLayout::Undefined),
+            };
+
+            let same_layout = match old_layouts.last() {
+                Some(&(layout, _, _)) => layout == old_layout,
+                None => false,
+            };
+
+            if same_layout {
+                old_layouts.last_mut().unwrap().2 += 1;
+            }
+            else {
+                old_layouts.push((old_layout, array_layer, 1));
+            }
+        }
+
+        let mut src_stage_mask = core::PipelineStageFlags::empty();
+        let mut barriers = Vec::with_capacity(old_layouts.len());
+        for (old_layout, base_array_layer, layer_count) in old_layouts {
+            let src_access_mask = if old_layout == core::ImageLayout::Undefined {
+                src_stage_mask |= core::PipelineStageFlags::TOP_OF_PIPE;
+                core::AccessFlags::empty()
+            }
+            else {
+                src_stage_mask |= core::PipelineStageFlags::ALL_COMMANDS;
+                core::AccessFlags::MEMORY_WRITE
+            };
+
+            barriers.push(core::ImageMemoryBarrier {
+                src_access_mask: src_access_mask,
+                dst_access_mask: core::AccessFlags::TRANSFER_WRITE,
+                old_layout: old_layout,
+                new_layout: core::ImageLayout::TransferDstOptimal,
+                src_queue_family_index: core::QueueFamilyIndex::Ignored,
+                dst_queue_family_index: core::QueueFamilyIndex::Ignored,
+                image: image.clone(),
+                subresource_range: core::ImageSubresourceRange {
+                    base_array_layer: base_array_layer,
+                    layer_count: core::OptionalArrayLayers::ArrayLayers(layer_count),
+                    .. subresource_range
+                },
+                chain: None,
+            });
+        }
+
+        if src_stage_mask.is_empty() {
+            src_stage_mask = core::PipelineStageFlags::TOP_OF_PIPE;
+        }
+
+        let command_buffer = self.recording.as_ref().unwrap().command_buffer.clone();
+        command_buffer.pipeline_barrier(src_stage_mask, core::PipelineStageFlags::TRANSFER, core::DependencyFlags::empty(), None, None, Some(&barriers));
+
+        command_buffer.copy_buffer_to_image(&self.staging_buffer, image, core::ImageLayout::TransferDstOptimal, &[core::BufferImageCopy {
+            buffer_offset: staging_offset,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_subresource: subresource,
+            image_offset: offset,
+            image_extent: extent,
+        }]);
+
+        let (src_queue_family_index, dst_queue_family_index) = self.queue_family_indices();
+        let barrier = core::ImageMemoryBarrier {
+            src_access_mask: core::AccessFlags::TRANSFER_WRITE,
+            dst_access_mask: next_access.access_mask(),
+            old_layout: core::ImageLayout::TransferDstOptimal,
+            new_layout: new_layout,
+            src_queue_family_index: src_queue_family_index,
+            dst_queue_family_index: dst_queue_family_index,
+            image: image.clone(),
+            subresource_range: subresource_range,
+            chain: None,
+        };
+
+        if self.dst_queue_family_index.is_some() {
+            self.wait_dst_stage_mask |= stage_mask(next_access);
+
+            let release = core::ImageMemoryBarrier {
+                dst_access_mask: core::AccessFlags::empty(),
+                .. barrier.clone()
+            };
+            self.release(None, Some(release));
+
+            if self.transfers_ownership() {
+                let acquire = core::ImageMemoryBarrier {
+                    src_access_mask: core::AccessFlags::empty(),
+                    .. barrier
+                };
+                self.acquire_barriers.dst_stage_mask |= stage_mask(next_access);
+                self.acquire_barriers.image_memory_barriers.push(acquire);
+            }
+        }
+        else {
+            command_buffer.pipeline_barrier(core::PipelineStageFlags::TRANSFER, stage_mask(next_access), core::DependencyFlags::empty(), None, None, Some(&[barrier]));
+        }
+
+        Ok(())
+    }
+
+    /// Submits all recorded uploads.
+    ///
+    /// If uploads are submitted to a dedicated transfer queue, a semaphore is returned, which is
+    /// signalled, when all uploads since the last call to `flush` have completed.
+    pub fn flush(&mut self) -> Result<Option<UploadSemaphore>, UploadError> {
+        if self.dst_queue_family_index.is_none() || (!self.unsignalled && self.recording.is_none()) {
+            self.submit(None)?;
+            return Ok(None);
+        }
+
+        let semaphore = self.device.create_semaphore(&core::SemaphoreCreateInfo::default(), None)?;
+        self.submit(Some(semaphore.clone()))?;
+
+        let wait_dst_stage_mask = if !self.wait_dst_stage_mask.is_empty() {
+            self.wait_dst_stage_mask
+        }
+        else {
+            core::PipelineStageFlags::TOP_OF_PIPE
+        };
+
+        self.unsignalled = false;
+        self.wait_dst_stage_mask = core::PipelineStageFlags::empty();
+
+        Ok(Some(UploadSemaphore {
+            semaphore: semaphore,
+            wait_dst_stage_mask: wait_dst_stage_mask,
+        }))
+    }
+
+    /// Records barriers, which acquire ownership of all resources uploaded since the last call.
+    ///
+    /// This is only necessary, if the transfer queue belongs to a different queue family than the
+    /// queue, on which the resources are used. `command_buffer` must belong to that queue family
+    /// and must be submitted after waiting on the semaphore returned by `flush`.
+    pub fn record_acquire_barriers(&mut self, command_buffer: &CommandBuffer) {
+        let acquire_barriers = ::std::mem::replace(&mut self.acquire_barriers, AcquireBarriers::default());
+        if acquire_barriers.buffer_memory_barriers.is_empty() && acquire_barriers.image_memory_barriers.is_empty() {
+            return;
+        }
+
+        let buffer_memory_barriers = if !acquire_barriers.buffer_memory_barriers.is_empty() { Some(&acquire_barriers.buffer_memory_barriers[..]) } else { None };
+        let image_memory_barriers = if !acquire_barriers.image_memory_barriers.is_empty() { Some(&acquire_barriers.image_memory_barriers[..]) } else { None };
+        command_buffer.pipeline_barrier(acquire_barriers.dst_stage_mask, acquire_barriers.dst_stage_mask, core::DependencyFlags::empty(), None, buffer_memory_barriers, image_memory_barriers);
+    }
+
+    /// Waits until all submitted uploads have completed.
+    pub fn wait_idle(&mut self) -> Result<(), UploadError> {
+        while !self.in_flight.is_empty() {
+            self.retire_oldest(true)?;
+        }
+
+        Ok(())
+    }
+
+    fn transfers_ownership(&self) -> bool {
+        match self.dst_queue_family_index {
+            Some(dst_queue_family_index) => dst_queue_family_index != self.queue_family_index,
+            None => false,
+        }
+    }
+
+    fn queue_family_indices(&self) -> (core::QueueFamilyIndex, core::QueueFamilyIndex) {
+        if self.transfers_ownership() {
+            (core::QueueFamilyIndex::Index(self.queue_family_index), core::QueueFamilyIndex::Index(self.dst_queue_family_index.unwrap()))
+        }
+        else {
+            (core::QueueFamilyIndex::Ignored, core::QueueFamilyIndex::Ignored)
+        }
+    }
+
+    /// Records a barrier on the transfer queue, which makes a resource available to the
+    /// semaphore and releases it to the destination queue family, if necessary.
+    fn release(&self, buffer_memory_barrier: Option<core::BufferMemoryBarrier>, image_memory_barrier: Option<core::ImageMemoryBarrier>) {
+        let buffer_memory_barriers: Vec<_> = buffer_memory_barrier.into_iter().collect();
+        let image_memory_barriers: Vec<_> = image_memory_barrier.into_iter().collect();
+        let buffer_memory_barriers = if !buffer_memory_barriers.is_empty() { Some(&buffer_memory_barriers[..]) } else { None };
+        let image_memory_barriers = if !image_memory_barriers.is_empty() { Some(&image_memory_barriers[..]) } else { None };
+
+        let command_buffer = &self.recording.as_ref().unwrap().command_buffer;
+        command_buffer.pipeline_barrier(core::PipelineStageFlags::TRANSFER, core::PipelineStageFlags::BOTTOM_OF_PIPE, core::DependencyFlags::empty(), None, buffer_memory_barriers, image_memory_barriers);
+    }
+
+    /// Copies `data` into the staging buffer and returns its offset.
+    ///
+    /// A command buffer is recording, when this function returns successfully.
+    fn write(&mut self, data: &[u8], alignment: u64) -> Result<u64, UploadError> {
+        let size = data.len() as u64;
+        let offset = loop {
+            if let Some(offset) = self.allocate(size, alignment)? {
+                break offset;
+            }
+
+            // Not enough space: submit the current batch and wait for the oldest one.
+            if self.recording.is_some() {
+                self.submit(None)?;
+            }
+
+            self.retire_oldest(true)?;
+        };
+
+        unsafe {
+            let dst = (self.mapped.as_ptr() as *mut u8).offset(offset as isize);
+            ptr::copy_nonoverlapping(data.as_ptr(), dst, data.len());
+        }
+        self.mapped.flush_range(offset, core::OptionalDeviceSize::Size(size), &None)?;
+
+        if self.recording.is_none() {
+            let (command_buffer, fence) = match self.free.pop() {
+                Some(free) => free,
+                None => {
+                    let allocate_info = core::CommandBufferAllocateInfo {
+                        command_pool: self.command_pool.clone(),
+                        level: core::CommandBufferLevel::Primary,
+                        command_buffer_count: 1,
+                        chain: None,
+                    };
+                    let command_buffer = CommandPool::allocate_command_buffers(&allocate_info)?.remove(0);
+                    let fence = self.device.create_fence(&core::FenceCreateInfo::default(), None)?;
+                    (command_buffer, fence)
+                }
+            };
+
+            command_buffer.begin(&core::CommandBufferBeginInfo {
+                flags: core::CommandBufferUsageFlags::ONE_TIME_SUBMIT,
+                inheritance_info: None,
+                chain: None,
+            })?;
+
+            self.recording = Some(Batch {
+                command_buffer: command_buffer,
+                fence: fence,
+                end: self.head,
+            });
+        }
+
+        self.recording.as_mut().unwrap().end = self.head;
+        Ok(offset)
+    }
+
+    /// Reserves `size` bytes in the ring and returns their offset in the staging buffer.
+    fn allocate(&mut self, size: u64, alignment: u64) -> Result<Option<u64>, UploadError> {
+        // Retire completed submissions to make room.
+        while !self.in_flight.is_empty() && self.in_flight[0].fence.get_status()? {
+            self.retire_oldest(false)?;
+        }
+
+        // Alignment applies to offsets in the staging buffer, not to absolute ring positions.
+        let ring_start = self.head - self.head % self.capacity;
+        let offset = align(self.head % self.capacity, alignment);
+        let start = if offset + size <= self.capacity {
+            ring_start + offset
+        }
+        else {
+            // Skip the rest of the ring and wrap around.
+            ring_start + self.capacity
+        };
+
+        let end = start + size;
+        if end - self.tail > self.capacity {
+            return Ok(None);
+        }
+
+        self.head = end;
+        Ok(Some(start % self.capacity))
+    }
+
+    /// Waits for the oldest submission and frees its space in the ring.
+    ///
+    /// The submission stays in flight, if any of the steps fails, so that it can be retired again
+    /// later.
+    fn retire_oldest(&mut self, wait: bool) -> Result<(), UploadError> {
+        {
+            let batch = match self.in_flight.front() {
+                Some(batch) => batch,
+                None => return Ok(()),
+            };
+
+            if wait {
+                batch.fence.wait_for(core::Timeout::Infinite)?;
+            }
+
+            // The fence is reset last, because waiting for it again must not block.
+            batch.command_buffer.reset(core::CommandBufferResetFlags::empty())?;
+            batch.fence.reset()?;
+        }
+
+        let batch = self.in_flight.pop_front().unwrap();
+        self.tail = batch.end;
+        if self.in_flight.is_empty() && self.recording.is_none() {
+            // The ring is empty, restart at its beginning.
+            self.head = align(self.head, self.capacity);
+            self.tail = self.head;
+        }
+
+        self.free.push((batch.command_buffer, batch.fence));
+        Ok(())
+    }
+
+    fn submit(&mut self, signal_semaphore: Option<Semaphore>) -> Result<(), UploadError> {
+        let batch = self.recording.take();
+        if batch.is_none() && signal_semaphore.is_none() {
+            return Ok(());
+        }
+
+        let command_buffers = match batch {
+            Some(ref batch) => {
+                batch.command_buffer.end()?;
+                vec![batch.command_buffer.clone()]
+            }
+
+            None => vec![],
+        };
+
+        let submit_info = core::SubmitInfo {
+            wait_semaphores: vec![],
+            wait_dst_stage_mask: vec![],
+            command_buffers: command_buffers,
+            signal_semaphores: signal_semaphore.into_iter().collect(),
+            chain: None,
+        };
+
+        match batch {
+            Some(batch) => {
+                self.queue.submit(Some(&[submit_info]), Some(&batch.fence))?;
+                self.in_flight.push_back(batch);
+                if self.dst_queue_family_index.is_some() {
+                    self.unsignalled = true;
+                }
+            }
+
+            None => self.queue.submit(Some(&[submit_info]), None)?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for UploadManager {
+    fn drop(&mut self) {
+        let _ = self.submit(None);
+        let _ = self.wait_idle();
+    }
+}
+
+fn align(offset: u64, alignment: u64) -> u64 {
+    (offset + alignment - 1) / alignment * alignment
+}
+
+/// Returns the stages of `access`, or `BOTTOM_OF_PIPE` for accesses without stages.
+fn stage_mask(access: AccessType) -> core::PipelineStageFlags {
+    let stage_mask = access.stage_mask();
+    if !stage_mask.is_empty() {
+        stage_mask
+    }
+    else {
+        core::PipelineStageFlags::BOTTOM_OF_PIPE
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! Vulkan 1.1 definitions, which are not (yet) provided by vks.
+
+#![allow(non_snake_case)]
+#![allow(non_camel_case_types)]
+#![allow(non_upper_case_globals)]
+#![allow(dead_code)]
+
+use libc::{c_char, c_void};
+use std::fmt;
+use std::mem;
+use std::ptr;
+use vks::khr_get_physical_device_properties2;
+use vks::vk;
+
+pub const VK_API_VERSION_1_1: u32 = (1 << 22) | (1 << 12);
+
+pub const VK_MAX_DEVICE_GROUP_SIZE: usize = 32;
+
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES: vk::VkStructureType = 1000094000;
+pub const VK_STRUCTURE_TYPE_BIND_BUFFER_MEMORY_INFO: vk::VkStructureType = 1000157000;
+pub const VK_STRUCTURE_TYPE_BIND_IMAGE_MEMORY_INFO: vk::VkStructureType = 1000157001;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_GROUP_PROPERTIES: vk::VkStructureType = 1000070000;
+pub const VK_STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO: vk::VkStructureType = 1000070001;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2: vk::VkStructureType = 1000059000;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROPERTIES_2: vk::VkStructureType = 1000059001;
+pub const VK_STRUCTURE_TYPE_FORMAT_PROPERTIES_2: vk::VkStructureType = 1000059002;
+pub const VK_STRUCTURE_TYPE_IMAGE_FORMAT_PROPERTIES_2: vk::VkStructureType = 1000059003;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_IMAGE_FORMAT_INFO_2: vk::VkStructureType = 1000059004;
+pub const VK_STRUCTURE_TYPE_QUEUE_FAMILY_PROPERTIES_2: vk::VkStructureType = 1000059005;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_PROPERTIES_2: vk::VkStructureType = 1000059006;
+pub const VK_STRUCTURE_TYPE_SPARSE_IMAGE_FORMAT_PROPERTIES_2: vk::VkStructureType = 1000059007;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SPARSE_IMAGE_FORMAT_INFO_2: vk::VkStructureType = 1000059008;
+pub const VK_STRUCTURE_TYPE_PROTECTED_SUBMIT_INFO: vk::VkStructureType = 1000145000;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES: vk::VkStructureType = 1000145001;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_PROPERTIES: vk::VkStructureType = 1000145002;
+pub const VK_STRUCTURE_TYPE_DEVICE_QUEUE_INFO_2: vk::VkStructureType = 1000145003;
+
+pub const VK_QUEUE_PROTECTED_BIT: vk::VkQueueFlagBits = 0x00000010;
+pub const VK_MEMORY_PROPERTY_PROTECTED_BIT: vk::VkMemoryPropertyFlagBits = 0x00000020;
+pub const VK_DEVICE_QUEUE_CREATE_PROTECTED_BIT: vk::VkDeviceQueueCreateFlagBits = 0x00000001;
+pub const VK_COMMAND_POOL_CREATE_PROTECTED_BIT: vk::VkCommandPoolCreateFlagBits = 0x00000004;
+pub const VK_BUFFER_CREATE_PROTECTED_BIT: vk::VkBufferCreateFlagBits = 0x00000008;
+pub const VK_IMAGE_CREATE_PROTECTED_BIT: vk::VkImageCreateFlagBits = 0x00000800;
+
+pub type VkSubgroupFeatureFlags = u32;
+pub type VkSubgroupFeatureFlagBits = u32;
+pub const VK_SUBGROUP_FEATURE_BASIC_BIT: VkSubgroupFeatureFlagBits = 0x00000001;
+pub const VK_SUBGROUP_FEATURE_VOTE_BIT: VkSubgroupFeatureFlagBits = 0x00000002;
+pub const VK_SUBGROUP_FEATURE_ARITHMETIC_BIT: VkSubgroupFeatureFlagBits = 0x00000004;
+pub const VK_SUBGROUP_FEATURE_BALLOT_BIT: VkSubgroupFeatureFlagBits = 0x00000008;
+pub const VK_SUBGROUP_FEATURE_SHUFFLE_BIT: VkSubgroupFeatureFlagBits = 0x00000010;
+pub const VK_SUBGROUP_FEATURE_SHUFFLE_RELATIVE_BIT: VkSubgroupFeatureFlagBits = 0x00000020;
+pub const VK_SUBGROUP_FEATURE_CLUSTERED_BIT: VkSubgroupFeatureFlagBits = 0x00000040;
+pub const VK_SUBGROUP_FEATURE_QUAD_BIT: VkSubgroupFeatureFlagBits = 0x00000080;
+pub const VK_SUBGROUP_FEATURE_FLAG_BITS_MAX_ENUM: VkSubgroupFeatureFlagBits = 0x7fffffff;
+
+pub type VkPeerMemoryFeatureFlags = u32;
+pub type VkPeerMemoryFeatureFlagBits = u32;
+pub const VK_PEER_MEMORY_FEATURE_COPY_SRC_BIT: VkPeerMemoryFeatureFlagBits = 0x00000001;
+pub const VK_PEER_MEMORY_FEATURE_COPY_DST_BIT: VkPeerMemoryFeatureFlagBits = 0x00000002;
+pub const VK_PEER_MEMORY_FEATURE_GENERIC_SRC_BIT: VkPeerMemoryFeatureFlagBits = 0x00000004;
+pub const VK_PEER_MEMORY_FEATURE_GENERIC_DST_BIT: VkPeerMemoryFeatureFlagBits = 0x00000008;
+pub const VK_PEER_MEMORY_FEATURE_FLAG_BITS_MAX_ENUM: VkPeerMemoryFeatureFlagBits = 0x7fffffff;
+
+pub type VkCommandPoolTrimFlags = u32;
+pub type VkCommandPoolTrimFlagBits = u32;
+pub const VK_COMMAND_POOL_TRIM_FLAG_BITS_MAX_ENUM: VkCommandPoolTrimFlagBits = 0x7fffffff;
+
+// The promoted *2 structures are identical to their KHR counterparts. Only the names of the
+// structure type constants changed, which share the same values.
+pub type VkPhysicalDeviceFeatures2 = khr_get_physical_device_properties2::VkPhysicalDeviceFeatures2KHR;
+pub type VkPhysicalDeviceProperties2 = khr_get_physical_device_properties2::VkPhysicalDeviceProperties2KHR;
+pub type VkFormatProperties2 = khr_get_physical_device_properties2::VkFormatProperties2KHR;
+pub type VkImageFormatProperties2 = khr_get_physical_device_properties2::VkImageFormatProperties2KHR;
+pub type VkPhysicalDeviceImageFormatInfo2 = khr_get_physical_device_properties2::VkPhysicalDeviceImageFormatInfo2KHR;
+pub type VkQueueFamilyProperties2 = khr_get_physical_device_properties2::VkQueueFamilyProperties2KHR;
+pub type VkPhysicalDeviceMemoryProperties2 = khr_get_physical_device_properties2::VkPhysicalDeviceMemoryProperties2KHR;
+pub type VkSparseImageFormatProperties2 = khr_get_physical_device_properties2::VkSparseImageFormatProperties2KHR;
+pub type VkPhysicalDeviceSparseImageFormatInfo2 = khr_get_physical_device_properties2::VkPhysicalDeviceSparseImageFormatInfo2KHR;
+
+/// See [`VkPhysicalDeviceSubgroupProperties`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceSubgroupProperties)
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkPhysicalDeviceSubgroupProperties {
+    pub sType: vk::VkStructureType,
+    pub pNext: *mut c_void,
+    pub subgroupSize: u32,
+    pub supportedStages: vk::VkShaderStageFlags,
+    pub supportedOperations: VkSubgroupFeatureFlags,
+    pub quadOperationsInAllStages: vk::VkBool32,
+}
+
+impl Default for VkPhysicalDeviceSubgroupProperties {
+    fn default() -> Self {
+        VkPhysicalDeviceSubgroupProperties {
+            sType: VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES,
+            pNext: ptr::null_mut(),
+            subgroupSize: Default::default(),
+            supportedStages: Default::default(),
+            supportedOperations: Default::default(),
+            quadOperationsInAllStages: Default::default(),
+        }
+    }
+}
+
+/// See [`VkPhysicalDeviceProtectedMemoryFeatures`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceProtectedMemoryFeatures)
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkPhysicalDeviceProtectedMemoryFeatures {
+    pub sType: vk::VkStructureType,
+    pub pNext: *mut c_void,
+    pub protectedMemory: vk::VkBool32,
+}
+
+impl Default for VkPhysicalDeviceProtectedMemoryFeatures {
+    fn default() -> Self {
+        VkPhysicalDeviceProtectedMemoryFeatures {
+            sType: VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES,
+            pNext: ptr::null_mut(),
+            protectedMemory: Default::default(),
+        }
+    }
+}
+
+/// See [`VkPhysicalDeviceProtectedMemoryProperties`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceProtectedMemoryProperties)
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkPhysicalDeviceProtectedMemoryProperties {
+    pub sType: vk::VkStructureType,
+    pub pNext: *mut c_void,
+    pub protectedNoFault: vk::VkBool32,
+}
+
+impl Default for VkPhysicalDeviceProtectedMemoryProperties {
+    fn default() -> Self {
+        VkPhysicalDeviceProtectedMemoryProperties {
+            sType: VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_PROPERTIES,
+            pNext: ptr::null_mut(),
+            protectedNoFault: Default::default(),
+        }
+    }
+}
+
+/// See [`VkPhysicalDeviceGroupProperties`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkPhysicalDeviceGroupProperties)
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct VkPhysicalDeviceGroupProperties {
+    pub sType: vk::VkStructureType,
+    pub pNext: *mut c_void,
+    pub physicalDeviceCount: u32,
+    pub physicalDevices: [vk::VkPhysicalDevice; VK_MAX_DEVICE_GROUP_SIZE],
+    pub subsetAllocation: vk::VkBool32,
+}
+
+impl fmt::Debug for VkPhysicalDeviceGroupProperties {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VkPhysicalDeviceGroupProperties")
+            .field("sType", &self.sType)
+            .field("pNext", &self.pNext)
+            .field("physicalDeviceCount", &self.physicalDeviceCount)
+            .field("physicalDevices", &&self.physicalDevices[..])
+            .field("subsetAllocation", &self.subsetAllocation)
+            .finish()
+    }
+}
+
+impl Default for VkPhysicalDeviceGroupProperties {
+    fn default() -> Self {
+        VkPhysicalDeviceGroupProperties {
+            sType: VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_GROUP_PROPERTIES,
+            pNext: ptr::null_mut(),
+            physicalDeviceCount: Default::default(),
+            physicalDevices: [ptr::null_mut(); VK_MAX_DEVICE_GROUP_SIZE],
+            subsetAllocation: Default::default(),
+        }
+    }
+}
+
+/// See [`VkDeviceGroupDeviceCreateInfo`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkDeviceGroupDeviceCreateInfo)
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkDeviceGroupDeviceCreateInfo {
+    pub sType: vk::VkStructureType,
+    pub pNext: *const c_void,
+    pub physicalDeviceCount: u32,
+    pub pPhysicalDevices: *const vk::VkPhysicalDevice,
+}
+
+/// See [`VkDeviceQueueInfo2`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkDeviceQueueInfo2)
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkDeviceQueueInfo2 {
+    pub sType: vk::VkStructureType,
+    pub pNext: *const c_void,
+    pub flags: vk::VkDeviceQueueCreateFlags,
+    pub queueFamilyIndex: u32,
+    pub queueIndex: u32,
+}
+
+/// See [`VkBindBufferMemoryInfo`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkBindBufferMemoryInfo)
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkBindBufferMemoryInfo {
+    pub sType: vk::VkStructureType,
+    pub pNext: *const c_void,
+    pub buffer: vk::VkBuffer,
+    pub memory: vk::VkDeviceMemory,
+    pub memoryOffset: vk::VkDeviceSize,
+}
+
+/// See [`VkBindImageMemoryInfo`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkBindImageMemoryInfo)
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkBindImageMemoryInfo {
+    pub sType: vk::VkStructureType,
+    pub pNext: *const c_void,
+    pub image: vk::VkImage,
+    pub memory: vk::VkDeviceMemory,
+    pub memoryOffset: vk::VkDeviceSize,
+}
+
+/// See [`VkProtectedSubmitInfo`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/html/vkspec.html#VkProtectedSubmitInfo)
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkProtectedSubmitInfo {
+    pub sType: vk::VkStructureType,
+    pub pNext: *const c_void,
+    pub protectedSubmit: vk::VkBool32,
+}
+
+pub type PFN_vkEnumerateInstanceVersion = Option<unsafe extern "system" fn(pApiVersion: *mut u32) -> vk::VkResult>;
+pub type PFN_vkEnumeratePhysicalDeviceGroups = Option<unsafe extern "system" fn(instance: vk::VkInstance, pPhysicalDeviceGroupCount: *mut u32, pPhysicalDeviceGroupProperties: *mut VkPhysicalDeviceGroupProperties) -> vk::VkResult>;
+pub type PFN_vkGetPhysicalDeviceFeatures2 = Option<unsafe extern "system" fn(physicalDevice: vk::VkPhysicalDevice, pFeatures: *mut VkPhysicalDeviceFeatures2)>;
+pub type PFN_vkGetPhysicalDeviceProperties2 = Option<unsafe extern "system" fn(physicalDevice: vk::VkPhysicalDevice, pProperties: *mut VkPhysicalDeviceProperties2)>;
+pub type PFN_vkGetPhysicalDeviceFormatProperties2 = Option<unsafe extern "system" fn(physicalDevice: vk::VkPhysicalDevice, format: vk::VkFormat, pFormatProperties: *mut VkFormatProperties2)>;
+pub type PFN_vkGetPhysicalDeviceImageFormatProperties2 = Option<unsafe extern "system" fn(physicalDevice: vk::VkPhysicalDevice, pImageFormatInfo: *const VkPhysicalDeviceImageFormatInfo2, pImageFormatProperties: *mut VkImageFormatProperties2) -> vk::VkResult>;
+pub type PFN_vkGetPhysicalDeviceQueueFamilyProperties2 = Option<unsafe extern "system" fn(physicalDevice: vk::VkPhysicalDevice, pQueueFamilyPropertyCount: *mut u32, pQueueFamilyProperties: *mut VkQueueFamilyProperties2)>;
+pub type PFN_vkGetPhysicalDeviceMemoryProperties2 = Option<unsafe extern "system" fn(physicalDevice: vk::VkPhysicalDevice, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties2)>;
+pub type PFN_vkGetPhysicalDeviceSparseImageFormatProperties2 = Option<unsafe extern "system" fn(physicalDevice: vk::VkPhysicalDevice, pFormatInfo: *const VkPhysicalDeviceSparseImageFormatInfo2, pPropertyCount: *mut u32, pProperties: *mut VkSparseImageFormatProperties2)>;
+pub type PFN_vkGetDeviceQueue2 = Option<unsafe extern "system" fn(device: vk::VkDevice, pQueueInfo: *const VkDeviceQueueInfo2, pQueue: *mut vk::VkQueue)>;
+pub type PFN_vkTrimCommandPool = Option<unsafe extern "system" fn(device: vk::VkDevice, commandPool: vk::VkCommandPool, flags: VkCommandPoolTrimFlags)>;
+pub type PFN_vkBindBufferMemory2 = Option<unsafe extern "system" fn(device: vk::VkDevice, bindInfoCount: u32, pBindInfos: *const VkBindBufferMemoryInfo) -> vk::VkResult>;
+pub type PFN_vkBindImageMemory2 = Option<unsafe extern "system" fn(device: vk::VkDevice, bindInfoCount: u32, pBindInfos: *const VkBindImageMemoryInfo) -> vk::VkResult>;
+pub type PFN_vkGetDeviceGroupPeerMemoryFeatures = Option<unsafe extern "system" fn(device: vk::VkDevice, heapIndex: u32, localDeviceIndex: u32, remoteDeviceIndex: u32, pPeerMemoryFeatures: *mut VkPeerMemoryFeatureFlags)>;
+
+macro_rules! gen_functions {
+    (
+        pub struct $name:ident [$handle:ty, $get_proc_addr:ty] {
+            $(
+                pub fn $fn:ident( $( $arg:ident: $arg_ty:ty ),* ) $( -> $fn_ret:ty )* ; [$symbol:ident: $ty:ty],
+            )*
+        }
+    ) => (
+        #[derive(Copy, Clone)]
+        pub struct $name {
+            $( pub $symbol: $ty, )*
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let mut debug_struct = f.debug_struct(stringify!($name));
+                $( {
+                    let $symbol = self.$symbol.map(|$symbol| $symbol as *mut c_void);
+                    debug_struct.field(stringify!($symbol), &$symbol);
+                } )*
+                debug_struct.finish()
+            }
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                $name {
+                    $( $symbol: None, )*
+                }
+            }
+
+            pub unsafe fn load(&mut self, get_proc_addr: $get_proc_addr, handle: $handle) {
+                let get_proc_addr = get_proc_addr.expect("get_proc_addr is None");
+                $(
+                    self.$symbol = (get_proc_addr)(handle, concat!(stringify!($fn), '\x00').as_ptr() as *const c_char)
+                        .map(|$symbol| mem::transmute($symbol));
+                )*
+            }
+
+            $(
+                #[inline]
+                pub unsafe fn $fn(&self, $( $arg: $arg_ty ),* ) $( -> $fn_ret )* {
+                    let $symbol = self.$symbol.expect(concat!(stringify!($fn), " is not available"));
+                    ($symbol)($( $arg ),*)
+                }
+            )*
+        }
+    )
+}
+
+gen_functions! {
+    pub struct InstanceFunctions [vk::VkInstance, vk::PFN_vkGetInstanceProcAddr] {
+        pub fn vkEnumeratePhysicalDeviceGroups(instance: vk::VkInstance, pPhysicalDeviceGroupCount: *mut u32, pPhysicalDeviceGroupProperties: *mut VkPhysicalDeviceGroupProperties) -> vk::VkResult; [pfn_vkEnumeratePhysicalDeviceGroups: PFN_vkEnumeratePhysicalDeviceGroups],
+        pub fn vkGetPhysicalDeviceFeatures2(physicalDevice: vk::VkPhysicalDevice, pFeatures: *mut VkPhysicalDeviceFeatures2); [pfn_vkGetPhysicalDeviceFeatures2: PFN_vkGetPhysicalDeviceFeatures2],
+        pub fn vkGetPhysicalDeviceProperties2(physicalDevice: vk::VkPhysicalDevice, pProperties: *mut VkPhysicalDeviceProperties2); [pfn_vkGetPhysicalDeviceProperties2: PFN_vkGetPhysicalDeviceProperties2],
+        pub fn vkGetPhysicalDeviceFormatProperties2(physicalDevice: vk::VkPhysicalDevice, format: vk::VkFormat, pFormatProperties: *mut VkFormatProperties2); [pfn_vkGetPhysicalDeviceFormatProperties2: PFN_vkGetPhysicalDeviceFormatProperties2],
+        pub fn vkGetPhysicalDeviceImageFormatProperties2(physicalDevice: vk::VkPhysicalDevice, pImageFormatInfo: *const VkPhysicalDeviceImageFormatInfo2, pImageFormatProperties: *mut VkImageFormatProperties2) -> vk::VkResult; [pfn_vkGetPhysicalDeviceImageFormatProperties2: PFN_vkGetPhysicalDeviceImageFormatProperties2],
+        pub fn vkGetPhysicalDeviceQueueFamilyProperties2(physicalDevice: vk::VkPhysicalDevice, pQueueFamilyPropertyCount: *mut u32, pQueueFamilyProperties: *mut VkQueueFamilyProperties2); [pfn_vkGetPhysicalDeviceQueueFamilyProperties2: PFN_vkGetPhysicalDeviceQueueFamilyProperties2],
+        pub fn vkGetPhysicalDeviceMemoryProperties2(physicalDevice: vk::VkPhysicalDevice, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties2); [pfn_vkGetPhysicalDeviceMemoryProperties2: PFN_vkGetPhysicalDeviceMemoryProperties2],
+        pub fn vkGetPhysicalDeviceSparseImageFormatProperties2(physicalDevice: vk::VkPhysicalDevice, pFormatInfo: *const VkPhysicalDeviceSparseImageFormatInfo2, pPropertyCount: *mut u32, pProperties: *mut VkSparseImageFormatProperties2); [pfn_vkGetPhysicalDeviceSparseImageFormatProperties2: PFN_vkGetPhysicalDeviceSparseImageFormatProperties2],
+    }
+}
+
+gen_functions! {
+    pub struct DeviceFunctions [vk::VkDevice, vk::PFN_vkGetDeviceProcAddr] {
+        pub fn vkGetDeviceQueue2(device: vk::VkDevice, pQueueInfo: *const VkDeviceQueueInfo2, pQueue: *mut vk::VkQueue); [pfn_vkGetDeviceQueue2: PFN_vkGetDeviceQueue2],
+        pub fn vkTrimCommandPool(device: vk::VkDevice, commandPool: vk::VkCommandPool, flags: VkCommandPoolTrimFlags); [pfn_vkTrimCommandPool: PFN_vkTrimCommandPool],
+        pub fn vkBindBufferMemory2(device: vk::VkDevice, bindInfoCount: u32, pBindInfos: *const VkBindBufferMemoryInfo) -> vk::VkResult; [pfn_vkBindBufferMemory2: PFN_vkBindBufferMemory2],
+        pub fn vkBindImageMemory2(device: vk::VkDevice, bindInfoCount: u32, pBindInfos: *const VkBindImageMemoryInfo) -> vk::VkResult; [pfn_vkBindImageMemory2: PFN_vkBindImageMemory2],
+        pub fn vkGetDeviceGroupPeerMemoryFeatures(device: vk::VkDevice, heapIndex: u32, localDeviceIndex: u32, remoteDeviceIndex: u32, pPeerMemoryFeatures: *mut VkPeerMemoryFeatureFlags); [pfn_vkGetDeviceGroupPeerMemoryFeatures: PFN_vkGetDeviceGroupPeerMemoryFeatures],
+    }
+}
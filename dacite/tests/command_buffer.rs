@@ -0,0 +1,601 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::FromNativeObject;
+use dacite::VulkanObject;
+use dacite::core;
+
+fn create_command_buffers(device: &core::Device, count: u32) -> (core::CommandPool, Vec<core::CommandBuffer>) {
+    let create_info = core::CommandPoolCreateInfo {
+        flags: core::CommandPoolCreateFlags::RESET_COMMAND_BUFFER,
+        queue_family_index: 0,
+        chain: None,
+    };
+    let command_pool = device.create_command_pool(&create_info, None).unwrap();
+
+    let allocate_info = core::CommandBufferAllocateInfo {
+        command_pool: command_pool.clone(),
+        level: core::CommandBufferLevel::Primary,
+        command_buffer_count: count,
+        chain: None,
+    };
+    let command_buffers = core::CommandPool::allocate_command_buffers(&allocate_info).unwrap();
+
+    (command_pool, command_buffers)
+}
+
+#[test]
+fn allocate_and_free() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let (command_pool, command_buffers) = create_command_buffers(&device, 2);
+    assert_eq!(command_buffers.len(), 2);
+    assert_eq!(mock::live_object_count("VkCommandBuffer"), 2);
+
+    drop(command_pool);
+    assert_eq!(mock::call_count("vkDestroyCommandPool"), 0);
+
+    drop(command_buffers);
+    assert_eq!(mock::live_object_count("VkCommandBuffer"), 0);
+    assert_eq!(mock::live_object_count("VkCommandPool"), 0);
+}
+
+#[test]
+fn record_and_submit() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+
+    mock::clear_calls();
+
+    let begin_info = core::CommandBufferBeginInfo {
+        flags: core::CommandBufferUsageFlags::ONE_TIME_SUBMIT,
+        inheritance_info: None,
+        chain: None,
+    };
+
+    command_buffer.begin(&begin_info).unwrap();
+    command_buffer.pipeline_barrier(core::PipelineStageFlags::TOP_OF_PIPE, core::PipelineStageFlags::TRANSFER, core::DependencyFlags::empty(), None, None, None);
+    command_buffer.draw(3, 1, 0, 0);
+    command_buffer.end().unwrap();
+
+    let submit_info = core::SubmitInfo {
+        wait_semaphores: vec![],
+        wait_dst_stage_mask: vec![],
+        command_buffers: vec![command_buffer.clone()],
+        signal_semaphores: vec![],
+        chain: None,
+    };
+    device.get_queue(0, 0).submit(Some(&[submit_info]), None).unwrap();
+
+    assert_eq!(mock::calls(), vec![
+        "vkBeginCommandBuffer",
+        "vkCmdPipelineBarrier",
+        "vkCmdDraw",
+        "vkEndCommandBuffer",
+        "vkGetDeviceQueue",
+        "vkQueueSubmit",
+    ]);
+}
+
+#[test]
+fn begin_error() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffers) = create_command_buffers(&device, 1);
+
+    let begin_info = core::CommandBufferBeginInfo {
+        flags: core::CommandBufferUsageFlags::empty(),
+        inheritance_info: None,
+        chain: None,
+    };
+
+    mock::inject_result("vkBeginCommandBuffer", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    assert_eq!(command_buffers[0].begin(&begin_info), Err(core::Error::OutOfDeviceMemory));
+
+    mock::inject_result("vkResetCommandBuffer", vks::vk::VK_ERROR_OUT_OF_HOST_MEMORY);
+    assert_eq!(command_buffers[0].reset(core::CommandBufferResetFlags::empty()), Err(core::Error::OutOfHostMemory));
+}
+
+#[test]
+fn allocate_error() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let create_info = core::CommandPoolCreateInfo {
+        flags: core::CommandPoolCreateFlags::empty(),
+        queue_family_index: 0,
+        chain: None,
+    };
+    let command_pool = device.create_command_pool(&create_info, None).unwrap();
+
+    let allocate_info = core::CommandBufferAllocateInfo {
+        command_pool: command_pool,
+        level: core::CommandBufferLevel::Primary,
+        command_buffer_count: 4,
+        chain: None,
+    };
+
+    mock::inject_result("vkAllocateCommandBuffers", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    let res = core::CommandPool::allocate_command_buffers(&allocate_info);
+    assert_eq!(res.unwrap_err(), core::Error::OutOfDeviceMemory);
+    assert_eq!(mock::call_count("vkFreeCommandBuffers"), 0);
+}
+
+fn create_render_pass_begin_info(device: &core::Device) -> core::RenderPassBeginInfo {
+    let create_info = core::RenderPassCreateInfo {
+        flags: core::RenderPassCreateFlags::empty(),
+        attachments: vec![],
+        subpasses: vec![core::SubpassDescription {
+            flags: core::SubpassDescriptionFlags::empty(),
+            pipeline_bind_point: core::PipelineBindPoint::Graphics,
+            input_attachments: vec![],
+            color_attachments: vec![],
+            resolve_attachments: vec![],
+            depth_stencil_attachment: None,
+            preserve_attachments: vec![],
+        }],
+        dependencies: vec![],
+        chain: None,
+    };
+    let render_pass = device.create_render_pass(&create_info, None).unwrap();
+
+    let create_info = core::FramebufferCreateInfo {
+        flags: core::FramebufferCreateFlags::empty(),
+        render_pass: render_pass.clone(),
+        attachments: vec![],
+        width: 64,
+        height: 64,
+        layers: 1,
+        chain: None,
+    };
+    let framebuffer = device.create_framebuffer(&create_info, None).unwrap();
+
+    core::RenderPassBeginInfo {
+        render_pass: render_pass,
+        framebuffer: framebuffer,
+        render_area: core::Rect2D::new(core::Offset2D::zero(), core::Extent2D::new(64, 64)),
+        clear_values: vec![],
+        chain: None,
+    }
+}
+
+#[test]
+fn recorder() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffers) = create_command_buffers(&device, 1);
+    let render_pass_begin_info = create_render_pass_begin_info(&device);
+
+    let begin_info = core::CommandBufferBeginInfo {
+        flags: core::CommandBufferUsageFlags::ONE_TIME_SUBMIT,
+        inheritance_info: None,
+        chain: None,
+    };
+
+    mock::clear_calls();
+
+    {
+        let mut recorder = command_buffers[0].record(&begin_info).unwrap();
+        assert_eq!(*recorder.command_buffer(), command_buffers[0]);
+        recorder.pipeline_barrier(core::PipelineStageFlags::TOP_OF_PIPE, core::PipelineStageFlags::TRANSFER, core::DependencyFlags::empty(), None, None, None);
+        recorder.dispatch(1, 1, 1);
+
+        {
+            let render_pass = recorder.begin_render_pass(&render_pass_begin_info, core::SubpassContents::Inline);
+            render_pass.draw(3, 1, 0, 0);
+            render_pass.next_subpass(core::SubpassContents::Inline);
+            render_pass.draw_indexed(6, 1, 0, 0, 0);
+        }
+
+        let render_pass = recorder.begin_render_pass(&render_pass_begin_info, core::SubpassContents::Inline);
+        render_pass.draw(3, 1, 0, 0);
+        render_pass.end();
+        assert_eq!(mock::call_count("vkCmdEndRenderPass"), 2);
+
+        recorder.end().unwrap();
+    }
+
+    assert_eq!(mock::calls(), vec![
+        "vkBeginCommandBuffer",
+        "vkCmdPipelineBarrier",
+        "vkCmdDispatch",
+        "vkCmdBeginRenderPass",
+        "vkCmdDraw",
+        "vkCmdNextSubpass",
+        "vkCmdDrawIndexed",
+        "vkCmdEndRenderPass",
+        "vkCmdBeginRenderPass",
+        "vkCmdDraw",
+        "vkCmdEndRenderPass",
+        "vkEndCommandBuffer",
+    ]);
+}
+
+#[test]
+fn recorder_ends_on_drop() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffers) = create_command_buffers(&device, 1);
+
+    let begin_info = core::CommandBufferBeginInfo {
+        flags: core::CommandBufferUsageFlags::empty(),
+        inheritance_info: None,
+        chain: None,
+    };
+
+    {
+        let recorder = command_buffers[0].record(&begin_info).unwrap();
+        recorder.dispatch(1, 1, 1);
+    }
+    assert_eq!(mock::call_count("vkEndCommandBuffer"), 1);
+
+    mock::inject_result("vkEndCommandBuffer", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    let recorder = command_buffers[0].record(&begin_info).unwrap();
+    assert_eq!(recorder.end(), Err(core::Error::OutOfDeviceMemory));
+    assert_eq!(mock::call_count("vkEndCommandBuffer"), 2);
+
+    mock::inject_result("vkBeginCommandBuffer", vks::vk::VK_ERROR_OUT_OF_HOST_MEMORY);
+    assert_eq!(command_buffers[0].record(&begin_info).unwrap_err(), core::Error::OutOfHostMemory);
+    assert_eq!(mock::call_count("vkEndCommandBuffer"), 2);
+}
+
+#[test]
+fn continue_render_pass() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffers) = create_command_buffers(&device, 1);
+
+    let begin_info = core::CommandBufferBeginInfo {
+        flags: core::CommandBufferUsageFlags::RENDER_PASS_CONTINUE,
+        inheritance_info: None,
+        chain: None,
+    };
+
+    mock::clear_calls();
+    {
+        let mut recorder = command_buffers[0].record(&begin_info).unwrap();
+        recorder.continue_render_pass().draw(3, 1, 0, 0);
+        recorder.continue_render_pass().end();
+    }
+
+    assert_eq!(mock::calls(), vec!["vkBeginCommandBuffer", "vkCmdDraw", "vkEndCommandBuffer"]);
+}
+
+#[test]
+#[should_panic]
+fn continue_render_pass_without_flag() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffers) = create_command_buffers(&device, 1);
+
+    let begin_info = core::CommandBufferBeginInfo {
+        flags: core::CommandBufferUsageFlags::empty(),
+        inheritance_info: None,
+        chain: None,
+    };
+
+    let mut recorder = command_buffers[0].record(&begin_info).unwrap();
+    recorder.continue_render_pass();
+}
+
+fn record_copy(command_buffer: &core::CommandBuffer, flags: core::CommandBufferUsageFlags, src_buffer: core::Buffer, dst_buffer: core::Buffer) {
+    let begin_info = core::CommandBufferBeginInfo {
+        flags: flags,
+        inheritance_info: None,
+        chain: None,
+    };
+
+    let regions = [core::BufferCopy {
+        src_offset: 0,
+        dst_offset: 0,
+        size: 1024,
+    }];
+
+    command_buffer.begin(&begin_info).unwrap();
+    command_buffer.copy_buffer(&src_buffer, &dst_buffer, &regions);
+    command_buffer.end().unwrap();
+}
+
+fn submit(device: &core::Device, command_buffer: &core::CommandBuffer, fence: Option<&core::Fence>) {
+    let submit_info = core::SubmitInfo {
+        wait_semaphores: vec![],
+        wait_dst_stage_mask: vec![],
+        command_buffers: vec![command_buffer.clone()],
+        signal_semaphores: vec![],
+        chain: None,
+    };
+
+    device.get_queue(0, 0).submit(Some(&[submit_info]), fence).unwrap();
+}
+
+fn create_fence(device: &core::Device) -> core::Fence {
+    let create_info = core::FenceCreateInfo {
+        flags: core::FenceCreateFlags::empty(),
+        chain: None,
+    };
+
+    device.create_fence(&create_info, None).unwrap()
+}
+
+#[test]
+fn resources_kept_alive_until_reset() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::empty(), mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    command_buffer.reset(core::CommandBufferResetFlags::empty()).unwrap();
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn resources_released_on_command_pool_reset() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (command_pool, command_buffers) = create_command_buffers(&device, 2);
+
+    for command_buffer in &command_buffers {
+        record_copy(command_buffer, core::CommandBufferUsageFlags::empty(), mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    }
+    assert_eq!(mock::live_object_count("VkBuffer"), 4);
+
+    command_pool.reset(core::CommandPoolResetFlags::empty()).unwrap();
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn resources_released_on_begin() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::empty(), mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    let buffer = mock::create_buffer(&device, 1024);
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::empty(), buffer.clone(), buffer);
+    assert_eq!(mock::live_object_count("VkBuffer"), 1);
+
+    drop(command_buffer);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn resources_released_when_fence_signals() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let fence = create_fence(&device);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::ONE_TIME_SUBMIT, mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    submit(&device, &command_buffer, Some(&fence));
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    assert!(fence.wait_for(core::Timeout::Infinite).unwrap());
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn resources_released_when_fence_status_signaled() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let fence = create_fence(&device);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::ONE_TIME_SUBMIT, mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    submit(&device, &command_buffer, Some(&fence));
+
+    assert!(fence.get_status().unwrap());
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn resources_of_reusable_command_buffer_kept_after_fence() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let fence = create_fence(&device);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::empty(), mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    submit(&device, &command_buffer, Some(&fence));
+
+    assert!(fence.wait_for(core::Timeout::Infinite).unwrap());
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+}
+
+#[test]
+fn stale_fence_does_not_release_new_recording() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let fence = create_fence(&device);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::ONE_TIME_SUBMIT, mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    submit(&device, &command_buffer, Some(&fence));
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::ONE_TIME_SUBMIT, mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    assert!(fence.wait_for(core::Timeout::Infinite).unwrap());
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+}
+
+#[test]
+fn resources_released_on_queue_wait_idle() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let fence = create_fence(&device);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::ONE_TIME_SUBMIT, mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    submit(&device, &command_buffer, Some(&fence));
+
+    device.get_queue(0, 1).wait_idle().unwrap();
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    device.get_queue(0, 0).wait_idle().unwrap();
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn resources_released_on_device_wait_idle() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::ONE_TIME_SUBMIT, mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    submit(&device, &command_buffer, None);
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    device.wait_idle().unwrap();
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn fence_does_not_keep_command_buffer_alive() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let fence = create_fence(&device);
+
+    record_copy(&command_buffer, core::CommandBufferUsageFlags::ONE_TIME_SUBMIT, mock::create_buffer(&device, 1024), mock::create_buffer(&device, 1024));
+    submit(&device, &command_buffer, Some(&fence));
+
+    drop(command_buffer);
+    assert_eq!(mock::live_object_count("VkCommandBuffer"), 0);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+    assert!(fence.wait_for(core::Timeout::Infinite).unwrap());
+}
+
+fn mipmapped_image_create_info() -> core::ImageCreateInfo {
+    core::ImageCreateInfo::builder(core::Format::R8G8B8A8_UNorm, core::Extent3D::new(16, 8, 1), core::ImageUsageFlags::TRANSFER_SRC | core::ImageUsageFlags::TRANSFER_DST | core::ImageUsageFlags::SAMPLED)
+        .mip_levels(5)
+        .array_layers(2)
+        .build()
+}
+
+#[test]
+fn generate_mipmaps() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let create_info = mipmapped_image_create_info();
+    let image = device.create_image(&create_info, None).unwrap();
+
+    command_buffer.generate_mipmaps(&image, core::ImageLayout::TransferDstOptimal, core::ImageLayout::ShaderReadOnlyOptimal, core::PipelineStageFlags::FRAGMENT_SHADER).unwrap();
+    assert_eq!(mock::call_count("vkCmdBlitImage"), 4);
+
+    // One barrier before the first blit, one between each pair of blits and one at the end.
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers.len(), 5);
+
+    let first = &barriers[0].image_barriers;
+    assert_eq!(first.len(), 2);
+    assert_eq!((first[0].base_mip_level, first[0].level_count, first[0].layer_count), (0, 1, 2));
+    assert_eq!(first[0].old_layout, vks::vk::VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL);
+    assert_eq!(first[0].new_layout, vks::vk::VK_IMAGE_LAYOUT_TRANSFER_SRC_OPTIMAL);
+    assert_eq!((first[1].base_mip_level, first[1].level_count), (1, 4));
+    assert_eq!(first[1].old_layout, vks::vk::VK_IMAGE_LAYOUT_UNDEFINED);
+
+    for (mip_level, barrier) in (1..4).zip(&barriers[1..4]) {
+        assert_eq!(barrier.src_stage_mask, vks::vk::VK_PIPELINE_STAGE_TRANSFER_BIT);
+        assert_eq!(barrier.image_barriers[0].base_mip_level, mip_level);
+        assert_eq!(barrier.image_barriers[0].new_layout, vks::vk::VK_IMAGE_LAYOUT_TRANSFER_SRC_OPTIMAL);
+    }
+
+    let last = &barriers[4];
+    assert_eq!(last.dst_stage_mask, vks::vk::VK_PIPELINE_STAGE_FRAGMENT_SHADER_BIT);
+    assert_eq!((last.image_barriers[0].base_mip_level, last.image_barriers[0].level_count), (0, 4));
+    assert_eq!((last.image_barriers[1].base_mip_level, last.image_barriers[1].level_count), (4, 1));
+    assert!(last.image_barriers.iter().all(|b| b.new_layout == vks::vk::VK_IMAGE_LAYOUT_SHADER_READ_ONLY_OPTIMAL));
+}
+
+#[test]
+fn generate_mipmaps_without_linear_filter() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let create_info = mipmapped_image_create_info();
+    let image = device.create_image(&create_info, None).unwrap();
+
+    mock::configure(|config| {
+        config.format_properties.insert(vks::vk::VK_FORMAT_R8G8B8A8_UNORM, vks::vk::VkFormatProperties {
+            linearTilingFeatures: 0,
+            optimalTilingFeatures: vks::vk::VK_FORMAT_FEATURE_BLIT_SRC_BIT | vks::vk::VK_FORMAT_FEATURE_BLIT_DST_BIT,
+            bufferFeatures: 0,
+        });
+    });
+
+    let res = command_buffer.generate_mipmaps(&image, core::ImageLayout::TransferDstOptimal, core::ImageLayout::ShaderReadOnlyOptimal, core::PipelineStageFlags::FRAGMENT_SHADER);
+    assert_eq!(res, Err(core::MipmapError::LinearFilterNotSupported(core::Format::R8G8B8A8_UNorm)));
+    assert_eq!(mock::call_count("vkCmdBlitImage"), 0);
+    assert!(mock::pipeline_barriers().is_empty());
+}
+
+#[test]
+fn generate_mipmaps_of_depth_stencil_image() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+
+    for &format in &[core::Format::D32_SFloat, core::Format::S8_UInt, core::Format::D24_UNorm_S8_UInt] {
+        let mut create_info = mipmapped_image_create_info();
+        create_info.format = format;
+        let image = device.create_image(&create_info, None).unwrap();
+
+        let res = command_buffer.generate_mipmaps(&image, core::ImageLayout::TransferDstOptimal, core::ImageLayout::ShaderReadOnlyOptimal, core::PipelineStageFlags::FRAGMENT_SHADER);
+        assert_eq!(res, Err(core::MipmapError::DepthStencilFormat(format)));
+    }
+
+    assert_eq!(mock::call_count("vkCmdBlitImage"), 0);
+    assert!(mock::pipeline_barriers().is_empty());
+}
+
+#[test]
+fn generate_mipmaps_of_unknown_image() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, mut command_buffers) = create_command_buffers(&device, 1);
+    let command_buffer = command_buffers.remove(0);
+    let image = device.create_image(&mipmapped_image_create_info(), None).unwrap();
+    let image = unsafe {
+        core::Image::from_native_object(image.as_native_vulkan_object(), core::FromNativeImageParameters::new(false, device.clone(), None))
+    };
+
+    let res = command_buffer.generate_mipmaps(&image, core::ImageLayout::TransferDstOptimal, core::ImageLayout::ShaderReadOnlyOptimal, core::PipelineStageFlags::FRAGMENT_SHADER);
+    assert_eq!(res, Err(core::MipmapError::UnknownImageProperties));
+    assert_eq!(mock::call_count("vkCmdBlitImage"), 0);
+    assert!(mock::pipeline_barriers().is_empty());
+}
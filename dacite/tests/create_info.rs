@@ -0,0 +1,170 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::core;
+
+#[test]
+fn defaults() {
+    let create_info = core::BufferCreateInfo::builder(64, core::BufferUsageFlags::UNIFORM_BUFFER).build();
+    assert_eq!(create_info.flags, core::BufferCreateFlags::empty());
+    assert_eq!(create_info.size, 64);
+    assert_eq!(create_info.usage, core::BufferUsageFlags::UNIFORM_BUFFER);
+    assert_eq!(create_info.sharing_mode, core::SharingMode::Exclusive);
+    assert!(create_info.queue_family_indices.is_empty());
+    assert!(create_info.chain.is_none());
+
+    let create_info = core::ImageCreateInfo::builder(core::Format::R8G8B8A8_UNorm, core::Extent3D::new(16, 16, 1), core::ImageUsageFlags::SAMPLED).build();
+    assert_eq!(create_info.image_type, core::ImageType::Type2D);
+    assert_eq!(create_info.mip_levels, 1);
+    assert_eq!(create_info.array_layers, 1);
+    assert_eq!(create_info.samples, core::SampleCountFlagBits::SampleCount1);
+    assert_eq!(create_info.tiling, core::ImageTiling::Optimal);
+    assert_eq!(create_info.initial_layout, core::ImageLayout::Undefined);
+
+    let create_info = core::SamplerCreateInfo::default();
+    assert_eq!(create_info.max_anisotropy, 1.0);
+    assert_eq!(create_info.max_lod, vks::vk::VK_LOD_CLAMP_NONE);
+
+    let create_info = core::PipelineRasterizationStateCreateInfo::default();
+    assert_eq!(create_info.polygon_mode, core::PolygonMode::Fill);
+    assert_eq!(create_info.cull_mode, core::CullModeFlags::NONE);
+    assert_eq!(create_info.line_width, 1.0);
+
+    assert_eq!(core::DeviceQueueCreateInfo::default().queue_priorities, vec![1.0]);
+    assert!(core::RenderPassCreateInfo::default().subpasses.is_empty());
+}
+
+#[test]
+fn builder() {
+    let create_info = core::ImageCreateInfo::builder(core::Format::R8G8B8A8_UNorm, core::Extent3D::new(256, 128, 1), core::ImageUsageFlags::SAMPLED | core::ImageUsageFlags::TRANSFER_DST)
+        .mip_levels(8)
+        .build();
+
+    assert_eq!(create_info, core::ImageCreateInfo {
+        format: core::Format::R8G8B8A8_UNorm,
+        extent: core::Extent3D::new(256, 128, 1),
+        mip_levels: 8,
+        usage: core::ImageUsageFlags::SAMPLED | core::ImageUsageFlags::TRANSFER_DST,
+        flags: core::ImageCreateFlags::empty(),
+        image_type: core::ImageType::Type2D,
+        array_layers: 1,
+        samples: core::SampleCountFlagBits::SampleCount1,
+        tiling: core::ImageTiling::Optimal,
+        sharing_mode: core::SharingMode::Exclusive,
+        queue_family_indices: vec![],
+        initial_layout: core::ImageLayout::Undefined,
+        chain: None,
+    });
+
+    let create_info: core::FenceCreateInfo = core::FenceCreateInfoBuilder::new().flags(core::FenceCreateFlags::SIGNALED).into();
+    assert_eq!(create_info.flags, core::FenceCreateFlags::SIGNALED);
+}
+
+#[test]
+fn builder_with_required_fields() {
+    let create_info = core::QueryPoolCreateInfo::builder(core::QueryType::Timestamp, 4).build();
+    assert_eq!(create_info.query_type, core::QueryType::Timestamp);
+    assert_eq!(create_info.query_count, 4);
+    assert_eq!(create_info.pipeline_statistics, core::QueryPipelineStatisticFlags::empty());
+
+    let create_info = core::DescriptorPoolCreateInfo::builder(16)
+        .pool_sizes(vec![core::DescriptorPoolSize {
+            descriptor_type: core::DescriptorType::UniformBuffer,
+            descriptor_count: 16,
+        }])
+        .max_sets(32)
+        .build();
+    assert_eq!(create_info.max_sets, 32);
+    assert_eq!(create_info.pool_sizes.len(), 1);
+}
+
+#[test]
+fn create_objects_with_builders() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let buffer = device.create_buffer(&core::BufferCreateInfo::builder(1024, core::BufferUsageFlags::VERTEX_BUFFER).build(), None).unwrap();
+    assert_eq!(mock::live_object_count("VkBuffer"), 1);
+
+    let image = device.create_image(&core::ImageCreateInfo::builder(core::Format::B8G8R8A8_UNorm, core::Extent3D::new(4, 4, 1), core::ImageUsageFlags::SAMPLED).build(), None).unwrap();
+    let view = device.create_image_view(&core::ImageViewCreateInfo::builder(image, core::ImageViewType::Type2D, core::Format::B8G8R8A8_UNorm).build(), None).unwrap();
+    assert_eq!(mock::live_object_count("VkImageView"), 1);
+
+    drop(view);
+    drop(buffer);
+    assert_eq!(mock::live_object_count("VkImageView"), 0);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn subresource_aspects_from_format() {
+    let range = core::ImageSubresourceRange::full(core::Format::D24_UNorm_S8_UInt);
+    assert_eq!(range.aspect_mask, core::ImageAspectFlags::DEPTH | core::ImageAspectFlags::STENCIL);
+    assert_eq!(range.base_mip_level, 0);
+    assert_eq!(range.level_count, core::OptionalMipLevels::Remaining);
+    assert_eq!(range.base_array_layer, 0);
+    assert_eq!(range.layer_count, core::OptionalArrayLayers::Remaining);
+
+    let range = core::ImageSubresourceRange::new(core::Format::D32_SFloat, 2, 3, 1, 4);
+    assert_eq!(range.aspect_mask, core::ImageAspectFlags::DEPTH);
+    assert_eq!(range.level_count, core::OptionalMipLevels::MipLevels(3));
+    assert_eq!(range.layer_count, core::OptionalArrayLayers::ArrayLayers(4));
+
+    let layers = core::ImageSubresourceLayers::new(core::Format::R8G8B8A8_UNorm, 1, 0, 6);
+    assert_eq!(layers.aspect_mask, core::ImageAspectFlags::COLOR);
+    assert_eq!((layers.mip_level, layers.base_array_layer, layers.layer_count), (1, 0, 6));
+
+    let subresource = core::ImageSubresource::new(core::Format::S8_UInt, 2, 5);
+    assert_eq!(subresource, core::ImageSubresource {
+        aspect_mask: core::ImageAspectFlags::STENCIL,
+        mip_level: 2,
+        array_layer: 5,
+    });
+}
+
+#[test]
+fn image_view_for_image() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let create_info = core::ImageCreateInfo::builder(core::Format::S8_UInt, core::Extent3D::new(16, 16, 1), core::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT).build();
+    let image = device.create_image(&create_info, None).unwrap();
+
+    let create_info = core::ImageViewCreateInfo::for_image(image.clone(), core::ImageViewType::Type2D, core::Format::S8_UInt);
+    assert_eq!(create_info.image, image);
+    assert_eq!(create_info.format, core::Format::S8_UInt);
+    assert_eq!(create_info.components, core::ComponentMapping::identity());
+    assert_eq!(create_info.subresource_range, core::ImageSubresourceRange::full(core::Format::S8_UInt));
+    assert_eq!(create_info.subresource_range.aspect_mask, core::ImageAspectFlags::STENCIL);
+
+    device.create_image_view(&create_info, None).unwrap();
+}
+
+#[test]
+fn image_view_builder_aspects_from_format() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let create_info = core::ImageCreateInfo::builder(core::Format::D32_SFloat, core::Extent3D::new(16, 16, 1), core::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT).build();
+    let image = device.create_image(&create_info, None).unwrap();
+
+    let create_info = core::ImageViewCreateInfo::builder(image, core::ImageViewType::Type2D, core::Format::D32_SFloat).build();
+    assert_eq!(create_info.subresource_range, core::ImageSubresourceRange::full(core::Format::D32_SFloat));
+    assert_eq!(create_info.subresource_range.aspect_mask, core::ImageAspectFlags::DEPTH);
+}
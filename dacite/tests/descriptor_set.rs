@@ -0,0 +1,87 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::core;
+
+fn write_buffer(descriptor_set: &core::DescriptorSet, array_element: u32, buffer: core::Buffer) -> core::WriteDescriptorSet {
+    core::WriteDescriptorSet {
+        dst_set: descriptor_set.clone(),
+        dst_binding: 0,
+        dst_array_element: array_element,
+        descriptor_type: core::DescriptorType::UniformBuffer,
+        elements: core::WriteDescriptorSetElements::BufferInfo(vec![core::DescriptorBufferInfo {
+            buffer: buffer,
+            offset: 0,
+            range: core::OptionalDeviceSize::WholeSize,
+        }]),
+        chain: None,
+    }
+}
+
+#[test]
+fn written_resources_kept_alive() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_descriptor_pool, descriptor_set) = mock::create_descriptor_set(&device);
+
+    core::DescriptorSet::update(Some(&[
+        write_buffer(&descriptor_set, 0, mock::create_buffer(&device, 256)),
+        write_buffer(&descriptor_set, 1, mock::create_buffer(&device, 256)),
+    ]), None);
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    core::DescriptorSet::update(Some(&[write_buffer(&descriptor_set, 0, mock::create_buffer(&device, 256))]), None);
+    assert_eq!(mock::call_count("vkDestroyBuffer"), 1);
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    descriptor_set.free().unwrap();
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn copied_resources_kept_alive() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_descriptor_pool, descriptor_set) = mock::create_descriptor_set(&device);
+
+    core::DescriptorSet::update(Some(&[
+        write_buffer(&descriptor_set, 0, mock::create_buffer(&device, 256)),
+        write_buffer(&descriptor_set, 1, mock::create_buffer(&device, 256)),
+    ]), None);
+
+    let copy = core::CopyDescriptorSet {
+        src_set: descriptor_set.clone(),
+        src_binding: 0,
+        src_array_element: 0,
+        dst_set: descriptor_set.clone(),
+        dst_binding: 0,
+        dst_array_element: 1,
+        descriptor_count: 1,
+        chain: None,
+    };
+    core::DescriptorSet::update(None, Some(&[copy]));
+    assert_eq!(mock::live_object_count("VkBuffer"), 1);
+
+    core::DescriptorSet::update(Some(&[write_buffer(&descriptor_set, 0, mock::create_buffer(&device, 256))]), None);
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    drop(descriptor_set);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
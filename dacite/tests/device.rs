@@ -0,0 +1,275 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::core;
+
+#[test]
+fn create_and_destroy_device() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    assert_eq!(mock::live_object_count("VkDevice"), 1);
+
+    drop(instance);
+    assert_eq!(mock::call_count("vkDestroyInstance"), 0);
+
+    drop(device);
+    assert_eq!(mock::live_object_count("VkDevice"), 0);
+    assert_eq!(mock::live_object_count("VkInstance"), 0);
+
+    let calls = mock::calls();
+    let destroy_device = calls.iter().position(|&c| c == "vkDestroyDevice").unwrap();
+    let destroy_instance = calls.iter().position(|&c| c == "vkDestroyInstance").unwrap();
+    assert!(destroy_device < destroy_instance);
+}
+
+#[test]
+fn wait_idle_error() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    mock::inject_result("vkDeviceWaitIdle", vks::vk::VK_ERROR_DEVICE_LOST);
+    assert_eq!(device.wait_idle(), Err(core::Error::DeviceLost));
+    assert_eq!(device.wait_idle(), Ok(()));
+}
+
+#[test]
+fn fences() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let create_info = core::FenceCreateInfo {
+        flags: core::FenceCreateFlags::SIGNALED,
+        chain: None,
+    };
+
+    let fence = device.create_fence(&create_info, None).unwrap();
+    assert_eq!(fence.get_status(), Ok(true));
+
+    fence.reset().unwrap();
+    assert_eq!(fence.get_status(), Ok(false));
+    assert_eq!(fence.wait_for(core::Timeout::Some(::std::time::Duration::from_millis(0))), Ok(false));
+
+    let queue = device.get_queue(0, 0);
+    queue.submit(None, Some(&fence)).unwrap();
+    assert_eq!(fence.wait_for(core::Timeout::Infinite), Ok(true));
+
+    drop(fence);
+    assert_eq!(mock::live_object_count("VkFence"), 0);
+}
+
+#[test]
+fn map_memory() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let allocate_info = core::MemoryAllocateInfo {
+        allocation_size: 1024,
+        memory_type_index: 1,
+        chain: None,
+    };
+
+    let memory = device.allocate_memory(&allocate_info, None).unwrap();
+    assert_eq!(memory.size(), 1024);
+
+    {
+        let mapped = memory.map(16, core::OptionalDeviceSize::Size(4), core::MemoryMapFlags::empty()).unwrap();
+        unsafe {
+            *(mapped.as_ptr() as *mut u32) = 0xdeadbeef;
+        }
+    }
+    assert_eq!(mock::call_count("vkUnmapMemory"), 1);
+
+    let contents = mock::memory_contents(dacite::VulkanObject::id(&memory));
+    assert_eq!(&contents[16..20], &[0xef, 0xbe, 0xad, 0xde]);
+
+    mock::inject_result("vkMapMemory", vks::vk::VK_ERROR_MEMORY_MAP_FAILED);
+    let res = memory.map(0, core::OptionalDeviceSize::WholeSize, core::MemoryMapFlags::empty());
+    assert_eq!(res.unwrap_err(), core::Error::MemoryMapFailed);
+}
+
+#[test]
+fn create_buffer_with_memory() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let create_info = core::BufferCreateInfo {
+        flags: core::BufferCreateFlags::empty(),
+        size: 1000,
+        usage: core::BufferUsageFlags::VERTEX_BUFFER,
+        sharing_mode: core::SharingMode::Exclusive,
+        queue_family_indices: vec![],
+        chain: None,
+    };
+
+    let buffer = device.create_buffer_with_memory(&create_info, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::HOST_CACHED).unwrap();
+    assert_eq!(buffer.memory().unwrap().size(), 1024);
+    assert_eq!(mock::call_count("vkBindBufferMemory"), 1);
+    assert_eq!(mock::live_object_count("VkDeviceMemory"), 1);
+
+    drop(buffer);
+    assert_eq!(mock::live_object_count("VkDeviceMemory"), 0);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+
+    let calls = mock::calls();
+    let destroy_buffer = calls.iter().position(|&c| c == "vkDestroyBuffer").unwrap();
+    let free_memory = calls.iter().position(|&c| c == "vkFreeMemory").unwrap();
+    assert!(destroy_buffer < free_memory);
+
+    let buffer = device.create_buffer(&create_info, None).unwrap();
+    assert!(buffer.memory().is_none());
+}
+
+#[test]
+fn create_image_with_memory() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let create_info = core::ImageCreateInfo {
+        flags: core::ImageCreateFlags::empty(),
+        image_type: core::ImageType::Type2D,
+        format: core::Format::R8G8B8A8_UNorm,
+        extent: core::Extent3D::new(16, 16, 1),
+        mip_levels: 1,
+        array_layers: 1,
+        samples: core::SampleCountFlagBits::SampleCount1,
+        tiling: core::ImageTiling::Optimal,
+        usage: core::ImageUsageFlags::SAMPLED,
+        sharing_mode: core::SharingMode::Exclusive,
+        queue_family_indices: vec![],
+        initial_layout: core::ImageLayout::Undefined,
+        chain: None,
+    };
+
+    let image = device.create_image_with_memory(&create_info, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty()).unwrap();
+    assert_eq!(image.memory().unwrap().size(), 1024);
+    assert_eq!(mock::call_count("vkBindImageMemory"), 1);
+
+    mock::configure(|c| c.memory_requirements.memoryTypeBits = 0b110);
+    let res = device.create_image_with_memory(&create_info, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty());
+    assert_eq!(res.unwrap_err(), core::AllocationError::NoSuitableMemoryType);
+    assert_eq!(mock::live_object_count("VkImage"), 1);
+
+    mock::configure(|c| c.memory_requirements.memoryTypeBits = 0b111);
+    mock::inject_result("vkAllocateMemory", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    let res = device.create_image_with_memory(&create_info, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty());
+    assert_eq!(res.unwrap_err(), core::AllocationError::VulkanError(core::Error::OutOfDeviceMemory));
+}
+
+#[test]
+fn destroy_deferred_until_fence_signals() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let deletion_queue = core::DeletionQueue::new(device.clone());
+    let fence = device.create_fence(&core::FenceCreateInfo { flags: core::FenceCreateFlags::empty(), chain: None }, None).unwrap();
+
+    deletion_queue.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Fence(fence.clone()));
+    deletion_queue.poll();
+    assert_eq!(deletion_queue.pending_count(), 1);
+    assert_eq!(mock::live_object_count("VkBuffer"), 1);
+
+    device.get_queue(0, 0).submit(None, Some(&fence)).unwrap();
+    assert_eq!(deletion_queue.pending_count(), 0);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn destroy_deferred_until_frame_completes() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let deletion_queue = core::DeletionQueue::new(device.clone());
+
+    deletion_queue.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Frame(1));
+    deletion_queue.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Frame(2));
+
+    deletion_queue.complete_frame(0);
+    assert_eq!(mock::live_object_count("VkBuffer"), 2);
+
+    deletion_queue.complete_frame(1);
+    assert_eq!(mock::live_object_count("VkBuffer"), 1);
+
+    deletion_queue.complete_frame(0);
+    deletion_queue.complete_frame(2);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn flush_deletion_queue() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let deletion_queue = core::DeletionQueue::new(device.clone());
+    let fence = device.create_fence(&core::FenceCreateInfo { flags: core::FenceCreateFlags::empty(), chain: None }, None).unwrap();
+
+    deletion_queue.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Fence(fence));
+    deletion_queue.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Frame(5));
+
+    mock::inject_result("vkDeviceWaitIdle", vks::vk::VK_ERROR_DEVICE_LOST);
+    assert_eq!(deletion_queue.flush(), Err(core::Error::DeviceLost));
+    assert_eq!(deletion_queue.pending_count(), 2);
+
+    deletion_queue.flush().unwrap();
+    assert_eq!(deletion_queue.pending_count(), 0);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+    assert_eq!(mock::live_object_count("VkFence"), 0);
+
+    drop(deletion_queue);
+    drop(device);
+    assert_eq!(mock::live_object_count("VkDevice"), 0);
+}
+
+#[test]
+fn device_wait_idle_flushes_all_deletion_queues() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let first = core::DeletionQueue::new(device.clone());
+    let second = core::DeletionQueue::new(device.clone());
+
+    first.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Frame(1));
+    second.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Frame(1));
+    drop(second);
+    assert_eq!(mock::live_object_count("VkBuffer"), 1);
+
+    device.wait_idle().unwrap();
+    assert_eq!(first.pending_count(), 0);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+}
+
+#[test]
+fn drop_device_with_pending_deletions() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let deletion_queue = core::DeletionQueue::new(device.clone());
+    let fence = device.create_fence(&core::FenceCreateInfo { flags: core::FenceCreateFlags::empty(), chain: None }, None).unwrap();
+
+    deletion_queue.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Fence(fence));
+    deletion_queue.destroy_deferred(mock::create_buffer(&device, 1024), core::DeletionCondition::Frame(1));
+
+    let clone = deletion_queue.clone();
+    drop(device);
+    drop(deletion_queue);
+    assert_eq!(clone.pending_count(), 2);
+    assert_eq!(mock::call_count("vkDestroyDevice"), 0);
+
+    drop(clone);
+    assert_eq!(mock::call_count("vkDeviceWaitIdle"), 0);
+    assert_eq!(mock::live_object_count("VkBuffer"), 0);
+    assert_eq!(mock::live_object_count("VkFence"), 0);
+    assert_eq!(mock::call_count("vkDestroyDevice"), 1);
+    assert_eq!(mock::live_object_count("VkDevice"), 0);
+}
@@ -0,0 +1,133 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::VulkanObject;
+use dacite::core;
+
+fn allocate_memory(device: &core::Device, size: u64) -> core::DeviceMemory {
+    let allocate_info = core::MemoryAllocateInfo {
+        allocation_size: size,
+        memory_type_index: 2,
+        chain: None,
+    };
+
+    device.allocate_memory(&allocate_info, None).unwrap()
+}
+
+#[test]
+fn typed_slices() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let memory = allocate_memory(&device, 1024);
+
+    {
+        let mut mapped = memory.map(64, core::OptionalDeviceSize::Size(18), core::MemoryMapFlags::empty()).unwrap();
+
+        {
+            let slice = mapped.as_mut_slice::<u32>().unwrap();
+            assert_eq!(slice.len(), 4);
+            slice.copy_from_slice(&[1, 2, 3, 4]);
+        }
+
+        assert_eq!(mapped.as_slice::<u32>().unwrap(), &[1, 2, 3, 4]);
+        assert_eq!(mapped.as_slice::<[u8; 3]>().unwrap().len(), 6);
+        assert_eq!(mapped.as_slice::<()>().unwrap().len(), 0);
+    }
+
+    let contents = mock::memory_contents(memory.id());
+    assert_eq!(&contents[64..72], &[1, 0, 0, 0, 2, 0, 0, 0]);
+
+    let mapped = memory.map(65, core::OptionalDeviceSize::WholeSize, core::MemoryMapFlags::empty()).unwrap();
+    assert_eq!(mapped.as_slice::<u32>().unwrap_err(), core::MappedMemoryError::Misaligned);
+    assert_eq!(mapped.as_slice::<u8>().unwrap().len(), 1024 - 65);
+}
+
+#[test]
+fn write_and_read_at() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let memory = allocate_memory(&device, 256);
+
+    let mut mapped = memory.map(0, core::OptionalDeviceSize::Size(16), core::MemoryMapFlags::empty()).unwrap();
+    mapped.write_at(3, &[0x0102u16, 0x0304]).unwrap();
+    mapped.write_at(12, &[0xffu8; 4]).unwrap();
+    assert_eq!(mapped.write_at(13, &[0u8; 4]).unwrap_err(), core::MappedMemoryError::OutOfBounds);
+    assert_eq!(mapped.write_at(u64::max_value(), &[0u8; 1]).unwrap_err(), core::MappedMemoryError::OutOfBounds);
+
+    let mut values = [0u16; 2];
+    mapped.read_at(3, &mut values).unwrap();
+    assert_eq!(values, [0x0102, 0x0304]);
+
+    let mut bytes = [0u8; 8];
+    mapped.read_at(8, &mut bytes).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+    assert_eq!(mapped.read_at(9, &mut bytes).unwrap_err(), core::MappedMemoryError::OutOfBounds);
+
+    assert_eq!(&mock::memory_contents(memory.id())[..8], &[0, 0, 0, 2, 1, 4, 3, 0]);
+}
+
+#[test]
+fn flush_rounds_to_non_coherent_atom_size() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let memory = allocate_memory(&device, 1000);
+    let handle = memory.id();
+
+    // The mock's non_coherent_atom_size is 64.
+    let mapped = memory.map(64, core::OptionalDeviceSize::Size(256), core::MemoryMapFlags::empty()).unwrap();
+    mapped.flush(&None).unwrap();
+    mapped.flush_range(10, core::OptionalDeviceSize::Size(20), &None).unwrap();
+    mapped.invalidate_range(150, core::OptionalDeviceSize::WholeSize, &None).unwrap();
+    drop(mapped);
+
+    // Unaligned mappings start at the previous multiple of the atom size. Rounded ranges use
+    // VK_WHOLE_SIZE, if they reach beyond the end of the mapping.
+    let mut mapped = memory.map(16, core::OptionalDeviceSize::Size(4), core::MemoryMapFlags::empty()).unwrap();
+    mapped.write_at(0, &[0xffu8; 4]).unwrap();
+    mapped.flush(&None).unwrap();
+    drop(mapped);
+    assert_eq!(&mock::memory_contents(handle)[12..24], &[0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+
+    let mapped = memory.map(900, core::OptionalDeviceSize::WholeSize, core::MemoryMapFlags::empty()).unwrap();
+    mapped.flush(&None).unwrap();
+    mapped.invalidate(&None).unwrap();
+
+    assert_eq!(mock::mapped_memory_ranges(), vec![
+        (handle, 64, 256),
+        (handle, 64, 64),
+        (handle, 192, 128),
+        (handle, 0, vks::vk::VK_WHOLE_SIZE),
+        (handle, 896, vks::vk::VK_WHOLE_SIZE),
+        (handle, 896, vks::vk::VK_WHOLE_SIZE),
+    ]);
+}
+
+#[test]
+fn flush_range_outside_of_mapping() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let memory = allocate_memory(&device, 1024);
+
+    let mapped = memory.map(0, core::OptionalDeviceSize::Size(128), core::MemoryMapFlags::empty()).unwrap();
+    assert_eq!(mapped.flush_range(64, core::OptionalDeviceSize::Size(128), &None).unwrap_err(), core::MappedMemoryError::OutOfBounds);
+    assert_eq!(mapped.invalidate_range(129, core::OptionalDeviceSize::WholeSize, &None).unwrap_err(), core::MappedMemoryError::OutOfBounds);
+    assert_eq!(mapped.flush_range(1, core::OptionalDeviceSize::Size(u64::max_value()), &None).unwrap_err(), core::MappedMemoryError::OutOfBounds);
+    assert_eq!(mock::mapped_memory_ranges(), vec![]);
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::core;
+use dacite::device_selector::{PhysicalDeviceSelector, RejectionReason, SelectionError};
+
+#[test]
+fn select_without_requirements() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+
+    let selected = PhysicalDeviceSelector::new().select(&instance).unwrap();
+    assert_eq!(selected.properties.device_name, "dacite mock device");
+    assert!(selected.enabled_extensions.is_empty());
+    assert!(selected.queue_family_indices.is_empty());
+    assert_eq!(selected.present_queue_family_index, None);
+}
+
+#[test]
+fn rank_by_device_type() {
+    mock::configure(|config| {
+        config.physical_device_count = 3;
+        config.device_types = vec![
+            vks::vk::VK_PHYSICAL_DEVICE_TYPE_CPU,
+            vks::vk::VK_PHYSICAL_DEVICE_TYPE_INTEGRATED_GPU,
+            vks::vk::VK_PHYSICAL_DEVICE_TYPE_DISCRETE_GPU,
+        ];
+    });
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let (selected, rejected) = PhysicalDeviceSelector::new().rank(physical_devices.clone());
+    assert!(rejected.is_empty());
+    let types: Vec<_> = selected.iter().map(|s| s.properties.device_type).collect();
+    assert_eq!(types, vec![core::PhysicalDeviceType::DiscreteGpu, core::PhysicalDeviceType::IntegratedGpu, core::PhysicalDeviceType::Cpu]);
+    assert_eq!(selected[0].physical_device, physical_devices[2]);
+
+    let selected = PhysicalDeviceSelector::new()
+        .preferred_device_types(vec![core::PhysicalDeviceType::IntegratedGpu])
+        .select(&instance)
+        .unwrap();
+    assert_eq!(selected.physical_device, physical_devices[1]);
+}
+
+#[test]
+fn enable_optional_extensions() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+
+    let mut required_extensions = core::DeviceExtensions::new();
+    required_extensions.add_khr_swapchain();
+    let mut optional_extensions = core::DeviceExtensions::new();
+    optional_extensions.add_nv_dedicated_allocation();
+    optional_extensions.add("VK_KHR_maintenance1");
+
+    let selected = PhysicalDeviceSelector::new()
+        .required_extensions(required_extensions.clone())
+        .optional_extensions(optional_extensions)
+        .select(&instance)
+        .unwrap();
+    assert_eq!(selected.enabled_extensions, required_extensions);
+}
+
+#[test]
+fn queue_families_and_presentation() {
+    let instance = mock::create_instance(mock::instance_extensions());
+    let surface = mock::create_surface(&instance);
+
+    let selected = PhysicalDeviceSelector::new()
+        .queue_flags(core::QueueFlags::GRAPHICS)
+        .queue_flags(core::QueueFlags::TRANSFER)
+        .surface(surface)
+        .select(&instance)
+        .unwrap();
+    assert_eq!(selected.queue_family_indices, vec![0, 0]);
+    assert_eq!(selected.present_queue_family_index, Some(0));
+    assert_eq!(selected.queue_family_properties.len(), 2);
+}
+
+#[test]
+fn explain_rejections() {
+    mock::configure(|config| config.surface_support = false);
+    let instance = mock::create_instance(mock::instance_extensions());
+    let surface = mock::create_surface(&instance);
+
+    let mut extensions = core::DeviceExtensions::new();
+    extensions.add_khr_swapchain();
+    extensions.add_khr_display_swapchain();
+
+    let mut features = core::PhysicalDeviceFeatures::new();
+    features.geometry_shader = true;
+
+    let res = PhysicalDeviceSelector::new()
+        .required_extensions(extensions)
+        .required_features(features)
+        .queue_flags(core::QueueFlags::SPARSE_BINDING)
+        .surface(surface)
+        .min_api_version(dacite::DACITE_API_VERSION_1_1)
+        .select(&instance);
+
+    let rejected = match res {
+        Err(SelectionError::NoSuitableDevice(rejected)) => rejected,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(rejected.len(), 1);
+
+    let mut missing_extensions = core::DeviceExtensions::new();
+    missing_extensions.add_khr_display_swapchain();
+    assert_eq!(rejected[0].reasons, vec![
+        RejectionReason::ApiVersionTooLow {
+            required: dacite::DACITE_API_VERSION_1_1,
+            supported: core::Version { major: 1, minor: 0, patch: 61 },
+        },
+        RejectionReason::MissingExtensions(missing_extensions),
+        RejectionReason::MissingFeatures(features),
+        RejectionReason::MissingQueueFlags(core::QueueFlags::SPARSE_BINDING),
+        RejectionReason::PresentationNotSupported,
+    ]);
+
+    assert_eq!(rejected[0].to_string(), "dacite mock device: API version 1.0.61 is lower than 1.1.0; \
+                                         missing extensions VK_KHR_display_swapchain; missing features geometry_shader; \
+                                         no queue family supports SPARSE_BINDING; no queue family supports presentation");
+}
+
+#[test]
+fn query_errors_reject_device() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    mock::inject_result("vkEnumerateDeviceExtensionProperties", vks::vk::VK_ERROR_OUT_OF_HOST_MEMORY);
+
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+    let (selected, rejected) = PhysicalDeviceSelector::new().rank(physical_devices);
+    assert!(selected.is_empty());
+    assert_eq!(rejected[0].reasons, vec![RejectionReason::VulkanError(core::Error::OutOfHostMemory)]);
+
+    mock::inject_result("vkEnumeratePhysicalDevices", vks::vk::VK_ERROR_INITIALIZATION_FAILED);
+    match PhysicalDeviceSelector::new().select(&instance) {
+        Err(SelectionError::VulkanError(core::Error::InitializationFailed)) => { }
+        res => panic!("unexpected result {:?}", res),
+    }
+}
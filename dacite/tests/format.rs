@@ -0,0 +1,113 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+
+use dacite::core::{self, ComponentBits, Format, FormatCompression, NumericFormat};
+
+#[test]
+fn uncompressed_color_format() {
+    let format = Format::A2B10G10R10_UNorm_Pack32;
+    assert_eq!(format.block_size(), Some(4));
+    assert_eq!(format.block_extent(), Some(core::Extent3D::new(1, 1, 1)));
+    assert_eq!(format.component_count(), Some(4));
+    assert_eq!(format.component_bits(), Some(ComponentBits { red: 10, green: 10, blue: 10, alpha: 2, depth: 0, stencil: 0 }));
+    assert_eq!(format.numeric_format(), Some(NumericFormat::UNorm));
+    assert_eq!(format.aspect_mask(), core::ImageAspectFlags::COLOR);
+    assert!(!format.is_compressed());
+    assert!(!format.is_srgb());
+
+    assert_eq!(Format::R16G16B16_SFloat.block_size(), Some(6));
+    assert!(Format::R16G16B16_SFloat.numeric_format().unwrap().is_float());
+    assert!(Format::R32_SInt.numeric_format().unwrap().is_integer());
+    assert!(!Format::R8_UScaled.numeric_format().unwrap().is_integer());
+    assert!(Format::R8G8_sRGB.numeric_format().unwrap().is_normalized());
+}
+
+#[test]
+fn depth_stencil_formats() {
+    assert!(Format::D32_SFloat.has_depth());
+    assert!(!Format::D32_SFloat.has_stencil());
+    assert!(!Format::S8_UInt.has_depth());
+    assert!(Format::S8_UInt.has_stencil());
+
+    let format = Format::D24_UNorm_S8_UInt;
+    assert!(format.has_depth() && format.has_stencil());
+    assert_eq!(format.aspect_mask(), core::ImageAspectFlags::DEPTH | core::ImageAspectFlags::STENCIL);
+    assert_eq!(format.component_count(), Some(2));
+    assert_eq!(format.numeric_format(), Some(NumericFormat::UNorm));
+
+    assert_eq!(Format::X8_D24_UNorm_Pack32.block_size(), Some(4));
+    assert_eq!(Format::X8_D24_UNorm_Pack32.component_bits().unwrap().depth, 24);
+    assert_eq!(Format::D32_SFloat_S8_UInt.block_size(), Some(5));
+}
+
+#[test]
+fn compressed_formats() {
+    let format = Format::BC1_RGB_sRGB_Block;
+    assert_eq!(format.compression(), Some(FormatCompression::Bc));
+    assert_eq!(format.block_size(), Some(8));
+    assert_eq!(format.block_extent(), Some(core::Extent3D::new(4, 4, 1)));
+    assert_eq!(format.component_count(), Some(3));
+    assert_eq!(format.component_bits(), None);
+    assert!(format.is_srgb());
+
+    assert_eq!(Format::EAC_R11G11_SNorm_Block.compression(), Some(FormatCompression::Etc2));
+    assert_eq!(Format::ASTC_10x6_UNorm_Block.block_extent(), Some(core::Extent3D::new(10, 6, 1)));
+    assert_eq!(Format::ASTC_10x6_UNorm_Block.block_size(), Some(16));
+    assert_eq!(Format::PVRTC1_2BPP_UNorm_Block_Img.block_extent(), Some(core::Extent3D::new(8, 4, 1)));
+}
+
+#[test]
+fn data_size() {
+    let color = core::ImageAspectFlags::COLOR;
+    let depth = core::ImageAspectFlags::DEPTH;
+    let stencil = core::ImageAspectFlags::STENCIL;
+    let extent = core::Extent3D::new(4, 4, 1);
+
+    assert_eq!(Format::R8G8B8A8_UNorm.data_size(color, core::Extent3D::new(16, 8, 2)), Some(16 * 8 * 2 * 4));
+    assert_eq!(Format::BC3_UNorm_Block.data_size(color, core::Extent3D::new(10, 4, 1)), Some(3 * 16));
+    assert_eq!(Format::Undefined.data_size(color, core::Extent3D::new(1, 1, 1)), None);
+    assert_eq!(Format::R8G8B8A8_UNorm.data_size(depth, extent), None);
+
+    // Depth/stencil copies operate on a single aspect.
+    assert_eq!(Format::D16_UNorm.data_size(depth, extent), Some(16 * 2));
+    assert_eq!(Format::X8_D24_UNorm_Pack32.data_size(depth, extent), Some(16 * 4));
+    assert_eq!(Format::S8_UInt.data_size(stencil, extent), Some(16));
+    assert_eq!(Format::D16_UNorm_S8_UInt.data_size(depth, extent), Some(16 * 2));
+    assert_eq!(Format::D24_UNorm_S8_UInt.data_size(depth, extent), Some(16 * 4));
+    assert_eq!(Format::D24_UNorm_S8_UInt.data_size(stencil, extent), Some(16));
+    assert_eq!(Format::D32_SFloat_S8_UInt.data_size(depth, extent), Some(16 * 4));
+    assert_eq!(Format::D32_SFloat_S8_UInt.data_size(stencil, extent), Some(16));
+    assert_eq!(Format::D32_SFloat_S8_UInt.data_size(depth | stencil, extent), None);
+    assert_eq!(Format::D32_SFloat_S8_UInt.data_size(color, extent), None);
+    assert_eq!(Format::D32_SFloat.data_size(stencil, extent), None);
+}
+
+#[test]
+fn srgb_counterparts() {
+    assert_eq!(Format::B8G8R8A8_UNorm.srgb_counterpart(), Some(Format::B8G8R8A8_sRGB));
+    assert_eq!(Format::B8G8R8A8_sRGB.unorm_counterpart(), Some(Format::B8G8R8A8_UNorm));
+    assert_eq!(Format::ASTC_4x4_sRGB_Block.unorm_counterpart(), Some(Format::ASTC_4x4_UNorm_Block));
+    assert_eq!(Format::BC4_UNorm_Block.srgb_counterpart(), None);
+    assert_eq!(Format::R8G8B8A8_sRGB.srgb_counterpart(), None);
+}
+
+#[test]
+fn unknown_formats() {
+    assert_eq!(Format::Undefined.block_size(), None);
+    assert_eq!(Format::Unknown(0x7fff_0000).numeric_format(), None);
+    assert!(!Format::Undefined.is_compressed());
+    assert_eq!(Format::Undefined.aspect_mask(), core::ImageAspectFlags::COLOR);
+}
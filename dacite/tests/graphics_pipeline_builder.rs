@@ -0,0 +1,221 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::VulkanObject;
+use dacite::core;
+use dacite::spirv;
+use vks::vk;
+
+struct Objects {
+    device: core::Device,
+    layout: core::PipelineLayout,
+    render_pass: core::RenderPass,
+    shader_module: core::ShaderModule,
+}
+
+fn create_objects() -> Objects {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let layout = device.create_pipeline_layout(&core::PipelineLayoutCreateInfo {
+        flags: core::PipelineLayoutCreateFlags::empty(),
+        set_layouts: vec![],
+        push_constant_ranges: vec![],
+        chain: None,
+    }, None).unwrap();
+
+    let render_pass = device.create_render_pass(&core::RenderPassCreateInfo {
+        flags: core::RenderPassCreateFlags::empty(),
+        attachments: vec![],
+        subpasses: vec![],
+        dependencies: vec![],
+        chain: None,
+    }, None).unwrap();
+
+    let code = vec![spirv::MAGIC_NUMBER, 0x0001_0000, 0, 1, 0];
+    let shader_module = device.create_shader_module(&core::ShaderModuleCreateInfo::from_words(code).unwrap(), None).unwrap();
+
+    Objects {
+        device: device,
+        layout: layout,
+        render_pass: render_pass,
+        shader_module: shader_module,
+    }
+}
+
+fn builder(objects: &Objects) -> core::GraphicsPipelineBuilder {
+    core::GraphicsPipelineBuilder::new(objects.layout.clone(), objects.render_pass.clone(), 0)
+        .vertex_shader(objects.shader_module.clone(), "main")
+        .fragment_shader(objects.shader_module.clone(), "main")
+}
+
+#[test]
+fn defaults() {
+    let objects = create_objects();
+    let pipeline = builder(&objects).build(&objects.device, None).unwrap();
+    let info = mock::graphics_pipeline_info(pipeline.id()).unwrap();
+
+    assert_eq!(info.pipeline_cache, 0);
+    assert_eq!(info.stages, vec![(vk::VK_SHADER_STAGE_VERTEX_BIT, "main".to_owned()), (vk::VK_SHADER_STAGE_FRAGMENT_BIT, "main".to_owned())]);
+    assert_eq!(info.vertex_binding_count, 0);
+    assert_eq!(info.vertex_attribute_count, 0);
+    assert_eq!(info.topology, vk::VK_PRIMITIVE_TOPOLOGY_TRIANGLE_LIST);
+    assert_eq!(info.patch_control_points, None);
+    assert_eq!(info.viewport_count, 1);
+    assert_eq!(info.scissor_count, 1);
+    assert_eq!(info.dynamic_states, vec![vk::VK_DYNAMIC_STATE_VIEWPORT, vk::VK_DYNAMIC_STATE_SCISSOR]);
+    assert_eq!(info.polygon_mode, vk::VK_POLYGON_MODE_FILL);
+    assert_eq!(info.cull_mode, vk::VK_CULL_MODE_NONE);
+    assert_eq!(info.depth_test_enable, Some(false));
+    assert_eq!(info.blend_enables, vec![false]);
+    assert_eq!(info.color_write_masks, vec![vk::VK_COLOR_COMPONENT_R_BIT | vk::VK_COLOR_COMPONENT_G_BIT | vk::VK_COLOR_COMPONENT_B_BIT | vk::VK_COLOR_COMPONENT_A_BIT]);
+}
+
+#[test]
+fn static_viewport_and_scissor() {
+    let objects = create_objects();
+    let extent = core::Extent2D::new(640, 480);
+
+    let pipeline = builder(&objects)
+        .viewport(core::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: 640.0,
+            height: 480.0,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })
+        .dynamic_state(core::DynamicState::LineWidth)
+        .build(&objects.device, None)
+        .unwrap();
+    let info = mock::graphics_pipeline_info(pipeline.id()).unwrap();
+    assert_eq!(info.viewport_count, 1);
+    assert_eq!(info.dynamic_states, vec![vk::VK_DYNAMIC_STATE_LINE_WIDTH, vk::VK_DYNAMIC_STATE_SCISSOR]);
+
+    let pipeline = builder(&objects)
+        .scissor(core::Rect2D::new(core::Offset2D::zero(), extent))
+        .scissor(core::Rect2D::new(core::Offset2D::zero(), extent))
+        .build(&objects.device, None)
+        .unwrap();
+    let info = mock::graphics_pipeline_info(pipeline.id()).unwrap();
+    assert_eq!(info.scissor_count, 2);
+    assert_eq!(info.viewport_count, 2);
+    assert_eq!(info.dynamic_states, vec![vk::VK_DYNAMIC_STATE_VIEWPORT]);
+
+    let viewport = core::Viewport {
+        x: 0.0,
+        y: 0.0,
+        width: 640.0,
+        height: 480.0,
+        min_depth: 0.0,
+        max_depth: 1.0,
+    };
+    let pipeline = builder(&objects)
+        .viewport(viewport)
+        .viewport(viewport)
+        .viewport(viewport)
+        .build(&objects.device, None)
+        .unwrap();
+    let info = mock::graphics_pipeline_info(pipeline.id()).unwrap();
+    assert_eq!(info.viewport_count, 3);
+    assert_eq!(info.scissor_count, 3);
+    assert_eq!(info.dynamic_states, vec![vk::VK_DYNAMIC_STATE_SCISSOR]);
+}
+
+#[test]
+fn custom_state() {
+    let objects = create_objects();
+    let blend_attachment = core::PipelineColorBlendAttachmentState {
+        blend_enable: true,
+        src_color_blend_factor: core::BlendFactor::SrcAlpha,
+        dst_color_blend_factor: core::BlendFactor::OneMinusSrcAlpha,
+        color_blend_op: core::BlendOp::Add,
+        src_alpha_blend_factor: core::BlendFactor::One,
+        dst_alpha_blend_factor: core::BlendFactor::Zero,
+        alpha_blend_op: core::BlendOp::Add,
+        color_write_mask: core::ColorComponentFlags::R,
+    };
+
+    let pipeline = builder(&objects)
+        .vertex_binding(0, 20, core::VertexInputRate::Vertex)
+        .vertex_attribute(0, 0, core::Format::R32G32_SFloat, 0)
+        .vertex_attribute(1, 0, core::Format::R32G32B32_SFloat, 8)
+        .topology(core::PrimitiveTopology::PatchList)
+        .tessellation(3)
+        .polygon_mode(core::PolygonMode::Line)
+        .cull_mode(core::CullModeFlags::BACK)
+        .front_face(core::FrontFace::Clockwise)
+        .depth_test(core::CompareOp::Less, true)
+        .color_blend_attachment(blend_attachment)
+        .color_blend_attachment(blend_attachment)
+        .build(&objects.device, None)
+        .unwrap();
+    let info = mock::graphics_pipeline_info(pipeline.id()).unwrap();
+
+    assert_eq!(info.vertex_binding_count, 1);
+    assert_eq!(info.vertex_attribute_count, 2);
+    assert_eq!(info.topology, vk::VK_PRIMITIVE_TOPOLOGY_PATCH_LIST);
+    assert_eq!(info.patch_control_points, Some(3));
+    assert_eq!(info.polygon_mode, vk::VK_POLYGON_MODE_LINE);
+    assert_eq!(info.cull_mode, vk::VK_CULL_MODE_BACK_BIT);
+    assert_eq!(info.front_face, vk::VK_FRONT_FACE_CLOCKWISE);
+    assert_eq!(info.depth_test_enable, Some(true));
+    assert_eq!(info.blend_enables, vec![true, true]);
+    assert_eq!(info.color_write_masks, vec![vk::VK_COLOR_COMPONENT_R_BIT, vk::VK_COLOR_COMPONENT_R_BIT]);
+}
+
+#[test]
+fn depth_only() {
+    let objects = create_objects();
+    let pipeline = builder(&objects)
+        .depth_test(core::CompareOp::LessOrEqual, true)
+        .color_attachment_count(0)
+        .build(&objects.device, None)
+        .unwrap();
+    let info = mock::graphics_pipeline_info(pipeline.id()).unwrap();
+
+    assert_eq!(info.depth_test_enable, Some(true));
+    assert_eq!(info.blend_enables, vec![]);
+}
+
+#[test]
+fn build_with_pipeline_cache() {
+    let objects = create_objects();
+    let pipeline_cache = objects.device.create_pipeline_cache(&core::PipelineCacheCreateInfo {
+        flags: core::PipelineCacheCreateFlags::empty(),
+        initial_data: vec![],
+        chain: None,
+    }, None).unwrap();
+
+    let pipeline = builder(&objects).build(&objects.device, Some(&pipeline_cache)).unwrap();
+    assert_eq!(mock::graphics_pipeline_info(pipeline.id()).unwrap().pipeline_cache, pipeline_cache.id());
+
+    drop(pipeline);
+    assert_eq!(mock::live_object_count("VkPipeline"), 0);
+}
+
+#[test]
+fn build_error() {
+    let objects = create_objects();
+    mock::inject_result("vkCreateGraphicsPipelines", vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    assert_eq!(builder(&objects).build(&objects.device, None).unwrap_err(), core::Error::OutOfDeviceMemory);
+    assert_eq!(mock::live_object_count("VkPipeline"), 0);
+}
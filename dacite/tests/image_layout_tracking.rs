@@ -0,0 +1,217 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::FromNativeObject;
+use dacite::VulkanObject;
+use dacite::core;
+
+fn mip_range(base_mip_level: u32, level_count: u32) -> core::ImageSubresourceRange {
+    core::ImageSubresourceRange {
+        aspect_mask: core::ImageAspectFlags::COLOR,
+        base_mip_level: base_mip_level,
+        level_count: core::OptionalMipLevels::MipLevels(level_count),
+        base_array_layer: 0,
+        layer_count: core::OptionalArrayLayers::Remaining,
+    }
+}
+
+#[test]
+fn tracking_is_optional() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let create_info = core::ImageCreateInfo::builder(core::Format::R8G8B8A8_UNorm, core::Extent3D::new(64, 64, 1), core::ImageUsageFlags::SAMPLED).build();
+    let image = device.create_image(&create_info, None).unwrap();
+    assert!(!image.is_layout_tracked());
+    assert_eq!(image.layout(0, 0), None);
+
+    image.enable_layout_tracking(core::ImageLayout::Preinitialized).unwrap();
+    assert!(image.is_layout_tracked());
+    assert_eq!(image.layout(0, 0), Some(core::ImageLayout::Preinitialized));
+    assert_eq!(image.layout(1, 0), None);
+
+    image.disable_layout_tracking();
+    assert!(!image.is_layout_tracked());
+}
+
+#[test]
+fn untracked_images() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffer) = mock::create_command_buffer(&device);
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 4, 2);
+
+    assert_eq!(command_buffer.transition_to(&image, core::ImageLayout::General, core::PipelineStageFlags::TRANSFER), Err(core::LayoutTrackingError::NotEnabled));
+    assert_eq!(command_buffer.transition_range_to(&image, &mip_range(0, 1), core::ImageLayout::General, core::PipelineStageFlags::TRANSFER), Err(core::LayoutTrackingError::NotEnabled));
+    assert_eq!(mock::call_count("vkCmdPipelineBarrier"), 0);
+
+    // The subresources of images not created by dacite are unknown.
+    let params = core::FromNativeImageParameters::new(false, device.clone(), None);
+    let native = unsafe { core::Image::from_native_object(image.as_native_vulkan_object(), params) };
+    assert_eq!(native.enable_layout_tracking(core::ImageLayout::Undefined), Err(core::LayoutTrackingError::UnknownSubresources));
+
+    native.enable_layout_tracking_with(core::Format::R8G8B8A8_UNorm, 4, 2, core::ImageLayout::Undefined);
+    assert_eq!(native.layout(3, 1), Some(core::ImageLayout::Undefined));
+    assert_eq!(native.layout(4, 0), None);
+}
+
+#[test]
+fn transition_whole_image() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffer) = mock::create_command_buffer(&device);
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 4, 2);
+    image.enable_layout_tracking(core::ImageLayout::Undefined).unwrap();
+
+    command_buffer.transition_to(&image, core::ImageLayout::TransferDstOptimal, core::PipelineStageFlags::TRANSFER).unwrap();
+
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers.len(), 1);
+    assert_eq!(barriers[0].src_stage_mask, vks::vk::VK_PIPELINE_STAGE_TOP_OF_PIPE_BIT);
+    assert_eq!(barriers[0].dst_stage_mask, vks::vk::VK_PIPELINE_STAGE_TRANSFER_BIT);
+    assert_eq!(barriers[0].image_barriers, vec![mock::ImageBarrierInfo {
+        image: image.id(),
+        src_access_mask: 0,
+        dst_access_mask: vks::vk::VK_ACCESS_TRANSFER_WRITE_BIT,
+        old_layout: vks::vk::VK_IMAGE_LAYOUT_UNDEFINED,
+        new_layout: vks::vk::VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL,
+        base_mip_level: 0,
+        level_count: 4,
+        base_array_layer: 0,
+        layer_count: 2,
+        src_queue_family_index: vks::vk::VK_QUEUE_FAMILY_IGNORED,
+        dst_queue_family_index: vks::vk::VK_QUEUE_FAMILY_IGNORED,
+    }]);
+
+    for mip_level in 0..4 {
+        assert_eq!(image.layout(mip_level, 1), Some(core::ImageLayout::TransferDstOptimal));
+    }
+
+    command_buffer.transition_to(&image, core::ImageLayout::TransferDstOptimal, core::PipelineStageFlags::TRANSFER).unwrap();
+    assert_eq!(mock::call_count("vkCmdPipelineBarrier"), 1);
+}
+
+#[test]
+fn transition_mip_levels() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffer) = mock::create_command_buffer(&device);
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 4, 2);
+    image.enable_layout_tracking(core::ImageLayout::Undefined).unwrap();
+
+    command_buffer.transition_to(&image, core::ImageLayout::TransferDstOptimal, core::PipelineStageFlags::TRANSFER).unwrap();
+    command_buffer.transition_range_to(&image, &mip_range(0, 1), core::ImageLayout::TransferSrcOptimal, core::PipelineStageFlags::TRANSFER).unwrap();
+    assert_eq!(image.layout(0, 0), Some(core::ImageLayout::TransferSrcOptimal));
+    assert_eq!(image.layout(1, 0), Some(core::ImageLayout::TransferDstOptimal));
+
+    command_buffer.transition_to(&image, core::ImageLayout::ShaderReadOnlyOptimal, core::PipelineStageFlags::FRAGMENT_SHADER).unwrap();
+
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers.len(), 3);
+    assert_eq!(barriers[2].src_stage_mask, vks::vk::VK_PIPELINE_STAGE_TRANSFER_BIT);
+    assert_eq!(barriers[2].image_barriers.len(), 2);
+
+    let first = &barriers[2].image_barriers[0];
+    assert_eq!(first.old_layout, vks::vk::VK_IMAGE_LAYOUT_TRANSFER_SRC_OPTIMAL);
+    assert_eq!(first.src_access_mask, vks::vk::VK_ACCESS_TRANSFER_READ_BIT);
+    assert_eq!((first.base_mip_level, first.level_count, first.base_array_layer, first.layer_count), (0, 1, 0, 2));
+
+    let second = &barriers[2].image_barriers[1];
+    assert_eq!(second.old_layout, vks::vk::VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL);
+    assert_eq!(second.src_access_mask, vks::vk::VK_ACCESS_TRANSFER_WRITE_BIT);
+    assert_eq!(second.dst_access_mask, vks::vk::VK_ACCESS_SHADER_READ_BIT);
+    assert_eq!((second.base_mip_level, second.level_count, second.base_array_layer, second.layer_count), (1, 3, 0, 2));
+}
+
+#[test]
+fn pipeline_barrier_updates_layout() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffer) = mock::create_command_buffer(&device);
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 2, 1);
+    image.enable_layout_tracking(core::ImageLayout::Undefined).unwrap();
+
+    let barrier = core::ImageMemoryBarrier {
+        src_access_mask: core::AccessFlags::empty(),
+        dst_access_mask: core::AccessFlags::SHADER_WRITE,
+        old_layout: core::ImageLayout::Undefined,
+        new_layout: core::ImageLayout::General,
+        src_queue_family_index: core::QueueFamilyIndex::Ignored,
+        dst_queue_family_index: core::QueueFamilyIndex::Ignored,
+        image: image.clone(),
+        subresource_range: mip_range(1, 1),
+        chain: None,
+    };
+    command_buffer.pipeline_barrier(core::PipelineStageFlags::TOP_OF_PIPE, core::PipelineStageFlags::COMPUTE_SHADER, core::DependencyFlags::empty(), None, None, Some(&[barrier]));
+    assert_eq!(image.layout(0, 0), Some(core::ImageLayout::Undefined));
+    assert_eq!(image.layout(1, 0), Some(core::ImageLayout::General));
+
+    command_buffer.transition_range_to(&image, &mip_range(1, 1), core::ImageLayout::ShaderReadOnlyOptimal, core::PipelineStageFlags::FRAGMENT_SHADER).unwrap();
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers[1].src_stage_mask, vks::vk::VK_PIPELINE_STAGE_COMPUTE_SHADER_BIT);
+    assert_eq!(barriers[1].image_barriers[0].src_access_mask, vks::vk::VK_ACCESS_SHADER_WRITE_BIT);
+    assert_eq!(barriers[1].image_barriers[0].old_layout, vks::vk::VK_IMAGE_LAYOUT_GENERAL);
+}
+
+#[test]
+fn render_pass_updates_layout() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffer) = mock::create_command_buffer(&device);
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+    image.enable_layout_tracking(core::ImageLayout::Undefined).unwrap();
+
+    let create_info = core::RenderPassCreateInfo::builder()
+        .attachments(vec![core::AttachmentDescription {
+            flags: core::AttachmentDescriptionFlags::empty(),
+            format: core::Format::R8G8B8A8_UNorm,
+            samples: core::SampleCountFlagBits::SampleCount1,
+            load_op: core::AttachmentLoadOp::Clear,
+            store_op: core::AttachmentStoreOp::Store,
+            stencil_load_op: core::AttachmentLoadOp::DontCare,
+            stencil_store_op: core::AttachmentStoreOp::DontCare,
+            initial_layout: core::ImageLayout::Undefined,
+            final_layout: core::ImageLayout::ShaderReadOnlyOptimal,
+        }])
+        .build();
+    let render_pass = device.create_render_pass(&create_info, None).unwrap();
+
+    let create_info = core::ImageViewCreateInfo::builder(image.clone(), core::ImageViewType::Type2D, core::Format::R8G8B8A8_UNorm)
+        .subresource_range(mip_range(0, 1))
+        .build();
+    let image_view = device.create_image_view(&create_info, None).unwrap();
+
+    let create_info = core::FramebufferCreateInfo::builder(render_pass.clone(), 64, 64)
+        .attachments(vec![image_view])
+        .build();
+    let framebuffer = device.create_framebuffer(&create_info, None).unwrap();
+
+    command_buffer.begin_render_pass(&core::RenderPassBeginInfo {
+        render_pass: render_pass,
+        framebuffer: framebuffer,
+        render_area: core::Rect2D::new(core::Offset2D::zero(), core::Extent2D::new(64, 64)),
+        clear_values: vec![core::ClearValue::Color(core::ClearColorValue::Float32([0.0; 4]))],
+        chain: None,
+    }, core::SubpassContents::Inline);
+    command_buffer.end_render_pass();
+
+    assert_eq!(image.layout(0, 0), Some(core::ImageLayout::ShaderReadOnlyOptimal));
+}
@@ -0,0 +1,117 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::core;
+
+#[test]
+fn create_and_destroy_instance() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    assert_eq!(mock::live_object_count("VkInstance"), 1);
+
+    drop(instance);
+    assert_eq!(mock::live_object_count("VkInstance"), 0);
+    assert_eq!(mock::call_count("vkDestroyInstance"), 1);
+}
+
+#[test]
+fn create_instance_error() {
+    mock::inject_result("vkCreateInstance", vks::vk::VK_ERROR_INCOMPATIBLE_DRIVER);
+
+    let create_info = core::InstanceCreateInfo {
+        flags: core::InstanceCreateFlags::empty(),
+        application_info: None,
+        enabled_layers: vec![],
+        enabled_extensions: core::InstanceExtensions::new(),
+        chain: None,
+    };
+
+    let res = core::Instance::create(&mock::loader(), &create_info, None);
+    assert_eq!(res.unwrap_err(), core::EarlyInstanceError::VulkanError(core::Error::IncompatibleDriver));
+    assert_eq!(mock::call_count("vkDestroyInstance"), 0);
+}
+
+#[test]
+fn loader_without_function_pointer() {
+    let res = unsafe { core::Loader::from_get_instance_proc_addr(None) };
+    assert!(res.is_err());
+}
+
+#[test]
+fn loader_from_missing_library() {
+    match core::Loader::from_path("/nonexistent/libvulkan.so.1") {
+        Err(core::EarlyInstanceError::LoadLibraryFailed(path)) => assert_eq!(path, "/nonexistent/libvulkan.so.1"),
+        _ => panic!("expected LoadLibraryFailed"),
+    }
+}
+
+#[test]
+fn instance_extension_properties() {
+    let loader = mock::loader();
+    let properties = core::Instance::get_instance_extension_properties(&loader, None).unwrap();
+    assert!(properties.has_khr_surface());
+    assert_eq!(properties.get_khr_surface(), Some(25));
+    assert!(!properties.has_khr_display());
+}
+
+#[test]
+fn instance_layer_properties() {
+    let loader = mock::loader();
+    let layers: Vec<_> = core::Instance::enumerate_instance_layer_properties(&loader).unwrap();
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].layer_name, "VK_LAYER_MOCK_validation");
+}
+
+#[test]
+fn instance_layer_properties_error() {
+    mock::inject_result("vkEnumerateInstanceLayerProperties", vks::vk::VK_ERROR_OUT_OF_HOST_MEMORY);
+
+    let loader = mock::loader();
+    let res: Result<Vec<_>, _> = core::Instance::enumerate_instance_layer_properties(&loader);
+    assert_eq!(res.unwrap_err(), core::EarlyInstanceError::VulkanError(core::Error::OutOfHostMemory));
+}
+
+#[test]
+fn instance_layers() {
+    mock::configure(|config| {
+        config.instance_layers.push(("VK_LAYER_MOCK_overlay".to_owned(), 2));
+        config.layer_extensions.insert("VK_LAYER_MOCK_validation".to_owned(), vec![
+            (vks::ext_debug_report::VK_EXT_DEBUG_REPORT_EXTENSION_NAME_STR.to_owned(), 8),
+        ]);
+    });
+
+    let loader = mock::loader();
+    let layers: Vec<_> = core::Instance::enumerate_instance_layers(&loader).unwrap();
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0].properties.layer_name, "VK_LAYER_MOCK_validation");
+    assert_eq!(layers[0].extensions.get_ext_debug_report(), Some(8));
+    assert!(!layers[0].extensions.has_khr_surface());
+    assert_eq!(layers[1].properties.layer_name, "VK_LAYER_MOCK_overlay");
+    assert!(layers[1].extensions.is_empty());
+    assert_eq!(mock::call_count("vkEnumerateInstanceExtensionProperties"), 4);
+}
+
+#[test]
+fn enumerate_physical_devices() {
+    mock::configure(|config| config.physical_device_count = 3);
+
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+    assert_eq!(physical_devices.len(), 3);
+}
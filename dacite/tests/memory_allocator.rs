@@ -0,0 +1,213 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::core;
+use dacite::core::AllocationError;
+use dacite::memory_allocator::{MemoryAllocator, ResourceKind};
+
+fn requirements(size: u64) -> core::MemoryRequirements {
+    core::MemoryRequirements {
+        size: size,
+        alignment: 256,
+        memory_type_bits: 0b111,
+    }
+}
+
+#[test]
+fn find_memory_type() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+    let properties = physical_device.get_memory_properties();
+
+    assert_eq!(properties.find_memory_type(0b111, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty()), Some(0));
+    assert_eq!(properties.find_memory_type(0b111, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::empty()), Some(1));
+    assert_eq!(properties.find_memory_type(0b111, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::HOST_CACHED), Some(2));
+    assert_eq!(properties.find_memory_type(0b101, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::HOST_COHERENT), Some(2));
+    assert_eq!(properties.find_memory_type(0b001, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::empty()), None);
+}
+
+#[test]
+fn suballocate_from_one_block() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::new(&device, &physical_device);
+
+    let a = allocator.allocate(&requirements(100), ResourceKind::Buffer, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty()).unwrap();
+    let b = allocator.allocate(&requirements(1000), ResourceKind::Buffer, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty()).unwrap();
+    let c = allocator.allocate(&requirements(10), ResourceKind::Buffer, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty()).unwrap();
+
+    assert_eq!(mock::call_count("vkAllocateMemory"), 1);
+    assert_eq!(allocator.block_count(), 1);
+    assert_eq!(a.memory(), b.memory());
+    assert_eq!(a.memory_type_index(), 0);
+    assert_eq!((a.offset(), b.offset(), c.offset()), (0, 256, 1280));
+
+    // Freed ranges are reused.
+    drop(b);
+    let d = allocator.allocate(&requirements(512), ResourceKind::Buffer, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty()).unwrap();
+    assert_eq!(d.offset(), 256);
+    assert_eq!(mock::call_count("vkAllocateMemory"), 1);
+}
+
+#[test]
+fn buffer_image_granularity() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::new(&device, &physical_device);
+
+    let buffer = allocator.allocate(&requirements(256), ResourceKind::Buffer, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty()).unwrap();
+    let linear_image = allocator.allocate(&requirements(256), ResourceKind::LinearImage, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty()).unwrap();
+    let optimal_image = allocator.allocate(&requirements(256), ResourceKind::OptimalImage, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty()).unwrap();
+    let buffer2 = allocator.allocate(&requirements(256), ResourceKind::Buffer, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty()).unwrap();
+
+    assert_eq!(buffer.offset(), 0);
+    assert_eq!(linear_image.offset(), 256);
+    assert_eq!(optimal_image.offset(), 1024);
+
+    // The gap in front of the optimal image is still usable by linear resources.
+    assert_eq!(buffer2.offset(), 512);
+
+    let optimal_image2 = allocator.allocate(&requirements(256), ResourceKind::OptimalImage, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty()).unwrap();
+    let buffer3 = allocator.allocate(&requirements(256), ResourceKind::Buffer, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty()).unwrap();
+    assert_eq!(optimal_image2.offset(), 1280);
+    assert_eq!(buffer3.offset(), 768);
+
+    let buffer4 = allocator.allocate(&requirements(256), ResourceKind::Buffer, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty()).unwrap();
+    assert_eq!(buffer4.offset(), 2048);
+}
+
+#[test]
+fn dedicated_allocations() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::new(&device, &physical_device);
+
+    // The mock heaps are 256 MiB large, which results in blocks of 32 MiB.
+    let large = allocator.allocate(&requirements(20 * 1024 * 1024), ResourceKind::OptimalImage, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty()).unwrap();
+    assert!(large.is_dedicated());
+    assert_eq!(large.offset(), 0);
+    assert_eq!(large.memory().size(), 20 * 1024 * 1024);
+    assert_eq!(allocator.block_count(), 0);
+    assert_eq!(mock::live_object_count("VkDeviceMemory"), 1);
+
+    drop(large);
+    assert_eq!(mock::live_object_count("VkDeviceMemory"), 0);
+}
+
+#[test]
+fn empty_blocks_are_freed() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::with_block_size(&device, &physical_device, 4096);
+
+    let allocations: Vec<_> = (0..6)
+        .map(|_| allocator.allocate(&requirements(1024), ResourceKind::Buffer, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty()).unwrap())
+        .collect();
+    assert_eq!(allocator.block_count(), 2);
+    assert_eq!(mock::live_object_count("VkDeviceMemory"), 2);
+
+    drop(allocations);
+    assert_eq!(allocator.block_count(), 1);
+    assert_eq!(mock::live_object_count("VkDeviceMemory"), 1);
+
+    drop(allocator);
+    assert_eq!(mock::live_object_count("VkDeviceMemory"), 0);
+}
+
+#[test]
+fn no_suitable_memory_type() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::new(&device, &physical_device);
+
+    let mut requirements = requirements(256);
+    requirements.memory_type_bits = 0b001;
+    let res = allocator.allocate(&requirements, ResourceKind::Buffer, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::empty());
+    assert_eq!(res.unwrap_err(), AllocationError::NoSuitableMemoryType);
+}
+
+#[test]
+fn zero_size() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::new(&device, &physical_device);
+
+    let res = allocator.allocate(&requirements(0), ResourceKind::Buffer, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty());
+    assert_eq!(res.unwrap_err(), AllocationError::ZeroSize);
+    assert_eq!(allocator.block_count(), 0);
+}
+
+#[test]
+fn fall_back_to_other_memory_types() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::new(&device, &physical_device);
+
+    mock::inject_result("vkAllocateMemory", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    let allocation = allocator.allocate(&requirements(256), ResourceKind::Buffer, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::HOST_COHERENT).unwrap();
+    assert_eq!(allocation.memory_type_index(), 2);
+
+    mock::inject_result("vkAllocateMemory", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    let mut requirements = requirements(256);
+    requirements.memory_type_bits = 0b001;
+    let res = allocator.allocate(&requirements, ResourceKind::Buffer, core::MemoryPropertyFlags::empty(), core::MemoryPropertyFlags::empty());
+    assert_eq!(res.unwrap_err(), AllocationError::VulkanError(core::Error::OutOfDeviceMemory));
+}
+
+#[test]
+fn out_of_memory_without_fallback() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::new(&device, &physical_device);
+
+    // Only memory type 0 of the two allowed ones is device local.
+    mock::inject_result("vkAllocateMemory", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    let mut requirements = requirements(256);
+    requirements.memory_type_bits = 0b011;
+    let res = allocator.allocate(&requirements, ResourceKind::Buffer, core::MemoryPropertyFlags::DEVICE_LOCAL, core::MemoryPropertyFlags::empty());
+    assert_eq!(res.unwrap_err(), AllocationError::VulkanError(core::Error::OutOfDeviceMemory));
+    assert_eq!(mock::call_count("vkAllocateMemory"), 1);
+}
+
+#[test]
+fn allocate_for_buffer() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let allocator = MemoryAllocator::new(&device, &physical_device);
+
+    let create_info = core::BufferCreateInfo {
+        flags: core::BufferCreateFlags::empty(),
+        size: 1000,
+        usage: core::BufferUsageFlags::UNIFORM_BUFFER,
+        sharing_mode: core::SharingMode::Exclusive,
+        queue_family_indices: vec![],
+        chain: None,
+    };
+
+    let buffer = device.create_buffer(&create_info, None).unwrap();
+    let allocation = allocator.allocate_for_buffer(&buffer, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::empty()).unwrap();
+    assert_eq!(allocation.size(), 1024);
+    assert_eq!(mock::call_count("vkBindBufferMemory"), 1);
+
+    let buffer2 = device.create_buffer(&create_info, None).unwrap();
+    mock::inject_result("vkBindBufferMemory", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    let res = allocator.allocate_for_buffer(&buffer2, core::MemoryPropertyFlags::HOST_VISIBLE, core::MemoryPropertyFlags::empty());
+    assert_eq!(res.unwrap_err(), AllocationError::VulkanError(core::Error::OutOfDeviceMemory));
+}
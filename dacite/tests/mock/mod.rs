@@ -0,0 +1,1840 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+//! A mock Vulkan driver for tests.
+//!
+//! The driver implements `vkGetInstanceProcAddr` and `vkGetDeviceProcAddr` and hands out fake
+//! handles. Every call is recorded, and errors can be injected for individual functions.
+//!
+//! All state is thread-local. Since each test runs on its own thread, tests don't interfere with
+//! each other.
+
+#![allow(dead_code)]
+#![allow(non_snake_case)]
+
+use dacite::FromNativeObject;
+use dacite::core;
+use dacite::khr_surface;
+use libc::{c_char, c_void};
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::CStr;
+use std::mem;
+use std::ptr;
+use std::slice;
+use vks;
+
+/// Configuration of the mock driver.
+///
+/// The configuration can be changed with `configure()` before any objects are created.
+#[derive(Clone)]
+pub struct Config {
+    pub api_version: u32,
+    pub physical_device_count: u32,
+    pub device_type: vks::vk::VkPhysicalDeviceType,
+
+    /// Device types of the individual physical devices. Overrides `device_type`, if not empty.
+    pub device_types: Vec<vks::vk::VkPhysicalDeviceType>,
+
+    pub device_name: String,
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub pipeline_cache_uuid: [u8; 16],
+    pub limits: vks::vk::VkPhysicalDeviceLimits,
+    pub features: vks::vk::VkPhysicalDeviceFeatures,
+    pub queue_families: Vec<vks::vk::VkQueueFamilyProperties>,
+    pub memory_types: Vec<vks::vk::VkMemoryType>,
+    pub memory_heaps: Vec<vks::vk::VkMemoryHeap>,
+    pub format_properties: HashMap<vks::vk::VkFormat, vks::vk::VkFormatProperties>,
+    pub default_format_properties: vks::vk::VkFormatProperties,
+    pub memory_requirements: vks::vk::VkMemoryRequirements,
+    pub instance_layers: Vec<(String, u32)>,
+    pub instance_extensions: Vec<(String, u32)>,
+    pub device_extensions: Vec<(String, u32)>,
+    pub layer_extensions: HashMap<String, Vec<(String, u32)>>,
+    pub surface_support: bool,
+    pub surface_formats: Vec<vks::khr_surface::VkSurfaceFormatKHR>,
+    pub present_modes: Vec<vks::khr_surface::VkPresentModeKHR>,
+    pub swapchain_image_count: u32,
+    pub subgroup_size: u32,
+    pub protected_memory: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let mut limits: vks::vk::VkPhysicalDeviceLimits = unsafe { mem::zeroed() };
+        limits.maxImageDimension2D = 16384;
+        limits.maxMemoryAllocationCount = 4096;
+        limits.bufferImageGranularity = 1024;
+        limits.nonCoherentAtomSize = 64;
+        limits.minMemoryMapAlignment = 64;
+        limits.minUniformBufferOffsetAlignment = 256;
+        limits.maxBoundDescriptorSets = 8;
+
+        let all_features = 0x1fff;
+
+        Config {
+            api_version: vks::vk_make_version(1, 0, 61),
+            physical_device_count: 1,
+            device_type: vks::vk::VK_PHYSICAL_DEVICE_TYPE_DISCRETE_GPU,
+            device_types: Vec::new(),
+            device_name: "dacite mock device".to_owned(),
+            vendor_id: 0x1234,
+            device_id: 0x5678,
+            pipeline_cache_uuid: [0xda; 16],
+            limits: limits,
+            features: unsafe { mem::zeroed() },
+            queue_families: vec![
+                vks::vk::VkQueueFamilyProperties {
+                    queueFlags: vks::vk::VK_QUEUE_GRAPHICS_BIT | vks::vk::VK_QUEUE_COMPUTE_BIT | vks::vk::VK_QUEUE_TRANSFER_BIT,
+                    queueCount: 2,
+                    timestampValidBits: 64,
+                    minImageTransferGranularity: vks::vk::VkExtent3D { width: 1, height: 1, depth: 1 },
+                },
+                vks::vk::VkQueueFamilyProperties {
+                    queueFlags: vks::vk::VK_QUEUE_TRANSFER_BIT,
+                    queueCount: 1,
+                    timestampValidBits: 64,
+                    minImageTransferGranularity: vks::vk::VkExtent3D { width: 1, height: 1, depth: 1 },
+                },
+            ],
+            memory_types: vec![
+                vks::vk::VkMemoryType {
+                    propertyFlags: vks::vk::VK_MEMORY_PROPERTY_DEVICE_LOCAL_BIT,
+                    heapIndex: 0,
+                },
+                vks::vk::VkMemoryType {
+                    propertyFlags: vks::vk::VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT | vks::vk::VK_MEMORY_PROPERTY_HOST_COHERENT_BIT,
+                    heapIndex: 1,
+                },
+                vks::vk::VkMemoryType {
+                    propertyFlags: vks::vk::VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT | vks::vk::VK_MEMORY_PROPERTY_HOST_CACHED_BIT,
+                    heapIndex: 1,
+                },
+            ],
+            memory_heaps: vec![
+                vks::vk::VkMemoryHeap {
+                    size: 256 * 1024 * 1024,
+                    flags: vks::vk::VK_MEMORY_HEAP_DEVICE_LOCAL_BIT,
+                },
+                vks::vk::VkMemoryHeap {
+                    size: 256 * 1024 * 1024,
+                    flags: 0,
+                },
+            ],
+            format_properties: HashMap::new(),
+            default_format_properties: vks::vk::VkFormatProperties {
+                linearTilingFeatures: all_features,
+                optimalTilingFeatures: all_features,
+                bufferFeatures: 0,
+            },
+            memory_requirements: vks::vk::VkMemoryRequirements {
+                size: 0,
+                alignment: 256,
+                memoryTypeBits: 0b111,
+            },
+            instance_layers: vec![("VK_LAYER_MOCK_validation".to_owned(), 1)],
+            instance_extensions: vec![
+                (vks::khr_surface::VK_KHR_SURFACE_EXTENSION_NAME_STR.to_owned(), 25),
+                (vks::ext_debug_report::VK_EXT_DEBUG_REPORT_EXTENSION_NAME_STR.to_owned(), 8),
+            ],
+            device_extensions: vec![
+                (vks::khr_swapchain::VK_KHR_SWAPCHAIN_EXTENSION_NAME_STR.to_owned(), 68),
+            ],
+            layer_extensions: HashMap::new(),
+            surface_support: true,
+            surface_formats: vec![
+                vks::khr_surface::VkSurfaceFormatKHR {
+                    format: vks::vk::VK_FORMAT_B8G8R8A8_UNORM,
+                    colorSpace: vks::khr_surface::VK_COLOR_SPACE_SRGB_NONLINEAR_KHR,
+                },
+            ],
+            present_modes: vec![vks::khr_surface::VK_PRESENT_MODE_FIFO_KHR, vks::khr_surface::VK_PRESENT_MODE_MAILBOX_KHR],
+            swapchain_image_count: 3,
+            subgroup_size: 32,
+            protected_memory: false,
+        }
+    }
+}
+
+/// Parts of a `VkGraphicsPipelineCreateInfo` recorded by `vkCreateGraphicsPipelines`.
+#[derive(Debug, Clone)]
+pub struct GraphicsPipelineInfo {
+    pub pipeline_cache: u64,
+    pub stages: Vec<(vks::vk::VkShaderStageFlagBits, String)>,
+    pub vertex_binding_count: u32,
+    pub vertex_attribute_count: u32,
+    pub topology: vks::vk::VkPrimitiveTopology,
+    pub patch_control_points: Option<u32>,
+    pub viewport_count: u32,
+    pub scissor_count: u32,
+    pub cull_mode: vks::vk::VkCullModeFlags,
+    pub front_face: vks::vk::VkFrontFace,
+    pub polygon_mode: vks::vk::VkPolygonMode,
+    pub depth_test_enable: Option<bool>,
+    pub color_write_masks: Vec<vks::vk::VkColorComponentFlags>,
+    pub blend_enables: Vec<bool>,
+    pub dynamic_states: Vec<vks::vk::VkDynamicState>,
+}
+
+struct State {
+    config: Config,
+    next_handle: u64,
+    calls: Vec<&'static str>,
+    injected_results: HashMap<&'static str, VecDeque<vks::vk::VkResult>>,
+    live_objects: HashMap<u64, &'static str>,
+    memory: HashMap<u64, Vec<u8>>,
+    fences: HashMap<u64, bool>,
+    buffer_sizes: HashMap<u64, u64>,
+    mapped_memory_ranges: Vec<(u64, u64, u64)>,
+    swapchains: HashMap<u64, (Vec<u64>, u32)>,
+    shader_modules: HashMap<u64, Vec<u32>>,
+    render_pass_samples: HashMap<u64, Vec<vks::vk::VkSampleCountFlagBits>>,
+    render_pass_input_attachments: HashMap<u64, Vec<(u32, vks::vk::VkImageLayout)>>,
+    pipeline_caches: HashMap<u64, Vec<u8>>,
+    graphics_pipelines: HashMap<u64, GraphicsPipelineInfo>,
+    pipeline_barriers: Vec<PipelineBarrierInfo>,
+    buffer_copies: Vec<BufferCopyInfo>,
+    buffer_image_copies: Vec<BufferImageCopyInfo>,
+}
+
+impl State {
+    fn new() -> Self {
+        State {
+            config: Config::default(),
+            next_handle: 1,
+            calls: Vec::new(),
+            injected_results: HashMap::new(),
+            live_objects: HashMap::new(),
+            memory: HashMap::new(),
+            fences: HashMap::new(),
+            buffer_sizes: HashMap::new(),
+            mapped_memory_ranges: Vec::new(),
+            swapchains: HashMap::new(),
+            shader_modules: HashMap::new(),
+            render_pass_samples: HashMap::new(),
+            render_pass_input_attachments: HashMap::new(),
+            pipeline_caches: HashMap::new(),
+            graphics_pipelines: HashMap::new(),
+            pipeline_barriers: Vec::new(),
+            buffer_copies: Vec::new(),
+            buffer_image_copies: Vec::new(),
+        }
+    }
+
+    fn new_handle(&mut self, kind: &'static str) -> u64 {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.live_objects.insert(handle, kind);
+        handle
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State::new());
+}
+
+fn with_state<F, T>(f: F) -> T
+    where F: FnOnce(&mut State) -> T
+{
+    STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Resets the mock driver to its default configuration and clears all recorded calls.
+pub fn reset() {
+    with_state(|state| *state = State::new());
+}
+
+/// Changes the configuration of the mock driver.
+pub fn configure<F>(f: F)
+    where F: FnOnce(&mut Config)
+{
+    with_state(|state| f(&mut state.config));
+}
+
+/// Returns the names of all Vulkan functions called so far, in order.
+pub fn calls() -> Vec<&'static str> {
+    with_state(|state| state.calls.clone())
+}
+
+/// Returns how often the Vulkan function `name` was called.
+pub fn call_count(name: &str) -> usize {
+    with_state(|state| state.calls.iter().filter(|&&c| c == name).count())
+}
+
+/// Clears the list of recorded calls.
+pub fn clear_calls() {
+    with_state(|state| state.calls.clear());
+}
+
+/// Makes the next call to the Vulkan function `name` fail with `result`.
+///
+/// Multiple results can be queued for the same function.
+pub fn inject_result(name: &'static str, result: vks::vk::VkResult) {
+    with_state(|state| state.injected_results.entry(name).or_insert_with(VecDeque::new).push_back(result));
+}
+
+/// Returns the number of objects created by the driver, that have not been destroyed yet.
+pub fn live_object_count(kind: &str) -> usize {
+    with_state(|state| state.live_objects.values().filter(|&&k| k == kind).count())
+}
+
+/// Returns `true`, if the fence `handle` is signaled.
+pub fn is_fence_signaled(handle: u64) -> bool {
+    with_state(|state| state.fences.get(&handle).cloned().unwrap_or(false))
+}
+
+/// Returns the contents of the device memory `handle`.
+pub fn memory_contents(handle: u64) -> Vec<u8> {
+    with_state(|state| state.memory.get(&handle).cloned().unwrap_or_else(Vec::new))
+}
+
+/// Returns all `(memory, offset, size)` ranges passed to `vkFlushMappedMemoryRanges` and
+/// `vkInvalidateMappedMemoryRanges` so far.
+pub fn mapped_memory_ranges() -> Vec<(u64, u64, u64)> {
+    with_state(|state| state.mapped_memory_ranges.clone())
+}
+
+/// Parts of a `VkImageMemoryBarrier` recorded by `vkCmdPipelineBarrier`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageBarrierInfo {
+    pub image: u64,
+    pub src_access_mask: vks::vk::VkAccessFlags,
+    pub dst_access_mask: vks::vk::VkAccessFlags,
+    pub old_layout: vks::vk::VkImageLayout,
+    pub new_layout: vks::vk::VkImageLayout,
+    pub base_mip_level: u32,
+    pub level_count: u32,
+    pub base_array_layer: u32,
+    pub layer_count: u32,
+    pub src_queue_family_index: u32,
+    pub dst_queue_family_index: u32,
+}
+
+/// Parts of a `VkBufferMemoryBarrier` recorded by `vkCmdPipelineBarrier`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferBarrierInfo {
+    pub buffer: u64,
+    pub src_access_mask: vks::vk::VkAccessFlags,
+    pub dst_access_mask: vks::vk::VkAccessFlags,
+    pub src_queue_family_index: u32,
+    pub dst_queue_family_index: u32,
+}
+
+/// A call to `vkCmdPipelineBarrier`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineBarrierInfo {
+    pub src_stage_mask: vks::vk::VkPipelineStageFlags,
+    pub dst_stage_mask: vks::vk::VkPipelineStageFlags,
+    pub buffer_barrier_count: u32,
+    pub buffer_barriers: Vec<BufferBarrierInfo>,
+    pub image_barriers: Vec<ImageBarrierInfo>,
+}
+
+/// A region of a `vkCmdCopyBuffer` call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferCopyInfo {
+    pub src_buffer: u64,
+    pub dst_buffer: u64,
+    pub src_offset: u64,
+    pub dst_offset: u64,
+    pub size: u64,
+}
+
+/// A region of a `vkCmdCopyBufferToImage` call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferImageCopyInfo {
+    pub buffer: u64,
+    pub image: u64,
+    pub layout: vks::vk::VkImageLayout,
+    pub buffer_offset: u64,
+    pub mip_level: u32,
+}
+
+/// Returns the sample counts of the attachments passed to `vkCreateRenderPass` for the render pass
+/// `handle`.
+pub fn render_pass_samples(handle: u64) -> Vec<vks::vk::VkSampleCountFlagBits> {
+    with_state(|state| state.render_pass_samples.get(&handle).cloned().unwrap_or_else(Vec::new))
+}
+
+/// Returns the input attachment references of the first subpass passed to `vkCreateRenderPass` for
+/// the render pass `handle`.
+pub fn render_pass_input_attachments(handle: u64) -> Vec<(u32, vks::vk::VkImageLayout)> {
+    with_state(|state| state.render_pass_input_attachments.get(&handle).cloned().unwrap_or_else(Vec::new))
+}
+
+/// Returns the code passed to `vkCreateShaderModule` for the shader module `handle`.
+pub fn shader_module_code(handle: u64) -> Vec<u32> {
+    with_state(|state| state.shader_modules.get(&handle).cloned().unwrap_or_else(Vec::new))
+}
+
+/// Returns the initial data passed to `vkCreatePipelineCache` for the pipeline cache `handle`.
+pub fn pipeline_cache_initial_data(handle: u64) -> Vec<u8> {
+    with_state(|state| state.pipeline_caches.get(&handle).cloned().unwrap_or_else(Vec::new))
+}
+
+/// Returns the create info recorded for the graphics pipeline `handle`.
+pub fn graphics_pipeline_info(handle: u64) -> Option<GraphicsPipelineInfo> {
+    with_state(|state| state.graphics_pipelines.get(&handle).cloned())
+}
+
+/// Returns all pipeline barriers recorded into any command buffer.
+pub fn pipeline_barriers() -> Vec<PipelineBarrierInfo> {
+    with_state(|state| state.pipeline_barriers.clone())
+}
+
+/// Returns all regions of `vkCmdCopyBuffer` calls recorded into any command buffer.
+pub fn buffer_copies() -> Vec<BufferCopyInfo> {
+    with_state(|state| state.buffer_copies.clone())
+}
+
+/// Returns all regions of `vkCmdCopyBufferToImage` calls recorded into any command buffer.
+pub fn buffer_image_copies() -> Vec<BufferImageCopyInfo> {
+    with_state(|state| state.buffer_image_copies.clone())
+}
+
+/// Returns pipeline cache data with a valid header for the mock device followed by `payload`.
+pub fn pipeline_cache_data(payload: &[u8]) -> Vec<u8> {
+    let (vendor_id, device_id, pipeline_cache_uuid) = with_state(|state| (state.config.vendor_id, state.config.device_id, state.config.pipeline_cache_uuid));
+
+    let mut data = Vec::new();
+    for &value in &[32, vks::vk::VK_PIPELINE_CACHE_HEADER_VERSION_ONE, vendor_id, device_id] {
+        data.extend_from_slice(&[value as u8, (value >> 8) as u8, (value >> 16) as u8, (value >> 24) as u8]);
+    }
+    data.extend_from_slice(&pipeline_cache_uuid);
+    data.extend_from_slice(payload);
+    data
+}
+
+/// Returns a `Loader`, which uses the mock driver.
+pub fn loader() -> core::Loader {
+    unsafe { core::Loader::from_get_instance_proc_addr(Some(vkGetInstanceProcAddr)).unwrap() }
+}
+
+/// Creates an `Instance` with the given extensions enabled.
+pub fn create_instance(enabled_extensions: core::InstanceExtensions) -> core::Instance {
+    let create_info = core::InstanceCreateInfo {
+        flags: core::InstanceCreateFlags::empty(),
+        application_info: None,
+        enabled_layers: vec![],
+        enabled_extensions: enabled_extensions,
+        chain: None,
+    };
+
+    core::Instance::create(&loader(), &create_info, None).unwrap()
+}
+
+/// Creates a `Device` on the first physical device with one queue of family 0.
+pub fn create_device(instance: &core::Instance, enabled_extensions: core::DeviceExtensions) -> (core::PhysicalDevice, core::Device) {
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let create_info = core::DeviceCreateInfo {
+        flags: core::DeviceCreateFlags::empty(),
+        queue_create_infos: vec![core::DeviceQueueCreateInfo {
+            flags: core::DeviceQueueCreateFlags::empty(),
+            queue_family_index: 0,
+            queue_priorities: vec![1.0],
+            chain: None,
+        }],
+        enabled_layers: vec![],
+        enabled_extensions: enabled_extensions,
+        enabled_features: None,
+        chain: None,
+    };
+
+    let device = physical_device.create_device(&create_info, None).unwrap();
+    (physical_device, device)
+}
+
+/// Creates a `Buffer`, which can be used as a transfer source and destination and as a vertex
+/// buffer.
+pub fn create_buffer(device: &core::Device, size: u64) -> core::Buffer {
+    let usage = core::BufferUsageFlags::TRANSFER_SRC | core::BufferUsageFlags::TRANSFER_DST | core::BufferUsageFlags::VERTEX_BUFFER;
+    let create_info = core::BufferCreateInfo::builder(size, usage).build();
+
+    device.create_buffer(&create_info, None).unwrap()
+}
+
+/// Creates a 64x64 `Image`, which can be used for transfers, sampling and as a color attachment.
+pub fn create_image(device: &core::Device, format: core::Format, mip_levels: u32, array_layers: u32) -> core::Image {
+    let usage = core::ImageUsageFlags::TRANSFER_SRC | core::ImageUsageFlags::TRANSFER_DST | core::ImageUsageFlags::SAMPLED | core::ImageUsageFlags::COLOR_ATTACHMENT;
+    let create_info = core::ImageCreateInfo::builder(format, core::Extent3D::new(64, 64, 1), usage)
+        .mip_levels(mip_levels)
+        .array_layers(array_layers)
+        .build();
+
+    device.create_image(&create_info, None).unwrap()
+}
+
+/// Creates a `DescriptorPool` and allocates one `DescriptorSet` with a single uniform buffer
+/// binding from it.
+pub fn create_descriptor_set(device: &core::Device) -> (core::DescriptorPool, core::DescriptorSet) {
+    let layout_create_info = core::DescriptorSetLayoutCreateInfo {
+        flags: core::DescriptorSetLayoutCreateFlags::empty(),
+        bindings: vec![core::DescriptorSetLayoutBinding {
+            binding: 0,
+            descriptor_type: core::DescriptorType::UniformBuffer,
+            descriptor_count: 2,
+            stage_flags: core::ShaderStageFlags::VERTEX,
+            immutable_samplers: vec![],
+        }],
+        chain: None,
+    };
+    let layout = device.create_descriptor_set_layout(&layout_create_info, None).unwrap();
+
+    let pool_create_info = core::DescriptorPoolCreateInfo::builder(1)
+        .flags(core::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET)
+        .pool_sizes(vec![core::DescriptorPoolSize {
+            descriptor_type: core::DescriptorType::UniformBuffer,
+            descriptor_count: 2,
+        }])
+        .build();
+    let descriptor_pool = device.create_descriptor_pool(&pool_create_info, None).unwrap();
+
+    let allocate_info = core::DescriptorSetAllocateInfo {
+        descriptor_pool: descriptor_pool.clone(),
+        set_layouts: vec![layout],
+        chain: None,
+    };
+    let descriptor_set = core::DescriptorPool::allocate_descriptor_sets(&allocate_info).unwrap().remove(0);
+
+    (descriptor_pool, descriptor_set)
+}
+
+/// Creates a `CommandPool` for queue family 0 and allocates one primary `CommandBuffer` from it.
+pub fn create_command_buffer(device: &core::Device) -> (core::CommandPool, core::CommandBuffer) {
+    let command_pool = device.create_command_pool(&core::CommandPoolCreateInfo::default(), None).unwrap();
+
+    let allocate_info = core::CommandBufferAllocateInfo {
+        command_pool: command_pool.clone(),
+        level: core::CommandBufferLevel::Primary,
+        command_buffer_count: 1,
+        chain: None,
+    };
+    let command_buffer = core::CommandPool::allocate_command_buffers(&allocate_info).unwrap().remove(0);
+
+    (command_pool, command_buffer)
+}
+
+/// Returns `InstanceExtensions` with `VK_KHR_surface` enabled.
+pub fn instance_extensions() -> core::InstanceExtensions {
+    let mut extensions = core::InstanceExtensions::new();
+    extensions.add_khr_surface();
+    extensions
+}
+
+/// Creates a `SurfaceKhr`, which is not backed by any window.
+///
+/// The instance must have been created with `VK_KHR_surface` enabled.
+pub fn create_surface(instance: &core::Instance) -> khr_surface::SurfaceKhr {
+    let handle = new_handle("VkSurfaceKHR");
+    let params = khr_surface::FromNativeSurfaceKhrParameters::new(true, instance.clone(), None);
+    unsafe { khr_surface::SurfaceKhr::from_native_object(handle, params) }
+}
+
+/// Returns `true`, if the configured API version is at least 1.1.
+///
+/// The Vulkan 1.1 entry points are only exposed in that case.
+fn supports_vulkan_1_1() -> bool {
+    with_state(|state| vks::vk_version_minor(state.config.api_version) >= 1)
+}
+
+/// Records a call and returns an injected result, if there is one.
+fn record(name: &'static str) -> Option<vks::vk::VkResult> {
+    with_state(|state| {
+        state.calls.push(name);
+        state.injected_results.get_mut(name).and_then(VecDeque::pop_front)
+    })
+}
+
+fn new_handle(kind: &'static str) -> u64 {
+    with_state(|state| state.new_handle(kind))
+}
+
+fn destroy_handle(handle: u64) {
+    with_state(|state| {
+        state.live_objects.remove(&handle);
+    });
+}
+
+unsafe fn copy_cstr(dst: &mut [c_char], src: &str) {
+    let len = dst.len() - 1;
+    for (d, s) in dst.iter_mut().zip(src.bytes().take(len)) {
+        *d = s as c_char;
+    }
+}
+
+/// Implements the two-call idiom for enumerations.
+unsafe fn enumerate<T: Copy>(items: &[T], count: *mut u32, out: *mut T) -> vks::vk::VkResult {
+    if out.is_null() {
+        *count = items.len() as u32;
+        return vks::vk::VK_SUCCESS;
+    }
+
+    let num = (*count as usize).min(items.len());
+    ptr::copy_nonoverlapping(items.as_ptr(), out, num);
+    *count = num as u32;
+
+    if num < items.len() {
+        vks::vk::VK_INCOMPLETE
+    }
+    else {
+        vks::vk::VK_SUCCESS
+    }
+}
+
+fn extension_properties(extensions: &[(String, u32)]) -> Vec<vks::vk::VkExtensionProperties> {
+    extensions.iter().map(|&(ref name, spec_version)| {
+        let mut properties: vks::vk::VkExtensionProperties = unsafe { mem::zeroed() };
+        unsafe { copy_cstr(&mut properties.extensionName, name); }
+        properties.specVersion = spec_version;
+        properties
+    }).collect()
+}
+
+/// Returns the extensions of the layer `layer_name`, or `None` if `layer_name` is null.
+unsafe fn layer_extension_properties(layer_name: *const c_char) -> Result<Option<Vec<vks::vk::VkExtensionProperties>>, vks::vk::VkResult> {
+    if layer_name.is_null() {
+        return Ok(None);
+    }
+
+    let layer_name = CStr::from_ptr(layer_name).to_str().unwrap();
+    with_state(|state| {
+        if !state.config.instance_layers.iter().any(|&(ref name, _)| name == layer_name) {
+            return Err(vks::vk::VK_ERROR_LAYER_NOT_PRESENT);
+        }
+
+        let extensions = state.config.layer_extensions.get(layer_name).map_or(&[][..], |e| &e[..]);
+        Ok(Some(extension_properties(extensions)))
+    })
+}
+
+fn layer_properties(layers: &[(String, u32)]) -> Vec<vks::vk::VkLayerProperties> {
+    layers.iter().map(|&(ref name, implementation_version)| {
+        let mut properties: vks::vk::VkLayerProperties = unsafe { mem::zeroed() };
+        unsafe {
+            copy_cstr(&mut properties.layerName, name);
+            copy_cstr(&mut properties.description, "dacite mock layer");
+        }
+        properties.specVersion = vks::vk_make_version(1, 0, 61);
+        properties.implementationVersion = implementation_version;
+        properties
+    }).collect()
+}
+
+macro_rules! proc_addr {
+    ($name:expr, [ $( $function:ident ),* $(,)* ]) => {
+        match $name {
+            $(
+                stringify!($function) => Some(mem::transmute($function as *const ())),
+            )*
+            _ => None,
+        }
+    }
+}
+
+macro_rules! try_injected {
+    ($name:expr) => {
+        if let Some(res) = record($name) {
+            return res;
+        }
+    }
+}
+
+pub unsafe extern "system" fn vkGetInstanceProcAddr(_instance: vks::vk::VkInstance, pName: *const c_char) -> vks::vk::PFN_vkVoidFunction {
+    let name = CStr::from_ptr(pName).to_str().unwrap();
+
+    if supports_vulkan_1_1() {
+        let function: vks::vk::PFN_vkVoidFunction = proc_addr!(name, [
+            vkEnumerateInstanceVersion,
+            vkEnumeratePhysicalDeviceGroups,
+            vkGetPhysicalDeviceFeatures2,
+            vkGetPhysicalDeviceProperties2,
+            vkGetPhysicalDeviceFormatProperties2,
+            vkGetPhysicalDeviceQueueFamilyProperties2,
+            vkGetPhysicalDeviceMemoryProperties2,
+        ]);
+
+        if function.is_some() {
+            return function;
+        }
+    }
+
+    proc_addr!(name, [
+        vkGetInstanceProcAddr,
+        vkCreateInstance,
+        vkDestroyInstance,
+        vkEnumerateInstanceExtensionProperties,
+        vkEnumerateInstanceLayerProperties,
+        vkEnumeratePhysicalDevices,
+        vkGetPhysicalDeviceProperties,
+        vkGetPhysicalDeviceFeatures,
+        vkGetPhysicalDeviceQueueFamilyProperties,
+        vkGetPhysicalDeviceMemoryProperties,
+        vkGetPhysicalDeviceFormatProperties,
+        vkGetPhysicalDeviceImageFormatProperties,
+        vkEnumerateDeviceExtensionProperties,
+        vkEnumerateDeviceLayerProperties,
+        vkCreateDevice,
+        vkGetDeviceProcAddr,
+        vkDestroySurfaceKHR,
+        vkGetPhysicalDeviceSurfaceSupportKHR,
+        vkGetPhysicalDeviceSurfaceCapabilitiesKHR,
+        vkGetPhysicalDeviceSurfaceFormatsKHR,
+        vkGetPhysicalDeviceSurfacePresentModesKHR,
+    ])
+}
+
+pub unsafe extern "system" fn vkGetDeviceProcAddr(_device: vks::vk::VkDevice, pName: *const c_char) -> vks::vk::PFN_vkVoidFunction {
+    let name = CStr::from_ptr(pName).to_str().unwrap();
+
+    if supports_vulkan_1_1() {
+        let function: vks::vk::PFN_vkVoidFunction = proc_addr!(name, [
+            vkGetDeviceQueue2,
+            vkTrimCommandPool,
+            vkBindBufferMemory2,
+            vkBindImageMemory2,
+            vkGetDeviceGroupPeerMemoryFeatures,
+        ]);
+
+        if function.is_some() {
+            return function;
+        }
+    }
+
+    proc_addr!(name, [
+        vkGetDeviceProcAddr,
+        vkDestroyDevice,
+        vkGetDeviceQueue,
+        vkDeviceWaitIdle,
+        vkQueueSubmit,
+        vkQueueWaitIdle,
+        vkCreateCommandPool,
+        vkDestroyCommandPool,
+        vkResetCommandPool,
+        vkAllocateCommandBuffers,
+        vkFreeCommandBuffers,
+        vkBeginCommandBuffer,
+        vkEndCommandBuffer,
+        vkResetCommandBuffer,
+        vkCreateFence,
+        vkDestroyFence,
+        vkResetFences,
+        vkGetFenceStatus,
+        vkWaitForFences,
+        vkCreateSemaphore,
+        vkDestroySemaphore,
+        vkCreateBuffer,
+        vkDestroyBuffer,
+        vkGetBufferMemoryRequirements,
+        vkBindBufferMemory,
+        vkCreateImage,
+        vkDestroyImage,
+        vkGetImageMemoryRequirements,
+        vkBindImageMemory,
+        vkCreateImageView,
+        vkDestroyImageView,
+        vkCreateRenderPass,
+        vkDestroyRenderPass,
+        vkCreateShaderModule,
+        vkDestroyShaderModule,
+        vkCreatePipelineCache,
+        vkDestroyPipelineCache,
+        vkGetPipelineCacheData,
+        vkCreateGraphicsPipelines,
+        vkDestroyPipeline,
+        vkCreatePipelineLayout,
+        vkDestroyPipelineLayout,
+        vkCreateFramebuffer,
+        vkDestroyFramebuffer,
+        vkCreateDescriptorSetLayout,
+        vkDestroyDescriptorSetLayout,
+        vkCreateDescriptorPool,
+        vkDestroyDescriptorPool,
+        vkAllocateDescriptorSets,
+        vkFreeDescriptorSets,
+        vkUpdateDescriptorSets,
+        vkAllocateMemory,
+        vkFreeMemory,
+        vkMapMemory,
+        vkUnmapMemory,
+        vkFlushMappedMemoryRanges,
+        vkInvalidateMappedMemoryRanges,
+        vkCmdBindPipeline,
+        vkCmdSetViewport,
+        vkCmdSetScissor,
+        vkCmdBindDescriptorSets,
+        vkCmdBindIndexBuffer,
+        vkCmdBindVertexBuffers,
+        vkCmdDraw,
+        vkCmdDrawIndexed,
+        vkCmdDispatch,
+        vkCmdCopyBuffer,
+        vkCmdCopyImage,
+        vkCmdBlitImage,
+        vkCmdCopyBufferToImage,
+        vkCmdCopyImageToBuffer,
+        vkCmdUpdateBuffer,
+        vkCmdFillBuffer,
+        vkCmdClearColorImage,
+        vkCmdPipelineBarrier,
+        vkCmdPushConstants,
+        vkCmdBeginRenderPass,
+        vkCmdNextSubpass,
+        vkCmdEndRenderPass,
+        vkCmdExecuteCommands,
+        vkCreateSwapchainKHR,
+        vkDestroySwapchainKHR,
+        vkGetSwapchainImagesKHR,
+        vkAcquireNextImageKHR,
+        vkQueuePresentKHR,
+    ])
+}
+
+unsafe extern "system" fn vkCreateInstance(_pCreateInfo: *const vks::vk::VkInstanceCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pInstance: *mut vks::vk::VkInstance) -> vks::vk::VkResult {
+    try_injected!("vkCreateInstance");
+    *pInstance = new_handle("VkInstance") as vks::vk::VkInstance;
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyInstance(instance: vks::vk::VkInstance, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyInstance");
+    destroy_handle(instance as u64);
+}
+
+unsafe extern "system" fn vkEnumerateInstanceExtensionProperties(pLayerName: *const c_char, pPropertyCount: *mut u32, pProperties: *mut vks::vk::VkExtensionProperties) -> vks::vk::VkResult {
+    try_injected!("vkEnumerateInstanceExtensionProperties");
+    let properties = match layer_extension_properties(pLayerName) {
+        Ok(Some(properties)) => properties,
+        Ok(None) => with_state(|state| extension_properties(&state.config.instance_extensions)),
+        Err(res) => return res,
+    };
+    enumerate(&properties, pPropertyCount, pProperties)
+}
+
+unsafe extern "system" fn vkEnumerateInstanceLayerProperties(pPropertyCount: *mut u32, pProperties: *mut vks::vk::VkLayerProperties) -> vks::vk::VkResult {
+    try_injected!("vkEnumerateInstanceLayerProperties");
+    let properties = with_state(|state| layer_properties(&state.config.instance_layers));
+    enumerate(&properties, pPropertyCount, pProperties)
+}
+
+unsafe extern "system" fn vkEnumeratePhysicalDevices(_instance: vks::vk::VkInstance, pPhysicalDeviceCount: *mut u32, pPhysicalDevices: *mut vks::vk::VkPhysicalDevice) -> vks::vk::VkResult {
+    try_injected!("vkEnumeratePhysicalDevices");
+    let physical_devices: Vec<_> = with_state(|state| {
+        (0..state.config.physical_device_count).map(|i| (0x1000_0000 + i as u64) as vks::vk::VkPhysicalDevice).collect()
+    });
+    enumerate(&physical_devices, pPhysicalDeviceCount, pPhysicalDevices)
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceProperties(physicalDevice: vks::vk::VkPhysicalDevice, pProperties: *mut vks::vk::VkPhysicalDeviceProperties) {
+    record("vkGetPhysicalDeviceProperties");
+    with_state(|state| {
+        let index = (physicalDevice as u64 - 0x1000_0000) as usize;
+        let properties = &mut *pProperties;
+        *properties = mem::zeroed();
+        properties.apiVersion = state.config.api_version;
+        properties.driverVersion = 1;
+        properties.vendorID = state.config.vendor_id;
+        properties.deviceID = state.config.device_id;
+        properties.deviceType = state.config.device_types.get(index).cloned().unwrap_or(state.config.device_type);
+        copy_cstr(&mut properties.deviceName, &state.config.device_name);
+        properties.pipelineCacheUUID = state.config.pipeline_cache_uuid;
+        properties.limits = state.config.limits;
+    });
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceFeatures(_physicalDevice: vks::vk::VkPhysicalDevice, pFeatures: *mut vks::vk::VkPhysicalDeviceFeatures) {
+    record("vkGetPhysicalDeviceFeatures");
+    *pFeatures = with_state(|state| state.config.features);
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceQueueFamilyProperties(_physicalDevice: vks::vk::VkPhysicalDevice, pQueueFamilyPropertyCount: *mut u32, pQueueFamilyProperties: *mut vks::vk::VkQueueFamilyProperties) {
+    record("vkGetPhysicalDeviceQueueFamilyProperties");
+    let queue_families = with_state(|state| state.config.queue_families.clone());
+    enumerate(&queue_families, pQueueFamilyPropertyCount, pQueueFamilyProperties);
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceMemoryProperties(_physicalDevice: vks::vk::VkPhysicalDevice, pMemoryProperties: *mut vks::vk::VkPhysicalDeviceMemoryProperties) {
+    record("vkGetPhysicalDeviceMemoryProperties");
+    with_state(|state| {
+        let properties = &mut *pMemoryProperties;
+        *properties = mem::zeroed();
+        properties.memoryTypeCount = state.config.memory_types.len() as u32;
+        properties.memoryTypes[..state.config.memory_types.len()].copy_from_slice(&state.config.memory_types);
+        properties.memoryHeapCount = state.config.memory_heaps.len() as u32;
+        properties.memoryHeaps[..state.config.memory_heaps.len()].copy_from_slice(&state.config.memory_heaps);
+    });
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceFormatProperties(_physicalDevice: vks::vk::VkPhysicalDevice, format: vks::vk::VkFormat, pFormatProperties: *mut vks::vk::VkFormatProperties) {
+    record("vkGetPhysicalDeviceFormatProperties");
+    *pFormatProperties = with_state(|state| {
+        state.config.format_properties.get(&format).cloned().unwrap_or(state.config.default_format_properties)
+    });
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceImageFormatProperties(_physicalDevice: vks::vk::VkPhysicalDevice, _format: vks::vk::VkFormat, _type: vks::vk::VkImageType, _tiling: vks::vk::VkImageTiling, _usage: vks::vk::VkImageUsageFlags, _flags: vks::vk::VkImageCreateFlags, pImageFormatProperties: *mut vks::vk::VkImageFormatProperties) -> vks::vk::VkResult {
+    try_injected!("vkGetPhysicalDeviceImageFormatProperties");
+    *pImageFormatProperties = vks::vk::VkImageFormatProperties {
+        maxExtent: vks::vk::VkExtent3D { width: 16384, height: 16384, depth: 1 },
+        maxMipLevels: 15,
+        maxArrayLayers: 2048,
+        sampleCounts: vks::vk::VK_SAMPLE_COUNT_1_BIT | vks::vk::VK_SAMPLE_COUNT_4_BIT,
+        maxResourceSize: 1 << 31,
+    };
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkEnumerateDeviceExtensionProperties(_physicalDevice: vks::vk::VkPhysicalDevice, pLayerName: *const c_char, pPropertyCount: *mut u32, pProperties: *mut vks::vk::VkExtensionProperties) -> vks::vk::VkResult {
+    try_injected!("vkEnumerateDeviceExtensionProperties");
+    let properties = match layer_extension_properties(pLayerName) {
+        Ok(Some(properties)) => properties,
+        Ok(None) => with_state(|state| extension_properties(&state.config.device_extensions)),
+        Err(res) => return res,
+    };
+    enumerate(&properties, pPropertyCount, pProperties)
+}
+
+unsafe extern "system" fn vkEnumerateDeviceLayerProperties(_physicalDevice: vks::vk::VkPhysicalDevice, pPropertyCount: *mut u32, pProperties: *mut vks::vk::VkLayerProperties) -> vks::vk::VkResult {
+    try_injected!("vkEnumerateDeviceLayerProperties");
+    let properties = with_state(|state| layer_properties(&state.config.instance_layers));
+    enumerate(&properties, pPropertyCount, pProperties)
+}
+
+unsafe extern "system" fn vkCreateDevice(_physicalDevice: vks::vk::VkPhysicalDevice, _pCreateInfo: *const vks::vk::VkDeviceCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pDevice: *mut vks::vk::VkDevice) -> vks::vk::VkResult {
+    try_injected!("vkCreateDevice");
+    *pDevice = new_handle("VkDevice") as vks::vk::VkDevice;
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyDevice(device: vks::vk::VkDevice, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyDevice");
+    destroy_handle(device as u64);
+}
+
+unsafe extern "system" fn vkGetDeviceQueue(_device: vks::vk::VkDevice, queueFamilyIndex: u32, queueIndex: u32, pQueue: *mut vks::vk::VkQueue) {
+    record("vkGetDeviceQueue");
+    *pQueue = (0x2000_0000 + ((queueFamilyIndex as u64) << 8) + queueIndex as u64) as vks::vk::VkQueue;
+}
+
+unsafe extern "system" fn vkDeviceWaitIdle(_device: vks::vk::VkDevice) -> vks::vk::VkResult {
+    try_injected!("vkDeviceWaitIdle");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkQueueSubmit(_queue: vks::vk::VkQueue, _submitCount: u32, _pSubmits: *const vks::vk::VkSubmitInfo, fence: vks::vk::VkFence) -> vks::vk::VkResult {
+    try_injected!("vkQueueSubmit");
+
+    // All submitted work completes immediately.
+    if fence != 0 {
+        with_state(|state| state.fences.insert(fence, true));
+    }
+
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkQueueWaitIdle(_queue: vks::vk::VkQueue) -> vks::vk::VkResult {
+    try_injected!("vkQueueWaitIdle");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkCreateCommandPool(_device: vks::vk::VkDevice, _pCreateInfo: *const vks::vk::VkCommandPoolCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pCommandPool: *mut vks::vk::VkCommandPool) -> vks::vk::VkResult {
+    try_injected!("vkCreateCommandPool");
+    *pCommandPool = new_handle("VkCommandPool");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyCommandPool(_device: vks::vk::VkDevice, commandPool: vks::vk::VkCommandPool, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyCommandPool");
+    destroy_handle(commandPool);
+}
+
+unsafe extern "system" fn vkResetCommandPool(_device: vks::vk::VkDevice, _commandPool: vks::vk::VkCommandPool, _flags: vks::vk::VkCommandPoolResetFlags) -> vks::vk::VkResult {
+    try_injected!("vkResetCommandPool");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkAllocateCommandBuffers(_device: vks::vk::VkDevice, pAllocateInfo: *const vks::vk::VkCommandBufferAllocateInfo, pCommandBuffers: *mut vks::vk::VkCommandBuffer) -> vks::vk::VkResult {
+    try_injected!("vkAllocateCommandBuffers");
+    let count = (*pAllocateInfo).commandBufferCount as usize;
+    for command_buffer in slice::from_raw_parts_mut(pCommandBuffers, count) {
+        *command_buffer = new_handle("VkCommandBuffer") as vks::vk::VkCommandBuffer;
+    }
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkFreeCommandBuffers(_device: vks::vk::VkDevice, _commandPool: vks::vk::VkCommandPool, commandBufferCount: u32, pCommandBuffers: *const vks::vk::VkCommandBuffer) {
+    record("vkFreeCommandBuffers");
+    for &command_buffer in slice::from_raw_parts(pCommandBuffers, commandBufferCount as usize) {
+        destroy_handle(command_buffer as u64);
+    }
+}
+
+unsafe extern "system" fn vkBeginCommandBuffer(_commandBuffer: vks::vk::VkCommandBuffer, _pBeginInfo: *const vks::vk::VkCommandBufferBeginInfo) -> vks::vk::VkResult {
+    try_injected!("vkBeginCommandBuffer");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkEndCommandBuffer(_commandBuffer: vks::vk::VkCommandBuffer) -> vks::vk::VkResult {
+    try_injected!("vkEndCommandBuffer");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkResetCommandBuffer(_commandBuffer: vks::vk::VkCommandBuffer, _flags: vks::vk::VkCommandBufferResetFlags) -> vks::vk::VkResult {
+    try_injected!("vkResetCommandBuffer");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkCreateFence(_device: vks::vk::VkDevice, pCreateInfo: *const vks::vk::VkFenceCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pFence: *mut vks::vk::VkFence) -> vks::vk::VkResult {
+    try_injected!("vkCreateFence");
+    let signaled = (*pCreateInfo).flags & vks::vk::VK_FENCE_CREATE_SIGNALED_BIT != 0;
+    let fence = new_handle("VkFence");
+    with_state(|state| state.fences.insert(fence, signaled));
+    *pFence = fence;
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyFence(_device: vks::vk::VkDevice, fence: vks::vk::VkFence, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyFence");
+    destroy_handle(fence);
+    with_state(|state| state.fences.remove(&fence));
+}
+
+unsafe extern "system" fn vkResetFences(_device: vks::vk::VkDevice, fenceCount: u32, pFences: *const vks::vk::VkFence) -> vks::vk::VkResult {
+    try_injected!("vkResetFences");
+    with_state(|state| {
+        for fence in slice::from_raw_parts(pFences, fenceCount as usize) {
+            state.fences.insert(*fence, false);
+        }
+    });
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkGetFenceStatus(_device: vks::vk::VkDevice, fence: vks::vk::VkFence) -> vks::vk::VkResult {
+    try_injected!("vkGetFenceStatus");
+    if is_fence_signaled(fence) {
+        vks::vk::VK_SUCCESS
+    }
+    else {
+        vks::vk::VK_NOT_READY
+    }
+}
+
+unsafe extern "system" fn vkWaitForFences(_device: vks::vk::VkDevice, fenceCount: u32, pFences: *const vks::vk::VkFence, waitAll: vks::vk::VkBool32, _timeout: u64) -> vks::vk::VkResult {
+    try_injected!("vkWaitForFences");
+    let fences = slice::from_raw_parts(pFences, fenceCount as usize);
+    let done = if waitAll == vks::vk::VK_TRUE {
+        fences.iter().all(|&f| is_fence_signaled(f))
+    }
+    else {
+        fences.iter().any(|&f| is_fence_signaled(f))
+    };
+
+    if done {
+        vks::vk::VK_SUCCESS
+    }
+    else {
+        vks::vk::VK_TIMEOUT
+    }
+}
+
+unsafe extern "system" fn vkCreateSemaphore(_device: vks::vk::VkDevice, _pCreateInfo: *const vks::vk::VkSemaphoreCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pSemaphore: *mut vks::vk::VkSemaphore) -> vks::vk::VkResult {
+    try_injected!("vkCreateSemaphore");
+    *pSemaphore = new_handle("VkSemaphore");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroySemaphore(_device: vks::vk::VkDevice, semaphore: vks::vk::VkSemaphore, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroySemaphore");
+    destroy_handle(semaphore);
+}
+
+unsafe extern "system" fn vkCreateBuffer(_device: vks::vk::VkDevice, pCreateInfo: *const vks::vk::VkBufferCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pBuffer: *mut vks::vk::VkBuffer) -> vks::vk::VkResult {
+    try_injected!("vkCreateBuffer");
+    let buffer = new_handle("VkBuffer");
+    with_state(|state| state.buffer_sizes.insert(buffer, (*pCreateInfo).size));
+    *pBuffer = buffer;
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyBuffer(_device: vks::vk::VkDevice, buffer: vks::vk::VkBuffer, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyBuffer");
+    destroy_handle(buffer);
+}
+
+unsafe extern "system" fn vkGetBufferMemoryRequirements(_device: vks::vk::VkDevice, buffer: vks::vk::VkBuffer, pMemoryRequirements: *mut vks::vk::VkMemoryRequirements) {
+    record("vkGetBufferMemoryRequirements");
+    *pMemoryRequirements = with_state(|state| {
+        let mut requirements = state.config.memory_requirements;
+        let alignment = requirements.alignment;
+        let size = state.buffer_sizes.get(&buffer).cloned().unwrap_or(0);
+        requirements.size = (size + alignment - 1) / alignment * alignment;
+        requirements
+    });
+}
+
+unsafe extern "system" fn vkBindBufferMemory(_device: vks::vk::VkDevice, _buffer: vks::vk::VkBuffer, _memory: vks::vk::VkDeviceMemory, _memoryOffset: vks::vk::VkDeviceSize) -> vks::vk::VkResult {
+    try_injected!("vkBindBufferMemory");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkCreateImage(_device: vks::vk::VkDevice, pCreateInfo: *const vks::vk::VkImageCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pImage: *mut vks::vk::VkImage) -> vks::vk::VkResult {
+    try_injected!("vkCreateImage");
+    let extent = (*pCreateInfo).extent;
+    let size = extent.width as u64 * extent.height as u64 * extent.depth as u64 * (*pCreateInfo).arrayLayers as u64 * 4;
+    let image = new_handle("VkImage");
+    with_state(|state| state.buffer_sizes.insert(image, size));
+    *pImage = image;
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyImage(_device: vks::vk::VkDevice, image: vks::vk::VkImage, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyImage");
+    destroy_handle(image);
+}
+
+unsafe extern "system" fn vkGetImageMemoryRequirements(_device: vks::vk::VkDevice, image: vks::vk::VkImage, pMemoryRequirements: *mut vks::vk::VkMemoryRequirements) {
+    record("vkGetImageMemoryRequirements");
+    *pMemoryRequirements = with_state(|state| {
+        let mut requirements = state.config.memory_requirements;
+        let alignment = requirements.alignment;
+        let size = state.buffer_sizes.get(&image).cloned().unwrap_or(0);
+        requirements.size = (size + alignment - 1) / alignment * alignment;
+        requirements
+    });
+}
+
+unsafe extern "system" fn vkBindImageMemory(_device: vks::vk::VkDevice, _image: vks::vk::VkImage, _memory: vks::vk::VkDeviceMemory, _memoryOffset: vks::vk::VkDeviceSize) -> vks::vk::VkResult {
+    try_injected!("vkBindImageMemory");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkCreateImageView(_device: vks::vk::VkDevice, _pCreateInfo: *const vks::vk::VkImageViewCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pView: *mut vks::vk::VkImageView) -> vks::vk::VkResult {
+    try_injected!("vkCreateImageView");
+    *pView = new_handle("VkImageView");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyImageView(_device: vks::vk::VkDevice, imageView: vks::vk::VkImageView, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyImageView");
+    destroy_handle(imageView);
+}
+
+unsafe extern "system" fn vkCreateRenderPass(_device: vks::vk::VkDevice, pCreateInfo: *const vks::vk::VkRenderPassCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pRenderPass: *mut vks::vk::VkRenderPass) -> vks::vk::VkResult {
+    try_injected!("vkCreateRenderPass");
+    let create_info = &*pCreateInfo;
+    let samples = if create_info.attachmentCount > 0 {
+        slice::from_raw_parts(create_info.pAttachments, create_info.attachmentCount as usize).iter().map(|a| a.samples).collect()
+    }
+    else {
+        Vec::new()
+    };
+
+    let input_attachments = if create_info.subpassCount > 0 && (*create_info.pSubpasses).inputAttachmentCount > 0 {
+        let subpass = &*create_info.pSubpasses;
+        slice::from_raw_parts(subpass.pInputAttachments, subpass.inputAttachmentCount as usize).iter().map(|a| (a.attachment, a.layout)).collect()
+    }
+    else {
+        Vec::new()
+    };
+
+    *pRenderPass = new_handle("VkRenderPass");
+    with_state(|state| {
+        state.render_pass_samples.insert(*pRenderPass, samples);
+        state.render_pass_input_attachments.insert(*pRenderPass, input_attachments);
+    });
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyRenderPass(_device: vks::vk::VkDevice, renderPass: vks::vk::VkRenderPass, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyRenderPass");
+    destroy_handle(renderPass);
+    with_state(|state| {
+        state.render_pass_samples.remove(&renderPass);
+        state.render_pass_input_attachments.remove(&renderPass);
+    });
+}
+
+unsafe extern "system" fn vkCreateShaderModule(_device: vks::vk::VkDevice, pCreateInfo: *const vks::vk::VkShaderModuleCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pShaderModule: *mut vks::vk::VkShaderModule) -> vks::vk::VkResult {
+    try_injected!("vkCreateShaderModule");
+    let create_info = &*pCreateInfo;
+    let code = slice::from_raw_parts(create_info.pCode, create_info.codeSize / 4).to_vec();
+    *pShaderModule = new_handle("VkShaderModule");
+    with_state(|state| state.shader_modules.insert(*pShaderModule, code));
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyShaderModule(_device: vks::vk::VkDevice, shaderModule: vks::vk::VkShaderModule, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyShaderModule");
+    destroy_handle(shaderModule);
+    with_state(|state| state.shader_modules.remove(&shaderModule));
+}
+
+unsafe extern "system" fn vkCreatePipelineCache(_device: vks::vk::VkDevice, pCreateInfo: *const vks::vk::VkPipelineCacheCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pPipelineCache: *mut vks::vk::VkPipelineCache) -> vks::vk::VkResult {
+    try_injected!("vkCreatePipelineCache");
+    let create_info = &*pCreateInfo;
+    let initial_data = if create_info.initialDataSize > 0 {
+        slice::from_raw_parts(create_info.pInitialData as *const u8, create_info.initialDataSize).to_vec()
+    }
+    else {
+        Vec::new()
+    };
+
+    *pPipelineCache = new_handle("VkPipelineCache");
+    with_state(|state| state.pipeline_caches.insert(*pPipelineCache, initial_data));
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyPipelineCache(_device: vks::vk::VkDevice, pipelineCache: vks::vk::VkPipelineCache, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyPipelineCache");
+    destroy_handle(pipelineCache);
+    with_state(|state| state.pipeline_caches.remove(&pipelineCache));
+}
+
+/// Returns the initial data of the cache, or a header followed by 4 bytes of payload if the cache
+/// was created empty.
+unsafe extern "system" fn vkGetPipelineCacheData(_device: vks::vk::VkDevice, pipelineCache: vks::vk::VkPipelineCache, pDataSize: *mut usize, pData: *mut c_void) -> vks::vk::VkResult {
+    try_injected!("vkGetPipelineCacheData");
+    let mut data = pipeline_cache_initial_data(pipelineCache);
+    if data.is_empty() {
+        data = pipeline_cache_data(&[1, 2, 3, 4]);
+    }
+
+    if pData.is_null() {
+        *pDataSize = data.len();
+        return vks::vk::VK_SUCCESS;
+    }
+
+    let size = cmp::min(*pDataSize, data.len());
+    ptr::copy_nonoverlapping(data.as_ptr(), pData as *mut u8, size);
+    *pDataSize = size;
+    if size < data.len() {
+        vks::vk::VK_INCOMPLETE
+    }
+    else {
+        vks::vk::VK_SUCCESS
+    }
+}
+
+unsafe extern "system" fn vkCreateGraphicsPipelines(_device: vks::vk::VkDevice, pipelineCache: vks::vk::VkPipelineCache, createInfoCount: u32, pCreateInfos: *const vks::vk::VkGraphicsPipelineCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pPipelines: *mut vks::vk::VkPipeline) -> vks::vk::VkResult {
+    if let Some(res) = record("vkCreateGraphicsPipelines") {
+        ptr::write_bytes(pPipelines, 0, createInfoCount as usize);
+        return res;
+    }
+
+    let create_infos = slice::from_raw_parts(pCreateInfos, createInfoCount as usize);
+    for (i, create_info) in create_infos.iter().enumerate() {
+        let stages = slice::from_raw_parts(create_info.pStages, create_info.stageCount as usize).iter().map(|stage| {
+            (stage.stage, CStr::from_ptr(stage.pName).to_string_lossy().into_owned())
+        }).collect();
+
+        let vertex_input_state = &*create_info.pVertexInputState;
+        let input_assembly_state = &*create_info.pInputAssemblyState;
+        let rasterization_state = &*create_info.pRasterizationState;
+
+        let patch_control_points = create_info.pTessellationState.as_ref().map(|s| s.patchControlPoints);
+        let (viewport_count, scissor_count) = create_info.pViewportState.as_ref().map_or((0, 0), |s| (s.viewportCount, s.scissorCount));
+        let depth_test_enable = create_info.pDepthStencilState.as_ref().map(|s| s.depthTestEnable == vks::vk::VK_TRUE);
+
+        let attachments = match create_info.pColorBlendState.as_ref() {
+            Some(s) if s.attachmentCount > 0 => slice::from_raw_parts(s.pAttachments, s.attachmentCount as usize),
+            _ => &[],
+        };
+
+        let dynamic_states = match create_info.pDynamicState.as_ref() {
+            Some(s) => slice::from_raw_parts(s.pDynamicStates, s.dynamicStateCount as usize).to_vec(),
+            None => Vec::new(),
+        };
+
+        let info = GraphicsPipelineInfo {
+            pipeline_cache: pipelineCache,
+            stages: stages,
+            vertex_binding_count: vertex_input_state.vertexBindingDescriptionCount,
+            vertex_attribute_count: vertex_input_state.vertexAttributeDescriptionCount,
+            topology: input_assembly_state.topology,
+            patch_control_points: patch_control_points,
+            viewport_count: viewport_count,
+            scissor_count: scissor_count,
+            cull_mode: rasterization_state.cullMode,
+            front_face: rasterization_state.frontFace,
+            polygon_mode: rasterization_state.polygonMode,
+            depth_test_enable: depth_test_enable,
+            color_write_masks: attachments.iter().map(|a| a.colorWriteMask).collect(),
+            blend_enables: attachments.iter().map(|a| a.blendEnable == vks::vk::VK_TRUE).collect(),
+            dynamic_states: dynamic_states,
+        };
+
+        let pipeline = new_handle("VkPipeline");
+        *pPipelines.offset(i as isize) = pipeline;
+        with_state(|state| state.graphics_pipelines.insert(pipeline, info));
+    }
+
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyPipeline(_device: vks::vk::VkDevice, pipeline: vks::vk::VkPipeline, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyPipeline");
+    destroy_handle(pipeline);
+    with_state(|state| state.graphics_pipelines.remove(&pipeline));
+}
+
+unsafe extern "system" fn vkCreatePipelineLayout(_device: vks::vk::VkDevice, _pCreateInfo: *const vks::vk::VkPipelineLayoutCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pPipelineLayout: *mut vks::vk::VkPipelineLayout) -> vks::vk::VkResult {
+    try_injected!("vkCreatePipelineLayout");
+    *pPipelineLayout = new_handle("VkPipelineLayout");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyPipelineLayout(_device: vks::vk::VkDevice, pipelineLayout: vks::vk::VkPipelineLayout, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyPipelineLayout");
+    destroy_handle(pipelineLayout);
+}
+
+unsafe extern "system" fn vkCreateFramebuffer(_device: vks::vk::VkDevice, _pCreateInfo: *const vks::vk::VkFramebufferCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pFramebuffer: *mut vks::vk::VkFramebuffer) -> vks::vk::VkResult {
+    try_injected!("vkCreateFramebuffer");
+    *pFramebuffer = new_handle("VkFramebuffer");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyFramebuffer(_device: vks::vk::VkDevice, framebuffer: vks::vk::VkFramebuffer, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyFramebuffer");
+    destroy_handle(framebuffer);
+}
+
+unsafe extern "system" fn vkCreateDescriptorSetLayout(_device: vks::vk::VkDevice, _pCreateInfo: *const vks::vk::VkDescriptorSetLayoutCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pSetLayout: *mut vks::vk::VkDescriptorSetLayout) -> vks::vk::VkResult {
+    try_injected!("vkCreateDescriptorSetLayout");
+    *pSetLayout = new_handle("VkDescriptorSetLayout");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyDescriptorSetLayout(_device: vks::vk::VkDevice, descriptorSetLayout: vks::vk::VkDescriptorSetLayout, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyDescriptorSetLayout");
+    destroy_handle(descriptorSetLayout);
+}
+
+unsafe extern "system" fn vkCreateDescriptorPool(_device: vks::vk::VkDevice, _pCreateInfo: *const vks::vk::VkDescriptorPoolCreateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pDescriptorPool: *mut vks::vk::VkDescriptorPool) -> vks::vk::VkResult {
+    try_injected!("vkCreateDescriptorPool");
+    *pDescriptorPool = new_handle("VkDescriptorPool");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroyDescriptorPool(_device: vks::vk::VkDevice, descriptorPool: vks::vk::VkDescriptorPool, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroyDescriptorPool");
+    destroy_handle(descriptorPool);
+}
+
+unsafe extern "system" fn vkAllocateDescriptorSets(_device: vks::vk::VkDevice, pAllocateInfo: *const vks::vk::VkDescriptorSetAllocateInfo, pDescriptorSets: *mut vks::vk::VkDescriptorSet) -> vks::vk::VkResult {
+    try_injected!("vkAllocateDescriptorSets");
+    let count = (*pAllocateInfo).descriptorSetCount as usize;
+    for descriptor_set in slice::from_raw_parts_mut(pDescriptorSets, count) {
+        *descriptor_set = new_handle("VkDescriptorSet");
+    }
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkFreeDescriptorSets(_device: vks::vk::VkDevice, _descriptorPool: vks::vk::VkDescriptorPool, descriptorSetCount: u32, pDescriptorSets: *const vks::vk::VkDescriptorSet) -> vks::vk::VkResult {
+    try_injected!("vkFreeDescriptorSets");
+    for &descriptor_set in slice::from_raw_parts(pDescriptorSets, descriptorSetCount as usize) {
+        destroy_handle(descriptor_set);
+    }
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkUpdateDescriptorSets(_device: vks::vk::VkDevice, _descriptorWriteCount: u32, _pDescriptorWrites: *const vks::vk::VkWriteDescriptorSet, _descriptorCopyCount: u32, _pDescriptorCopies: *const vks::vk::VkCopyDescriptorSet) {
+    record("vkUpdateDescriptorSets");
+}
+
+unsafe extern "system" fn vkAllocateMemory(_device: vks::vk::VkDevice, pAllocateInfo: *const vks::vk::VkMemoryAllocateInfo, _pAllocator: *const vks::vk::VkAllocationCallbacks, pMemory: *mut vks::vk::VkDeviceMemory) -> vks::vk::VkResult {
+    try_injected!("vkAllocateMemory");
+    let size = (*pAllocateInfo).allocationSize as usize;
+    let memory = new_handle("VkDeviceMemory");
+    with_state(|state| state.memory.insert(memory, vec![0; size]));
+    *pMemory = memory;
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkFreeMemory(_device: vks::vk::VkDevice, memory: vks::vk::VkDeviceMemory, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkFreeMemory");
+    destroy_handle(memory);
+    with_state(|state| state.memory.remove(&memory));
+}
+
+unsafe extern "system" fn vkMapMemory(_device: vks::vk::VkDevice, memory: vks::vk::VkDeviceMemory, offset: vks::vk::VkDeviceSize, _size: vks::vk::VkDeviceSize, _flags: vks::vk::VkMemoryMapFlags, ppData: *mut *mut c_void) -> vks::vk::VkResult {
+    try_injected!("vkMapMemory");
+    with_state(|state| match state.memory.get_mut(&memory) {
+        Some(data) => {
+            *ppData = data.as_mut_ptr().offset(offset as isize) as *mut c_void;
+            vks::vk::VK_SUCCESS
+        }
+
+        None => vks::vk::VK_ERROR_MEMORY_MAP_FAILED,
+    })
+}
+
+unsafe extern "system" fn vkUnmapMemory(_device: vks::vk::VkDevice, _memory: vks::vk::VkDeviceMemory) {
+    record("vkUnmapMemory");
+}
+
+unsafe fn record_mapped_memory_ranges(memoryRangeCount: u32, pMemoryRanges: *const vks::vk::VkMappedMemoryRange) {
+    let ranges = slice::from_raw_parts(pMemoryRanges, memoryRangeCount as usize);
+    with_state(|state| state.mapped_memory_ranges.extend(ranges.iter().map(|r| (r.memory, r.offset, r.size))));
+}
+
+unsafe extern "system" fn vkFlushMappedMemoryRanges(_device: vks::vk::VkDevice, memoryRangeCount: u32, pMemoryRanges: *const vks::vk::VkMappedMemoryRange) -> vks::vk::VkResult {
+    try_injected!("vkFlushMappedMemoryRanges");
+    record_mapped_memory_ranges(memoryRangeCount, pMemoryRanges);
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkInvalidateMappedMemoryRanges(_device: vks::vk::VkDevice, memoryRangeCount: u32, pMemoryRanges: *const vks::vk::VkMappedMemoryRange) -> vks::vk::VkResult {
+    try_injected!("vkInvalidateMappedMemoryRanges");
+    record_mapped_memory_ranges(memoryRangeCount, pMemoryRanges);
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkCmdBindPipeline(_commandBuffer: vks::vk::VkCommandBuffer, _pipelineBindPoint: vks::vk::VkPipelineBindPoint, _pipeline: vks::vk::VkPipeline) {
+    record("vkCmdBindPipeline");
+}
+
+unsafe extern "system" fn vkCmdSetViewport(_commandBuffer: vks::vk::VkCommandBuffer, _firstViewport: u32, _viewportCount: u32, _pViewports: *const vks::vk::VkViewport) {
+    record("vkCmdSetViewport");
+}
+
+unsafe extern "system" fn vkCmdSetScissor(_commandBuffer: vks::vk::VkCommandBuffer, _firstScissor: u32, _scissorCount: u32, _pScissors: *const vks::vk::VkRect2D) {
+    record("vkCmdSetScissor");
+}
+
+unsafe extern "system" fn vkCmdBindDescriptorSets(_commandBuffer: vks::vk::VkCommandBuffer, _pipelineBindPoint: vks::vk::VkPipelineBindPoint, _layout: vks::vk::VkPipelineLayout, _firstSet: u32, _descriptorSetCount: u32, _pDescriptorSets: *const vks::vk::VkDescriptorSet, _dynamicOffsetCount: u32, _pDynamicOffsets: *const u32) {
+    record("vkCmdBindDescriptorSets");
+}
+
+unsafe extern "system" fn vkCmdBindIndexBuffer(_commandBuffer: vks::vk::VkCommandBuffer, _buffer: vks::vk::VkBuffer, _offset: vks::vk::VkDeviceSize, _indexType: vks::vk::VkIndexType) {
+    record("vkCmdBindIndexBuffer");
+}
+
+unsafe extern "system" fn vkCmdBindVertexBuffers(_commandBuffer: vks::vk::VkCommandBuffer, _firstBinding: u32, _bindingCount: u32, _pBuffers: *const vks::vk::VkBuffer, _pOffsets: *const vks::vk::VkDeviceSize) {
+    record("vkCmdBindVertexBuffers");
+}
+
+unsafe extern "system" fn vkCmdDraw(_commandBuffer: vks::vk::VkCommandBuffer, _vertexCount: u32, _instanceCount: u32, _firstVertex: u32, _firstInstance: u32) {
+    record("vkCmdDraw");
+}
+
+unsafe extern "system" fn vkCmdDrawIndexed(_commandBuffer: vks::vk::VkCommandBuffer, _indexCount: u32, _instanceCount: u32, _firstIndex: u32, _vertexOffset: i32, _firstInstance: u32) {
+    record("vkCmdDrawIndexed");
+}
+
+unsafe extern "system" fn vkCmdDispatch(_commandBuffer: vks::vk::VkCommandBuffer, _groupCountX: u32, _groupCountY: u32, _groupCountZ: u32) {
+    record("vkCmdDispatch");
+}
+
+unsafe extern "system" fn vkCmdCopyBuffer(_commandBuffer: vks::vk::VkCommandBuffer, srcBuffer: vks::vk::VkBuffer, dstBuffer: vks::vk::VkBuffer, regionCount: u32, pRegions: *const vks::vk::VkBufferCopy) {
+    record("vkCmdCopyBuffer");
+
+    let regions = slice::from_raw_parts(pRegions, regionCount as usize);
+    with_state(|state| {
+        for region in regions {
+            state.buffer_copies.push(BufferCopyInfo {
+                src_buffer: srcBuffer,
+                dst_buffer: dstBuffer,
+                src_offset: region.srcOffset,
+                dst_offset: region.dstOffset,
+                size: region.size,
+            });
+        }
+    });
+}
+
+unsafe extern "system" fn vkCmdCopyImage(_commandBuffer: vks::vk::VkCommandBuffer, _srcImage: vks::vk::VkImage, _srcImageLayout: vks::vk::VkImageLayout, _dstImage: vks::vk::VkImage, _dstImageLayout: vks::vk::VkImageLayout, _regionCount: u32, _pRegions: *const vks::vk::VkImageCopy) {
+    record("vkCmdCopyImage");
+}
+
+unsafe extern "system" fn vkCmdBlitImage(_commandBuffer: vks::vk::VkCommandBuffer, _srcImage: vks::vk::VkImage, _srcImageLayout: vks::vk::VkImageLayout, _dstImage: vks::vk::VkImage, _dstImageLayout: vks::vk::VkImageLayout, _regionCount: u32, _pRegions: *const vks::vk::VkImageBlit, _filter: vks::vk::VkFilter) {
+    record("vkCmdBlitImage");
+}
+
+unsafe extern "system" fn vkCmdCopyBufferToImage(_commandBuffer: vks::vk::VkCommandBuffer, srcBuffer: vks::vk::VkBuffer, dstImage: vks::vk::VkImage, dstImageLayout: vks::vk::VkImageLayout, regionCount: u32, pRegions: *const vks::vk::VkBufferImageCopy) {
+    record("vkCmdCopyBufferToImage");
+
+    let regions = slice::from_raw_parts(pRegions, regionCount as usize);
+    with_state(|state| {
+        for region in regions {
+            state.buffer_image_copies.push(BufferImageCopyInfo {
+                buffer: srcBuffer,
+                image: dstImage,
+                layout: dstImageLayout,
+                buffer_offset: region.bufferOffset,
+                mip_level: region.imageSubresource.mipLevel,
+            });
+        }
+    });
+}
+
+unsafe extern "system" fn vkCmdCopyImageToBuffer(_commandBuffer: vks::vk::VkCommandBuffer, _srcImage: vks::vk::VkImage, _srcImageLayout: vks::vk::VkImageLayout, _dstBuffer: vks::vk::VkBuffer, _regionCount: u32, _pRegions: *const vks::vk::VkBufferImageCopy) {
+    record("vkCmdCopyImageToBuffer");
+}
+
+unsafe extern "system" fn vkCmdUpdateBuffer(_commandBuffer: vks::vk::VkCommandBuffer, _dstBuffer: vks::vk::VkBuffer, _dstOffset: vks::vk::VkDeviceSize, _dataSize: vks::vk::VkDeviceSize, _pData: *const c_void) {
+    record("vkCmdUpdateBuffer");
+}
+
+unsafe extern "system" fn vkCmdFillBuffer(_commandBuffer: vks::vk::VkCommandBuffer, _dstBuffer: vks::vk::VkBuffer, _dstOffset: vks::vk::VkDeviceSize, _size: vks::vk::VkDeviceSize, _data: u32) {
+    record("vkCmdFillBuffer");
+}
+
+unsafe extern "system" fn vkCmdClearColorImage(_commandBuffer: vks::vk::VkCommandBuffer, _image: vks::vk::VkImage, _imageLayout: vks::vk::VkImageLayout, _pColor: *const vks::vk::VkClearColorValue, _rangeCount: u32, _pRanges: *const vks::vk::VkImageSubresourceRange) {
+    record("vkCmdClearColorImage");
+}
+
+unsafe extern "system" fn vkCmdPipelineBarrier(_commandBuffer: vks::vk::VkCommandBuffer, srcStageMask: vks::vk::VkPipelineStageFlags, dstStageMask: vks::vk::VkPipelineStageFlags, _dependencyFlags: vks::vk::VkDependencyFlags, _memoryBarrierCount: u32, _pMemoryBarriers: *const vks::vk::VkMemoryBarrier, bufferMemoryBarrierCount: u32, pBufferMemoryBarriers: *const vks::vk::VkBufferMemoryBarrier, imageMemoryBarrierCount: u32, pImageMemoryBarriers: *const vks::vk::VkImageMemoryBarrier) {
+    record("vkCmdPipelineBarrier");
+
+    let buffer_barriers = if bufferMemoryBarrierCount > 0 {
+        slice::from_raw_parts(pBufferMemoryBarriers, bufferMemoryBarrierCount as usize).iter().map(|barrier| BufferBarrierInfo {
+            buffer: barrier.buffer,
+            src_access_mask: barrier.srcAccessMask,
+            dst_access_mask: barrier.dstAccessMask,
+            src_queue_family_index: barrier.srcQueueFamilyIndex,
+            dst_queue_family_index: barrier.dstQueueFamilyIndex,
+        }).collect()
+    }
+    else {
+        Vec::new()
+    };
+
+    let image_barriers = if imageMemoryBarrierCount > 0 {
+        slice::from_raw_parts(pImageMemoryBarriers, imageMemoryBarrierCount as usize).iter().map(|barrier| ImageBarrierInfo {
+            image: barrier.image,
+            src_access_mask: barrier.srcAccessMask,
+            dst_access_mask: barrier.dstAccessMask,
+            old_layout: barrier.oldLayout,
+            new_layout: barrier.newLayout,
+            base_mip_level: barrier.subresourceRange.baseMipLevel,
+            level_count: barrier.subresourceRange.levelCount,
+            base_array_layer: barrier.subresourceRange.baseArrayLayer,
+            layer_count: barrier.subresourceRange.layerCount,
+            src_queue_family_index: barrier.srcQueueFamilyIndex,
+            dst_queue_family_index: barrier.dstQueueFamilyIndex,
+        }).collect()
+    }
+    else {
+        Vec::new()
+    };
+
+    with_state(|state| state.pipeline_barriers.push(PipelineBarrierInfo {
+        src_stage_mask: srcStageMask,
+        dst_stage_mask: dstStageMask,
+        buffer_barrier_count: bufferMemoryBarrierCount,
+        buffer_barriers: buffer_barriers,
+        image_barriers: image_barriers,
+    }));
+}
+
+unsafe extern "system" fn vkCmdPushConstants(_commandBuffer: vks::vk::VkCommandBuffer, _layout: vks::vk::VkPipelineLayout, _stageFlags: vks::vk::VkShaderStageFlags, _offset: u32, _size: u32, _pValues: *const c_void) {
+    record("vkCmdPushConstants");
+}
+
+unsafe extern "system" fn vkCmdBeginRenderPass(_commandBuffer: vks::vk::VkCommandBuffer, _pRenderPassBegin: *const vks::vk::VkRenderPassBeginInfo, _contents: vks::vk::VkSubpassContents) {
+    record("vkCmdBeginRenderPass");
+}
+
+unsafe extern "system" fn vkCmdNextSubpass(_commandBuffer: vks::vk::VkCommandBuffer, _contents: vks::vk::VkSubpassContents) {
+    record("vkCmdNextSubpass");
+}
+
+unsafe extern "system" fn vkCmdEndRenderPass(_commandBuffer: vks::vk::VkCommandBuffer) {
+    record("vkCmdEndRenderPass");
+}
+
+unsafe extern "system" fn vkCmdExecuteCommands(_commandBuffer: vks::vk::VkCommandBuffer, _commandBufferCount: u32, _pCommandBuffers: *const vks::vk::VkCommandBuffer) {
+    record("vkCmdExecuteCommands");
+}
+
+unsafe extern "system" fn vkDestroySurfaceKHR(_instance: vks::vk::VkInstance, surface: vks::khr_surface::VkSurfaceKHR, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroySurfaceKHR");
+    destroy_handle(surface);
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceSurfaceSupportKHR(_physicalDevice: vks::vk::VkPhysicalDevice, _queueFamilyIndex: u32, _surface: vks::khr_surface::VkSurfaceKHR, pSupported: *mut vks::vk::VkBool32) -> vks::vk::VkResult {
+    try_injected!("vkGetPhysicalDeviceSurfaceSupportKHR");
+    *pSupported = if with_state(|state| state.config.surface_support) { vks::vk::VK_TRUE } else { vks::vk::VK_FALSE };
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceSurfaceCapabilitiesKHR(_physicalDevice: vks::vk::VkPhysicalDevice, _surface: vks::khr_surface::VkSurfaceKHR, pSurfaceCapabilities: *mut vks::khr_surface::VkSurfaceCapabilitiesKHR) -> vks::vk::VkResult {
+    try_injected!("vkGetPhysicalDeviceSurfaceCapabilitiesKHR");
+    let extent = vks::vk::VkExtent2D { width: 800, height: 600 };
+    *pSurfaceCapabilities = vks::khr_surface::VkSurfaceCapabilitiesKHR {
+        minImageCount: 2,
+        maxImageCount: 8,
+        currentExtent: extent,
+        minImageExtent: extent,
+        maxImageExtent: extent,
+        maxImageArrayLayers: 1,
+        supportedTransforms: vks::khr_surface::VK_SURFACE_TRANSFORM_IDENTITY_BIT_KHR,
+        currentTransform: vks::khr_surface::VK_SURFACE_TRANSFORM_IDENTITY_BIT_KHR,
+        supportedCompositeAlpha: vks::khr_surface::VK_COMPOSITE_ALPHA_OPAQUE_BIT_KHR,
+        supportedUsageFlags: vks::vk::VK_IMAGE_USAGE_COLOR_ATTACHMENT_BIT | vks::vk::VK_IMAGE_USAGE_TRANSFER_DST_BIT,
+    };
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceSurfaceFormatsKHR(_physicalDevice: vks::vk::VkPhysicalDevice, _surface: vks::khr_surface::VkSurfaceKHR, pSurfaceFormatCount: *mut u32, pSurfaceFormats: *mut vks::khr_surface::VkSurfaceFormatKHR) -> vks::vk::VkResult {
+    try_injected!("vkGetPhysicalDeviceSurfaceFormatsKHR");
+    let formats = with_state(|state| state.config.surface_formats.clone());
+    enumerate(&formats, pSurfaceFormatCount, pSurfaceFormats)
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceSurfacePresentModesKHR(_physicalDevice: vks::vk::VkPhysicalDevice, _surface: vks::khr_surface::VkSurfaceKHR, pPresentModeCount: *mut u32, pPresentModes: *mut vks::khr_surface::VkPresentModeKHR) -> vks::vk::VkResult {
+    try_injected!("vkGetPhysicalDeviceSurfacePresentModesKHR");
+    let present_modes = with_state(|state| state.config.present_modes.clone());
+    enumerate(&present_modes, pPresentModeCount, pPresentModes)
+}
+
+unsafe extern "system" fn vkCreateSwapchainKHR(_device: vks::vk::VkDevice, _pCreateInfo: *const vks::khr_swapchain::VkSwapchainCreateInfoKHR, _pAllocator: *const vks::vk::VkAllocationCallbacks, pSwapchain: *mut vks::khr_swapchain::VkSwapchainKHR) -> vks::vk::VkResult {
+    try_injected!("vkCreateSwapchainKHR");
+    with_state(|state| {
+        let swapchain = state.new_handle("VkSwapchainKHR");
+        let images = (0..state.config.swapchain_image_count).map(|_| {
+            let handle = state.next_handle;
+            state.next_handle += 1;
+            handle
+        }).collect();
+        state.swapchains.insert(swapchain, (images, 0));
+        *pSwapchain = swapchain;
+    });
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkDestroySwapchainKHR(_device: vks::vk::VkDevice, swapchain: vks::khr_swapchain::VkSwapchainKHR, _pAllocator: *const vks::vk::VkAllocationCallbacks) {
+    record("vkDestroySwapchainKHR");
+    destroy_handle(swapchain);
+    with_state(|state| state.swapchains.remove(&swapchain));
+}
+
+unsafe extern "system" fn vkGetSwapchainImagesKHR(_device: vks::vk::VkDevice, swapchain: vks::khr_swapchain::VkSwapchainKHR, pSwapchainImageCount: *mut u32, pSwapchainImages: *mut vks::vk::VkImage) -> vks::vk::VkResult {
+    try_injected!("vkGetSwapchainImagesKHR");
+    let images = with_state(|state| state.swapchains.get(&swapchain).map(|s| s.0.clone()).unwrap_or_else(Vec::new));
+    enumerate(&images, pSwapchainImageCount, pSwapchainImages)
+}
+
+unsafe extern "system" fn vkAcquireNextImageKHR(_device: vks::vk::VkDevice, swapchain: vks::khr_swapchain::VkSwapchainKHR, _timeout: u64, _semaphore: vks::vk::VkSemaphore, fence: vks::vk::VkFence, pImageIndex: *mut u32) -> vks::vk::VkResult {
+    try_injected!("vkAcquireNextImageKHR");
+    with_state(|state| {
+        if let Some(&mut (ref images, ref mut next)) = state.swapchains.get_mut(&swapchain) {
+            *pImageIndex = *next;
+            *next = (*next + 1) % images.len() as u32;
+        }
+
+        if fence != 0 {
+            state.fences.insert(fence, true);
+        }
+    });
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkQueuePresentKHR(_queue: vks::vk::VkQueue, pPresentInfo: *const vks::khr_swapchain::VkPresentInfoKHR) -> vks::vk::VkResult {
+    try_injected!("vkQueuePresentKHR");
+    let present_info = &*pPresentInfo;
+    if !present_info.pResults.is_null() {
+        for result in slice::from_raw_parts_mut(present_info.pResults, present_info.swapchainCount as usize) {
+            *result = vks::vk::VK_SUCCESS;
+        }
+    }
+    vks::vk::VK_SUCCESS
+}
+
+// Vulkan 1.1 definitions, which are not provided by vks.
+
+const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES: vks::vk::VkStructureType = 1000094000;
+const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES: vks::vk::VkStructureType = 1000145001;
+const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_PROPERTIES: vks::vk::VkStructureType = 1000145002;
+
+#[repr(C)]
+struct VkBaseOutStructure {
+    sType: vks::vk::VkStructureType,
+    pNext: *mut VkBaseOutStructure,
+}
+
+#[repr(C)]
+struct VkPhysicalDeviceSubgroupProperties {
+    sType: vks::vk::VkStructureType,
+    pNext: *mut c_void,
+    subgroupSize: u32,
+    supportedStages: vks::vk::VkShaderStageFlags,
+    supportedOperations: u32,
+    quadOperationsInAllStages: vks::vk::VkBool32,
+}
+
+#[repr(C)]
+struct VkPhysicalDeviceProtectedMemoryFeatures {
+    sType: vks::vk::VkStructureType,
+    pNext: *mut c_void,
+    protectedMemory: vks::vk::VkBool32,
+}
+
+#[repr(C)]
+struct VkPhysicalDeviceProtectedMemoryProperties {
+    sType: vks::vk::VkStructureType,
+    pNext: *mut c_void,
+    protectedNoFault: vks::vk::VkBool32,
+}
+
+#[repr(C)]
+struct VkPhysicalDeviceGroupProperties {
+    sType: vks::vk::VkStructureType,
+    pNext: *mut c_void,
+    physicalDeviceCount: u32,
+    physicalDevices: [vks::vk::VkPhysicalDevice; 32],
+    subsetAllocation: vks::vk::VkBool32,
+}
+
+#[repr(C)]
+struct VkDeviceQueueInfo2 {
+    sType: vks::vk::VkStructureType,
+    pNext: *const c_void,
+    flags: vks::vk::VkDeviceQueueCreateFlags,
+    queueFamilyIndex: u32,
+    queueIndex: u32,
+}
+
+/// Calls `f` for every structure in the `pNext` chain starting at `pnext`.
+unsafe fn for_each_in_chain<F>(pnext: *mut c_void, mut f: F)
+    where F: FnMut(*mut VkBaseOutStructure)
+{
+    let mut pnext = pnext as *mut VkBaseOutStructure;
+    while !pnext.is_null() {
+        f(pnext);
+        pnext = (*pnext).pNext;
+    }
+}
+
+unsafe extern "system" fn vkEnumerateInstanceVersion(pApiVersion: *mut u32) -> vks::vk::VkResult {
+    try_injected!("vkEnumerateInstanceVersion");
+    *pApiVersion = with_state(|state| state.config.api_version);
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkEnumeratePhysicalDeviceGroups(_instance: vks::vk::VkInstance, pPhysicalDeviceGroupCount: *mut u32, pPhysicalDeviceGroupProperties: *mut VkPhysicalDeviceGroupProperties) -> vks::vk::VkResult {
+    try_injected!("vkEnumeratePhysicalDeviceGroups");
+
+    // All physical devices form a single group.
+    if pPhysicalDeviceGroupProperties.is_null() {
+        *pPhysicalDeviceGroupCount = 1;
+        return vks::vk::VK_SUCCESS;
+    }
+
+    if *pPhysicalDeviceGroupCount < 1 {
+        return vks::vk::VK_INCOMPLETE;
+    }
+
+    let group = &mut *pPhysicalDeviceGroupProperties;
+    let physical_device_count = with_state(|state| state.config.physical_device_count);
+    group.physicalDeviceCount = physical_device_count;
+    for i in 0..physical_device_count as usize {
+        group.physicalDevices[i] = (0x1000_0000 + i as u64) as vks::vk::VkPhysicalDevice;
+    }
+    group.subsetAllocation = vks::vk::VK_FALSE;
+    *pPhysicalDeviceGroupCount = 1;
+
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceFeatures2(_physicalDevice: vks::vk::VkPhysicalDevice, pFeatures: *mut vks::khr_get_physical_device_properties2::VkPhysicalDeviceFeatures2KHR) {
+    record("vkGetPhysicalDeviceFeatures2");
+    (*pFeatures).features = with_state(|state| state.config.features);
+
+    let protected_memory = with_state(|state| state.config.protected_memory);
+    for_each_in_chain((*pFeatures).pNext, |next| {
+        if (*next).sType == VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES {
+            let features = &mut *(next as *mut VkPhysicalDeviceProtectedMemoryFeatures);
+            features.protectedMemory = if protected_memory { vks::vk::VK_TRUE } else { vks::vk::VK_FALSE };
+        }
+    });
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceProperties2(physicalDevice: vks::vk::VkPhysicalDevice, pProperties: *mut vks::khr_get_physical_device_properties2::VkPhysicalDeviceProperties2KHR) {
+    vkGetPhysicalDeviceProperties(physicalDevice, &mut (*pProperties).properties);
+    record("vkGetPhysicalDeviceProperties2");
+
+    let (subgroup_size, protected_memory) = with_state(|state| (state.config.subgroup_size, state.config.protected_memory));
+    for_each_in_chain((*pProperties).pNext, |next| {
+        match (*next).sType {
+            VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES => {
+                let properties = &mut *(next as *mut VkPhysicalDeviceSubgroupProperties);
+                properties.subgroupSize = subgroup_size;
+                properties.supportedStages = vks::vk::VK_SHADER_STAGE_COMPUTE_BIT;
+                properties.supportedOperations = 0x1 | 0x2 | 0x8;
+                properties.quadOperationsInAllStages = vks::vk::VK_FALSE;
+            }
+
+            VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_PROPERTIES => {
+                let properties = &mut *(next as *mut VkPhysicalDeviceProtectedMemoryProperties);
+                properties.protectedNoFault = if protected_memory { vks::vk::VK_TRUE } else { vks::vk::VK_FALSE };
+            }
+
+            _ => { }
+        }
+    });
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceFormatProperties2(physicalDevice: vks::vk::VkPhysicalDevice, format: vks::vk::VkFormat, pFormatProperties: *mut vks::khr_get_physical_device_properties2::VkFormatProperties2KHR) {
+    vkGetPhysicalDeviceFormatProperties(physicalDevice, format, &mut (*pFormatProperties).formatProperties);
+    record("vkGetPhysicalDeviceFormatProperties2");
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceQueueFamilyProperties2(_physicalDevice: vks::vk::VkPhysicalDevice, pQueueFamilyPropertyCount: *mut u32, pQueueFamilyProperties: *mut vks::khr_get_physical_device_properties2::VkQueueFamilyProperties2KHR) {
+    record("vkGetPhysicalDeviceQueueFamilyProperties2");
+    let queue_families = with_state(|state| state.config.queue_families.clone());
+
+    if pQueueFamilyProperties.is_null() {
+        *pQueueFamilyPropertyCount = queue_families.len() as u32;
+        return;
+    }
+
+    let num = (*pQueueFamilyPropertyCount as usize).min(queue_families.len());
+    for (properties, queue_family) in slice::from_raw_parts_mut(pQueueFamilyProperties, num).iter_mut().zip(queue_families) {
+        properties.queueFamilyProperties = queue_family;
+    }
+    *pQueueFamilyPropertyCount = num as u32;
+}
+
+unsafe extern "system" fn vkGetPhysicalDeviceMemoryProperties2(physicalDevice: vks::vk::VkPhysicalDevice, pMemoryProperties: *mut vks::khr_get_physical_device_properties2::VkPhysicalDeviceMemoryProperties2KHR) {
+    vkGetPhysicalDeviceMemoryProperties(physicalDevice, &mut (*pMemoryProperties).memoryProperties);
+    record("vkGetPhysicalDeviceMemoryProperties2");
+}
+
+unsafe extern "system" fn vkGetDeviceQueue2(device: vks::vk::VkDevice, pQueueInfo: *const VkDeviceQueueInfo2, pQueue: *mut vks::vk::VkQueue) {
+    vkGetDeviceQueue(device, (*pQueueInfo).queueFamilyIndex, (*pQueueInfo).queueIndex, pQueue);
+    record("vkGetDeviceQueue2");
+}
+
+unsafe extern "system" fn vkTrimCommandPool(_device: vks::vk::VkDevice, _commandPool: vks::vk::VkCommandPool, _flags: u32) {
+    record("vkTrimCommandPool");
+}
+
+unsafe extern "system" fn vkBindBufferMemory2(_device: vks::vk::VkDevice, _bindInfoCount: u32, _pBindInfos: *const c_void) -> vks::vk::VkResult {
+    try_injected!("vkBindBufferMemory2");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkBindImageMemory2(_device: vks::vk::VkDevice, _bindInfoCount: u32, _pBindInfos: *const c_void) -> vks::vk::VkResult {
+    try_injected!("vkBindImageMemory2");
+    vks::vk::VK_SUCCESS
+}
+
+unsafe extern "system" fn vkGetDeviceGroupPeerMemoryFeatures(_device: vks::vk::VkDevice, _heapIndex: u32, _localDeviceIndex: u32, _remoteDeviceIndex: u32, pPeerMemoryFeatures: *mut u32) {
+    record("vkGetDeviceGroupPeerMemoryFeatures");
+    *pPeerMemoryFeatures = 0x1 | 0x2 | 0x4 | 0x8;
+}
@@ -0,0 +1,174 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::core;
+
+#[test]
+fn properties() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let properties = physical_device.get_properties();
+    assert_eq!(properties.device_name, "dacite mock device");
+    assert_eq!(properties.device_type, core::PhysicalDeviceType::DiscreteGpu);
+    assert_eq!(properties.api_version.major, 1);
+    assert_eq!(properties.api_version.minor, 0);
+    assert_eq!(properties.limits.non_coherent_atom_size, 64);
+}
+
+#[test]
+fn queue_family_properties() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let queue_families: Vec<_> = physical_device.get_queue_family_properties();
+    assert_eq!(queue_families.len(), 2);
+    assert!(queue_families[0].queue_flags.contains(core::QueueFlags::GRAPHICS));
+    assert_eq!(queue_families[0].queue_count, 2);
+    assert_eq!(queue_families[1].queue_flags, core::QueueFlags::TRANSFER);
+}
+
+#[test]
+fn memory_properties() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let memory_properties = physical_device.get_memory_properties();
+    assert_eq!(memory_properties.memory_types.len(), 3);
+    assert_eq!(memory_properties.memory_heaps.len(), 2);
+    assert!(memory_properties.memory_types[1].property_flags.contains(core::MemoryPropertyFlags::HOST_VISIBLE));
+}
+
+#[test]
+fn device_extension_properties() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let properties = physical_device.get_device_extension_properties(None).unwrap();
+    assert!(properties.has_khr_swapchain());
+
+    mock::inject_result("vkEnumerateDeviceExtensionProperties", vks::vk::VK_ERROR_LAYER_NOT_PRESENT);
+    let res = physical_device.get_device_extension_properties(Some("VK_LAYER_missing"));
+    assert_eq!(res.unwrap_err(), core::Error::LayerNotPresent);
+}
+
+#[test]
+fn create_device_error() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    mock::inject_result("vkCreateDevice", vks::vk::VK_ERROR_DEVICE_LOST);
+
+    let create_info = core::DeviceCreateInfo {
+        flags: core::DeviceCreateFlags::empty(),
+        queue_create_infos: vec![],
+        enabled_layers: vec![],
+        enabled_extensions: core::DeviceExtensions::new(),
+        enabled_features: None,
+        chain: None,
+    };
+
+    let res = physical_device.create_device(&create_info, None);
+    assert_eq!(res.unwrap_err(), core::Error::DeviceLost);
+}
+
+fn set_optimal_tiling_features(format: vks::vk::VkFormat, features: vks::vk::VkFormatFeatureFlags) {
+    mock::configure(|config| {
+        config.format_properties.insert(format, vks::vk::VkFormatProperties {
+            linearTilingFeatures: 0,
+            optimalTilingFeatures: features,
+            bufferFeatures: 0,
+        });
+    });
+}
+
+#[test]
+fn find_supported_format() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+    set_optimal_tiling_features(vks::vk::VK_FORMAT_D32_SFLOAT_S8_UINT, vks::vk::VK_FORMAT_FEATURE_SAMPLED_IMAGE_BIT);
+
+    let candidates = [core::Format::D32_SFloat_S8_UInt, core::Format::D24_UNorm_S8_UInt];
+    let format = physical_device.find_supported_format(&candidates, core::ImageTiling::Optimal, core::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT);
+    assert_eq!(format, Ok(core::Format::D24_UNorm_S8_UInt));
+
+    let err = physical_device.find_supported_format(&candidates[..1], core::ImageTiling::Optimal, core::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT | core::FormatFeatureFlags::SAMPLED_IMAGE).unwrap_err();
+    assert_eq!(err.rejections, vec![(core::Format::D32_SFloat_S8_UInt, core::FormatRejection::MissingFeatures(core::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT))]);
+    assert!(err.to_string().contains("D32_SFloat_S8_UInt"));
+}
+
+#[test]
+fn find_supported_image_format() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+    set_optimal_tiling_features(vks::vk::VK_FORMAT_R16G16B16A16_SFLOAT, vks::vk::VK_FORMAT_FEATURE_SAMPLED_IMAGE_BIT);
+
+    let candidates = [core::Format::R16G16B16A16_SFloat, core::Format::R32G32B32A32_SFloat, core::Format::R8G8B8A8_UNorm];
+    let create_info = core::ImageCreateInfo::builder(candidates[0], core::Extent3D::new(1024, 1024, 1), core::ImageUsageFlags::STORAGE)
+        .samples(core::SampleCountFlagBits::SampleCount4)
+        .build();
+
+    mock::inject_result("vkGetPhysicalDeviceImageFormatProperties", vks::vk::VK_ERROR_FORMAT_NOT_SUPPORTED);
+    let format = physical_device.find_supported_image_format(&candidates, &create_info, core::FormatFeatureFlags::STORAGE_IMAGE);
+    assert_eq!(format, Ok(core::Format::R8G8B8A8_UNorm));
+
+    let create_info = core::ImageCreateInfo {
+        extent: core::Extent3D::new(32768, 1, 1),
+        .. create_info
+    };
+    let err = physical_device.find_supported_image_format(&candidates[2..], &create_info, core::FormatFeatureFlags::empty()).unwrap_err();
+    assert_eq!(err, core::FindImageFormatError::NoSupportedFormat(core::NoSupportedFormatError {
+        rejections: vec![(core::Format::R8G8B8A8_UNorm, core::FormatRejection::ExtentTooLarge(core::Extent3D::new(16384, 16384, 1)))],
+    }));
+
+    let create_info = core::ImageCreateInfo {
+        extent: core::Extent3D::new(16, 16, 1),
+        samples: core::SampleCountFlagBits::SampleCount8,
+        .. create_info
+    };
+    let err = physical_device.find_supported_image_format(&candidates[2..], &create_info, core::FormatFeatureFlags::empty()).unwrap_err();
+    assert_eq!(err, core::FindImageFormatError::NoSupportedFormat(core::NoSupportedFormatError {
+        rejections: vec![(core::Format::R8G8B8A8_UNorm, core::FormatRejection::SampleCountNotSupported(core::SampleCountFlags::SAMPLE_COUNT_1 | core::SampleCountFlags::SAMPLE_COUNT_4))],
+    }));
+}
+
+#[test]
+fn find_supported_image_format_errors() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let candidates = [core::Format::R16G16B16A16_SFloat, core::Format::R8G8B8A8_UNorm];
+    let create_info = core::ImageCreateInfo::builder(candidates[0], core::Extent3D::new(16, 16, 1), core::ImageUsageFlags::SAMPLED).build();
+
+    mock::inject_result("vkGetPhysicalDeviceImageFormatProperties", vks::vk::VK_ERROR_FORMAT_NOT_SUPPORTED);
+    mock::inject_result("vkGetPhysicalDeviceImageFormatProperties", vks::vk::VK_ERROR_FORMAT_NOT_SUPPORTED);
+    let err = physical_device.find_supported_image_format(&candidates, &create_info, core::FormatFeatureFlags::empty()).unwrap_err();
+    assert_eq!(err, core::FindImageFormatError::NoSupportedFormat(core::NoSupportedFormatError {
+        rejections: vec![
+            (core::Format::R16G16B16A16_SFloat, core::FormatRejection::ImageNotSupported),
+            (core::Format::R8G8B8A8_UNorm, core::FormatRejection::ImageNotSupported),
+        ],
+    }));
+
+    mock::inject_result("vkGetPhysicalDeviceImageFormatProperties", vks::vk::VK_ERROR_OUT_OF_HOST_MEMORY);
+    let err = physical_device.find_supported_image_format(&candidates, &create_info, core::FormatFeatureFlags::empty()).unwrap_err();
+    assert_eq!(err, core::FindImageFormatError::VulkanError(core::Error::OutOfHostMemory));
+    assert_eq!(mock::call_count("vkGetPhysicalDeviceImageFormatProperties"), 3);
+}
@@ -0,0 +1,159 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::VulkanObject;
+use dacite::core;
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("dacite-test-pipeline-cache-{}", name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn read_file(path: &Path) -> Vec<u8> {
+    let mut data = Vec::new();
+    fs::File::open(path).unwrap().read_to_end(&mut data).unwrap();
+    data
+}
+
+fn write_file(path: &Path, data: &[u8]) {
+    fs::File::create(path).unwrap().write_all(data).unwrap();
+}
+
+fn temp_files_exist(path: &Path) -> bool {
+    let prefix = format!("{}.", path.file_name().unwrap().to_str().unwrap());
+    fs::read_dir(path.parent().unwrap()).unwrap().any(|entry| {
+        let file_name = entry.unwrap().file_name();
+        let file_name = file_name.to_str().unwrap();
+        file_name.starts_with(&prefix) && file_name.ends_with(".tmp")
+    })
+}
+
+#[test]
+fn parse_header() {
+    let data = mock::pipeline_cache_data(&[1, 2, 3]);
+    let header = core::PipelineCacheHeader::parse(&data).unwrap();
+    assert_eq!(header, core::PipelineCacheHeader {
+        header_size: 32,
+        header_version: core::PipelineCacheHeaderVersion::One,
+        vendor_id: 0x1234,
+        device_id: 0x5678,
+        pipeline_cache_uuid: [0xda; 16],
+    });
+
+    assert_eq!(core::PipelineCacheHeader::parse(&data[..31]), None);
+
+    let mut unknown_version = data.clone();
+    unknown_version[4] = 2;
+    assert_eq!(core::PipelineCacheHeader::parse(&unknown_version), None);
+
+    let mut oversized_header = data.clone();
+    oversized_header[0] = 64;
+    assert_eq!(core::PipelineCacheHeader::parse(&oversized_header), None);
+}
+
+#[test]
+fn header_compatibility() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, _) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let properties = physical_device.get_properties();
+
+    let mut header = core::PipelineCacheHeader::parse(&mock::pipeline_cache_data(&[])).unwrap();
+    assert!(header.is_compatible_with(&properties));
+
+    header.pipeline_cache_uuid[0] = 0;
+    assert!(!header.is_compatible_with(&properties));
+}
+
+#[test]
+fn store_and_load() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let path = temp_path("store-and-load");
+
+    let pipeline_cache = device.load_pipeline_cache(&path, None).unwrap();
+    assert!(mock::pipeline_cache_initial_data(pipeline_cache.id()).is_empty());
+
+    pipeline_cache.store(&path).unwrap();
+    pipeline_cache.store(&path).unwrap();
+    assert_eq!(read_file(&path), mock::pipeline_cache_data(&[1, 2, 3, 4]));
+    assert!(!temp_files_exist(&path));
+
+    let pipeline_cache = device.load_pipeline_cache(&path, None).unwrap();
+    assert_eq!(mock::pipeline_cache_initial_data(pipeline_cache.id()), mock::pipeline_cache_data(&[1, 2, 3, 4]));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn load_discards_foreign_cache() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let path = temp_path("foreign");
+
+    let mut data = mock::pipeline_cache_data(&[1, 2, 3, 4]);
+    data[8] = 0xff;
+    write_file(&path, &data);
+
+    let pipeline_cache = device.load_pipeline_cache(&path, None).unwrap();
+    assert!(mock::pipeline_cache_initial_data(pipeline_cache.id()).is_empty());
+
+    write_file(&path, &[1, 2, 3]);
+    let pipeline_cache = device.load_pipeline_cache(&path, None).unwrap();
+    assert!(mock::pipeline_cache_initial_data(pipeline_cache.id()).is_empty());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn store_error() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let pipeline_cache = device.load_pipeline_cache(temp_path("unused"), None).unwrap();
+
+    mock::inject_result("vkGetPipelineCacheData", vks::vk::VK_ERROR_OUT_OF_HOST_MEMORY);
+    match pipeline_cache.store(temp_path("error")) {
+        Err(core::PipelineCacheError::VulkanError(core::Error::OutOfHostMemory)) => { }
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    let path = temp_path("missing-directory").join("cache");
+    match pipeline_cache.store(&path) {
+        Err(core::PipelineCacheError::Io(_)) => { }
+        res => panic!("unexpected result {:?}", res),
+    }
+}
+
+#[test]
+fn load_error() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    match device.load_pipeline_cache(env::temp_dir(), None) {
+        Err(core::PipelineCacheError::Io(_)) => { }
+        res => panic!("unexpected result {:?}", res),
+    }
+    assert_eq!(mock::live_object_count("VkPipelineCache"), 0);
+}
@@ -0,0 +1,170 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::VulkanObject;
+use dacite::core;
+use dacite::queue_planner::{QueuePlanError, QueuePlanner, QueueRole};
+
+fn queue_family(flags: vks::vk::VkQueueFlags, count: u32) -> vks::vk::VkQueueFamilyProperties {
+    vks::vk::VkQueueFamilyProperties {
+        queueFlags: flags,
+        queueCount: count,
+        timestampValidBits: 64,
+        minImageTransferGranularity: vks::vk::VkExtent3D { width: 1, height: 1, depth: 1 },
+    }
+}
+
+fn create_device(physical_device: &core::PhysicalDevice, queue_create_infos: &[core::DeviceQueueCreateInfo]) -> core::Device {
+    let create_info = core::DeviceCreateInfo {
+        flags: core::DeviceCreateFlags::empty(),
+        queue_create_infos: queue_create_infos.to_vec(),
+        enabled_layers: vec![],
+        enabled_extensions: core::DeviceExtensions::new(),
+        enabled_features: None,
+        chain: None,
+    };
+
+    physical_device.create_device(&create_info, None).unwrap()
+}
+
+#[test]
+fn prefer_dedicated_transfer_family() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let plan = QueuePlanner::new()
+        .request(QueueRole::Graphics, 1)
+        .request(QueueRole::Compute, 1)
+        .request(QueueRole::Transfer, 1)
+        .plan(&physical_device)
+        .unwrap();
+
+    assert_eq!(plan.queues_for(QueueRole::Graphics), &[(0, 0)]);
+    assert_eq!(plan.queues_for(QueueRole::Compute), &[(0, 1)]);
+    assert_eq!(plan.queues_for(QueueRole::Transfer), &[(1, 0)]);
+    assert_eq!(plan.queues_for(QueueRole::Present), &[]);
+    assert_eq!(plan.queue_family_index(QueueRole::Transfer), Some(1));
+
+    let queue_create_infos = plan.queue_create_infos();
+    assert_eq!(queue_create_infos.len(), 2);
+    assert_eq!(queue_create_infos[0].queue_family_index, 0);
+    assert_eq!(queue_create_infos[0].queue_priorities, vec![1.0, 1.0]);
+    assert_eq!(queue_create_infos[1].queue_family_index, 1);
+    assert_eq!(queue_create_infos[1].queue_priorities, vec![1.0]);
+}
+
+#[test]
+fn prefer_dedicated_compute_family() {
+    mock::configure(|config| {
+        config.queue_families = vec![
+            queue_family(vks::vk::VK_QUEUE_GRAPHICS_BIT | vks::vk::VK_QUEUE_COMPUTE_BIT | vks::vk::VK_QUEUE_TRANSFER_BIT, 1),
+            queue_family(vks::vk::VK_QUEUE_COMPUTE_BIT | vks::vk::VK_QUEUE_TRANSFER_BIT, 0),
+            queue_family(vks::vk::VK_QUEUE_COMPUTE_BIT | vks::vk::VK_QUEUE_TRANSFER_BIT, 2),
+        ];
+    });
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let plan = QueuePlanner::new()
+        .request(QueueRole::Compute, 1)
+        .request(QueueRole::Transfer, 1)
+        .plan(&physical_device)
+        .unwrap();
+
+    assert_eq!(plan.queues_for(QueueRole::Compute), &[(2, 0)]);
+    assert_eq!(plan.queues_for(QueueRole::Transfer), &[(2, 1)]);
+    assert_eq!(plan.queue_create_infos().len(), 1);
+}
+
+#[test]
+fn share_queues_when_family_is_exhausted() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let plan = QueuePlanner::new()
+        .request(QueueRole::Graphics, 2)
+        .request(QueueRole::Graphics, 1)
+        .plan(&physical_device)
+        .unwrap();
+
+    assert_eq!(plan.queues_for(QueueRole::Graphics), &[(0, 0), (0, 1), (0, 0)]);
+    assert_eq!(plan.queue_create_infos()[0].queue_priorities.len(), 2);
+}
+
+#[test]
+fn present_prefers_graphics_family() {
+    let instance = mock::create_instance(mock::instance_extensions());
+    let surface = mock::create_surface(&instance);
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let plan = QueuePlanner::new()
+        .request(QueueRole::Present, 1)
+        .request(QueueRole::Graphics, 1)
+        .surface(surface)
+        .plan(&physical_device)
+        .unwrap();
+
+    assert_eq!(plan.queues_for(QueueRole::Present), &[(0, 0)]);
+    assert_eq!(plan.queues_for(QueueRole::Graphics), &[(0, 1)]);
+    assert_eq!(plan.queue_create_infos().len(), 1);
+}
+
+#[test]
+fn get_queues() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let plan = QueuePlanner::new()
+        .request(QueueRole::Graphics, 1)
+        .request(QueueRole::Transfer, 1)
+        .plan(&physical_device)
+        .unwrap();
+
+    let device = create_device(&physical_device, plan.queue_create_infos());
+    let queues = plan.get_queues(&device);
+    assert_eq!(queues.len(), 2);
+    assert_eq!(queues[&QueueRole::Graphics].len(), 1);
+    assert_eq!(queues[&QueueRole::Graphics][0].id(), device.get_queue(0, 0).id());
+    assert_eq!(queues[&QueueRole::Transfer][0].id(), device.get_queue(1, 0).id());
+}
+
+#[test]
+fn plan_errors() {
+    mock::configure(|config| {
+        config.queue_families = vec![queue_family(vks::vk::VK_QUEUE_TRANSFER_BIT, 1)];
+        config.surface_support = false;
+    });
+    let instance = mock::create_instance(mock::instance_extensions());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let res = QueuePlanner::new().request(QueueRole::Graphics, 1).plan(&physical_device);
+    assert_eq!(res.unwrap_err(), QueuePlanError::NoSuitableQueueFamily(QueueRole::Graphics));
+
+    let res = QueuePlanner::new().request(QueueRole::Present, 1).plan(&physical_device);
+    assert_eq!(res.unwrap_err(), QueuePlanError::NoSurface);
+
+    let res = QueuePlanner::new()
+        .request(QueueRole::Transfer, 1)
+        .request(QueueRole::Present, 1)
+        .surface(mock::create_surface(&instance))
+        .plan(&physical_device);
+    assert_eq!(res.unwrap_err(), QueuePlanError::NoSuitableQueueFamily(QueueRole::Present));
+}
@@ -0,0 +1,251 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::VulkanObject;
+use dacite::core;
+use dacite::render_graph::{AttachmentLoad, ImageAccess, ImportedImage, RenderGraph, RenderGraphError, TransientImage};
+use std::cell::Cell;
+use std::rc::Rc;
+
+fn transient(format: core::Format) -> TransientImage {
+    TransientImage {
+        format: format,
+        extent: core::Extent2D::new(64, 64),
+        samples: core::SampleCountFlagBits::SampleCount1,
+    }
+}
+
+#[test]
+fn barriers_between_passes() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let mut graph = RenderGraph::new();
+    let gbuffer = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+    let mut swapchain_image = ImportedImage::new(mock::create_image(&device, core::Format::B8G8R8A8_UNorm, 1, 1), core::Format::B8G8R8A8_UNorm, core::Extent2D::new(64, 64));
+    swapchain_image.final_layout = Some(core::ImageLayout::PresentSrcKhr);
+    let swapchain_image = graph.import_image(swapchain_image);
+
+    let geometry = graph.add_pass("geometry")
+        .color_attachment(gbuffer, AttachmentLoad::Clear(core::ClearValue::Color(core::ClearColorValue::Float32([0.0; 4]))))
+        .execute(|_| { });
+
+    let lighting = graph.add_pass("lighting")
+        .read_image(gbuffer, ImageAccess::sampled(core::PipelineStageFlags::FRAGMENT_SHADER))
+        .color_attachment(swapchain_image, AttachmentLoad::DontCare)
+        .execute(|_| { });
+
+    let compiled = graph.compile(&device).unwrap();
+    assert_eq!(compiled.pass_order(), vec![geometry, lighting]);
+    assert_eq!(compiled.transient_image_count(), 1);
+    assert!(compiled.render_pass(geometry).is_some());
+
+    let barriers = compiled.barriers(geometry).unwrap();
+    assert_eq!(barriers.image_memory_barriers.len(), 1);
+    assert_eq!(barriers.image_memory_barriers[0].old_layout, core::ImageLayout::Undefined);
+    assert_eq!(barriers.image_memory_barriers[0].new_layout, core::ImageLayout::ColorAttachmentOptimal);
+    assert_eq!(barriers.image_memory_barriers[0].dst_access_mask, core::AccessFlags::COLOR_ATTACHMENT_WRITE);
+
+    let barriers = compiled.barriers(lighting).unwrap();
+    assert!(barriers.src_stage_mask.contains(core::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT));
+    assert!(barriers.dst_stage_mask.contains(core::PipelineStageFlags::FRAGMENT_SHADER));
+    let gbuffer_barrier = barriers.image_memory_barriers.iter().find(|b| b.image == *compiled.image(gbuffer).unwrap()).unwrap();
+    assert_eq!(gbuffer_barrier.src_access_mask, core::AccessFlags::COLOR_ATTACHMENT_WRITE);
+    assert_eq!(gbuffer_barrier.dst_access_mask, core::AccessFlags::SHADER_READ);
+    assert_eq!(gbuffer_barrier.old_layout, core::ImageLayout::ColorAttachmentOptimal);
+    assert_eq!(gbuffer_barrier.new_layout, core::ImageLayout::ShaderReadOnlyOptimal);
+
+    let final_barriers = compiled.final_barriers().unwrap();
+    assert_eq!(final_barriers.image_memory_barriers.len(), 1);
+    assert_eq!(final_barriers.image_memory_barriers[0].old_layout, core::ImageLayout::ColorAttachmentOptimal);
+    assert_eq!(final_barriers.image_memory_barriers[0].new_layout, core::ImageLayout::PresentSrcKhr);
+}
+
+#[test]
+fn record_executes_passes() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let (_command_pool, command_buffer) = mock::create_command_buffer(&device);
+
+    let mut graph = RenderGraph::new();
+    let image = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+    let executed = Rc::new(Cell::new(0));
+
+    let counter = executed.clone();
+    graph.add_pass("draw")
+        .color_attachment(image, AttachmentLoad::DontCare)
+        .execute(move |context| {
+            assert!(context.render_pass().is_some());
+            assert_eq!(context.render_area().unwrap().extent, core::Extent2D::new(64, 64));
+            counter.set(counter.get() + 1);
+        });
+
+    let counter = executed.clone();
+    graph.add_pass("copy")
+        .read_image(image, ImageAccess::transfer_src())
+        .execute(move |context| {
+            assert!(context.render_pass().is_none());
+            assert!(context.image(image).is_some());
+            counter.set(counter.get() + 1);
+        });
+
+    let mut compiled = graph.compile(&device).unwrap();
+    assert_eq!(mock::call_count("vkCreateRenderPass"), 1);
+    assert_eq!(mock::call_count("vkCreateFramebuffer"), 1);
+
+    mock::clear_calls();
+    compiled.record(&command_buffer);
+    assert_eq!(executed.get(), 2);
+    assert_eq!(mock::call_count("vkCmdBeginRenderPass"), 1);
+    assert_eq!(mock::call_count("vkCmdEndRenderPass"), 1);
+    assert_eq!(mock::call_count("vkCmdPipelineBarrier"), 2);
+}
+
+#[test]
+fn transient_images_are_aliased() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let mut graph = RenderGraph::new();
+    let sampled = ImageAccess::sampled(core::PipelineStageFlags::FRAGMENT_SHADER);
+    let first = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+    let second = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+    let third = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+    let unused = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+
+    graph.add_pass("first").color_attachment(first, AttachmentLoad::DontCare).execute(|_| { });
+    graph.add_pass("second").read_image(first, sampled).color_attachment(second, AttachmentLoad::DontCare).execute(|_| { });
+    graph.add_pass("third").read_image(second, sampled).color_attachment(third, AttachmentLoad::DontCare).execute(|_| { });
+    graph.add_pass("fourth").read_image(third, sampled).execute(|_| { });
+
+    let compiled = graph.compile(&device).unwrap();
+    assert_eq!(compiled.transient_image_count(), 2);
+    assert_eq!(mock::live_object_count("VkImage"), 2);
+    assert!(compiled.image(first) == compiled.image(third));
+    assert!(compiled.image(first) != compiled.image(second));
+    assert!(compiled.image(unused).is_none());
+}
+
+#[test]
+fn reading_uninitialized_image() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let mut graph = RenderGraph::new();
+    let image = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+    graph.add_pass("read").read_image(image, ImageAccess::transfer_src()).execute(|_| { });
+
+    match graph.compile(&device) {
+        Err(RenderGraphError::UninitializedImage { ref pass, image: id }) => {
+            assert_eq!(pass, "read");
+            assert_eq!(id, image);
+        }
+
+        res => panic!("unexpected result {:?}", res.map(|_| ())),
+    }
+}
+
+#[test]
+fn conflicting_layouts() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let mut graph = RenderGraph::new();
+    let image = graph.create_image(transient(core::Format::D32_SFloat));
+    graph.add_pass("write").depth_stencil_attachment(image, AttachmentLoad::DontCare).execute(|_| { });
+    graph.add_pass("read")
+        .read_image(image, ImageAccess::sampled(core::PipelineStageFlags::FRAGMENT_SHADER))
+        .read_image(image, ImageAccess::transfer_src())
+        .execute(|_| { });
+
+    match graph.compile(&device) {
+        Err(RenderGraphError::ConflictingImageLayouts { ref pass, .. }) => assert_eq!(pass, "read"),
+        res => panic!("unexpected result {:?}", res.map(|_| ())),
+    }
+}
+
+#[test]
+fn replaced_depth_stencil_attachment() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let mut graph = RenderGraph::new();
+    let replaced = graph.create_image(transient(core::Format::D32_SFloat));
+    let depth = graph.create_image(transient(core::Format::D32_SFloat));
+    graph.add_pass("draw")
+        .depth_stencil_attachment(replaced, AttachmentLoad::DontCare)
+        .depth_stencil_attachment(depth, AttachmentLoad::DontCare)
+        .execute(|_| { });
+
+    let compiled = graph.compile(&device).unwrap();
+    assert_eq!(compiled.transient_image_count(), 1);
+    assert!(compiled.image(replaced).is_none());
+    assert!(compiled.image(depth).is_some());
+}
+
+#[test]
+fn imported_image_samples() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let mut graph = RenderGraph::new();
+    let mut image = ImportedImage::new(mock::create_image(&device, core::Format::B8G8R8A8_UNorm, 1, 1), core::Format::B8G8R8A8_UNorm, core::Extent2D::new(64, 64));
+    image.samples = core::SampleCountFlagBits::SampleCount4;
+    let image = graph.import_image(image);
+    let pass = graph.add_pass("draw").color_attachment(image, AttachmentLoad::DontCare).execute(|_| { });
+
+    let compiled = graph.compile(&device).unwrap();
+    let render_pass = compiled.render_pass(pass).unwrap();
+    assert_eq!(mock::render_pass_samples(render_pass.id()), vec![vks::vk::VK_SAMPLE_COUNT_4_BIT]);
+}
+
+#[test]
+fn input_attachments() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let mut graph = RenderGraph::new();
+    let gbuffer = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+    let output = graph.create_image(transient(core::Format::R8G8B8A8_UNorm));
+    graph.add_pass("geometry")
+        .color_attachment(gbuffer, AttachmentLoad::DontCare)
+        .execute(|_| { });
+
+    let lighting = graph.add_pass("lighting")
+        .input_attachment(gbuffer)
+        .execute(|_| { });
+
+    graph.add_pass("tonemap")
+        .color_attachment(output, AttachmentLoad::DontCare)
+        .input_attachment(gbuffer)
+        .execute(|_| { });
+
+    let compiled = graph.compile(&device).unwrap();
+    assert!(compiled.image_view(gbuffer).is_some());
+
+    let render_pass = compiled.render_pass(lighting).unwrap();
+    assert_eq!(mock::render_pass_input_attachments(render_pass.id()), vec![(0, vks::vk::VK_IMAGE_LAYOUT_SHADER_READ_ONLY_OPTIMAL)]);
+
+    let barriers = compiled.barriers(lighting).unwrap();
+    assert_eq!(barriers.image_memory_barriers[0].old_layout, core::ImageLayout::ColorAttachmentOptimal);
+    assert_eq!(barriers.image_memory_barriers[0].new_layout, core::ImageLayout::ShaderReadOnlyOptimal);
+    assert_eq!(barriers.image_memory_barriers[0].dst_access_mask, core::AccessFlags::INPUT_ATTACHMENT_READ);
+}
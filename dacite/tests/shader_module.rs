@@ -0,0 +1,94 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::VulkanObject;
+use dacite::core;
+use dacite::spirv;
+
+const CODE: [u32; 5] = [spirv::MAGIC_NUMBER, 0x0001_0000, 0, 1, 0];
+
+fn to_bytes(words: &[u32], big_endian: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for &word in words {
+        let le = [word as u8, (word >> 8) as u8, (word >> 16) as u8, (word >> 24) as u8];
+        if big_endian {
+            bytes.extend(le.iter().rev());
+        }
+        else {
+            bytes.extend_from_slice(&le);
+        }
+    }
+
+    bytes
+}
+
+#[test]
+fn from_bytes() {
+    let create_info = core::ShaderModuleCreateInfo::from_bytes(&to_bytes(&CODE, false)).unwrap();
+    assert_eq!(create_info.code, CODE.to_vec());
+    assert_eq!(create_info.flags, core::ShaderModuleCreateFlags::empty());
+    assert!(create_info.chain.is_none());
+
+    let create_info = core::ShaderModuleCreateInfo::from_bytes(&to_bytes(&CODE, true)).unwrap();
+    assert_eq!(create_info.code, CODE.to_vec());
+}
+
+#[test]
+fn from_bytes_errors() {
+    let bytes = to_bytes(&CODE, false);
+    assert_eq!(core::ShaderModuleCreateInfo::from_bytes(&bytes[..18]), Err(spirv::ParseError::InvalidSize(18)));
+    assert_eq!(core::ShaderModuleCreateInfo::from_bytes(&bytes[..19]), Err(spirv::ParseError::InvalidSize(19)));
+    assert_eq!(core::ShaderModuleCreateInfo::from_bytes(&bytes[4..]), Err(spirv::ParseError::InvalidSize(16)));
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = 0;
+    assert_eq!(core::ShaderModuleCreateInfo::from_bytes(&bad_magic), Err(spirv::ParseError::InvalidMagicNumber(0x0723_0200)));
+
+    let mut newer = CODE;
+    newer[1] = 0x0001_0400;
+    assert_eq!(core::ShaderModuleCreateInfo::from_bytes(&to_bytes(&newer, true)), Err(spirv::ParseError::UnsupportedVersion(1, 4)));
+}
+
+#[test]
+fn from_words() {
+    let create_info = core::ShaderModuleCreateInfo::from_words(CODE.to_vec()).unwrap();
+    assert_eq!(create_info.code, CODE.to_vec());
+
+    let swapped: Vec<_> = CODE.iter().map(|w| w.swap_bytes()).collect();
+    let create_info = core::ShaderModuleCreateInfo::from_words(swapped).unwrap();
+    assert_eq!(create_info.code, CODE.to_vec());
+
+    assert_eq!(core::ShaderModuleCreateInfo::from_words(CODE[..4].to_vec()), Err(spirv::ParseError::InvalidSize(16)));
+    assert_eq!(core::ShaderModuleCreateInfo::from_words(vec![0; 5]), Err(spirv::ParseError::InvalidMagicNumber(0)));
+}
+
+#[test]
+fn create_shader_module() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let create_info = core::ShaderModuleCreateInfo::from_bytes(&to_bytes(&CODE, true)).unwrap();
+    let shader_module = device.create_shader_module(&create_info, None).unwrap();
+    assert_eq!(mock::shader_module_code(shader_module.id()), CODE.to_vec());
+
+    drop(shader_module);
+    assert_eq!(mock::live_object_count("VkShaderModule"), 0);
+}
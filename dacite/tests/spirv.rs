@@ -0,0 +1,400 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+
+use dacite::core;
+use dacite::spirv;
+
+fn instruction(opcode: u32, operands: &[u32]) -> Vec<u32> {
+    let mut words = vec![((operands.len() as u32 + 1) << 16) | opcode];
+    words.extend_from_slice(operands);
+    words
+}
+
+fn string(s: &str) -> Vec<u32> {
+    let mut bytes = s.as_bytes().to_vec();
+    bytes.push(0);
+    while bytes.len() % 4 != 0 {
+        bytes.push(0);
+    }
+
+    bytes.chunks(4).map(|b| (b[0] as u32) | ((b[1] as u32) << 8) | ((b[2] as u32) << 16) | ((b[3] as u32) << 24)).collect()
+}
+
+fn module(version: u32, instructions: &[Vec<u32>]) -> Vec<u32> {
+    let mut words = vec![spirv::MAGIC_NUMBER, version, 0, 100, 0];
+    for instruction in instructions {
+        words.extend_from_slice(instruction);
+    }
+
+    words
+}
+
+fn entry_point(execution_model: u32, function: u32, name: &str, interface: &[u32]) -> Vec<u32> {
+    let mut operands = vec![execution_model, function];
+    operands.extend(string(name));
+    operands.extend_from_slice(interface);
+    instruction(15, &operands)
+}
+
+fn name(id: u32, name: &str) -> Vec<u32> {
+    let mut operands = vec![id];
+    operands.extend(string(name));
+    instruction(5, &operands)
+}
+
+/// A vertex shader with:
+///
+/// - `layout(location = 0) in vec3 position;`
+/// - `layout(location = 1) in mat2 transform;`
+/// - `layout(set = 0, binding = 0) uniform Ubo { ... };`
+/// - `layout(set = 1, binding = 2) uniform sampler2D textures[4];`
+/// - `layout(push_constant) uniform Push { mat4 mvp; vec4 color; };`
+fn vertex_shader() -> Vec<u32> {
+    module(0x0001_0000, &[
+        entry_point(0, 1, "main", &[20, 21]),
+        name(20, "position"),
+        name(30, "ubo"),
+        instruction(71, &[20, 30, 0]),
+        instruction(71, &[21, 30, 1]),
+        instruction(71, &[22, 11, 0]),
+        instruction(71, &[33, 2]),
+        instruction(71, &[30, 34, 0]),
+        instruction(71, &[30, 33, 0]),
+        instruction(71, &[40, 34, 1]),
+        instruction(71, &[40, 33, 2]),
+        instruction(71, &[50, 2]),
+        instruction(72, &[50, 0, 35, 0]),
+        instruction(72, &[50, 0, 7, 16]),
+        instruction(72, &[50, 1, 35, 64]),
+        instruction(22, &[2, 32]),
+        instruction(23, &[3, 2, 3]),
+        instruction(23, &[4, 2, 4]),
+        instruction(24, &[5, 4, 4]),
+        instruction(23, &[6, 2, 2]),
+        instruction(24, &[7, 6, 2]),
+        instruction(21, &[8, 32, 0]),
+        instruction(43, &[8, 9, 4]),
+        instruction(32, &[10, 1, 3]),
+        instruction(32, &[11, 1, 7]),
+        instruction(32, &[12, 1, 4]),
+        instruction(59, &[10, 20, 1]),
+        instruction(59, &[11, 21, 1]),
+        instruction(59, &[12, 22, 1]),
+        instruction(30, &[33, 5]),
+        instruction(32, &[34, 2, 33]),
+        instruction(59, &[34, 30, 2]),
+        instruction(25, &[35, 2, 1, 0, 0, 0, 1, 0]),
+        instruction(27, &[36, 35]),
+        instruction(28, &[37, 36, 9]),
+        instruction(32, &[38, 0, 37]),
+        instruction(59, &[38, 40, 0]),
+        instruction(30, &[50, 5, 4]),
+        instruction(32, &[51, 9, 50]),
+        instruction(59, &[51, 52, 9]),
+    ])
+}
+
+/// A fragment shader with:
+///
+/// - `layout(set = 0, binding = 0) uniform Ubo { ... };`
+/// - `layout(set = 2, binding = 0) buffer Ssbo { ... };`
+/// - `layout(push_constant) uniform Push { layout(offset = 80) float alpha; };`
+fn fragment_shader() -> Vec<u32> {
+    module(0x0001_0300, &[
+        entry_point(4, 1, "main", &[]),
+        instruction(71, &[33, 2]),
+        instruction(71, &[30, 34, 0]),
+        instruction(71, &[30, 33, 0]),
+        instruction(71, &[43, 12]),
+        instruction(71, &[40, 34, 2]),
+        instruction(71, &[40, 33, 0]),
+        instruction(71, &[50, 2]),
+        instruction(72, &[50, 0, 35, 80]),
+        instruction(22, &[2, 32]),
+        instruction(23, &[4, 2, 4]),
+        instruction(30, &[33, 4]),
+        instruction(32, &[34, 2, 33]),
+        instruction(59, &[34, 30, 2]),
+        instruction(29, &[42, 2]),
+        instruction(30, &[43, 42]),
+        instruction(32, &[44, 12, 43]),
+        instruction(59, &[44, 40, 12]),
+        instruction(30, &[50, 2]),
+        instruction(32, &[51, 9, 50]),
+        instruction(59, &[51, 52, 9]),
+    ])
+}
+
+fn to_bytes(words: &[u32], big_endian: bool) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(words.len() * 4);
+    for &word in words {
+        let le = [word as u8, (word >> 8) as u8, (word >> 16) as u8, (word >> 24) as u8];
+        if big_endian {
+            bytes.extend(le.iter().rev());
+        }
+        else {
+            bytes.extend_from_slice(&le);
+        }
+    }
+
+    bytes
+}
+
+#[test]
+fn header_validation() {
+    let words = vertex_shader();
+    assert_eq!(spirv::words_from_bytes(&to_bytes(&words, false)[..19]), Err(spirv::ParseError::InvalidSize(19)));
+    assert_eq!(spirv::words_from_bytes(&[0; 20]), Err(spirv::ParseError::InvalidMagicNumber(0)));
+    assert_eq!(spirv::words_from_bytes(&to_bytes(&words, true)).unwrap(), words);
+
+    let header = spirv::parse_header(&words).unwrap();
+    assert_eq!((header.major_version, header.minor_version, header.bound), (1, 0, 100));
+
+    let mut unsupported = words.clone();
+    unsupported[1] = 0x0002_0000;
+    assert_eq!(spirv::parse_header(&unsupported), Err(spirv::ParseError::UnsupportedVersion(2, 0)));
+}
+
+#[test]
+fn truncated_instruction() {
+    let mut words = vertex_shader();
+    words.pop();
+    assert_eq!(spirv::ShaderReflection::from_words(&words), Err(spirv::ParseError::InvalidInstruction(words.len() - 3)));
+}
+
+#[test]
+fn struct_without_operands() {
+    let words = [spirv::MAGIC_NUMBER, 0x0001_0000, 0, 10, 0, (1 << 16) | 30];
+    assert_eq!(spirv::ShaderReflection::from_words(&words), Err(spirv::ParseError::InvalidInstruction(5)));
+}
+
+#[test]
+fn cyclic_types() {
+    // uniform sampler2D samplers[4], where the element type of the array is the array itself.
+    let words = module(0x0001_0000, &[
+        instruction(21, &[8, 32, 0]),
+        instruction(43, &[8, 9, 4]),
+        instruction(28, &[37, 37, 9]),
+        instruction(32, &[38, 0, 37]),
+        instruction(59, &[38, 40, 0]),
+    ]);
+    assert_eq!(spirv::ShaderReflection::from_words(&words), Err(spirv::ParseError::InvalidInstruction(13)));
+
+    // A push constant block, which contains itself.
+    let words = module(0x0001_0000, &[
+        instruction(30, &[50, 50, 50]),
+        instruction(32, &[51, 9, 50]),
+        instruction(59, &[51, 52, 9]),
+    ]);
+    assert_eq!(spirv::ShaderReflection::from_words(&words), Err(spirv::ParseError::InvalidInstruction(5)));
+}
+
+#[test]
+fn deeply_shared_types() {
+    // 40 nested structs with two members of the previous struct each. Without caching the sizes of
+    // types, this takes 2^40 steps.
+    let mut instructions = vec![instruction(22, &[100, 32])];
+    for id in 101..141 {
+        instructions.push(instruction(72, &[id, 1, 35, 4 * (id - 100)]));
+        instructions.push(instruction(30, &[id, id - 1, id - 1]));
+    }
+    instructions.push(instruction(32, &[200, 9, 140]));
+    instructions.push(instruction(59, &[200, 201, 9]));
+
+    let reflection = spirv::ShaderReflection::from_words(&module(0x0001_0000, &instructions)).unwrap();
+    assert_eq!(reflection.push_constant_ranges()[0].size, 4 * (1 + (1..41).sum::<u32>()));
+}
+
+#[test]
+fn overflowing_sizes() {
+    let int = instruction(21, &[8, 32, 0]);
+    let length = instruction(43, &[8, 9, 0x8000_0000]);
+
+    // uniform sampler2D samplers[0x80000000][0x80000000];
+    let words = module(0x0001_0000, &[
+        int.clone(),
+        length.clone(),
+        instruction(25, &[35, 2, 1, 0, 0, 0, 1, 0]),
+        instruction(27, &[36, 35]),
+        instruction(28, &[37, 36, 9]),
+        instruction(28, &[38, 37, 9]),
+        instruction(32, &[39, 0, 38]),
+        instruction(59, &[39, 40, 0]),
+    ]);
+    assert_eq!(spirv::ShaderReflection::from_words(&words), Err(spirv::ParseError::InvalidInstruction(25)));
+
+    // layout(push_constant) uniform Push { layout(offset = 0xfffffffc) vec2 v; };
+    let words = module(0x0001_0000, &[
+        instruction(72, &[50, 0, 35, 0xffff_fffc]),
+        instruction(22, &[2, 32]),
+        instruction(23, &[6, 2, 2]),
+        instruction(30, &[50, 6]),
+        instruction(32, &[51, 9, 50]),
+        instruction(59, &[51, 52, 9]),
+    ]);
+    assert_eq!(spirv::ShaderReflection::from_words(&words), Err(spirv::ParseError::InvalidInstruction(17)));
+
+    // layout(push_constant) uniform Push { int values[0x80000000]; }; with an array stride of 4.
+    let words = module(0x0001_0000, &[
+        instruction(71, &[37, 6, 4]),
+        int,
+        length,
+        instruction(28, &[37, 8, 9]),
+        instruction(30, &[50, 37]),
+        instruction(32, &[51, 9, 50]),
+        instruction(59, &[51, 52, 9]),
+    ]);
+    assert_eq!(spirv::ShaderReflection::from_words(&words), Err(spirv::ParseError::InvalidInstruction(17)));
+}
+
+#[test]
+fn reflect_vertex_shader() {
+    let reflection = spirv::ShaderReflection::new(&to_bytes(&vertex_shader(), false)).unwrap();
+
+    assert_eq!(reflection.entry_points(), &[spirv::EntryPoint {
+        name: "main".to_owned(),
+        stage: core::ShaderStageFlags::VERTEX,
+    }]);
+
+    assert_eq!(reflection.descriptor_bindings(), &[
+        spirv::DescriptorBinding {
+            set: 0,
+            binding: 0,
+            descriptor_type: core::DescriptorType::UniformBuffer,
+            descriptor_count: 1,
+            specialized_count: false,
+            stage_flags: core::ShaderStageFlags::VERTEX,
+            name: Some("ubo".to_owned()),
+        },
+        spirv::DescriptorBinding {
+            set: 1,
+            binding: 2,
+            descriptor_type: core::DescriptorType::CombinedImageSampler,
+            descriptor_count: 4,
+            specialized_count: false,
+            stage_flags: core::ShaderStageFlags::VERTEX,
+            name: None,
+        },
+    ]);
+
+    assert_eq!(reflection.push_constant_ranges(), &[core::PushConstantRange {
+        stage_flags: core::ShaderStageFlags::VERTEX,
+        offset: 0,
+        size: 80,
+    }]);
+
+    assert_eq!(reflection.vertex_inputs(), &[
+        spirv::VertexInput {
+            location: 0,
+            format: Some(core::Format::R32G32B32_SFloat),
+            name: Some("position".to_owned()),
+        },
+        spirv::VertexInput {
+            location: 1,
+            format: Some(core::Format::R32G32_SFloat),
+            name: None,
+        },
+        spirv::VertexInput {
+            location: 2,
+            format: Some(core::Format::R32G32_SFloat),
+            name: None,
+        },
+    ]);
+}
+
+#[test]
+fn spec_constant_array_lengths() {
+    // layout(constant_id = 0) const int N = 4;
+    // layout(set = 0, binding = 1) uniform sampler2D textures[N];
+    // layout(set = 0, binding = 2) uniform sampler2D more_textures[N * 2];
+    let words = module(0x0001_0000, &[
+        entry_point(4, 1, "main", &[]),
+        instruction(71, &[9, 1, 0]),
+        instruction(71, &[40, 34, 0]),
+        instruction(71, &[40, 33, 1]),
+        instruction(71, &[41, 34, 0]),
+        instruction(71, &[41, 33, 2]),
+        instruction(22, &[2, 32]),
+        instruction(21, &[8, 32, 1]),
+        instruction(50, &[8, 9, 4]),
+        instruction(43, &[8, 10, 2]),
+        instruction(52, &[8, 11, 132, 9, 10]),
+        instruction(25, &[35, 2, 1, 0, 0, 0, 1, 0]),
+        instruction(27, &[36, 35]),
+        instruction(28, &[37, 36, 9]),
+        instruction(28, &[38, 36, 11]),
+        instruction(32, &[39, 0, 37]),
+        instruction(32, &[42, 0, 38]),
+        instruction(59, &[39, 40, 0]),
+        instruction(59, &[42, 41, 0]),
+    ]);
+
+    let reflection = spirv::ShaderReflection::from_words(&words).unwrap();
+    let bindings = reflection.descriptor_bindings();
+    assert_eq!(bindings.len(), 2);
+
+    assert_eq!(bindings[0].binding, 1);
+    assert_eq!(bindings[0].descriptor_type, core::DescriptorType::CombinedImageSampler);
+    assert_eq!(bindings[0].descriptor_count, 4);
+    assert!(bindings[0].specialized_count);
+
+    assert_eq!(bindings[1].binding, 2);
+    assert_eq!(bindings[1].descriptor_count, 1);
+    assert!(bindings[1].specialized_count);
+}
+
+#[test]
+fn generate_layouts() {
+    let modules = [
+        spirv::ShaderReflection::from_words(&vertex_shader()).unwrap(),
+        spirv::ShaderReflection::from_words(&fragment_shader()).unwrap(),
+    ];
+
+    let set_layouts = spirv::descriptor_set_layout_create_infos(&modules).unwrap();
+    assert_eq!(set_layouts.len(), 3);
+
+    assert_eq!(set_layouts[0].bindings.len(), 1);
+    assert_eq!(set_layouts[0].bindings[0].descriptor_type, core::DescriptorType::UniformBuffer);
+    assert_eq!(set_layouts[0].bindings[0].stage_flags, core::ShaderStageFlags::VERTEX | core::ShaderStageFlags::FRAGMENT);
+
+    assert_eq!(set_layouts[1].bindings[0].binding, 2);
+    assert_eq!(set_layouts[1].bindings[0].descriptor_count, 4);
+
+    assert_eq!(set_layouts[2].bindings[0].descriptor_type, core::DescriptorType::StorageBuffer);
+    assert_eq!(set_layouts[2].bindings[0].stage_flags, core::ShaderStageFlags::FRAGMENT);
+
+    let pipeline_layout = spirv::pipeline_layout_create_info(&modules, vec![]);
+    assert_eq!(pipeline_layout.push_constant_ranges, vec![core::PushConstantRange {
+        stage_flags: core::ShaderStageFlags::VERTEX | core::ShaderStageFlags::FRAGMENT,
+        offset: 0,
+        size: 84,
+    }]);
+}
+
+#[test]
+fn conflicting_bindings() {
+    let mut fragment_shader = fragment_shader();
+    // Turn the storage buffer in set 2 into set 0, where the vertex shader has a uniform buffer.
+    let position = fragment_shader.windows(4).position(|w| w == [0x0004_0047, 40, 34, 2]).unwrap();
+    fragment_shader[position + 3] = 0;
+
+    let modules = [
+        spirv::ShaderReflection::from_words(&vertex_shader()).unwrap(),
+        spirv::ShaderReflection::from_words(&fragment_shader).unwrap(),
+    ];
+
+    assert_eq!(spirv::descriptor_set_layout_create_infos(&modules), Err(spirv::LayoutError::ConflictingBinding { set: 0, binding: 0 }));
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::core;
+use dacite::khr_surface;
+use dacite::khr_swapchain;
+
+fn device_extensions() -> core::DeviceExtensions {
+    let mut extensions = core::DeviceExtensions::new();
+    extensions.add_khr_swapchain();
+    extensions
+}
+
+fn swapchain_create_info(surface: &khr_surface::SurfaceKhr) -> khr_swapchain::SwapchainCreateInfoKhr {
+    khr_swapchain::SwapchainCreateInfoKhr {
+        flags: khr_swapchain::SwapchainCreateFlagsKhr::empty(),
+        surface: surface.clone(),
+        min_image_count: 3,
+        image_format: core::Format::B8G8R8A8_UNorm,
+        image_color_space: khr_surface::ColorSpaceKhr::SRGBNonLinear,
+        image_extent: core::Extent2D::new(800, 600),
+        image_array_layers: 1,
+        image_usage: core::ImageUsageFlags::COLOR_ATTACHMENT,
+        image_sharing_mode: core::SharingMode::Exclusive,
+        queue_family_indices: vec![],
+        pre_transform: khr_surface::SurfaceTransformFlagBitsKhr::Identity,
+        composite_alpha: khr_surface::CompositeAlphaFlagBitsKhr::Opaque,
+        present_mode: khr_surface::PresentModeKhr::Fifo,
+        clipped: true,
+        old_swapchain: None,
+        chain: None,
+    }
+}
+
+#[test]
+fn surface_queries() {
+    let instance = mock::create_instance(mock::instance_extensions());
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+    let surface = mock::create_surface(&instance);
+
+    assert_eq!(physical_device.get_surface_support_khr(0, &surface), Ok(true));
+
+    let capabilities = physical_device.get_surface_capabilities_khr(&surface).unwrap();
+    assert_eq!(capabilities.min_image_count, 2);
+    assert_eq!(capabilities.current_extent, Some(core::Extent2D::new(800, 600)));
+
+    let formats: Vec<_> = physical_device.get_surface_formats_khr(&surface).unwrap();
+    assert_eq!(formats.len(), 1);
+    assert_eq!(formats[0].format, core::Format::B8G8R8A8_UNorm);
+
+    let present_modes: Vec<_> = physical_device.get_surface_present_modes_khr(&surface).unwrap();
+    assert_eq!(present_modes, vec![khr_surface::PresentModeKhr::Fifo, khr_surface::PresentModeKhr::Mailbox]);
+
+    mock::inject_result("vkGetPhysicalDeviceSurfaceSupportKHR", vks::vk::VK_ERROR_SURFACE_LOST_KHR);
+    assert_eq!(physical_device.get_surface_support_khr(0, &surface), Err(core::Error::SurfaceLostKhr));
+
+    drop(surface);
+    assert_eq!(mock::live_object_count("VkSurfaceKHR"), 0);
+}
+
+#[test]
+fn acquire_and_present() {
+    let instance = mock::create_instance(mock::instance_extensions());
+    let (_, device) = mock::create_device(&instance, device_extensions());
+    let surface = mock::create_surface(&instance);
+
+    let swapchain = device.create_swapchain_khr(&swapchain_create_info(&surface), None).unwrap();
+    let images = swapchain.get_images_khr().unwrap();
+    assert_eq!(images.len(), 3);
+
+    let semaphore = device.create_semaphore(&core::SemaphoreCreateInfo { flags: core::SemaphoreCreateFlags::empty(), chain: None }, None).unwrap();
+    let queue = device.get_queue(0, 0);
+
+    for expected in &[0, 1, 2, 0] {
+        let index = match swapchain.acquire_next_image_khr(core::Timeout::Infinite, Some(&semaphore), None).unwrap() {
+            khr_swapchain::AcquireNextImageResultKhr::Index(index) => index,
+            res => panic!("unexpected result {:?}", res),
+        };
+        assert_eq!(index, *expected);
+
+        let mut present_info = khr_swapchain::PresentInfoKhr {
+            wait_semaphores: vec![semaphore.clone()],
+            swapchains: vec![swapchain.clone()],
+            image_indices: vec![index as u32],
+            results: Some(vec![]),
+            chain: None,
+        };
+        assert_eq!(queue.queue_present_khr(&mut present_info), Ok(khr_swapchain::QueuePresentResultKhr::Ok));
+        assert_eq!(present_info.results, Some(vec![Ok(khr_swapchain::QueuePresentResultKhr::Ok)]));
+    }
+
+    mock::inject_result("vkAcquireNextImageKHR", vks::vk::VK_ERROR_OUT_OF_DATE_KHR);
+    let res = swapchain.acquire_next_image_khr(core::Timeout::Infinite, Some(&semaphore), None);
+    assert_eq!(res.unwrap_err(), core::Error::OutOfDateKhr);
+
+    drop(images);
+    drop(swapchain);
+    assert_eq!(mock::live_object_count("VkSwapchainKHR"), 0);
+}
+
+#[test]
+fn create_swapchain_error() {
+    let instance = mock::create_instance(mock::instance_extensions());
+    let (_, device) = mock::create_device(&instance, device_extensions());
+    let surface = mock::create_surface(&instance);
+
+    mock::inject_result("vkCreateSwapchainKHR", vks::vk::VK_ERROR_NATIVE_WINDOW_IN_USE_KHR);
+    let res = device.create_swapchain_khr(&swapchain_create_info(&surface), None);
+    assert_eq!(res.unwrap_err(), core::Error::NativeWindowInUseKhr);
+}
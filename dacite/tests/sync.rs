@@ -0,0 +1,140 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+
+use dacite::core;
+use dacite::sync::{AccessType, BufferBarrier, GlobalBarrier, ImageBarrier, ImageLayoutHint, PipelineBarrier};
+
+fn color_range() -> core::ImageSubresourceRange {
+    core::ImageSubresourceRange {
+        aspect_mask: core::ImageAspectFlags::COLOR,
+        base_mip_level: 0,
+        level_count: core::OptionalMipLevels::Remaining,
+        base_array_layer: 0,
+        layer_count: core::OptionalArrayLayers::Remaining,
+    }
+}
+
+#[test]
+fn access_type_info() {
+    assert_eq!(AccessType::VertexShaderReadUniformBuffer.stage_mask(), core::PipelineStageFlags::VERTEX_SHADER);
+    assert_eq!(AccessType::VertexShaderReadUniformBuffer.access_mask(), core::AccessFlags::UNIFORM_READ);
+    assert!(!AccessType::VertexShaderReadUniformBuffer.is_write());
+
+    assert_eq!(AccessType::ColorAttachmentWrite.image_layout(), core::ImageLayout::ColorAttachmentOptimal);
+    assert!(AccessType::ColorAttachmentWrite.is_write());
+
+    assert_eq!(AccessType::Present.stage_mask(), core::PipelineStageFlags::empty());
+    assert_eq!(AccessType::Present.image_layout(), core::ImageLayout::PresentSrcKhr);
+}
+
+#[test]
+fn image_layout_transition() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+
+    let barrier = PipelineBarrier::new(None, &[], &[ImageBarrier::new(image.clone(), color_range(), &[AccessType::TransferWrite], &[AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer])]);
+    assert_eq!(barrier.src_stage_mask, core::PipelineStageFlags::TRANSFER);
+    assert_eq!(barrier.dst_stage_mask, core::PipelineStageFlags::FRAGMENT_SHADER);
+    assert!(barrier.memory_barrier.is_none());
+
+    let image_barrier = &barrier.image_memory_barriers[0];
+    assert_eq!(image_barrier.src_access_mask, core::AccessFlags::TRANSFER_WRITE);
+    assert_eq!(image_barrier.dst_access_mask, core::AccessFlags::SHADER_READ);
+    assert_eq!(image_barrier.old_layout, core::ImageLayout::TransferDstOptimal);
+    assert_eq!(image_barrier.new_layout, core::ImageLayout::ShaderReadOnlyOptimal);
+
+    let mut discard = ImageBarrier::new(image.clone(), color_range(), &[AccessType::Present], &[AccessType::ColorAttachmentWrite]);
+    discard.discard_contents = true;
+    let barrier = PipelineBarrier::new(None, &[], &[discard]);
+    assert_eq!(barrier.src_stage_mask, core::PipelineStageFlags::TOP_OF_PIPE);
+    assert_eq!(barrier.dst_stage_mask, core::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT);
+
+    let image_barrier = &barrier.image_memory_barriers[0];
+    assert_eq!(image_barrier.src_access_mask, core::AccessFlags::empty());
+    assert_eq!(image_barrier.dst_access_mask, core::AccessFlags::COLOR_ATTACHMENT_WRITE);
+    assert_eq!(image_barrier.old_layout, core::ImageLayout::Undefined);
+    assert_eq!(image_barrier.new_layout, core::ImageLayout::ColorAttachmentOptimal);
+
+    let mut general = ImageBarrier::new(image, color_range(), &[AccessType::ComputeShaderWrite], &[AccessType::ComputeShaderReadSampledImageOrUniformTexelBuffer]);
+    general.next_layout = ImageLayoutHint::General;
+    let barrier = PipelineBarrier::new(None, &[], &[general]);
+    assert_eq!(barrier.image_memory_barriers[0].old_layout, core::ImageLayout::General);
+    assert_eq!(barrier.image_memory_barriers[0].new_layout, core::ImageLayout::General);
+}
+
+#[test]
+fn read_after_read_is_execution_dependency() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let buffer = device.create_buffer(&core::BufferCreateInfo::builder(64, core::BufferUsageFlags::UNIFORM_BUFFER).build(), None).unwrap();
+
+    let barrier = PipelineBarrier::new(None, &[BufferBarrier::new(buffer, &[AccessType::VertexShaderReadUniformBuffer], &[AccessType::TransferWrite])], &[]);
+    assert_eq!(barrier.src_stage_mask, core::PipelineStageFlags::VERTEX_SHADER);
+    assert_eq!(barrier.dst_stage_mask, core::PipelineStageFlags::TRANSFER);
+    assert_eq!(barrier.buffer_memory_barriers[0].src_access_mask, core::AccessFlags::empty());
+    assert_eq!(barrier.buffer_memory_barriers[0].dst_access_mask, core::AccessFlags::empty());
+
+    let global = GlobalBarrier::new(&[AccessType::VertexShaderReadUniformBuffer], &[AccessType::ComputeShaderReadOther]);
+    assert!(PipelineBarrier::new(Some(&global), &[], &[]).memory_barrier.is_none());
+
+    let global = GlobalBarrier::new(&[AccessType::ComputeShaderWrite], &[AccessType::IndirectBuffer]);
+    let barrier = PipelineBarrier::new(Some(&global), &[], &[]);
+    assert_eq!(barrier.memory_barrier.unwrap().dst_access_mask, core::AccessFlags::INDIRECT_COMMAND_READ);
+    assert_eq!(barrier.dst_stage_mask, core::PipelineStageFlags::DRAW_INDIRECT);
+}
+
+#[test]
+#[should_panic]
+fn conflicting_layouts() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+
+    PipelineBarrier::new(None, &[], &[ImageBarrier::new(image, color_range(), &[AccessType::TransferWrite], &[AccessType::TransferRead, AccessType::ColorAttachmentRead])]);
+}
+
+#[test]
+fn record_barrier() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+
+    let command_pool = device.create_command_pool(&core::CommandPoolCreateInfo::default(), None).unwrap();
+    let allocate_info = core::CommandBufferAllocateInfo {
+        command_pool: command_pool.clone(),
+        level: core::CommandBufferLevel::Primary,
+        command_buffer_count: 1,
+        chain: None,
+    };
+    let command_buffer = core::CommandPool::allocate_command_buffers(&allocate_info).unwrap().remove(0);
+
+    command_buffer.barrier(None, &[], &[ImageBarrier::new(image, color_range(), &[AccessType::None], &[AccessType::TransferWrite])]);
+
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers.len(), 1);
+    assert_eq!(barriers[0].src_stage_mask, vks::vk::VK_PIPELINE_STAGE_TOP_OF_PIPE_BIT);
+    assert_eq!(barriers[0].dst_stage_mask, vks::vk::VK_PIPELINE_STAGE_TRANSFER_BIT);
+    assert_eq!(barriers[0].buffer_barrier_count, 0);
+    assert_eq!(barriers[0].image_barriers[0].old_layout, vks::vk::VK_IMAGE_LAYOUT_UNDEFINED);
+    assert_eq!(barriers[0].image_barriers[0].new_layout, vks::vk::VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL);
+    assert_eq!(barriers[0].image_barriers[0].dst_access_mask, vks::vk::VK_ACCESS_TRANSFER_WRITE_BIT);
+}
@@ -0,0 +1,275 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::VulkanObject;
+use dacite::core;
+use dacite::sync::AccessType;
+use dacite::upload::{UploadError, UploadManager};
+
+fn color_layers() -> core::ImageSubresourceLayers {
+    core::ImageSubresourceLayers {
+        aspect_mask: core::ImageAspectFlags::COLOR,
+        mip_level: 0,
+        base_array_layer: 0,
+        layer_count: 1,
+    }
+}
+
+fn staging_contents(upload: &UploadManager) -> Vec<u8> {
+    mock::memory_contents(upload.staging_buffer().memory().unwrap().id())
+}
+
+#[test]
+fn upload_buffer() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::new(&device, device.get_queue(0, 0), 0, 256).unwrap();
+    let buffer = mock::create_buffer(&device, 64);
+
+    upload.upload_buffer(&[1, 2, 3], &buffer, 16, AccessType::VertexBuffer).unwrap();
+    upload.upload_buffer(&[4, 5, 6, 7], &buffer, 32, AccessType::VertexBuffer).unwrap();
+    assert_eq!(&staging_contents(&upload)[..8], &[1, 2, 3, 0, 4, 5, 6, 7]);
+
+    let copies = mock::buffer_copies();
+    assert_eq!(copies.len(), 2);
+    assert_eq!((copies[0].src_offset, copies[0].dst_offset, copies[0].size), (0, 16, 3));
+    assert_eq!((copies[1].src_offset, copies[1].dst_offset, copies[1].size), (4, 32, 4));
+    assert_eq!(copies[1].dst_buffer, buffer.id());
+
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers.len(), 2);
+    assert_eq!(barriers[0].src_stage_mask, vks::vk::VK_PIPELINE_STAGE_TRANSFER_BIT);
+    assert_eq!(barriers[0].dst_stage_mask, vks::vk::VK_PIPELINE_STAGE_VERTEX_INPUT_BIT);
+    assert_eq!(barriers[0].buffer_barriers[0].dst_access_mask, vks::vk::VK_ACCESS_VERTEX_ATTRIBUTE_READ_BIT);
+
+    assert_eq!(mock::call_count("vkQueueSubmit"), 0);
+    assert!(upload.flush().unwrap().is_none());
+    assert_eq!(mock::call_count("vkQueueSubmit"), 1);
+    assert_eq!(upload.in_flight_count(), 1);
+
+    upload.wait_idle().unwrap();
+    assert_eq!(upload.in_flight_count(), 0);
+}
+
+#[test]
+fn upload_image() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::new(&device, device.get_queue(0, 0), 0, 1024).unwrap();
+    let buffer = mock::create_buffer(&device, 64);
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+
+    upload.upload_buffer(&[1], &buffer, 0, AccessType::VertexBuffer).unwrap();
+    upload.upload_image(&[7; 64], &image, color_layers(), core::Offset3D::zero(), core::Extent3D::new(4, 4, 1), AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer).unwrap();
+
+    // Image data is aligned to the texel block size of all formats.
+    let copies = mock::buffer_image_copies();
+    assert_eq!(copies.len(), 1);
+    assert_eq!(copies[0].buffer_offset, 96);
+    assert_eq!(copies[0].layout, vks::vk::VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL);
+    assert_eq!(&staging_contents(&upload)[96..160], &[7; 64][..]);
+
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers.len(), 3);
+    assert_eq!(barriers[1].src_stage_mask, vks::vk::VK_PIPELINE_STAGE_TOP_OF_PIPE_BIT);
+    assert_eq!(barriers[1].image_barriers[0].old_layout, vks::vk::VK_IMAGE_LAYOUT_UNDEFINED);
+    assert_eq!(barriers[1].image_barriers[0].new_layout, vks::vk::VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL);
+    assert_eq!(barriers[2].dst_stage_mask, vks::vk::VK_PIPELINE_STAGE_FRAGMENT_SHADER_BIT);
+    assert_eq!(barriers[2].image_barriers[0].new_layout, vks::vk::VK_IMAGE_LAYOUT_SHADER_READ_ONLY_OPTIMAL);
+}
+
+#[test]
+fn upload_image_from_tracked_layout() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::new(&device, device.get_queue(0, 0), 0, 1024).unwrap();
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+    image.enable_layout_tracking(core::ImageLayout::General).unwrap();
+
+    upload.upload_image(&[0; 64], &image, color_layers(), core::Offset3D::zero(), core::Extent3D::new(4, 4, 1), AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer).unwrap();
+    assert_eq!(image.layout(0, 0), Some(core::ImageLayout::ShaderReadOnlyOptimal));
+
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers[0].image_barriers[0].old_layout, vks::vk::VK_IMAGE_LAYOUT_GENERAL);
+    assert_eq!(barriers[0].image_barriers[0].src_access_mask, vks::vk::VK_ACCESS_MEMORY_WRITE_BIT);
+}
+
+#[test]
+fn upload_image_too_large() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::new(&device, device.get_queue(0, 0), 0, 32).unwrap();
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+
+    let res = upload.upload_image(&[0; 64], &image, color_layers(), core::Offset3D::zero(), core::Extent3D::new(4, 4, 1), AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer);
+    match res {
+        Err(UploadError::TooLarge { size, capacity }) => assert_eq!((size, capacity), (64, 32)),
+        _ => panic!("expected UploadError::TooLarge"),
+    }
+}
+
+#[test]
+fn staging_ring_wraps() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::new(&device, device.get_queue(0, 0), 0, 16).unwrap();
+    let buffer = mock::create_buffer(&device, 64);
+
+    // Larger uploads are split into chunks of the staging buffer size.
+    upload.upload_buffer(&[1; 40], &buffer, 0, AccessType::VertexBuffer).unwrap();
+    let copies = mock::buffer_copies();
+    let chunks: Vec<_> = copies.iter().map(|c| (c.src_offset, c.dst_offset, c.size)).collect();
+    assert_eq!(chunks, vec![(0, 0, 16), (0, 16, 16), (0, 32, 8)]);
+
+    // Running out of space submits the recorded commands and waits for them.
+    assert_eq!(mock::call_count("vkQueueSubmit"), 2);
+    upload.upload_buffer(&[2; 12], &buffer, 0, AccessType::VertexBuffer).unwrap();
+    assert_eq!(mock::call_count("vkQueueSubmit"), 3);
+    assert_eq!(mock::buffer_copies()[3].src_offset, 0);
+
+    upload.flush().unwrap();
+    assert_eq!(mock::call_count("vkQueueSubmit"), 4);
+    assert_eq!(mock::call_count("vkAllocateCommandBuffers"), 1);
+}
+
+#[test]
+fn retire_error_keeps_submission_in_flight() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::new(&device, device.get_queue(0, 0), 0, 16).unwrap();
+    let buffer = mock::create_buffer(&device, 64);
+
+    upload.upload_buffer(&[1; 12], &buffer, 0, AccessType::VertexBuffer).unwrap();
+    upload.flush().unwrap();
+    assert_eq!(upload.in_flight_count(), 1);
+
+    mock::inject_result("vkResetCommandBuffer", vks::vk::VK_ERROR_OUT_OF_HOST_MEMORY);
+    match upload.upload_buffer(&[2; 12], &buffer, 0, AccessType::VertexBuffer) {
+        Err(UploadError::VulkanError(core::Error::OutOfHostMemory)) => { }
+        res => panic!("expected UploadError::VulkanError, got {:?}", res),
+    }
+    assert_eq!(upload.in_flight_count(), 1);
+
+    upload.upload_buffer(&[3; 12], &buffer, 0, AccessType::VertexBuffer).unwrap();
+    assert_eq!(upload.in_flight_count(), 0);
+    assert_eq!(mock::buffer_copies()[1].src_offset, 0);
+}
+
+#[test]
+fn image_alignment_after_wrap() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::new(&device, device.get_queue(0, 0), 0, 200).unwrap();
+    let buffer = mock::create_buffer(&device, 128);
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+
+    upload.upload_buffer(&[1; 100], &buffer, 0, AccessType::VertexBuffer).unwrap();
+    upload.flush().unwrap();
+    upload.upload_buffer(&[2; 88], &buffer, 0, AccessType::VertexBuffer).unwrap();
+    assert_eq!(mock::buffer_copies()[1].src_offset, 0);
+
+    // The offset in the staging buffer is aligned, not the position in the ring.
+    upload.upload_image(&[3; 64], &image, color_layers(), core::Offset3D::zero(), core::Extent3D::new(4, 4, 1), AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer).unwrap();
+    assert_eq!(mock::buffer_image_copies()[0].buffer_offset, 96);
+}
+
+#[test]
+fn staging_size_too_small() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    match UploadManager::new(&device, device.get_queue(0, 0), 0, 3) {
+        Err(UploadError::StagingSizeTooSmall { size, minimum }) => assert_eq!((size, minimum), (3, 4)),
+        _ => panic!("expected UploadError::StagingSizeTooSmall"),
+    }
+}
+
+#[test]
+fn upload_image_layers_in_different_layouts() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::new(&device, device.get_queue(0, 0), 0, 1024).unwrap();
+    let create_info = core::ImageCreateInfo::builder(core::Format::R8G8B8A8_UNorm, core::Extent3D::new(4, 4, 1), core::ImageUsageFlags::TRANSFER_DST | core::ImageUsageFlags::SAMPLED)
+        .array_layers(3)
+        .build();
+    let image = device.create_image(&create_info, None).unwrap();
+    image.enable_layout_tracking(core::ImageLayout::General).unwrap();
+
+    upload.upload_image(&[0; 64], &image, core::ImageSubresourceLayers { base_array_layer: 1, .. color_layers() }, core::Offset3D::zero(), core::Extent3D::new(4, 4, 1), AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer).unwrap();
+    let layers = core::ImageSubresourceLayers { layer_count: 3, .. color_layers() };
+    upload.upload_image(&[0; 192], &image, layers, core::Offset3D::zero(), core::Extent3D::new(4, 4, 1), AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer).unwrap();
+
+    let barriers = mock::pipeline_barriers();
+    let layouts: Vec<_> = barriers[2].image_barriers.iter().map(|b| (b.old_layout, b.base_array_layer, b.layer_count)).collect();
+    assert_eq!(layouts, vec![
+        (vks::vk::VK_IMAGE_LAYOUT_GENERAL, 0, 1),
+        (vks::vk::VK_IMAGE_LAYOUT_SHADER_READ_ONLY_OPTIMAL, 1, 1),
+        (vks::vk::VK_IMAGE_LAYOUT_GENERAL, 2, 1),
+    ]);
+    assert_eq!(barriers[2].src_stage_mask, vks::vk::VK_PIPELINE_STAGE_ALL_COMMANDS_BIT);
+    assert_eq!(image.layout(0, 2), Some(core::ImageLayout::ShaderReadOnlyOptimal));
+}
+
+#[test]
+fn dedicated_transfer_queue() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+    let mut upload = UploadManager::with_transfer_queue(&device, device.get_queue(1, 0), 1, 0, 1024).unwrap();
+    let buffer = mock::create_buffer(&device, 64);
+    let image = mock::create_image(&device, core::Format::R8G8B8A8_UNorm, 1, 1);
+
+    upload.upload_buffer(&[1; 8], &buffer, 0, AccessType::VertexBuffer).unwrap();
+    upload.upload_image(&[2; 64], &image, color_layers(), core::Offset3D::zero(), core::Extent3D::new(4, 4, 1), AccessType::FragmentShaderReadSampledImageOrUniformTexelBuffer).unwrap();
+
+    // Ownership is released on the transfer queue.
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers.len(), 3);
+    let release = &barriers[0].buffer_barriers[0];
+    assert_eq!((release.src_queue_family_index, release.dst_queue_family_index), (1, 0));
+    assert_eq!(release.dst_access_mask, 0);
+    let release = &barriers[2].image_barriers[0];
+    assert_eq!((release.src_queue_family_index, release.dst_queue_family_index), (1, 0));
+    assert_eq!(release.new_layout, vks::vk::VK_IMAGE_LAYOUT_SHADER_READ_ONLY_OPTIMAL);
+
+    let semaphore = upload.flush().unwrap().unwrap();
+    assert_eq!(semaphore.wait_dst_stage_mask, core::PipelineStageFlags::VERTEX_INPUT | core::PipelineStageFlags::FRAGMENT_SHADER);
+    assert!(upload.flush().unwrap().is_none());
+
+    // And acquired on the queue, on which the resources are used.
+    let command_pool = device.create_command_pool(&core::CommandPoolCreateInfo::default(), None).unwrap();
+    let command_buffer = core::CommandPool::allocate_command_buffers(&core::CommandBufferAllocateInfo {
+        command_pool: command_pool,
+        level: core::CommandBufferLevel::Primary,
+        command_buffer_count: 1,
+        chain: None,
+    }).unwrap().remove(0);
+
+    upload.record_acquire_barriers(&command_buffer);
+    let barriers = mock::pipeline_barriers();
+    assert_eq!(barriers.len(), 4);
+    assert_eq!(barriers[3].dst_stage_mask, vks::vk::VK_PIPELINE_STAGE_VERTEX_INPUT_BIT | vks::vk::VK_PIPELINE_STAGE_FRAGMENT_SHADER_BIT);
+    let acquire = &barriers[3].buffer_barriers[0];
+    assert_eq!((acquire.src_queue_family_index, acquire.dst_queue_family_index, acquire.src_access_mask), (1, 0, 0));
+    let acquire = &barriers[3].image_barriers[0];
+    assert_eq!((acquire.src_queue_family_index, acquire.dst_queue_family_index, acquire.src_access_mask), (1, 0, 0));
+
+    upload.record_acquire_barriers(&command_buffer);
+    assert_eq!(mock::pipeline_barriers().len(), 4);
+}
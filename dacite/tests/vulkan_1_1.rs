@@ -0,0 +1,249 @@
+// Copyright (c) 2017, Dennis Hamester <dennis.hamester@startmail.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+// FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+
+extern crate dacite;
+extern crate libc;
+extern crate vks;
+
+mod mock;
+
+use dacite::core;
+
+fn use_vulkan_1_1() {
+    mock::configure(|c| c.api_version = vks::vk_make_version(1, 1, 70));
+}
+
+fn create_instance_1_1() -> core::Instance {
+    let create_info = core::InstanceCreateInfo {
+        flags: core::InstanceCreateFlags::empty(),
+        application_info: Some(core::ApplicationInfo {
+            application_name: None,
+            application_version: 0,
+            engine_name: None,
+            engine_version: 0,
+            api_version: Some(dacite::DACITE_API_VERSION_1_1),
+            chain: None,
+        }),
+        enabled_layers: vec![],
+        enabled_extensions: core::InstanceExtensions::new(),
+        chain: None,
+    };
+
+    core::Instance::create(&mock::loader(), &create_info, None).unwrap()
+}
+
+#[test]
+fn instance_version_on_vulkan_1_0() {
+    let version = core::Instance::enumerate_instance_version(&mock::loader()).unwrap();
+    assert_eq!(version, dacite::DACITE_API_VERSION_1_0);
+    assert_eq!(mock::call_count("vkEnumerateInstanceVersion"), 0);
+}
+
+#[test]
+fn instance_version() {
+    use_vulkan_1_1();
+
+    let version = core::Instance::enumerate_instance_version(&mock::loader()).unwrap();
+    assert_eq!(version, core::Version { major: 1, minor: 1, patch: 70 });
+
+    mock::inject_result("vkEnumerateInstanceVersion", vks::vk::VK_ERROR_OUT_OF_HOST_MEMORY);
+    let res = core::Instance::enumerate_instance_version(&mock::loader());
+    assert_eq!(res.unwrap_err(), core::EarlyInstanceError::VulkanError(core::Error::OutOfHostMemory));
+}
+
+#[test]
+fn physical_device_groups() {
+    use_vulkan_1_1();
+    mock::configure(|c| c.physical_device_count = 2);
+    let instance = create_instance_1_1();
+
+    let groups: Vec<_> = instance.enumerate_physical_device_groups(None).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].physical_devices, instance.enumerate_physical_devices().unwrap());
+    assert!(!groups[0].subset_allocation);
+}
+
+#[test]
+fn physical_device_groups_incomplete() {
+    use_vulkan_1_1();
+    let instance = create_instance_1_1();
+
+    // The count of the first call is left at 0, so the second call returns VK_INCOMPLETE.
+    mock::inject_result("vkEnumeratePhysicalDeviceGroups", vks::vk::VK_SUCCESS);
+    let groups: Vec<_> = instance.enumerate_physical_device_groups(None).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(mock::call_count("vkEnumeratePhysicalDeviceGroups"), 4);
+}
+
+#[test]
+fn functions_on_vulkan_1_0() {
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let res: Result<Vec<_>, _> = instance.enumerate_physical_device_groups(None);
+    assert_eq!(res.unwrap_err(), core::Error::FeatureNotPresent);
+    assert_eq!(physical_device.get_features2(None).unwrap_err(), core::Error::FeatureNotPresent);
+    assert_eq!(physical_device.get_properties2(None).unwrap_err(), core::Error::FeatureNotPresent);
+    assert_eq!(device.get_group_peer_memory_features(0, 0, 0).unwrap_err(), core::Error::FeatureNotPresent);
+    assert_eq!(device.bind_buffer_memory2(&[]).unwrap_err(), core::Error::FeatureNotPresent);
+
+    let command_pool = device.create_command_pool(&core::CommandPoolCreateInfo {
+        flags: core::CommandPoolCreateFlags::empty(),
+        queue_family_index: 0,
+        chain: None,
+    }, None).unwrap();
+    assert_eq!(command_pool.trim(core::CommandPoolTrimFlags::empty()).unwrap_err(), core::Error::FeatureNotPresent);
+    assert_eq!(mock::call_count("vkTrimCommandPool"), 0);
+}
+
+#[test]
+fn functions_on_vulkan_1_0_instance() {
+    use_vulkan_1_1();
+    let instance = mock::create_instance(core::InstanceExtensions::new());
+    assert_eq!(instance.get_api_version(), dacite::DACITE_API_VERSION_1_0);
+    let (physical_device, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let res: Result<Vec<_>, _> = instance.enumerate_physical_device_groups(None);
+    assert_eq!(res.unwrap_err(), core::Error::FeatureNotPresent);
+    assert_eq!(physical_device.get_features2(None).unwrap_err(), core::Error::FeatureNotPresent);
+    assert_eq!(device.bind_buffer_memory2(&[]).unwrap_err(), core::Error::FeatureNotPresent);
+    assert_eq!(mock::call_count("vkEnumeratePhysicalDeviceGroups"), 0);
+    assert_eq!(mock::call_count("vkGetPhysicalDeviceFeatures2"), 0);
+    assert_eq!(mock::call_count("vkBindBufferMemory2"), 0);
+}
+
+#[test]
+fn physical_device_api_version_is_cached() {
+    use_vulkan_1_1();
+    let instance = create_instance_1_1();
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+    let calls = mock::call_count("vkGetPhysicalDeviceProperties");
+
+    physical_device.get_features2(None).unwrap();
+    physical_device.get_format_properties2(core::Format::R8G8B8A8_UNorm, None).unwrap();
+    physical_device.get_features2(None).unwrap();
+    assert_eq!(mock::call_count("vkGetPhysicalDeviceProperties"), calls);
+}
+
+#[test]
+fn properties2_chain() {
+    use_vulkan_1_1();
+    mock::configure(|c| c.protected_memory = true);
+    let instance = create_instance_1_1();
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let mut chain_query = core::PhysicalDeviceProperties2ChainQuery::new();
+    chain_query
+        .add_physical_device_subgroup_properties()
+        .add_physical_device_protected_memory_properties();
+    let properties = physical_device.get_properties2(Some(&chain_query)).unwrap();
+    assert_eq!(properties.properties.device_name, "dacite mock device");
+
+    let chain = properties.chain.unwrap();
+    let subgroup_properties = chain.get_physical_device_subgroup_properties().unwrap();
+    assert_eq!(subgroup_properties.subgroup_size, 32);
+    assert_eq!(subgroup_properties.supported_stages, core::ShaderStageFlags::COMPUTE);
+    assert!(subgroup_properties.supported_operations.contains(core::SubgroupFeatureFlags::BALLOT));
+    assert!(chain.get_physical_device_protected_memory_properties().unwrap().protected_no_fault);
+
+    let properties = physical_device.get_properties2(None).unwrap();
+    assert!(properties.chain.is_none());
+}
+
+#[test]
+fn features2_chain() {
+    use_vulkan_1_1();
+    mock::configure(|c| c.protected_memory = true);
+    let instance = create_instance_1_1();
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let mut chain_query = core::PhysicalDeviceFeatures2ChainQuery::new();
+    chain_query.add_physical_device_protected_memory_features();
+    let features = physical_device.get_features2(Some(&chain_query)).unwrap();
+    assert!(features.chain.unwrap().get_physical_device_protected_memory_features().unwrap().protected_memory);
+}
+
+#[test]
+fn queue_family_and_memory_properties2() {
+    use_vulkan_1_1();
+    let instance = create_instance_1_1();
+    let physical_device = instance.enumerate_physical_devices().unwrap().remove(0);
+
+    let queue_families: Vec<_> = physical_device.get_queue_family_properties2(None).unwrap();
+    assert_eq!(queue_families.len(), 2);
+    assert_eq!(queue_families[1].queue_family_properties.queue_flags, core::QueueFlags::TRANSFER);
+
+    let memory_properties = physical_device.get_memory_properties2(None).unwrap();
+    assert_eq!(memory_properties.memory_properties, physical_device.get_memory_properties());
+}
+
+#[test]
+fn device_functions() {
+    use_vulkan_1_1();
+    let instance = create_instance_1_1();
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let queue = device.get_queue2(&core::DeviceQueueInfo2 {
+        flags: core::DeviceQueueCreateFlags::empty(),
+        queue_family_index: 0,
+        queue_index: 0,
+        chain: None,
+    }).unwrap();
+    assert_eq!(queue, device.get_queue(0, 0));
+    assert_eq!(mock::call_count("vkGetDeviceQueue2"), 1);
+
+    let command_pool = device.create_command_pool(&core::CommandPoolCreateInfo {
+        flags: core::CommandPoolCreateFlags::empty(),
+        queue_family_index: 0,
+        chain: None,
+    }, None).unwrap();
+    command_pool.trim(core::CommandPoolTrimFlags::empty()).unwrap();
+    assert_eq!(mock::call_count("vkTrimCommandPool"), 1);
+
+    let features = device.get_group_peer_memory_features(0, 0, 0).unwrap();
+    assert!(features.contains(core::PeerMemoryFeatureFlags::COPY_SRC | core::PeerMemoryFeatureFlags::GENERIC_DST));
+}
+
+#[test]
+fn bind_buffer_memory2() {
+    use_vulkan_1_1();
+    let instance = create_instance_1_1();
+    let (_, device) = mock::create_device(&instance, core::DeviceExtensions::new());
+
+    let buffer = device.create_buffer(&core::BufferCreateInfo {
+        flags: core::BufferCreateFlags::empty(),
+        size: 1024,
+        usage: core::BufferUsageFlags::VERTEX_BUFFER,
+        sharing_mode: core::SharingMode::Exclusive,
+        queue_family_indices: vec![],
+        chain: None,
+    }, None).unwrap();
+
+    let memory = device.allocate_memory(&core::MemoryAllocateInfo {
+        allocation_size: 1024,
+        memory_type_index: 0,
+        chain: None,
+    }, None).unwrap();
+
+    let bind_info = core::BindBufferMemoryInfo {
+        buffer: buffer,
+        memory: memory,
+        memory_offset: 0,
+        chain: None,
+    };
+    device.bind_buffer_memory2(&[bind_info.clone()]).unwrap();
+
+    mock::inject_result("vkBindBufferMemory2", vks::vk::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    assert_eq!(device.bind_buffer_memory2(&[bind_info]).unwrap_err(), core::Error::OutOfDeviceMemory);
+}
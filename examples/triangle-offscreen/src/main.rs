@@ -26,7 +26,13 @@ struct BufferSettings {
     memory: dacite::core::DeviceMemory,
 }
 
-fn create_instance() -> Result<dacite::core::Instance, ()> {
+fn create_loader() -> Result<dacite::core::Loader, ()> {
+    dacite::core::Loader::new().map_err(|e| {
+        println!("Failed to load Vulkan library ({})", e);
+    })
+}
+
+fn create_instance(loader: &dacite::core::Loader) -> Result<dacite::core::Instance, ()> {
     let application_info = dacite::core::ApplicationInfo {
         application_name: Some("dacite triangle-offscreen example".to_owned()),
         application_version: 0,
@@ -44,7 +50,7 @@ fn create_instance() -> Result<dacite::core::Instance, ()> {
         chain: None,
     };
 
-    dacite::core::Instance::create(&create_info, None).map_err(|e| {
+    dacite::core::Instance::create(loader, &create_info, None).map_err(|e| {
         println!("Failed to create instance ({})", e);
     })
 }
@@ -143,21 +149,7 @@ fn find_memory_type(types: &[dacite::core::MemoryType], type_bits: u32, properti
 }
 
 fn create_framebuffer(device: &dacite::core::Device, render_pass: &dacite::core::RenderPass, format: dacite::core::Format, extent: &dacite::core::Extent2D, memory_types: &[dacite::core::MemoryType]) -> Result<FramebufferSettings, ()> {
-    let image_create_info = dacite::core::ImageCreateInfo {
-        flags: dacite::core::ImageCreateFlags::empty(),
-        image_type: dacite::core::ImageType::Type2D,
-        format: format,
-        extent: dacite::core::Extent3D::from_2d(extent, 1),
-        mip_levels: 1,
-        array_layers: 1,
-        samples: dacite::core::SampleCountFlagBits::SampleCount1,
-        tiling: dacite::core::ImageTiling::Optimal,
-        usage: dacite::core::ImageUsageFlags::COLOR_ATTACHMENT | dacite::core::ImageUsageFlags::TRANSFER_SRC,
-        sharing_mode: dacite::core::SharingMode::Exclusive,
-        queue_family_indices: vec![],
-        initial_layout: dacite::core::ImageLayout::Undefined,
-        chain: None,
-    };
+    let image_create_info = dacite::core::ImageCreateInfo::builder(format, dacite::core::Extent3D::from_2d(extent, 1), dacite::core::ImageUsageFlags::COLOR_ATTACHMENT | dacite::core::ImageUsageFlags::TRANSFER_SRC).build();
 
     let image = device.create_image(&image_create_info, None).map_err(|e| {
         println!("Failed to create image ({})", e);
@@ -183,35 +175,15 @@ fn create_framebuffer(device: &dacite::core::Device, render_pass: &dacite::core:
         println!("Failed to bind image memory ({})", e);
     })?;
 
-    let view_create_info = dacite::core::ImageViewCreateInfo {
-        flags: dacite::core::ImageViewCreateFlags::empty(),
-        image: image.clone(),
-        view_type: dacite::core::ImageViewType::Type2D,
-        format: format,
-        components: dacite::core::ComponentMapping::identity(),
-        subresource_range: dacite::core::ImageSubresourceRange {
-            aspect_mask: dacite::core::ImageAspectFlags::COLOR,
-            base_mip_level: 0,
-            level_count: dacite::core::OptionalMipLevels::MipLevels(1),
-            base_array_layer: 0,
-            layer_count: dacite::core::OptionalArrayLayers::ArrayLayers(1),
-        },
-        chain: None,
-    };
+    let view_create_info = dacite::core::ImageViewCreateInfo::for_image(image.clone(), dacite::core::ImageViewType::Type2D, format);
 
     let view = device.create_image_view(&view_create_info, None).map_err(|e| {
         println!("Failed to create image view ({})", e);
     })?;
 
-    let create_info = dacite::core::FramebufferCreateInfo {
-        flags: dacite::core::FramebufferCreateFlags::empty(),
-        render_pass: render_pass.clone(),
-        attachments: vec![view.clone()],
-        width: extent.width,
-        height: extent.height,
-        layers: 1,
-        chain: None,
-    };
+    let create_info = dacite::core::FramebufferCreateInfo::builder(render_pass.clone(), extent.width, extent.height)
+        .attachments(vec![view.clone()])
+        .build();
 
     let framebuffer = device.create_framebuffer(&create_info, None).map_err(|e| {
         println!("Failed to create framebuffer ({})", e);
@@ -226,14 +198,7 @@ fn create_framebuffer(device: &dacite::core::Device, render_pass: &dacite::core:
 }
 
 fn create_buffer(device: &dacite::core::Device, extent: &dacite::core::Extent2D, memory_types: &[dacite::core::MemoryType]) -> Result<BufferSettings, ()> {
-    let create_info = dacite::core::BufferCreateInfo {
-        flags: dacite::core::BufferCreateFlags::empty(),
-        size: 4 * u64::from(extent.width) * u64::from(extent.height),
-        usage: dacite::core::BufferUsageFlags::TRANSFER_DST,
-        sharing_mode: dacite::core::SharingMode::Exclusive,
-        queue_family_indices: vec![],
-        chain: None,
-    };
+    let create_info = dacite::core::BufferCreateInfo::builder(4 * u64::from(extent.width) * u64::from(extent.height), dacite::core::BufferUsageFlags::TRANSFER_DST).build();
 
     let buffer = device.create_buffer(&create_info, None).map_err(|e| {
         println!("Failed to create buffer ({})", e);
@@ -293,11 +258,9 @@ fn create_vertex_shader(device: &dacite::core::Device) -> Result<dacite::core::S
         }
     "#};
 
-    let create_info = dacite::core::ShaderModuleCreateInfo {
-        flags: dacite::core::ShaderModuleCreateFlags::empty(),
-        code: vertex_shader_bytes.to_vec(),
-        chain: None,
-    };
+    let create_info = dacite::core::ShaderModuleCreateInfo::from_bytes(vertex_shader_bytes).map_err(|e| {
+        println!("Invalid vertex shader code ({})", e);
+    })?;
 
     device.create_shader_module(&create_info, None).map_err(|e| {
         println!("Failed to create vertex shader module ({})", e);
@@ -317,11 +280,9 @@ fn create_fragment_shader(device: &dacite::core::Device) -> Result<dacite::core:
         }
     "#};
 
-    let create_info = dacite::core::ShaderModuleCreateInfo {
-        flags: dacite::core::ShaderModuleCreateFlags::empty(),
-        code: fragment_shader_bytes.to_vec(),
-        chain: None,
-    };
+    let create_info = dacite::core::ShaderModuleCreateInfo::from_bytes(fragment_shader_bytes).map_err(|e| {
+        println!("Invalid fragment shader code ({})", e);
+    })?;
 
     device.create_shader_module(&create_info, None).map_err(|e| {
         println!("Failed to create fragment shader module ({})", e);
@@ -329,12 +290,7 @@ fn create_fragment_shader(device: &dacite::core::Device) -> Result<dacite::core:
 }
 
 fn create_pipeline_layout(device: &dacite::core::Device) -> Result<dacite::core::PipelineLayout, ()> {
-    let create_info = dacite::core::PipelineLayoutCreateInfo {
-        flags: dacite::core::PipelineLayoutCreateFlags::empty(),
-        set_layouts: vec![],
-        push_constant_ranges: vec![],
-        chain: None,
-    };
+    let create_info = dacite::core::PipelineLayoutCreateInfo::default();
 
     device.create_pipeline_layout(&create_info, None).map_err(|e| {
         println!("Failed to create pipeline layout ({})", e);
@@ -346,108 +302,23 @@ fn create_pipeline(device: &dacite::core::Device, render_pass: &dacite::core::Re
     let fragment_shader = create_fragment_shader(device)?;
     let layout = create_pipeline_layout(device)?;
 
-    let create_infos = vec![dacite::core::GraphicsPipelineCreateInfo {
-        flags: dacite::core::PipelineCreateFlags::empty(),
-        stages: vec![
-            dacite::core::PipelineShaderStageCreateInfo {
-                flags: dacite::core::PipelineShaderStageCreateFlags::empty(),
-                stage: dacite::core::ShaderStageFlagBits::Vertex,
-                module: vertex_shader.clone(),
-                name: "main".to_owned(),
-                specialization_info: None,
-                chain: None,
-            },
-            dacite::core::PipelineShaderStageCreateInfo {
-                flags: dacite::core::PipelineShaderStageCreateFlags::empty(),
-                stage: dacite::core::ShaderStageFlagBits::Fragment,
-                module: fragment_shader.clone(),
-                name: "main".to_owned(),
-                specialization_info: None,
-                chain: None,
-            },
-        ],
-        vertex_input_state: dacite::core::PipelineVertexInputStateCreateInfo {
-            flags: dacite::core::PipelineVertexInputStateCreateFlags::empty(),
-            vertex_binding_descriptions: vec![],
-            vertex_attribute_descriptions: vec![],
-            chain: None,
-        },
-        input_assembly_state: dacite::core::PipelineInputAssemblyStateCreateInfo {
-            flags: dacite::core::PipelineInputAssemblyStateCreateFlags::empty(),
-            topology: dacite::core::PrimitiveTopology::TriangleList,
-            primitive_restart_enable: false,
-            chain: None,
-        },
-        tessellation_state: None,
-        viewport_state: Some(dacite::core::PipelineViewportStateCreateInfo {
-            flags: dacite::core::PipelineViewportStateCreateFlags::empty(),
-            viewports: vec![dacite::core::Viewport {
-                x: 0.0,
-                y: 0.0,
-                width: extent.width as f32,
-                height: extent.height as f32,
-                min_depth: 0.0,
-                max_depth: 1.0,
-            }],
-            scissors: vec![dacite::core::Rect2D::new(dacite::core::Offset2D::zero(), *extent)],
-            chain: None,
-        }),
-        rasterization_state: dacite::core::PipelineRasterizationStateCreateInfo {
-            flags: dacite::core::PipelineRasterizationStateCreateFlags::empty(),
-            depth_clamp_enable: false,
-            rasterizer_discard_enable: false,
-            polygon_mode: dacite::core::PolygonMode::Fill,
-            cull_mode: dacite::core::CullModeFlags::NONE,
-            front_face: dacite::core::FrontFace::Clockwise,
-            depth_bias_enable: false,
-            depth_bias_constant_factor: 0.0,
-            depth_bias_clamp: 0.0,
-            depth_bias_slope_factor: 0.0,
-            line_width: 1.0,
-            chain: None,
-        },
-        multisample_state: Some(dacite::core::PipelineMultisampleStateCreateInfo {
-            flags: dacite::core::PipelineMultisampleStateCreateFlags::empty(),
-            rasterization_samples: dacite::core::SampleCountFlagBits::SampleCount1,
-            sample_shading_enable: false,
-            min_sample_shading: 1.0,
-            sample_mask: vec![],
-            alpha_to_coverage_enable: false,
-            alpha_to_one_enable: false,
-            chain: None,
-        }),
-        depth_stencil_state: None,
-        color_blend_state: Some(dacite::core::PipelineColorBlendStateCreateInfo {
-            flags: dacite::core::PipelineColorBlendStateCreateFlags::empty(),
-            logic_op_enable: false,
-            logic_op: dacite::core::LogicOp::Copy,
-            attachments: vec![dacite::core::PipelineColorBlendAttachmentState {
-                blend_enable: false,
-                src_color_blend_factor: dacite::core::BlendFactor::One,
-                dst_color_blend_factor: dacite::core::BlendFactor::Zero,
-                color_blend_op: dacite::core::BlendOp::Add,
-                src_alpha_blend_factor: dacite::core::BlendFactor::One,
-                dst_alpha_blend_factor: dacite::core::BlendFactor::Zero,
-                alpha_blend_op: dacite::core::BlendOp::Add,
-                color_write_mask: dacite::core::ColorComponentFlags::R | dacite::core::ColorComponentFlags::G | dacite::core::ColorComponentFlags::B,
-            }],
-            blend_constants: [0.0, 0.0, 0.0, 0.0],
-            chain: None,
-        }),
-        dynamic_state: None,
-        layout: layout.clone(),
-        render_pass: render_pass.clone(),
-        subpass: 0,
-        base_pipeline: None,
-        base_pipeline_index: None,
-        chain: None,
-    }];
-
-    let pipelines = device.create_graphics_pipelines(None, &create_infos, None).map_err(|(e, _)| {
-        println!("Failed to create pipeline ({})", e);
-    })?;
-
-    Ok(pipelines[0].clone())
+    dacite::core::GraphicsPipelineBuilder::new(layout, render_pass.clone(), 0)
+        .vertex_shader(vertex_shader, "main")
+        .fragment_shader(fragment_shader, "main")
+        .viewport(dacite::core::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: extent.width as f32,
+            height: extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })
+        .scissor(dacite::core::Rect2D::new(dacite::core::Offset2D::zero(), *extent))
+        .front_face(dacite::core::FrontFace::Clockwise)
+        .build(device, None)
+        .map_err(|e| {
+            println!("Failed to create pipeline ({})", e);
+        })
 }
 
 fn create_command_pool(device: &dacite::core::Device, queue_family_index: u32) -> Result<dacite::core::CommandPool, ()> {
@@ -480,44 +351,47 @@ fn record_command_buffer(command_pool: &dacite::core::CommandPool, pipeline: &da
         chain: None,
     };
 
-    command_buffer.begin(&begin_info).map_err(|e| {
-        println!("Failed to begin command buffer ({})", e);
-    })?;
+    {
+        let mut recorder = command_buffer.record(&begin_info).map_err(|e| {
+            println!("Failed to begin command buffer ({})", e);
+        })?;
 
-    let begin_info = dacite::core::RenderPassBeginInfo {
-        render_pass: render_pass.clone(),
-        framebuffer: framebuffer.clone(),
-        render_area: dacite::core::Rect2D::new(dacite::core::Offset2D::zero(), *extent),
-        clear_values: vec![dacite::core::ClearValue::Color(dacite::core::ClearColorValue::Float32([0.0, 0.0, 0.0, 1.0]))],
-        chain: None,
-    };
+        let begin_info = dacite::core::RenderPassBeginInfo {
+            render_pass: render_pass.clone(),
+            framebuffer: framebuffer.clone(),
+            render_area: dacite::core::Rect2D::new(dacite::core::Offset2D::zero(), *extent),
+            clear_values: vec![dacite::core::ClearValue::Color(dacite::core::ClearColorValue::Float32([0.0, 0.0, 0.0, 1.0]))],
+            chain: None,
+        };
 
-    command_buffer.begin_render_pass(&begin_info, dacite::core::SubpassContents::Inline);
-    command_buffer.bind_pipeline(dacite::core::PipelineBindPoint::Graphics, pipeline);
-    command_buffer.draw(3, 1, 0, 0);
-    command_buffer.end_render_pass();
-
-    command_buffer.pipeline_barrier(dacite::core::PipelineStageFlags::BOTTOM_OF_PIPE, dacite::core::PipelineStageFlags::TRANSFER, dacite::core::DependencyFlags::empty(), None, None, None);
-
-    let regions = vec![dacite::core::BufferImageCopy {
-        buffer_offset: 0,
-        buffer_row_length: 0,
-        buffer_image_height: 0,
-        image_subresource: dacite::core::ImageSubresourceLayers {
-            aspect_mask: dacite::core::ImageAspectFlags::COLOR,
-            mip_level: 0,
-            base_array_layer: 0,
-            layer_count: 1,
-        },
-        image_offset: dacite::core::Offset3D::zero(),
-        image_extent: dacite::core::Extent3D::from_2d(extent, 1),
-    }];
+        {
+            let render_pass_recorder = recorder.begin_render_pass(&begin_info, dacite::core::SubpassContents::Inline);
+            render_pass_recorder.bind_pipeline(dacite::core::PipelineBindPoint::Graphics, pipeline);
+            render_pass_recorder.draw(3, 1, 0, 0);
+        }
+
+        recorder.pipeline_barrier(dacite::core::PipelineStageFlags::BOTTOM_OF_PIPE, dacite::core::PipelineStageFlags::TRANSFER, dacite::core::DependencyFlags::empty(), None, None, None);
+
+        let regions = vec![dacite::core::BufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_subresource: dacite::core::ImageSubresourceLayers {
+                aspect_mask: dacite::core::ImageAspectFlags::COLOR,
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: 1,
+            },
+            image_offset: dacite::core::Offset3D::zero(),
+            image_extent: dacite::core::Extent3D::from_2d(extent, 1),
+        }];
 
-    command_buffer.copy_image_to_buffer(image, dacite::core::ImageLayout::TransferSrcOptimal, buffer, &regions);
+        recorder.copy_image_to_buffer(image, dacite::core::ImageLayout::TransferSrcOptimal, buffer, &regions);
 
-    command_buffer.end().map_err(|e| {
-        println!("Failed to record command buffer ({})", e);
-    })?;
+        recorder.end().map_err(|e| {
+            println!("Failed to record command buffer ({})", e);
+        })?;
+    }
 
     Ok(command_buffer)
 }
@@ -574,7 +448,8 @@ fn real_main() -> Result<(), ()> {
     let format = dacite::core::Format::B8G8R8A8_UNorm;
     let extent = dacite::core::Extent2D::new(800, 600);
 
-    let instance = create_instance()?;
+    let loader = create_loader()?;
+    let instance = create_instance(&loader)?;
 
     let DeviceSettings {
         physical_device,
@@ -21,7 +21,7 @@ struct QueueFamilyIndices {
 
 struct DeviceSettings {
     physical_device: dacite::core::PhysicalDevice,
-    queue_family_indices: QueueFamilyIndices,
+    queue_plan: dacite::queue_planner::QueuePlan,
     device_extensions: dacite::core::DeviceExtensions,
 }
 
@@ -55,8 +55,14 @@ fn create_window(extent: &dacite::core::Extent2D) -> Result<Window, ()> {
     })
 }
 
-fn compute_instance_extensions(window: &winit::Window) -> Result<dacite::core::InstanceExtensions, ()> {
-    let available_extensions = dacite::core::Instance::get_instance_extension_properties(None).map_err(|e| {
+fn create_loader() -> Result<dacite::core::Loader, ()> {
+    dacite::core::Loader::new().map_err(|e| {
+        println!("Failed to load Vulkan library ({})", e);
+    })
+}
+
+fn compute_instance_extensions(loader: &dacite::core::Loader, window: &winit::Window) -> Result<dacite::core::InstanceExtensions, ()> {
+    let available_extensions = dacite::core::Instance::get_instance_extension_properties(loader, None).map_err(|e| {
         println!("Failed to get instance extension properties ({})", e);
     })?;
 
@@ -78,7 +84,7 @@ fn compute_instance_extensions(window: &winit::Window) -> Result<dacite::core::I
     }
 }
 
-fn create_instance(instance_extensions: dacite::core::InstanceExtensions) -> Result<dacite::core::Instance, ()> {
+fn create_instance(loader: &dacite::core::Loader, instance_extensions: dacite::core::InstanceExtensions) -> Result<dacite::core::Instance, ()> {
     let application_info = dacite::core::ApplicationInfo {
         application_name: Some("dacite triangle example".to_owned()),
         application_version: 0,
@@ -96,103 +102,44 @@ fn create_instance(instance_extensions: dacite::core::InstanceExtensions) -> Res
         chain: None,
     };
 
-    dacite::core::Instance::create(&create_info, None).map_err(|e| {
+    dacite::core::Instance::create(loader, &create_info, None).map_err(|e| {
         println!("Failed to create instance ({})", e);
     })
 }
 
-fn find_queue_family_indices(physical_device: &dacite::core::PhysicalDevice, surface: &dacite::khr_surface::SurfaceKhr) -> Result<QueueFamilyIndices, ()> {
-    let mut graphics = None;
-    let mut present = None;
-
-    let queue_family_properties: Vec<_> = physical_device.get_queue_family_properties();
-    for (index, queue_family_properties) in queue_family_properties.into_iter().enumerate() {
-        if queue_family_properties.queue_count == 0 {
-            continue;
-        }
-
-        if graphics.is_none() && queue_family_properties.queue_flags.contains(dacite::core::QueueFlags::GRAPHICS) {
-            graphics = Some(index);
-        }
-
-        if present.is_none() {
-            if let Ok(true) = physical_device.get_surface_support_khr(index as u32, surface) {
-                present = Some(index);
-            }
-        }
-    }
-
-    if let (Some(graphics), Some(present)) = (graphics, present) {
-        Ok(QueueFamilyIndices {
-            graphics: graphics as u32,
-            present: present as u32,
-        })
-    }
-    else {
-        Err(())
-    }
-}
-
-fn check_device_extensions(physical_device: &dacite::core::PhysicalDevice) -> Result<dacite::core::DeviceExtensions, ()> {
-    let available_extensions = physical_device.get_device_extension_properties(None).map_err(|e| {
-        println!("Failed to get device extension properties ({})", e);
-    })?;
-
-    let mut required_extensions = dacite::core::DeviceExtensionsProperties::new();
-    required_extensions.add_khr_swapchain(67);
-
-    let missing_extensions = required_extensions.difference(&available_extensions);
-    if missing_extensions.is_empty() {
-        Ok(required_extensions.to_extensions())
-    }
-    else {
-        for (name, spec_version) in missing_extensions.properties() {
-            println!("Extension {} (revision {}) missing", name, spec_version);
-        }
-
-        Err(())
-    }
-}
+fn find_suitable_device(instance: &dacite::core::Instance, surface: &dacite::khr_surface::SurfaceKhr) -> Result<DeviceSettings, ()> {
+    let mut required_extensions = dacite::core::DeviceExtensions::new();
+    required_extensions.add_khr_swapchain();
+
+    let selected = dacite::device_selector::PhysicalDeviceSelector::new()
+        .required_extensions(required_extensions)
+        .queue_flags(dacite::core::QueueFlags::GRAPHICS)
+        .surface(surface.clone())
+        .select(instance)
+        .map_err(|e| {
+            println!("Failed to find a suitable device ({})", e);
+        })?;
 
-fn check_device_suitability(physical_device: dacite::core::PhysicalDevice, surface: &dacite::khr_surface::SurfaceKhr) -> Result<DeviceSettings, ()> {
-    let queue_family_indices = find_queue_family_indices(&physical_device, surface)?;
-    let device_extensions = check_device_extensions(&physical_device)?;
+    let queue_plan = dacite::queue_planner::QueuePlanner::new()
+        .request(dacite::queue_planner::QueueRole::Graphics, 1)
+        .request(dacite::queue_planner::QueueRole::Present, 1)
+        .surface(surface.clone())
+        .plan(&selected.physical_device)
+        .map_err(|e| {
+            println!("Failed to plan device queues ({})", e);
+        })?;
 
     Ok(DeviceSettings {
-        physical_device: physical_device,
-        queue_family_indices: queue_family_indices,
-        device_extensions: device_extensions,
+        physical_device: selected.physical_device,
+        queue_plan: queue_plan,
+        device_extensions: selected.enabled_extensions,
     })
 }
 
-fn find_suitable_device(instance: &dacite::core::Instance, surface: &dacite::khr_surface::SurfaceKhr) -> Result<DeviceSettings, ()> {
-    let physical_devices = instance.enumerate_physical_devices().map_err(|e| {
-        println!("Failed to enumerate physical devices ({})", e);
-    })?;
-
-    for physical_device in physical_devices {
-        if let Ok(device_settings) = check_device_suitability(physical_device, surface) {
-            return Ok(device_settings);
-        }
-    }
-
-    println!("Failed to find a suitable device");
-    Err(())
-}
-
-fn create_device(physical_device: &dacite::core::PhysicalDevice, device_extensions: dacite::core::DeviceExtensions, queue_family_indices: &QueueFamilyIndices) -> Result<dacite::core::Device, ()> {
-    let device_queue_create_infos = vec![
-        dacite::core::DeviceQueueCreateInfo {
-            flags: dacite::core::DeviceQueueCreateFlags::empty(),
-            queue_family_index: queue_family_indices.graphics,
-            queue_priorities: vec![1.0],
-            chain: None,
-        },
-    ];
-
+fn create_device(physical_device: &dacite::core::PhysicalDevice, device_extensions: dacite::core::DeviceExtensions, queue_plan: &dacite::queue_planner::QueuePlan) -> Result<dacite::core::Device, ()> {
     let device_create_info = dacite::core::DeviceCreateInfo {
         flags: dacite::core::DeviceCreateFlags::empty(),
-        queue_create_infos: device_queue_create_infos,
+        queue_create_infos: queue_plan.queue_create_infos().to_vec(),
         enabled_layers: vec![],
         enabled_extensions: device_extensions,
         enabled_features: None,
@@ -293,21 +240,7 @@ fn create_swapchain(physical_device: &dacite::core::PhysicalDevice, device: &dac
 
     let mut image_views = Vec::with_capacity(images.len());
     for image in &images {
-        let create_info = dacite::core::ImageViewCreateInfo {
-            flags: dacite::core::ImageViewCreateFlags::empty(),
-            image: image.clone(),
-            view_type: dacite::core::ImageViewType::Type2D,
-            format: format,
-            components: dacite::core::ComponentMapping::identity(),
-            subresource_range: dacite::core::ImageSubresourceRange {
-                aspect_mask: dacite::core::ImageAspectFlags::COLOR,
-                base_mip_level: 0,
-                level_count: dacite::core::OptionalMipLevels::MipLevels(1),
-                base_array_layer: 0,
-                layer_count: dacite::core::OptionalArrayLayers::ArrayLayers(1),
-            },
-            chain: None,
-        };
+        let create_info = dacite::core::ImageViewCreateInfo::for_image(image.clone(), dacite::core::ImageViewType::Type2D, format);
 
         let image_view = device.create_image_view(&create_info, None).map_err(|e| {
             println!("Failed to create swapchain image view ({})", e);
@@ -410,11 +343,9 @@ fn create_vertex_shader(device: &dacite::core::Device) -> Result<dacite::core::S
         }
     "#};
 
-    let create_info = dacite::core::ShaderModuleCreateInfo {
-        flags: dacite::core::ShaderModuleCreateFlags::empty(),
-        code: vertex_shader_bytes.to_vec(),
-        chain: None,
-    };
+    let create_info = dacite::core::ShaderModuleCreateInfo::from_bytes(vertex_shader_bytes).map_err(|e| {
+        println!("Invalid vertex shader code ({})", e);
+    })?;
 
     device.create_shader_module(&create_info, None).map_err(|e| {
         println!("Failed to create vertex shader module ({})", e);
@@ -434,11 +365,9 @@ fn create_fragment_shader(device: &dacite::core::Device) -> Result<dacite::core:
         }
     "#};
 
-    let create_info = dacite::core::ShaderModuleCreateInfo {
-        flags: dacite::core::ShaderModuleCreateFlags::empty(),
-        code: fragment_shader_bytes.to_vec(),
-        chain: None,
-    };
+    let create_info = dacite::core::ShaderModuleCreateInfo::from_bytes(fragment_shader_bytes).map_err(|e| {
+        println!("Invalid fragment shader code ({})", e);
+    })?;
 
     device.create_shader_module(&create_info, None).map_err(|e| {
         println!("Failed to create fragment shader module ({})", e);
@@ -446,12 +375,7 @@ fn create_fragment_shader(device: &dacite::core::Device) -> Result<dacite::core:
 }
 
 fn create_pipeline_layout(device: &dacite::core::Device) -> Result<dacite::core::PipelineLayout, ()> {
-    let create_info = dacite::core::PipelineLayoutCreateInfo {
-        flags: dacite::core::PipelineLayoutCreateFlags::empty(),
-        set_layouts: vec![],
-        push_constant_ranges: vec![],
-        chain: None,
-    };
+    let create_info = dacite::core::PipelineLayoutCreateInfo::default();
 
     device.create_pipeline_layout(&create_info, None).map_err(|e| {
         println!("Failed to create pipeline layout ({})", e);
@@ -463,108 +387,23 @@ fn create_pipeline(device: &dacite::core::Device, render_pass: &dacite::core::Re
     let fragment_shader = create_fragment_shader(device)?;
     let layout = create_pipeline_layout(device)?;
 
-    let create_infos = vec![dacite::core::GraphicsPipelineCreateInfo {
-        flags: dacite::core::PipelineCreateFlags::empty(),
-        stages: vec![
-            dacite::core::PipelineShaderStageCreateInfo {
-                flags: dacite::core::PipelineShaderStageCreateFlags::empty(),
-                stage: dacite::core::ShaderStageFlagBits::Vertex,
-                module: vertex_shader.clone(),
-                name: "main".to_owned(),
-                specialization_info: None,
-                chain: None,
-            },
-            dacite::core::PipelineShaderStageCreateInfo {
-                flags: dacite::core::PipelineShaderStageCreateFlags::empty(),
-                stage: dacite::core::ShaderStageFlagBits::Fragment,
-                module: fragment_shader.clone(),
-                name: "main".to_owned(),
-                specialization_info: None,
-                chain: None,
-            },
-        ],
-        vertex_input_state: dacite::core::PipelineVertexInputStateCreateInfo {
-            flags: dacite::core::PipelineVertexInputStateCreateFlags::empty(),
-            vertex_binding_descriptions: vec![],
-            vertex_attribute_descriptions: vec![],
-            chain: None,
-        },
-        input_assembly_state: dacite::core::PipelineInputAssemblyStateCreateInfo {
-            flags: dacite::core::PipelineInputAssemblyStateCreateFlags::empty(),
-            topology: dacite::core::PrimitiveTopology::TriangleList,
-            primitive_restart_enable: false,
-            chain: None,
-        },
-        tessellation_state: None,
-        viewport_state: Some(dacite::core::PipelineViewportStateCreateInfo {
-            flags: dacite::core::PipelineViewportStateCreateFlags::empty(),
-            viewports: vec![dacite::core::Viewport {
-                x: 0.0,
-                y: 0.0,
-                width: extent.width as f32,
-                height: extent.height as f32,
-                min_depth: 0.0,
-                max_depth: 1.0,
-            }],
-            scissors: vec![dacite::core::Rect2D::new(dacite::core::Offset2D::zero(), *extent)],
-            chain: None,
-        }),
-        rasterization_state: dacite::core::PipelineRasterizationStateCreateInfo {
-            flags: dacite::core::PipelineRasterizationStateCreateFlags::empty(),
-            depth_clamp_enable: false,
-            rasterizer_discard_enable: false,
-            polygon_mode: dacite::core::PolygonMode::Fill,
-            cull_mode: dacite::core::CullModeFlags::NONE,
-            front_face: dacite::core::FrontFace::Clockwise,
-            depth_bias_enable: false,
-            depth_bias_constant_factor: 0.0,
-            depth_bias_clamp: 0.0,
-            depth_bias_slope_factor: 0.0,
-            line_width: 1.0,
-            chain: None,
-        },
-        multisample_state: Some(dacite::core::PipelineMultisampleStateCreateInfo {
-            flags: dacite::core::PipelineMultisampleStateCreateFlags::empty(),
-            rasterization_samples: dacite::core::SampleCountFlagBits::SampleCount1,
-            sample_shading_enable: false,
-            min_sample_shading: 1.0,
-            sample_mask: vec![],
-            alpha_to_coverage_enable: false,
-            alpha_to_one_enable: false,
-            chain: None,
-        }),
-        depth_stencil_state: None,
-        color_blend_state: Some(dacite::core::PipelineColorBlendStateCreateInfo {
-            flags: dacite::core::PipelineColorBlendStateCreateFlags::empty(),
-            logic_op_enable: false,
-            logic_op: dacite::core::LogicOp::Copy,
-            attachments: vec![dacite::core::PipelineColorBlendAttachmentState {
-                blend_enable: false,
-                src_color_blend_factor: dacite::core::BlendFactor::One,
-                dst_color_blend_factor: dacite::core::BlendFactor::Zero,
-                color_blend_op: dacite::core::BlendOp::Add,
-                src_alpha_blend_factor: dacite::core::BlendFactor::One,
-                dst_alpha_blend_factor: dacite::core::BlendFactor::Zero,
-                alpha_blend_op: dacite::core::BlendOp::Add,
-                color_write_mask: dacite::core::ColorComponentFlags::R | dacite::core::ColorComponentFlags::G | dacite::core::ColorComponentFlags::B,
-            }],
-            blend_constants: [0.0, 0.0, 0.0, 0.0],
-            chain: None,
-        }),
-        dynamic_state: None,
-        layout: layout.clone(),
-        render_pass: render_pass.clone(),
-        subpass: 0,
-        base_pipeline: None,
-        base_pipeline_index: None,
-        chain: None,
-    }];
-
-    let pipelines = device.create_graphics_pipelines(None, &create_infos, None).map_err(|(e, _)| {
-        println!("Failed to create pipeline ({})", e);
-    })?;
-
-    Ok(pipelines[0].clone())
+    dacite::core::GraphicsPipelineBuilder::new(layout, render_pass.clone(), 0)
+        .vertex_shader(vertex_shader, "main")
+        .fragment_shader(fragment_shader, "main")
+        .viewport(dacite::core::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: extent.width as f32,
+            height: extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })
+        .scissor(dacite::core::Rect2D::new(dacite::core::Offset2D::zero(), *extent))
+        .front_face(dacite::core::FrontFace::Clockwise)
+        .build(device, None)
+        .map_err(|e| {
+            println!("Failed to create pipeline ({})", e);
+        })
 }
 
 fn create_command_pool(device: &dacite::core::Device, queue_family_index: u32) -> Result<dacite::core::CommandPool, ()> {
@@ -598,7 +437,7 @@ fn record_command_buffer(command_pool: &dacite::core::CommandPool, pipeline: &da
             chain: None,
         };
 
-        command_buffer.begin(&begin_info).map_err(|e| {
+        let mut recorder = command_buffer.record(&begin_info).map_err(|e| {
             println!("Failed to begin command buffer ({})", e);
         })?;
 
@@ -610,12 +449,13 @@ fn record_command_buffer(command_pool: &dacite::core::CommandPool, pipeline: &da
             chain: None,
         };
 
-        command_buffer.begin_render_pass(&begin_info, dacite::core::SubpassContents::Inline);
-        command_buffer.bind_pipeline(dacite::core::PipelineBindPoint::Graphics, pipeline);
-        command_buffer.draw(3, 1, 0, 0);
+        {
+            let render_pass_recorder = recorder.begin_render_pass(&begin_info, dacite::core::SubpassContents::Inline);
+            render_pass_recorder.bind_pipeline(dacite::core::PipelineBindPoint::Graphics, pipeline);
+            render_pass_recorder.draw(3, 1, 0, 0);
+        }
 
-        command_buffer.end_render_pass();
-        command_buffer.end().map_err(|e| {
+        recorder.end().map_err(|e| {
             println!("Failed to record command buffer ({})", e);
         })?;
     }
@@ -687,8 +527,9 @@ fn real_main() -> Result<(), ()> {
         window,
     } = create_window(&preferred_extent)?;
 
-    let instance_extensions = compute_instance_extensions(&window)?;
-    let instance = create_instance(instance_extensions)?;
+    let loader = create_loader()?;
+    let instance_extensions = compute_instance_extensions(&loader, &window)?;
+    let instance = create_instance(&loader, instance_extensions)?;
 
     let surface = window.create_surface(&instance, dacite_winit::SurfaceCreateFlags::empty(), None).map_err(|e| match e {
         dacite_winit::Error::Unsupported => println!("The windowing system is not supported"),
@@ -697,13 +538,19 @@ fn real_main() -> Result<(), ()> {
 
     let DeviceSettings {
         physical_device,
-        queue_family_indices,
+        queue_plan,
         device_extensions,
     } = find_suitable_device(&instance, &surface)?;
 
-    let device = create_device(&physical_device, device_extensions, &queue_family_indices)?;
-    let graphics_queue = device.get_queue(queue_family_indices.graphics, 0);
-    let present_queue = device.get_queue(queue_family_indices.present, 0);
+    let device = create_device(&physical_device, device_extensions, &queue_plan)?;
+    let mut queues = queue_plan.get_queues(&device);
+    let graphics_queue = queues.remove(&dacite::queue_planner::QueueRole::Graphics).unwrap().remove(0);
+    let present_queue = queues.remove(&dacite::queue_planner::QueueRole::Present).unwrap().remove(0);
+
+    let queue_family_indices = QueueFamilyIndices {
+        graphics: queue_plan.queue_family_index(dacite::queue_planner::QueueRole::Graphics).unwrap(),
+        present: queue_plan.queue_family_index(dacite::queue_planner::QueueRole::Present).unwrap(),
+    };
 
     let SwapchainSettings {
         swapchain,